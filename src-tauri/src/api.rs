@@ -1,6 +1,9 @@
 pub mod aleo_client;
+pub mod backend;
+pub mod chain_provider;
 pub mod client;
 pub mod encrypted_data;
 pub mod fee;
+pub mod resilience;
 pub mod tokens;
 pub mod user;
@@ -1,10 +1,13 @@
 use avail_common::errors::{AvailError, AvailResult};
 use once_cell::sync::Lazy;
-use std::sync::RwLock;
+use std::sync::{Arc, RwLock};
 
 use avail_common::aleo_tools::api::AleoAPIClient;
 use snarkvm::{console::network::Testnet3, prelude::Network};
 
+use super::chain_provider::{
+    get_chain_provider_override, ChainClient, NodeChainProvider, ResilientChainProvider,
+};
 use crate::models::event::Network as EventNetwork;
 use crate::services::local_storage::persistent_storage::update_network;
 
@@ -16,7 +19,19 @@ pub fn setup_local_client<N: Network>() -> AleoAPIClient<N> {
     api_client
 }
 
-pub fn setup_client<N: Network>() -> AvailResult<AleoAPIClient<N>> {
+/// The chain provider services read from, the injected provider if one is set, otherwise the node
+/// behind the network retry policy
+pub fn setup_client<N: Network>() -> AvailResult<ChainClient<N>> {
+    if let Some(provider) = get_chain_provider_override::<N>() {
+        return Ok(provider);
+    }
+
+    let node: ChainClient<N> = Arc::new(NodeChainProvider::new(setup_node_client::<N>()?));
+    Ok(Arc::new(ResilientChainProvider::new(node)))
+}
+
+/// Raw node client, for the program manager which talks to the node itself
+pub fn setup_node_client<N: Network>() -> AvailResult<AleoAPIClient<N>> {
     let node_api_obscura = env!("TESTNET_API_OBSCURA");
 
     println!("Node API Obscura: {:?}", node_api_obscura);
@@ -0,0 +1,1266 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use rand::{distributions::Alphanumeric, Rng};
+use rusqlite::{params, Connection, OptionalExtension};
+use snarkvm::prelude::Testnet3;
+use std::sync::{Arc, Mutex, RwLock};
+use tauri_plugin_http::reqwest;
+use uuid::Uuid;
+
+use crate::api::client::{get_rm_client_with_session, get_um_client_with_session, SESSION};
+use crate::api::resilience::{call_async_once, retry_async};
+use crate::models::account::AddressRequest;
+use crate::models::auth::SignInSessionRequest;
+use crate::models::resilience::NetworkService;
+use crate::models::wallet_connect::sign_in::SignInMessage;
+use crate::services::wallet_connect_api::verify_signature;
+
+use avail_common::{
+    errors::{AvailError, AvailErrorType, AvailResult},
+    models::{
+        encrypted_data::{
+            Data, DataRequest, EncryptedDataRecord, EncryptedDataSyncRequest,
+            EncryptedDataTypeCommon, EncryptedDataUpdateRequest, PageRequest,
+        },
+        server_auth::{CreateSessionRequest, CreateSessionResponse, VerifySessionRequest},
+        user::{UpdateBackupRequest, User},
+    },
+};
+
+/* --Avail Backend-- */
+
+/// Names a SQLite file to run against a [`LocalBackend`] instead of the Avail API
+pub const LOCAL_BACKEND_ENV: &str = "AVAIL_LOCAL_BACKEND";
+
+/// Records returned per page when recovering a backup
+pub const RECOVERY_PAGE_SIZE: i64 = 300;
+
+/// Everything the wallet asks of the Avail server, one call per endpoint
+#[async_trait]
+pub trait BackendService: Send + Sync {
+    /* Users */
+    async fn create_user(&self, user: User) -> AvailResult<String>;
+
+    async fn get_user(&self) -> AvailResult<User>;
+
+    async fn update_user(&self, user: User) -> AvailResult<String>;
+
+    async fn update_backup(&self, request: UpdateBackupRequest) -> AvailResult<()>;
+
+    /// Succeeds with "User not found" when there is no account to delete
+    async fn delete_user(&self) -> AvailResult<String>;
+
+    async fn get_address(&self, username: &str) -> AvailResult<String>;
+
+    async fn get_username(&self, address: &str) -> AvailResult<Option<String>>;
+
+    /* Encrypted data backup */
+    /// Returns the ids the data is stored under
+    async fn post_data(&self, data: Vec<EncryptedDataRecord>) -> AvailResult<Vec<String>>;
+
+    async fn update_data(&self, updates: Vec<EncryptedDataUpdateRequest>) -> AvailResult<String>;
+
+    async fn data_count(&self) -> AvailResult<i64>;
+
+    /// One page of [`RECOVERY_PAGE_SIZE`] backed up records
+    async fn recover_data(&self, page: PageRequest) -> AvailResult<Data>;
+
+    async fn import_data(&self, request: DataRequest) -> AvailResult<String>;
+
+    async fn delete_all_data(&self) -> AvailResult<String>;
+
+    /* Transaction messages */
+    /// Leaves a message for the owner of the record, the recipient of a transaction
+    async fn send_transaction_message(&self, message: EncryptedDataRecord) -> AvailResult<String>;
+
+    /// Messages left for the owner that are not synced yet
+    async fn get_transaction_messages(
+        &self,
+        request: EncryptedDataSyncRequest,
+    ) -> AvailResult<Vec<EncryptedDataRecord>>;
+
+    async fn mark_synced(&self, ids: Vec<Uuid>) -> AvailResult<String>;
+
+    async fn delete_transaction_messages(&self, ids: Vec<Uuid>) -> AvailResult<String>;
+
+    /* Authentication */
+    async fn request_hash(&self, address: &str) -> AvailResult<CreateSessionResponse>;
+
+    /// Logs in with a signed sign in message and returns the token authenticated calls are made with,
+    /// fails with NotFound if the server does not support sign in messages yet
+    async fn login(&self, request: SignInSessionRequest) -> AvailResult<String>;
+
+    /// Logs in with a signature over the bare hash, the login every server version accepts
+    async fn login_with_hash(&self, request: VerifySessionRequest) -> AvailResult<String>;
+}
+
+static BACKEND_OVERRIDE: Lazy<RwLock<Option<Arc<dyn BackendService>>>> =
+    Lazy::new(|| RwLock::new(None));
+
+pub fn set_backend_service(service: Arc<dyn BackendService>) {
+    *BACKEND_OVERRIDE.write().unwrap() = Some(service);
+}
+
+pub fn clear_backend_service() {
+    *BACKEND_OVERRIDE.write().unwrap() = None;
+}
+
+/// The backend services talk to, the Avail API unless overridden or [`LOCAL_BACKEND_ENV`] is set
+pub fn backend() -> AvailResult<Arc<dyn BackendService>> {
+    if let Some(service) = BACKEND_OVERRIDE.read().unwrap().as_ref() {
+        return Ok(service.clone());
+    }
+
+    match std::env::var(LOCAL_BACKEND_ENV) {
+        Ok(path) if !path.is_empty() => Ok(Arc::new(LocalBackend::open(&path)?)),
+        _ => Ok(Arc::new(HttpBackend)),
+    }
+}
+
+fn session_expired() -> AvailError {
+    AvailError::new(
+        AvailErrorType::Unauthorized,
+        "User session has expired.".to_string(),
+        "Your session has expired, please authenticate again.".to_string(),
+    )
+}
+
+fn no_connection() -> AvailError {
+    AvailError::new(
+        AvailErrorType::Network,
+        "No internet connection".to_string(),
+        "No internet connection".to_string(),
+    )
+}
+
+/* --HTTP Backend-- */
+
+/// The Avail API at the `API` url the wallet was built with
+pub struct HttpBackend;
+
+impl HttpBackend {
+    /// The session token a successful login sets as a cookie
+    fn session_token(res: reqwest::Response) -> AvailResult<String> {
+        if res.status() == 200 {
+            match res.cookies().next() {
+                Some(cookie) => Ok(cookie.value().to_string()),
+                None => Err(AvailError::new(
+                    AvailErrorType::Validation,
+                    "Session cookie not found in auth response".to_string(),
+                    "Session cookie not found in auth response".to_string(),
+                )),
+            }
+        } else if res.status() == 0 {
+            Err(no_connection())
+        } else {
+            Err(AvailError::new(
+                AvailErrorType::External,
+                "Invalid Signature".to_string(),
+                "Invalid Signature".to_string(),
+            ))
+        }
+    }
+
+    fn status_error(status: reqwest::StatusCode, internal: &str, external: &str) -> AvailError {
+        if status == 401 {
+            session_expired()
+        } else {
+            AvailError::new(
+                AvailErrorType::External,
+                format!("{}: status {}", internal.trim(), status.as_u16()),
+                external.to_string(),
+            )
+        }
+    }
+}
+
+/// One attempt at a request, server errors and rate limits are reported as the server being unreachable
+async fn attempt(action: &str, request: reqwest::RequestBuilder) -> AvailResult<reqwest::Response> {
+    let res = request.send().await.map_err(|e| {
+        AvailError::new(
+            AvailErrorType::Network,
+            format!("Error {}: {}", action, e),
+            "Could not reach the Avail server".to_string(),
+        )
+    })?;
+
+    let status = res.status();
+    if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(AvailError::new(
+            AvailErrorType::Network,
+            format!("Error {}: status {}", action, status.as_u16()),
+            format!("Error {}", action),
+        ));
+    }
+
+    Ok(res)
+}
+
+/// Sends an idempotent request under the network retry policy, server errors and rate limits are retried
+async fn send(action: &str, request: reqwest::RequestBuilder) -> AvailResult<reqwest::Response> {
+    retry_async(NetworkService::Avail, action, || {
+        let request = request.try_clone();
+        async move {
+            let request = request.ok_or_else(|| {
+                AvailError::new(
+                    AvailErrorType::Internal,
+                    format!("Request {} can't be retried", action),
+                    format!("Error {}", action),
+                )
+            })?;
+
+            attempt(action, request).await
+        }
+    })
+    .await
+}
+
+/// Sends a request that creates something or consumes a login challenge. A retry could repeat it after
+/// the server already handled it, so it is sent once under the timeout and circuit breaker.
+async fn send_once(
+    action: &str,
+    request: reqwest::RequestBuilder,
+) -> AvailResult<reqwest::Response> {
+    call_async_once(NetworkService::Avail, action, attempt(action, request)).await
+}
+
+#[async_trait]
+impl BackendService for HttpBackend {
+    async fn create_user(&self, user: User) -> AvailResult<String> {
+        let res = send_once(
+            "creating user",
+            reqwest::Client::new()
+                .post(format!("{}/user", env!("API")))
+                .json(&user),
+        )
+        .await?;
+
+        if res.status() == 200 {
+            Ok("User created".to_string())
+        } else {
+            Err(AvailError::new(
+                AvailErrorType::External,
+                "Error creating user".to_string(),
+                "Error creating user".to_string(),
+            ))
+        }
+    }
+
+    async fn get_user(&self) -> AvailResult<User> {
+        let res = send(
+            "getting user",
+            get_um_client_with_session(reqwest::Method::GET, "user")?,
+        )
+        .await?;
+
+        if res.status() == 200 {
+            Ok(res.json().await?)
+        } else {
+            Err(Self::status_error(
+                res.status(),
+                "Error getting user",
+                "Error getting user",
+            ))
+        }
+    }
+
+    async fn update_user(&self, user: User) -> AvailResult<String> {
+        let res = send(
+            "updating user",
+            get_um_client_with_session(reqwest::Method::PUT, "user")?.json(&user),
+        )
+        .await?;
+
+        if res.status() == 200 {
+            Ok("Username updated".to_string())
+        } else {
+            Err(Self::status_error(
+                res.status(),
+                "Error updating username",
+                "Error updating username",
+            ))
+        }
+    }
+
+    async fn update_backup(&self, request: UpdateBackupRequest) -> AvailResult<()> {
+        let res = send(
+            "updating backup flag",
+            get_um_client_with_session(reqwest::Method::PUT, "backup")?.json(&request),
+        )
+        .await?;
+
+        if res.status() == 200 {
+            Ok(())
+        } else {
+            Err(Self::status_error(
+                res.status(),
+                "Error updating backup flag",
+                "Error updating backup flag",
+            ))
+        }
+    }
+
+    async fn delete_user(&self) -> AvailResult<String> {
+        let res = send(
+            "deleting user",
+            get_um_client_with_session(reqwest::Method::DELETE, "user")?,
+        )
+        .await?;
+
+        if res.status() == 200 {
+            Ok("User deleted".to_string())
+        } else if res.status() == 404 {
+            Ok("User not found".to_string())
+        } else {
+            Err(Self::status_error(
+                res.status(),
+                "Error deleting user account on server side.",
+                "Error deleting online account.",
+            ))
+        }
+    }
+
+    async fn get_address(&self, username: &str) -> AvailResult<String> {
+        let request = AddressRequest {
+            username: username.to_string(),
+        };
+
+        let res = send(
+            "getting address",
+            get_um_client_with_session(reqwest::Method::GET, "user_address")?.json(&request),
+        )
+        .await?;
+
+        if res.status() == 200 {
+            Ok(res.json().await?)
+        } else {
+            Err(Self::status_error(
+                res.status(),
+                "Error getting address",
+                "Error getting address",
+            ))
+        }
+    }
+
+    async fn get_username(&self, address: &str) -> AvailResult<Option<String>> {
+        let res = send(
+            "getting username",
+            get_um_client_with_session(reqwest::Method::GET, &format!("username/{}", address))?,
+        )
+        .await?;
+
+        if res.status() == 200 {
+            Ok(res.json().await?)
+        } else {
+            Err(Self::status_error(
+                res.status(),
+                "Error getting username",
+                "Error getting username",
+            ))
+        }
+    }
+
+    async fn post_data(&self, data: Vec<EncryptedDataRecord>) -> AvailResult<Vec<String>> {
+        let res = send_once(
+            "posting encrypted data",
+            get_rm_client_with_session(reqwest::Method::POST, "data")?.json(&data),
+        )
+        .await?;
+
+        if res.status() == 200 {
+            let result = res.text().await?;
+            Ok(serde_json::from_str(&result)?)
+        } else {
+            Err(Self::status_error(
+                res.status(),
+                "Error posting encrypted data ",
+                "",
+            ))
+        }
+    }
+
+    async fn update_data(&self, updates: Vec<EncryptedDataUpdateRequest>) -> AvailResult<String> {
+        let res = send(
+            "updating encrypted data",
+            get_rm_client_with_session(reqwest::Method::PUT, "data")?.json(&updates),
+        )
+        .await?;
+
+        if res.status() == 200 {
+            Ok(res.text().await?)
+        } else {
+            Err(Self::status_error(
+                res.status(),
+                "Error updating encrypted data record ",
+                "Error updating backup data.",
+            ))
+        }
+    }
+
+    async fn data_count(&self) -> AvailResult<i64> {
+        let res = send(
+            "getting encrypted data count",
+            get_rm_client_with_session(reqwest::Method::GET, "data_count")?,
+        )
+        .await?;
+
+        if res.status() == 200 {
+            let result = res.text().await?;
+            Ok(result.parse::<i64>()?)
+        } else {
+            Err(Self::status_error(
+                res.status(),
+                "Error getting encrypted data count",
+                "Error getting encrypted data count",
+            ))
+        }
+    }
+
+    async fn recover_data(&self, page: PageRequest) -> AvailResult<Data> {
+        let res = send(
+            "recovering encrypted data",
+            get_rm_client_with_session(reqwest::Method::GET, "recover_data")?.json(&page),
+        )
+        .await?;
+
+        if res.status() == 200 {
+            Ok(res.json().await?)
+        } else {
+            Err(Self::status_error(
+                res.status(),
+                "Error recovering encrypted data ",
+                "Error recovering encrypted data",
+            ))
+        }
+    }
+
+    async fn import_data(&self, request: DataRequest) -> AvailResult<String> {
+        let res = send_once(
+            "importing encrypted data",
+            get_rm_client_with_session(reqwest::Method::POST, "import_data")?.json(&request),
+        )
+        .await?;
+
+        if res.status() == 200 {
+            Ok(res.text().await?)
+        } else {
+            Err(Self::status_error(
+                res.status(),
+                "Error importing encrypted data.",
+                "Error backing up encrypted data.",
+            ))
+        }
+    }
+
+    async fn delete_all_data(&self) -> AvailResult<String> {
+        let res = send(
+            "deleting encrypted data",
+            get_rm_client_with_session(reqwest::Method::DELETE, "data")?,
+        )
+        .await?;
+
+        if res.status() == 200 {
+            Ok(res.text().await?)
+        } else {
+            Err(Self::status_error(
+                res.status(),
+                "Error deleting encrypted data records ",
+                "Error deleting server side data.",
+            ))
+        }
+    }
+
+    async fn send_transaction_message(&self, message: EncryptedDataRecord) -> AvailResult<String> {
+        let res = send_once(
+            "sending transaction message",
+            get_rm_client_with_session(reqwest::Method::POST, "tx_sent")?.json(&message),
+        )
+        .await?;
+
+        if res.status() == 200 {
+            Ok(res.text().await?)
+        } else {
+            Err(Self::status_error(
+                res.status(),
+                "Error posting encrypted data ",
+                "",
+            ))
+        }
+    }
+
+    async fn get_transaction_messages(
+        &self,
+        request: EncryptedDataSyncRequest,
+    ) -> AvailResult<Vec<EncryptedDataRecord>> {
+        let res = send(
+            "checking transaction messages",
+            get_rm_client_with_session(reqwest::Method::POST, "txs_received")?.json(&request),
+        )
+        .await?;
+
+        if res.status() == 200 {
+            Ok(res.json().await?)
+        } else {
+            Err(Self::status_error(
+                res.status(),
+                "Error checking transaction messages",
+                "Error checking transaction messages",
+            ))
+        }
+    }
+
+    async fn mark_synced(&self, ids: Vec<Uuid>) -> AvailResult<String> {
+        let res = send(
+            "syncing data",
+            get_rm_client_with_session(reqwest::Method::PUT, "sync")?.json(&ids),
+        )
+        .await?;
+
+        if res.status() == 200 {
+            Ok(res.text().await?)
+        } else {
+            Err(Self::status_error(
+                res.status(),
+                "Error syncing data",
+                "Error syncing data",
+            ))
+        }
+    }
+
+    async fn delete_transaction_messages(&self, ids: Vec<Uuid>) -> AvailResult<String> {
+        let res = send(
+            "deleting transaction messages",
+            get_rm_client_with_session(reqwest::Method::DELETE, "txs_in")?.json(&ids),
+        )
+        .await?;
+
+        if res.status() == 200 {
+            Ok(res.text().await?)
+        } else {
+            Err(Self::status_error(
+                res.status(),
+                "Error deleting invalid transaction messages",
+                "Error deleting invalid transaction messages",
+            ))
+        }
+    }
+
+    async fn request_hash(&self, address: &str) -> AvailResult<CreateSessionResponse> {
+        let request = CreateSessionRequest {
+            public_key: address.to_owned(),
+        };
+
+        let res = send(
+            "requesting auth token",
+            reqwest::Client::new()
+                .post(format!("{}/auth/request/", env!("API")))
+                .header("Content-Type", "application/json")
+                .json(&request),
+        )
+        .await?;
+
+        if res.status() == 201 {
+            Ok(res.json::<CreateSessionResponse>().await?)
+        } else if res.status() == 0 {
+            Err(no_connection())
+        } else {
+            Err(AvailError::new(
+                AvailErrorType::External,
+                "Error requesting auth token.".to_string(),
+                "Error requesting auth token.".to_string(),
+            ))
+        }
+    }
+
+    async fn login(&self, request: SignInSessionRequest) -> AvailResult<String> {
+        let res = send_once(
+            "logging in",
+            reqwest::Client::new()
+                .post(format!("{}/auth/login/v2/", env!("API")))
+                .json(&request),
+        )
+        .await?;
+
+        if res.status() == 404 {
+            return Err(AvailError::new(
+                AvailErrorType::NotFound,
+                "Sign in message login not supported by the server".to_string(),
+                "Invalid Signature".to_string(),
+            ));
+        }
+
+        Self::session_token(res)
+    }
+
+    async fn login_with_hash(&self, request: VerifySessionRequest) -> AvailResult<String> {
+        let res = send_once(
+            "logging in",
+            reqwest::Client::new()
+                .post(format!("{}/auth/login/", env!("API")))
+                .json(&request),
+        )
+        .await?;
+
+        Self::session_token(res)
+    }
+}
+
+/* --Local Backend-- */
+
+/// Minutes a requested login hash can be signed in with
+const LOGIN_HASH_MINUTES: i64 = 5;
+const LOCAL_SESSION_TOKEN_LENGTH: usize = 48;
+
+/// Stand-in for the Avail API backed by its own SQLite database, so the account and backup
+/// lifecycle runs offline and in tests. Logins are checked like the server does, the sign in
+/// message or the bare hash must be the issued one and be signed by the account.
+pub struct LocalBackend {
+    conn: Mutex<Connection>,
+}
+
+impl LocalBackend {
+    pub fn open(path: &str) -> AvailResult<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn in_memory() -> AvailResult<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> AvailResult<Self> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS users (
+                address TEXT PRIMARY KEY,
+                username TEXT,
+                tag INTEGER,
+                backup INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS sessions (
+                session_id TEXT PRIMARY KEY,
+                address TEXT NOT NULL,
+                hash TEXT NOT NULL,
+                expires_on TEXT NOT NULL,
+                token TEXT
+            );
+            CREATE TABLE IF NOT EXISTS encrypted_data (
+                id TEXT PRIMARY KEY,
+                owner TEXT NOT NULL,
+                flavour TEXT NOT NULL,
+                record TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS transaction_messages (
+                id TEXT PRIMARY KEY,
+                owner TEXT NOT NULL,
+                record TEXT NOT NULL,
+                synced INTEGER NOT NULL DEFAULT 0
+            );",
+        )?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Address of the account the current session token was issued to
+    fn session_address(conn: &Connection) -> AvailResult<String> {
+        let token = match SESSION.get_session_token() {
+            Some(token) => token,
+            None => {
+                return Err(AvailError::new(
+                    AvailErrorType::Validation,
+                    "Session not found".to_string(),
+                    "Session not found".to_string(),
+                ))
+            }
+        };
+
+        let address: Option<String> = conn
+            .query_row(
+                "SELECT address FROM sessions WHERE token = ?1",
+                params![token],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        address.ok_or_else(session_expired)
+    }
+
+    /// Address and hash of a requested login that has not been used or expired
+    fn pending_login(conn: &Connection, session_id: &Uuid) -> AvailResult<(String, String)> {
+        let session: Option<(String, String, String)> = conn
+            .query_row(
+                "SELECT address, hash, expires_on FROM sessions WHERE session_id = ?1 AND token IS NULL",
+                params![session_id.to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?;
+
+        let (address, hash, expires_on) = session.ok_or_else(invalid_signature)?;
+
+        let expires_on = DateTime::parse_from_rfc3339(&expires_on)
+            .map_err(|_| invalid_signature())?
+            .with_timezone(&Utc);
+        if expires_on < Utc::now() {
+            return Err(invalid_signature());
+        }
+
+        Ok((address, hash))
+    }
+
+    fn issue_token(conn: &Connection, session_id: &Uuid) -> AvailResult<String> {
+        let token: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(LOCAL_SESSION_TOKEN_LENGTH)
+            .map(char::from)
+            .collect();
+
+        conn.execute(
+            "UPDATE sessions SET token = ?1 WHERE session_id = ?2",
+            params![token, session_id.to_string()],
+        )?;
+
+        Ok(token)
+    }
+
+    fn store_data(
+        conn: &Connection,
+        owner: &str,
+        data: Vec<EncryptedDataRecord>,
+    ) -> AvailResult<Vec<String>> {
+        data.into_iter()
+            .map(|mut record| {
+                let id = *record.id.get_or_insert_with(Uuid::new_v4);
+                conn.execute(
+                    "INSERT OR REPLACE INTO encrypted_data (id, owner, flavour, record) VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        id.to_string(),
+                        owner,
+                        record.flavour.to_str(),
+                        serde_json::to_string(&record)?
+                    ],
+                )?;
+                Ok(id.to_string())
+            })
+            .collect()
+    }
+
+    fn get_records(
+        conn: &Connection,
+        query: &str,
+        params: impl rusqlite::Params,
+    ) -> AvailResult<Vec<EncryptedDataRecord>> {
+        let mut statement = conn.prepare(query)?;
+        let rows = statement
+            .query_map(params, |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+
+        rows.iter()
+            .map(|record| Ok(serde_json::from_str(record)?))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl BackendService for LocalBackend {
+    async fn create_user(&self, user: User) -> AvailResult<String> {
+        let conn = self.conn.lock().unwrap();
+        let created = conn.execute(
+            "INSERT OR IGNORE INTO users (address, username, tag, backup) VALUES (?1, ?2, ?3, ?4)",
+            params![user.address, user.username, user.tag, user.backup],
+        )?;
+
+        match created {
+            0 => Err(AvailError::new(
+                AvailErrorType::External,
+                format!("User {} already exists", user.address),
+                "Error creating user".to_string(),
+            )),
+            _ => Ok("User created".to_string()),
+        }
+    }
+
+    async fn get_user(&self) -> AvailResult<User> {
+        let conn = self.conn.lock().unwrap();
+        let address = Self::session_address(&conn)?;
+
+        let user = conn
+            .query_row(
+                "SELECT address, username, tag, backup FROM users WHERE address = ?1",
+                params![address],
+                |row| {
+                    Ok(User {
+                        address: row.get(0)?,
+                        username: row.get(1)?,
+                        tag: row.get(2)?,
+                        backup: row.get(3)?,
+                    })
+                },
+            )
+            .optional()?;
+
+        user.ok_or_else(|| {
+            AvailError::new(
+                AvailErrorType::NotFound,
+                format!("User {} not found", address),
+                "Error getting user".to_string(),
+            )
+        })
+    }
+
+    async fn update_user(&self, user: User) -> AvailResult<String> {
+        let conn = self.conn.lock().unwrap();
+        let address = Self::session_address(&conn)?;
+
+        conn.execute(
+            "UPDATE users SET username = ?1, tag = ?2, backup = ?3 WHERE address = ?4",
+            params![user.username, user.tag, user.backup, address],
+        )?;
+
+        Ok("Username updated".to_string())
+    }
+
+    async fn update_backup(&self, request: UpdateBackupRequest) -> AvailResult<()> {
+        let conn = self.conn.lock().unwrap();
+        let address = Self::session_address(&conn)?;
+
+        conn.execute(
+            "UPDATE users SET backup = ?1 WHERE address = ?2",
+            params![request.backup, address],
+        )?;
+
+        Ok(())
+    }
+
+    async fn delete_user(&self) -> AvailResult<String> {
+        let conn = self.conn.lock().unwrap();
+        let address = Self::session_address(&conn)?;
+
+        let deleted = conn.execute("DELETE FROM users WHERE address = ?1", params![address])?;
+        conn.execute(
+            "DELETE FROM encrypted_data WHERE owner = ?1",
+            params![address],
+        )?;
+        conn.execute(
+            "DELETE FROM transaction_messages WHERE owner = ?1",
+            params![address],
+        )?;
+
+        match deleted {
+            0 => Ok("User not found".to_string()),
+            _ => Ok("User deleted".to_string()),
+        }
+    }
+
+    async fn get_address(&self, username: &str) -> AvailResult<String> {
+        let conn = self.conn.lock().unwrap();
+        Self::session_address(&conn)?;
+
+        let address: Option<String> = conn
+            .query_row(
+                "SELECT address FROM users WHERE username = ?1",
+                params![username],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        address.ok_or_else(|| {
+            AvailError::new(
+                AvailErrorType::NotFound,
+                format!("No user named {}", username),
+                "Error getting address".to_string(),
+            )
+        })
+    }
+
+    async fn get_username(&self, address: &str) -> AvailResult<Option<String>> {
+        let conn = self.conn.lock().unwrap();
+        Self::session_address(&conn)?;
+
+        let username: Option<Option<String>> = conn
+            .query_row(
+                "SELECT username FROM users WHERE address = ?1",
+                params![address],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(username.flatten())
+    }
+
+    async fn post_data(&self, data: Vec<EncryptedDataRecord>) -> AvailResult<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let address = Self::session_address(&conn)?;
+
+        Self::store_data(&conn, &address, data)
+    }
+
+    async fn update_data(&self, updates: Vec<EncryptedDataUpdateRequest>) -> AvailResult<String> {
+        let conn = self.conn.lock().unwrap();
+        let address = Self::session_address(&conn)?;
+
+        for update in updates {
+            let mut records = Self::get_records(
+                &conn,
+                "SELECT record FROM encrypted_data WHERE id = ?1 AND owner = ?2",
+                params![update.id.to_string(), address],
+            )?;
+
+            let mut record = match records.pop() {
+                Some(record) => record,
+                None => {
+                    return Err(AvailError::new(
+                        AvailErrorType::NotFound,
+                        format!("Encrypted data {} not found", update.id),
+                        "Error updating backup data.".to_string(),
+                    ))
+                }
+            };
+
+            record.ciphertext = update.ciphertext;
+            record.nonce = update.nonce;
+
+            conn.execute(
+                "UPDATE encrypted_data SET record = ?1 WHERE id = ?2",
+                params![serde_json::to_string(&record)?, update.id.to_string()],
+            )?;
+        }
+
+        Ok("Updated Succesfully".to_string())
+    }
+
+    async fn data_count(&self) -> AvailResult<i64> {
+        let conn = self.conn.lock().unwrap();
+        let address = Self::session_address(&conn)?;
+
+        Ok(conn.query_row(
+            "SELECT COUNT(*) FROM encrypted_data WHERE owner = ?1",
+            params![address],
+            |row| row.get(0),
+        )?)
+    }
+
+    async fn recover_data(&self, page: PageRequest) -> AvailResult<Data> {
+        let conn = self.conn.lock().unwrap();
+        let address = Self::session_address(&conn)?;
+
+        let records = Self::get_records(
+            &conn,
+            "SELECT record FROM encrypted_data WHERE owner = ?1 ORDER BY rowid LIMIT ?2 OFFSET ?3",
+            params![address, RECOVERY_PAGE_SIZE, page.page * RECOVERY_PAGE_SIZE],
+        )?;
+
+        let mut data = Data::new(vec![], vec![], vec![], vec![]);
+        for record in records {
+            match record.flavour {
+                EncryptedDataTypeCommon::Record => data.record_pointers.push(record),
+                EncryptedDataTypeCommon::Transaction => data.transactions.push(record),
+                EncryptedDataTypeCommon::Transition => data.transitions.push(record),
+                EncryptedDataTypeCommon::Deployment => data.deployments.push(record),
+                _ => {}
+            }
+        }
+
+        Ok(data)
+    }
+
+    async fn import_data(&self, request: DataRequest) -> AvailResult<String> {
+        let conn = self.conn.lock().unwrap();
+        let address = Self::session_address(&conn)?;
+
+        let data = request.data;
+        let records = data
+            .record_pointers
+            .into_iter()
+            .chain(data.transactions)
+            .chain(data.transitions)
+            .chain(data.deployments)
+            .collect::<Vec<EncryptedDataRecord>>();
+
+        Self::store_data(&conn, &address, records)?;
+
+        Ok("Imported Succesfully".to_string())
+    }
+
+    async fn delete_all_data(&self) -> AvailResult<String> {
+        let conn = self.conn.lock().unwrap();
+        let address = Self::session_address(&conn)?;
+
+        let deleted = conn.execute(
+            "DELETE FROM encrypted_data WHERE owner = ?1",
+            params![address],
+        )?;
+
+        Ok(format!("Deleted {} records", deleted))
+    }
+
+    async fn send_transaction_message(
+        &self,
+        mut message: EncryptedDataRecord,
+    ) -> AvailResult<String> {
+        let conn = self.conn.lock().unwrap();
+        Self::session_address(&conn)?;
+
+        let id = *message.id.get_or_insert_with(Uuid::new_v4);
+        conn.execute(
+            "INSERT OR REPLACE INTO transaction_messages (id, owner, record) VALUES (?1, ?2, ?3)",
+            params![
+                id.to_string(),
+                message.owner,
+                serde_json::to_string(&message)?
+            ],
+        )?;
+
+        Ok(id.to_string())
+    }
+
+    async fn get_transaction_messages(
+        &self,
+        request: EncryptedDataSyncRequest,
+    ) -> AvailResult<Vec<EncryptedDataRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let address = Self::session_address(&conn)?;
+
+        if address != request.owner {
+            return Err(AvailError::new(
+                AvailErrorType::Unauthorized,
+                format!("Session is not for {}", request.owner),
+                "Error checking transaction messages".to_string(),
+            ));
+        }
+
+        Self::get_records(
+            &conn,
+            "SELECT record FROM transaction_messages WHERE owner = ?1 AND synced = 0 ORDER BY rowid",
+            params![address],
+        )
+    }
+
+    async fn mark_synced(&self, ids: Vec<Uuid>) -> AvailResult<String> {
+        let conn = self.conn.lock().unwrap();
+        let address = Self::session_address(&conn)?;
+
+        for id in ids {
+            conn.execute(
+                "UPDATE transaction_messages SET synced = 1 WHERE id = ?1 AND owner = ?2",
+                params![id.to_string(), address],
+            )?;
+        }
+
+        Ok("Synced".to_string())
+    }
+
+    async fn delete_transaction_messages(&self, ids: Vec<Uuid>) -> AvailResult<String> {
+        let conn = self.conn.lock().unwrap();
+        let address = Self::session_address(&conn)?;
+
+        for id in ids {
+            conn.execute(
+                "DELETE FROM transaction_messages WHERE id = ?1 AND owner = ?2",
+                params![id.to_string(), address],
+            )?;
+        }
+
+        Ok("Deleted".to_string())
+    }
+
+    async fn request_hash(&self, address: &str) -> AvailResult<CreateSessionResponse> {
+        let hash: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect();
+
+        let response = CreateSessionResponse {
+            hash,
+            session_id: Uuid::new_v4(),
+            expires_on: Utc::now() + chrono::Duration::minutes(LOGIN_HASH_MINUTES),
+        };
+
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO sessions (session_id, address, hash, expires_on) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                response.session_id.to_string(),
+                address,
+                response.hash,
+                response.expires_on.to_rfc3339()
+            ],
+        )?;
+
+        Ok(response)
+    }
+
+    async fn login(&self, request: SignInSessionRequest) -> AvailResult<String> {
+        let conn = self.conn.lock().unwrap();
+        let (address, hash) = Self::pending_login(&conn, &request.session_id)?;
+
+        let message = SignInMessage::parse(&request.message)?;
+        if message.nonce() != &hash || message.address() != &address {
+            return Err(invalid_signature());
+        }
+
+        // The only network the wallet supports
+        if !verify_signature::<Testnet3>(&request.message, &address, &request.signature)? {
+            return Err(invalid_signature());
+        }
+
+        Self::issue_token(&conn, &request.session_id)
+    }
+
+    async fn login_with_hash(&self, request: VerifySessionRequest) -> AvailResult<String> {
+        let conn = self.conn.lock().unwrap();
+        let (address, hash) = Self::pending_login(&conn, &request.session_id)?;
+
+        if !verify_signature::<Testnet3>(&hash, &address, &request.signature)? {
+            return Err(invalid_signature());
+        }
+
+        Self::issue_token(&conn, &request.session_id)
+    }
+}
+
+fn invalid_signature() -> AvailError {
+    AvailError::new(
+        AvailErrorType::External,
+        "Invalid Signature".to_string(),
+        "Invalid Signature".to_string(),
+    )
+}
+
+#[cfg(test)]
+mod backend_tests {
+    use super::*;
+    use crate::services::authentication::session::login_message;
+    use crate::services::local_storage::utils::sign_message_w_key;
+
+    use avail_common::models::constants::TESTNET_PRIVATE_KEY;
+    use avail_common::models::encrypted_data::EncryptedData;
+    use snarkvm::prelude::{Address, PrivateKey};
+    use std::str::FromStr;
+
+    fn test_record(owner: &str, flavour: EncryptedDataTypeCommon) -> EncryptedDataRecord {
+        EncryptedDataRecord::from(EncryptedData::new(
+            Some(Uuid::new_v4()),
+            owner.to_string(),
+            "some ciphertext".to_string(),
+            "some nonce".to_string(),
+            flavour,
+            None,
+            None,
+            None,
+            Utc::now(),
+            None,
+            None,
+            "testnet3".to_string(),
+            None,
+            Some(false),
+            None,
+            None,
+            None,
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_local_backend_lifecycle() {
+        let backend = LocalBackend::in_memory().unwrap();
+        let private_key = PrivateKey::<Testnet3>::from_str(TESTNET_PRIVATE_KEY).unwrap();
+        let address = Address::<Testnet3>::try_from(&private_key)
+            .unwrap()
+            .to_string();
+
+        let user = || User::new(Some("Karp".to_string()), address.clone(), Some(1234), true);
+        backend.create_user(user()).await.unwrap();
+        assert!(backend.create_user(user()).await.is_err());
+
+        // login with the issued hash signed by the account
+        let session_request = backend.request_hash(&address).await.unwrap();
+        let message = login_message::<Testnet3>(&address, &session_request);
+        let (signature, _) = sign_message_w_key::<Testnet3>(&message, &private_key).unwrap();
+
+        let forged = SignInSessionRequest {
+            signature: signature.to_string(),
+            session_id: session_request.session_id,
+            message: message.replace(&session_request.hash, "forged"),
+        };
+        assert!(backend.login(forged).await.is_err());
+
+        let token = backend
+            .login(SignInSessionRequest {
+                signature: signature.to_string(),
+                session_id: session_request.session_id,
+                message,
+            })
+            .await
+            .unwrap();
+        SESSION.set_session_token(token);
+
+        assert_eq!(backend.get_user().await.unwrap().address, address);
+        assert_eq!(backend.get_address("Karp").await.unwrap(), address);
+
+        // backup and recovery
+        let record = test_record(&address, EncryptedDataTypeCommon::Record);
+        let transaction = test_record(&address, EncryptedDataTypeCommon::Transaction);
+        let ids = backend
+            .post_data(vec![record.clone(), transaction])
+            .await
+            .unwrap();
+        assert_eq!(ids[0], record.id.unwrap().to_string());
+        assert_eq!(backend.data_count().await.unwrap(), 2);
+
+        let recovered = backend.recover_data(PageRequest { page: 0 }).await.unwrap();
+        assert_eq!(recovered.record_pointers.len(), 1);
+        assert_eq!(recovered.transactions.len(), 1);
+
+        // transaction messages
+        let message = test_record(&address, EncryptedDataTypeCommon::TransactionMessage);
+        backend
+            .send_transaction_message(message.clone())
+            .await
+            .unwrap();
+
+        let sync_request = || EncryptedDataSyncRequest {
+            owner: address.clone(),
+            last_sync: 0,
+        };
+        let messages = backend
+            .get_transaction_messages(sync_request())
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), 1);
+
+        backend
+            .mark_synced(vec![message.id.unwrap()])
+            .await
+            .unwrap();
+        assert!(backend
+            .get_transaction_messages(sync_request())
+            .await
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(backend.delete_user().await.unwrap(), "User deleted");
+        assert_eq!(backend.data_count().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_local_backend_hash_login() {
+        let backend = LocalBackend::in_memory().unwrap();
+        let private_key = PrivateKey::<Testnet3>::from_str(TESTNET_PRIVATE_KEY).unwrap();
+        let address = Address::<Testnet3>::try_from(&private_key)
+            .unwrap()
+            .to_string();
+
+        let session_request = backend.request_hash(&address).await.unwrap();
+        let (signature, _) =
+            sign_message_w_key::<Testnet3>(&session_request.hash, &private_key).unwrap();
+        let request = || VerifySessionRequest {
+            signature: signature.to_string(),
+            session_id: session_request.session_id,
+        };
+
+        assert!(backend.login_with_hash(request()).await.is_ok());
+        // the hash can only be used once
+        assert!(backend.login_with_hash(request()).await.is_err());
+    }
+}
@@ -0,0 +1,814 @@
+use once_cell::sync::Lazy;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use snarkvm::ledger::{
+    query::Query,
+    store::{helpers::memory::BlockMemory, BlockStore},
+    Block,
+};
+use snarkvm::prelude::{ConfirmedTransaction, Network, Program, ProgramID, Transaction, Value};
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc, Mutex, RwLock,
+};
+
+use super::resilience::retry_blocking;
+use crate::models::resilience::NetworkService;
+
+use avail_common::{
+    aleo_tools::api::AleoAPIClient,
+    errors::{AvailError, AvailErrorType, AvailResult},
+};
+
+/* --Chain Access-- */
+
+/// Everything the wallet reads from or sends to the Aleo network
+pub trait ChainProvider<N: Network>: Send + Sync {
+    fn latest_height(&self) -> AvailResult<u32>;
+
+    fn get_block(&self, height: u32) -> AvailResult<Block<N>>;
+
+    /// Blocks from `start_height` up to but excluding `end_height`
+    fn get_blocks(&self, start_height: u32, end_height: u32) -> AvailResult<Vec<Block<N>>>;
+
+    fn get_transaction(&self, transaction_id: N::TransactionID) -> AvailResult<Transaction<N>>;
+
+    fn get_block_by_hash(&self, block_hash: N::BlockHash) -> AvailResult<Block<N>>;
+
+    /// Hash of the block a transaction landed in, accepted, rejected or aborted.
+    /// None while it is unconfirmed.
+    fn find_block_hash(
+        &self,
+        transaction_id: N::TransactionID,
+    ) -> AvailResult<Option<N::BlockHash>>;
+
+    /// The accepted transaction with its finalize operations, None if it was not accepted
+    fn get_confirmed_transaction(
+        &self,
+        transaction_id: N::TransactionID,
+    ) -> AvailResult<Option<ConfirmedTransaction<N>>>;
+
+    /// None if the program has not been deployed
+    fn get_program(&self, program_id: &ProgramID<N>) -> AvailResult<Option<Program<N>>>;
+
+    /// None if the mapping has no value at the key
+    fn get_mapping_value(
+        &self,
+        program_id: &str,
+        mapping_name: &str,
+        key: &str,
+    ) -> AvailResult<Option<Value<N>>>;
+
+    /// Broadcasts a transaction, returns the node's response
+    fn transaction_broadcast(&self, transaction: Transaction<N>) -> AvailResult<String>;
+
+    /// Endpoint of the provider
+    fn base_url(&self) -> String;
+
+    /// State root and record paths are read from here when proving, the endpoint by default
+    fn query(&self) -> AvailResult<Query<N, BlockMemory<N>>> {
+        Ok(Query::from(self.base_url()))
+    }
+
+    /// Fetches a program that must already be deployed
+    fn require_program(&self, program_id: &ProgramID<N>) -> AvailResult<Program<N>> {
+        match self.get_program(program_id)? {
+            Some(program) => Ok(program),
+            None => Err(AvailError::new(
+                AvailErrorType::NotFound,
+                format!("Program {} not found", program_id),
+                format!("The program '{}' is not deployed.", program_id),
+            )),
+        }
+    }
+}
+
+pub type ChainClient<N> = Arc<dyn ChainProvider<N>>;
+
+/// Provider returned by `setup_client` instead of the node, set by tests and offline tooling
+static CHAIN_PROVIDER_OVERRIDE: Lazy<RwLock<Option<Box<dyn Any + Send + Sync>>>> =
+    Lazy::new(|| RwLock::new(None));
+
+pub fn set_chain_provider<N: Network>(provider: ChainClient<N>) {
+    *CHAIN_PROVIDER_OVERRIDE.write().unwrap() = Some(Box::new(provider));
+}
+
+pub fn clear_chain_provider() {
+    *CHAIN_PROVIDER_OVERRIDE.write().unwrap() = None;
+}
+
+thread_local! {
+    /// Provider injected for the current thread only, takes precedence over the process wide one
+    static SCOPED_CHAIN_PROVIDER: RefCell<Option<Box<dyn Any>>> = RefCell::new(None);
+}
+
+/// Puts the previous scoped provider back when dropped, so a panicking `f` does not leak its provider
+struct ScopedProviderGuard {
+    previous: Option<Box<dyn Any>>,
+}
+
+impl Drop for ScopedProviderGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        SCOPED_CHAIN_PROVIDER.with(|scoped| *scoped.borrow_mut() = previous);
+    }
+}
+
+/// Runs `f` with `provider` returned by `setup_client` on this thread, so tests do not see each other's chains.
+/// Only this thread sees it: work `f` hands to rayon, `spawn_blocking` or other threads uses the
+/// process wide provider or the node, so code under test must call `setup_client` on the calling thread.
+pub fn with_chain_provider<N: Network, T>(provider: ChainClient<N>, f: impl FnOnce() -> T) -> T {
+    let _guard = ScopedProviderGuard {
+        previous: SCOPED_CHAIN_PROVIDER.with(|scoped| scoped.replace(Some(Box::new(provider)))),
+    };
+
+    f()
+}
+
+/// The injected provider, if one was set for this network
+pub fn get_chain_provider_override<N: Network>() -> Option<ChainClient<N>> {
+    let scoped = SCOPED_CHAIN_PROVIDER.with(|scoped| {
+        scoped
+            .borrow()
+            .as_ref()
+            .and_then(|provider| provider.downcast_ref::<ChainClient<N>>())
+            .cloned()
+    });
+
+    scoped.or_else(|| {
+        CHAIN_PROVIDER_OVERRIDE
+            .read()
+            .unwrap()
+            .as_ref()
+            .and_then(|provider| provider.downcast_ref::<ChainClient<N>>())
+            .cloned()
+    })
+}
+
+fn node_error(action: String, e: impl std::fmt::Display) -> AvailError {
+    AvailError::new(
+        AvailErrorType::Node,
+        format!("Error {}: {}", action, e),
+        format!("Error {}", action),
+    )
+}
+
+/* Node */
+
+/// Reads from and broadcasts to an Aleo node over its REST API
+pub struct NodeChainProvider<N: Network> {
+    client: AleoAPIClient<N>,
+}
+
+impl<N: Network> NodeChainProvider<N> {
+    pub fn new(client: AleoAPIClient<N>) -> Self {
+        Self { client }
+    }
+
+    /// Queries an endpoint the client has no method for, None if the node has nothing at it
+    fn query<T: DeserializeOwned>(&self, path: &str, action: String) -> AvailResult<Option<T>> {
+        let url = format!(
+            "{}/{}/{}",
+            self.client.base_url(),
+            self.client.network_id(),
+            path
+        );
+
+        match ureq::get(&url).call() {
+            Ok(response) => Ok(Some(
+                response
+                    .into_json::<T>()
+                    .map_err(|e| node_error(action, e))?,
+            )),
+            Err(ureq::Error::Status(code, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                let missing = body.to_lowercase();
+                match code == 404 || missing.contains("missing") || missing.contains("not found") {
+                    true => Ok(None),
+                    false => Err(node_error(
+                        action,
+                        format!("status code {}: {}", code, body),
+                    )),
+                }
+            }
+            Err(e) => Err(AvailError::new(
+                AvailErrorType::Network,
+                format!("Error {}: {}", action, e),
+                "Could not reach the Aleo node".to_string(),
+            )),
+        }
+    }
+}
+
+impl<N: Network> ChainProvider<N> for NodeChainProvider<N> {
+    fn latest_height(&self) -> AvailResult<u32> {
+        self.client
+            .latest_height()
+            .map_err(|e| node_error("fetching the latest height".to_string(), e))
+    }
+
+    fn get_block(&self, height: u32) -> AvailResult<Block<N>> {
+        self.client
+            .get_block(height)
+            .map_err(|e| node_error(format!("fetching block {}", height), e))
+    }
+
+    fn get_blocks(&self, start_height: u32, end_height: u32) -> AvailResult<Vec<Block<N>>> {
+        self.client
+            .get_blocks(start_height, end_height)
+            .map_err(|e| {
+                node_error(
+                    format!("fetching blocks {} to {}", start_height, end_height),
+                    e,
+                )
+            })
+    }
+
+    fn get_transaction(&self, transaction_id: N::TransactionID) -> AvailResult<Transaction<N>> {
+        self.client
+            .get_transaction(transaction_id)
+            .map_err(|e| node_error(format!("fetching transaction {}", transaction_id), e))
+    }
+
+    fn get_block_by_hash(&self, block_hash: N::BlockHash) -> AvailResult<Block<N>> {
+        let action = format!("fetching block {}", block_hash);
+        match self.query::<Block<N>>(&format!("block/{}", block_hash), action.clone())? {
+            Some(block) => Ok(block),
+            None => Err(node_error(action, "block not found")),
+        }
+    }
+
+    fn find_block_hash(
+        &self,
+        transaction_id: N::TransactionID,
+    ) -> AvailResult<Option<N::BlockHash>> {
+        // the node answers null for transactions it has not seen in a block
+        Ok(self
+            .query::<Option<N::BlockHash>>(
+                &format!("find/blockHash/{}", transaction_id),
+                format!("finding the block of transaction {}", transaction_id),
+            )?
+            .flatten())
+    }
+
+    fn get_confirmed_transaction(
+        &self,
+        transaction_id: N::TransactionID,
+    ) -> AvailResult<Option<ConfirmedTransaction<N>>> {
+        self.query(
+            &format!("transaction/confirmed/{}", transaction_id),
+            format!("fetching confirmed transaction {}", transaction_id),
+        )
+    }
+
+    fn get_program(&self, program_id: &ProgramID<N>) -> AvailResult<Option<Program<N>>> {
+        match self.client.get_program(*program_id) {
+            Ok(program) => Ok(Some(program)),
+            Err(e) => {
+                let error = e.to_string().to_lowercase();
+                match error.contains("missing") || error.contains("not found") {
+                    true => Ok(None),
+                    false => Err(node_error(format!("fetching program {}", program_id), e)),
+                }
+            }
+        }
+    }
+
+    fn get_mapping_value(
+        &self,
+        program_id: &str,
+        mapping_name: &str,
+        key: &str,
+    ) -> AvailResult<Option<Value<N>>> {
+        match self.client.get_mapping_value(program_id, mapping_name, key) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) => match e.to_string().as_str() {
+                "Mapping not found" => Ok(None),
+                _ => Err(node_error(
+                    format!("reading {}/{}", program_id, mapping_name),
+                    e,
+                )),
+            },
+        }
+    }
+
+    fn transaction_broadcast(&self, transaction: Transaction<N>) -> AvailResult<String> {
+        let id = transaction.id();
+        self.client
+            .transaction_broadcast(transaction)
+            .map_err(|e| node_error(format!("broadcasting transaction {}", id), e))
+    }
+
+    fn base_url(&self) -> String {
+        self.client.base_url().to_string()
+    }
+}
+
+/* Resilient */
+
+/// The node already has the transaction, in its mempool or in a block
+fn is_duplicate_broadcast(error: &AvailError) -> bool {
+    error.internal_msg.to_lowercase().contains("already exists")
+}
+
+/// Applies the network retry, timeout and circuit breaker policy to another provider
+pub struct ResilientChainProvider<N: Network> {
+    inner: ChainClient<N>,
+}
+
+impl<N: Network> ResilientChainProvider<N> {
+    pub fn new(inner: ChainClient<N>) -> Self {
+        Self { inner }
+    }
+
+    fn call<T: Send + 'static>(
+        &self,
+        action: &str,
+        call: impl Fn(&ChainClient<N>) -> AvailResult<T> + Send + Sync + 'static,
+    ) -> AvailResult<T> {
+        let inner = self.inner.clone();
+        retry_blocking(NetworkService::Node, action, move || call(&inner))
+    }
+}
+
+impl<N: Network> ChainProvider<N> for ResilientChainProvider<N> {
+    fn latest_height(&self) -> AvailResult<u32> {
+        self.call("fetching the latest height", |inner| inner.latest_height())
+    }
+
+    fn get_block(&self, height: u32) -> AvailResult<Block<N>> {
+        self.call(&format!("fetching block {}", height), move |inner| {
+            inner.get_block(height)
+        })
+    }
+
+    fn get_blocks(&self, start_height: u32, end_height: u32) -> AvailResult<Vec<Block<N>>> {
+        self.call(
+            &format!("fetching blocks {} to {}", start_height, end_height),
+            move |inner| inner.get_blocks(start_height, end_height),
+        )
+    }
+
+    fn get_transaction(&self, transaction_id: N::TransactionID) -> AvailResult<Transaction<N>> {
+        self.call(
+            &format!("fetching transaction {}", transaction_id),
+            move |inner| inner.get_transaction(transaction_id),
+        )
+    }
+
+    fn get_block_by_hash(&self, block_hash: N::BlockHash) -> AvailResult<Block<N>> {
+        self.call(&format!("fetching block {}", block_hash), move |inner| {
+            inner.get_block_by_hash(block_hash)
+        })
+    }
+
+    fn find_block_hash(
+        &self,
+        transaction_id: N::TransactionID,
+    ) -> AvailResult<Option<N::BlockHash>> {
+        self.call(
+            &format!("finding the block of transaction {}", transaction_id),
+            move |inner| inner.find_block_hash(transaction_id),
+        )
+    }
+
+    fn get_confirmed_transaction(
+        &self,
+        transaction_id: N::TransactionID,
+    ) -> AvailResult<Option<ConfirmedTransaction<N>>> {
+        self.call(
+            &format!("fetching confirmed transaction {}", transaction_id),
+            move |inner| inner.get_confirmed_transaction(transaction_id),
+        )
+    }
+
+    fn get_program(&self, program_id: &ProgramID<N>) -> AvailResult<Option<Program<N>>> {
+        let program_id = *program_id;
+        self.call(&format!("fetching program {}", program_id), move |inner| {
+            inner.get_program(&program_id)
+        })
+    }
+
+    fn get_mapping_value(
+        &self,
+        program_id: &str,
+        mapping_name: &str,
+        key: &str,
+    ) -> AvailResult<Option<Value<N>>> {
+        let (program_id, mapping_name, key) = (
+            program_id.to_string(),
+            mapping_name.to_string(),
+            key.to_string(),
+        );
+        self.call(
+            &format!("reading {}/{}", program_id, mapping_name),
+            move |inner| inner.get_mapping_value(&program_id, &mapping_name, &key),
+        )
+    }
+
+    /// Retrying is safe, the node accepts a transaction id once. An attempt that timed out may still
+    /// have reached the node, so a retry rejected as a duplicate means the transaction was broadcast.
+    fn transaction_broadcast(&self, transaction: Transaction<N>) -> AvailResult<String> {
+        let id = transaction.id().to_string();
+        let attempts = AtomicU32::new(0);
+
+        self.call(&format!("broadcasting transaction {}", id), move |inner| {
+            let retried = attempts.fetch_add(1, Ordering::SeqCst) > 0;
+
+            match inner.transaction_broadcast(transaction.clone()) {
+                Err(e) if retried && is_duplicate_broadcast(&e) => Ok(id.clone()),
+                result => result,
+            }
+        })
+    }
+
+    fn base_url(&self) -> String {
+        self.inner.base_url()
+    }
+
+    fn query(&self) -> AvailResult<Query<N, BlockMemory<N>>> {
+        self.inner.query()
+    }
+}
+
+/* Mock */
+
+/// Chain state a mock provider is loaded with, blocks and programs are stored in their JSON and source forms
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ChainFixture {
+    #[serde(default)]
+    pub blocks: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub programs: Vec<String>,
+    /// Values keyed by `program_id/mapping_name/key`
+    #[serde(default)]
+    pub mappings: HashMap<String, String>,
+}
+
+/// In-memory chain replaying fixture blocks, broadcast transactions are recorded instead of sent.
+/// Proving queries an in-memory block store of the fixture blocks, which then have to start at genesis.
+pub struct MockChainProvider<N: Network> {
+    blocks: RwLock<Vec<Block<N>>>,
+    programs: RwLock<HashMap<ProgramID<N>, Program<N>>>,
+    mappings: RwLock<HashMap<String, Value<N>>>,
+    broadcasted: Mutex<Vec<Transaction<N>>>,
+}
+
+impl<N: Network> MockChainProvider<N> {
+    pub fn new() -> Self {
+        Self {
+            blocks: RwLock::new(vec![]),
+            programs: RwLock::new(HashMap::new()),
+            mappings: RwLock::new(HashMap::new()),
+            broadcasted: Mutex::new(vec![]),
+        }
+    }
+
+    pub fn from_fixture(fixture: ChainFixture) -> AvailResult<Self> {
+        let provider = Self::new();
+
+        for block in fixture.blocks {
+            provider.push_block(serde_json::from_value(block)?);
+        }
+
+        for source in fixture.programs {
+            provider.add_program(Program::<N>::from_str(&source)?);
+        }
+
+        for (path, value) in fixture.mappings {
+            let (program_id, rest) = path.split_once('/').unwrap_or((&path, ""));
+            let (mapping_name, key) = rest.split_once('/').unwrap_or((rest, ""));
+            provider.set_mapping_value(
+                program_id,
+                mapping_name,
+                key,
+                Value::<N>::from_str(&value)?,
+            );
+        }
+
+        Ok(provider)
+    }
+
+    pub fn from_fixture_file(path: &Path) -> AvailResult<Self> {
+        let fixture: ChainFixture = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        Self::from_fixture(fixture)
+    }
+
+    /// Appends a block, the latest height follows the highest block
+    pub fn push_block(&self, block: Block<N>) {
+        let mut blocks = self.blocks.write().unwrap();
+        blocks.push(block);
+        blocks.sort_by_key(|block| block.height());
+    }
+
+    pub fn add_program(&self, program: Program<N>) {
+        self.programs
+            .write()
+            .unwrap()
+            .insert(*program.id(), program);
+    }
+
+    pub fn set_mapping_value(
+        &self,
+        program_id: &str,
+        mapping_name: &str,
+        key: &str,
+        value: Value<N>,
+    ) {
+        self.mappings
+            .write()
+            .unwrap()
+            .insert(mapping_path(program_id, mapping_name, key), value);
+    }
+
+    /// Transactions broadcast so far, in order
+    pub fn broadcasted(&self) -> Vec<Transaction<N>> {
+        self.broadcasted.lock().unwrap().clone()
+    }
+}
+
+impl<N: Network> Default for MockChainProvider<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn mapping_path(program_id: &str, mapping_name: &str, key: &str) -> String {
+    format!("{}/{}/{}", program_id, mapping_name, key)
+}
+
+fn missing(what: String) -> AvailError {
+    AvailError::new(
+        AvailErrorType::NotFound,
+        format!("{} not found", what),
+        format!("{} not found", what),
+    )
+}
+
+impl<N: Network> ChainProvider<N> for MockChainProvider<N> {
+    fn latest_height(&self) -> AvailResult<u32> {
+        Ok(self
+            .blocks
+            .read()
+            .unwrap()
+            .last()
+            .map(|block| block.height())
+            .unwrap_or(0))
+    }
+
+    fn get_block(&self, height: u32) -> AvailResult<Block<N>> {
+        self.blocks
+            .read()
+            .unwrap()
+            .iter()
+            .find(|block| block.height() == height)
+            .cloned()
+            .ok_or_else(|| missing(format!("Block {}", height)))
+    }
+
+    fn get_blocks(&self, start_height: u32, end_height: u32) -> AvailResult<Vec<Block<N>>> {
+        Ok(self
+            .blocks
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|block| block.height() >= start_height && block.height() < end_height)
+            .cloned()
+            .collect())
+    }
+
+    fn get_transaction(&self, transaction_id: N::TransactionID) -> AvailResult<Transaction<N>> {
+        if let Some(transaction) = self
+            .blocks
+            .read()
+            .unwrap()
+            .iter()
+            .find_map(|block| block.get_transaction(&transaction_id).cloned())
+        {
+            return Ok(transaction);
+        }
+
+        self.broadcasted
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|transaction| transaction.id() == transaction_id)
+            .cloned()
+            .ok_or_else(|| missing(format!("Transaction {}", transaction_id)))
+    }
+
+    fn get_block_by_hash(&self, block_hash: N::BlockHash) -> AvailResult<Block<N>> {
+        self.blocks
+            .read()
+            .unwrap()
+            .iter()
+            .find(|block| block.hash() == block_hash)
+            .cloned()
+            .ok_or_else(|| missing(format!("Block {}", block_hash)))
+    }
+
+    fn find_block_hash(
+        &self,
+        transaction_id: N::TransactionID,
+    ) -> AvailResult<Option<N::BlockHash>> {
+        Ok(self
+            .blocks
+            .read()
+            .unwrap()
+            .iter()
+            .find(|block| {
+                block.transactions().get(&transaction_id).is_some()
+                    || block.aborted_transaction_ids().contains(&transaction_id)
+                    || block.transactions().iter().any(|transaction| {
+                        transaction.to_unconfirmed_transaction_id().ok() == Some(transaction_id)
+                    })
+            })
+            .map(|block| block.hash()))
+    }
+
+    fn get_confirmed_transaction(
+        &self,
+        transaction_id: N::TransactionID,
+    ) -> AvailResult<Option<ConfirmedTransaction<N>>> {
+        Ok(self
+            .blocks
+            .read()
+            .unwrap()
+            .iter()
+            .find_map(|block| block.transactions().get(&transaction_id).cloned()))
+    }
+
+    fn get_program(&self, program_id: &ProgramID<N>) -> AvailResult<Option<Program<N>>> {
+        if program_id.to_string() == "credits.aleo" {
+            return Ok(Some(Program::<N>::credits()?));
+        }
+
+        Ok(self.programs.read().unwrap().get(program_id).cloned())
+    }
+
+    fn get_mapping_value(
+        &self,
+        program_id: &str,
+        mapping_name: &str,
+        key: &str,
+    ) -> AvailResult<Option<Value<N>>> {
+        Ok(self
+            .mappings
+            .read()
+            .unwrap()
+            .get(&mapping_path(program_id, mapping_name, key))
+            .cloned())
+    }
+
+    fn transaction_broadcast(&self, transaction: Transaction<N>) -> AvailResult<String> {
+        let id = transaction.id().to_string();
+        self.broadcasted.lock().unwrap().push(transaction);
+
+        Ok(id)
+    }
+
+    fn base_url(&self) -> String {
+        "mock://chain".to_string()
+    }
+
+    fn query(&self) -> AvailResult<Query<N, BlockMemory<N>>> {
+        let store = BlockStore::<N, BlockMemory<N>>::open(None)?;
+
+        for block in self.blocks.read().unwrap().iter() {
+            store.insert(block)?;
+        }
+
+        Ok(Query::VM(store))
+    }
+}
+
+/// Unproven single transition transaction whose only output is a `record_name` record owned by `owner`,
+/// for tests to broadcast into a mock chain. `data` lists the record entries besides the owner.
+/// The transition is committed to as if `owner` executed it, so their view key derives its transition view key.
+#[cfg(test)]
+pub fn mock_record_transaction<N: Network>(
+    owner: &snarkvm::prelude::Address<N>,
+    program_id: &str,
+    function_name: &str,
+    record_name: &str,
+    data: &str,
+) -> AvailResult<(
+    Transaction<N>,
+    snarkvm::prelude::Record<N, snarkvm::prelude::Plaintext<N>>,
+)> {
+    use snarkvm::prelude::{
+        Execution, Identifier, Output, Plaintext, Record, Scalar, ToBits, Transition, Uniform,
+    };
+
+    let rng = &mut rand::thread_rng();
+    let randomizer = Scalar::<N>::rand(rng);
+    let nonce = N::g_scalar_multiply(&randomizer);
+
+    let record = Record::<N, Plaintext<N>>::from_str(&format!(
+        "{{ owner: {}.private, {}, _nonce: {}.public }}",
+        owner, data, nonce
+    ))?;
+    let program_id = ProgramID::<N>::from_str(program_id)?;
+    let commitment = record.to_commitment(&program_id, &Identifier::<N>::from_str(record_name)?)?;
+    let ciphertext = record.encrypt(randomizer)?;
+    let checksum = N::hash_bhp1024(&ciphertext.to_bits_le())?;
+
+    let transition_randomizer = Scalar::<N>::rand(rng);
+    let tpk = N::g_scalar_multiply(&transition_randomizer);
+    let tvk = (**owner * transition_randomizer).to_x_coordinate();
+
+    let transition = Transition::new(
+        program_id,
+        Identifier::<N>::from_str(function_name)?,
+        vec![],
+        vec![Output::Record(commitment, checksum, Some(ciphertext))],
+        tpk,
+        N::hash_psd2(&[tvk])?,
+    )?;
+    let execution = Execution::from([transition].into_iter(), N::StateRoot::default(), None)?;
+
+    Ok((Transaction::from_execution(execution, None)?, record))
+}
+
+#[cfg(test)]
+mod chain_provider_tests {
+    use super::*;
+    use avail_common::models::constants::TESTNET_ADDRESS;
+    use snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_mock_fixture_lookups() {
+        let mut fixture = ChainFixture::default();
+        fixture.mappings.insert(
+            format!("credits.aleo/account/{}", TESTNET_ADDRESS),
+            "1500000u64".to_string(),
+        );
+
+        let provider = MockChainProvider::<Testnet3>::from_fixture(fixture).unwrap();
+
+        assert_eq!(provider.latest_height().unwrap(), 0);
+        assert!(provider.get_block(1).is_err());
+        assert!(provider.get_blocks(0, 50).unwrap().is_empty());
+
+        let balance = provider
+            .get_mapping_value("credits.aleo", "account", TESTNET_ADDRESS)
+            .unwrap();
+        assert_eq!(balance.unwrap().to_string(), "1500000u64");
+        assert!(provider
+            .get_mapping_value("credits.aleo", "account", "aleo1missing")
+            .unwrap()
+            .is_none());
+
+        let credits = ProgramID::<Testnet3>::from_str("credits.aleo").unwrap();
+        assert!(provider.require_program(&credits).is_ok());
+        let unknown = ProgramID::<Testnet3>::from_str("unknown_program.aleo").unwrap();
+        assert!(provider.get_program(&unknown).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_scoped_chain_provider() {
+        let provider: ChainClient<Testnet3> = Arc::new(MockChainProvider::<Testnet3>::new());
+
+        with_chain_provider::<Testnet3, _>(provider, || {
+            let injected = get_chain_provider_override::<Testnet3>().unwrap();
+            assert_eq!(injected.base_url(), "mock://chain");
+
+            // other threads, like other tests, keep their own provider
+            let elsewhere =
+                std::thread::spawn(|| get_chain_provider_override::<Testnet3>().is_some());
+            assert_eq!(
+                elsewhere.join().unwrap(),
+                CHAIN_PROVIDER_OVERRIDE.read().unwrap().is_some()
+            );
+        });
+
+        assert!(SCOPED_CHAIN_PROVIDER.with(|scoped| scoped.borrow().is_none()));
+
+        // a panicking test still restores the provider it replaced
+        let panicked = std::panic::catch_unwind(|| {
+            let provider: ChainClient<Testnet3> = Arc::new(MockChainProvider::<Testnet3>::new());
+            with_chain_provider::<Testnet3, ()>(provider, || panic!("test failure"))
+        });
+        assert!(panicked.is_err());
+        assert!(SCOPED_CHAIN_PROVIDER.with(|scoped| scoped.borrow().is_none()));
+    }
+
+    #[test]
+    fn test_resilient_provider_passes_through() {
+        let mock: ChainClient<Testnet3> = Arc::new(MockChainProvider::<Testnet3>::new());
+        let provider = ResilientChainProvider::new(mock);
+
+        assert_eq!(provider.latest_height().unwrap(), 0);
+        assert_eq!(provider.base_url(), "mock://chain");
+        // missing blocks are not retried
+        assert_eq!(
+            provider.get_block(1).unwrap_err().error_type,
+            AvailErrorType::NotFound
+        );
+    }
+}
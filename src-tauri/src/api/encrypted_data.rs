@@ -1,10 +1,9 @@
 use snarkvm::{console::program::Itertools, prelude::Network};
 use std::str::FromStr;
-use tauri_plugin_http::reqwest;
 use uuid::Uuid;
 
 use crate::{
-    api::client::get_rm_client_with_session,
+    api::backend::{backend, RECOVERY_PAGE_SIZE},
     models::pointers::message::TransactionMessage,
     services::local_storage::{
         persistent_storage::{get_address_string, get_last_tx_sync, update_last_tx_sync},
@@ -13,7 +12,7 @@ use crate::{
 };
 
 use avail_common::{
-    errors::{AvError, AvailError, AvailErrorType, AvailResult},
+    errors::{AvailError, AvailErrorType, AvailResult},
     models::{
         encrypted_data::{
             Data, DataRequest, EncryptedData, EncryptedDataRecord, EncryptedDataSyncRequest,
@@ -47,26 +46,7 @@ pub async fn update_data(data: Vec<EncryptedData>, idx: Vec<String>) -> AvailRes
             })
             .collect::<Result<Vec<EncryptedDataUpdateRequest>, AvailError>>()?;
 
-        let res = get_rm_client_with_session(reqwest::Method::PUT, "data")?
-            .json(&request)
-            .send()
-            .await?;
-
-        if res.status() == 200 {
-            let _result = res.text().await?;
-        } else if res.status() == 401 {
-            return Err(AvailError::new(
-                AvailErrorType::Unauthorized,
-                "User session has expired.".to_string(),
-                "Your session has expired, please authenticate again.".to_string(),
-            ));
-        } else {
-            return Err(AvailError::new(
-                AvailErrorType::External,
-                "Error updating encrypted data record ".to_string(),
-                "Error updating backup data.".to_string(),
-            ));
-        }
+        backend()?.update_data(request).await?;
     }
 
     Ok("Updated Succesfully".to_string())
@@ -74,21 +54,7 @@ pub async fn update_data(data: Vec<EncryptedData>, idx: Vec<String>) -> AvailRes
 
 /// update transactions received to synced
 pub async fn synced(ids: Vec<Uuid>) -> AvailResult<String> {
-    let res = get_rm_client_with_session(reqwest::Method::PUT, "sync")?
-        .json(&ids)
-        .send()
-        .await?;
-
-    if res.status() == 200 {
-        let result = res.text().await?;
-        Ok(result)
-    } else {
-        Err(AvailError::new(
-            AvailErrorType::External,
-            "Error syncing data".to_string(),
-            "Error syncing data".to_string(),
-        ))
-    }
+    backend()?.mark_synced(ids).await
 }
 
 ///recover_txs get all blocks, tx_in and tx_out back locally
@@ -102,198 +68,90 @@ pub async fn get_new_transaction_messages<N: Network>(
         last_sync: last_sync_time,
     };
 
-    let res = get_rm_client_with_session(reqwest::Method::POST, "txs_received")?
-        .json(&request)
-        .send()
-        .await?;
-
-    if res.status() == 200 {
-        // update last sync time
-        update_last_tx_sync(chrono::Utc::now())?;
-
-        let result: Vec<EncryptedDataRecord> = res.json().await?;
-
-        println!("Enc Data {:?}", result);
-        let encrypted_txs = result
-            .clone()
-            .into_iter()
-            .map(|x| x.to_enrypted_struct::<N>())
-            .collect::<AvailResult<Vec<EncryptedStruct<N>>>>()?;
-
-        let v_key = VIEWSESSION.get_instance::<N>()?;
-
-        let txs_in: Vec<TransactionMessage<N>> = encrypted_txs
-            .into_iter()
-            .map(|x| x.decrypt(v_key))
-            .collect::<Result<Vec<TransactionMessage<N>>, _>>()?;
-
-        let ids = result
-            .into_iter()
-            .map(|x| {
-                x.id.ok_or_else(|| {
-                    AvailError::new(
-                        AvailErrorType::Internal,
-                        "Transaction id not found".to_string(),
-                        "Transaction Id not found".to_string(),
-                    )
-                })
+    let result = backend()?.get_transaction_messages(request).await?;
+
+    // update last sync time
+    update_last_tx_sync(chrono::Utc::now())?;
+
+    println!("Enc Data {:?}", result);
+    let encrypted_txs = result
+        .clone()
+        .into_iter()
+        .map(|x| x.to_enrypted_struct::<N>())
+        .collect::<AvailResult<Vec<EncryptedStruct<N>>>>()?;
+
+    let v_key = VIEWSESSION.get_instance::<N>()?;
+
+    let txs_in: Vec<TransactionMessage<N>> = encrypted_txs
+        .into_iter()
+        .map(|x| x.decrypt(v_key))
+        .collect::<Result<Vec<TransactionMessage<N>>, _>>()?;
+
+    let ids = result
+        .into_iter()
+        .map(|x| {
+            x.id.ok_or_else(|| {
+                AvailError::new(
+                    AvailErrorType::Internal,
+                    "Transaction id not found".to_string(),
+                    "Transaction Id not found".to_string(),
+                )
             })
-            .collect::<Result<Vec<Uuid>, AvailError>>()?;
-
-        Ok((txs_in, ids))
-    } else if res.status() == 401 {
-        Err(AvailError::new(
-            AvailErrorType::Unauthorized,
-            "User session has expired.".to_string(),
-            "Your session has expired, please authenticate again.".to_string(),
-        ))
-    } else {
-        Err(AvailError::new(
-            AvailErrorType::External,
-            "Error checking transaction messages".to_string(),
-            "Error checking transaction messages".to_string(),
-        ))
-    }
+        })
+        .collect::<Result<Vec<Uuid>, AvailError>>()?;
+
+    Ok((txs_in, ids))
 }
 
 pub async fn post_encrypted_data(request: Vec<EncryptedData>) -> AvailResult<Vec<String>> {
+    let records = request
+        .into_iter()
+        .map(EncryptedDataRecord::from)
+        .collect::<Vec<EncryptedDataRecord>>();
+
+    post_encrypted_records(records).await
+}
+
+pub async fn post_encrypted_records(records: Vec<EncryptedDataRecord>) -> AvailResult<Vec<String>> {
     const MAX_BATCH_SIZE: usize = 300;
     let mut ids: Vec<String> = Vec::new();
 
     // Split the request into batches of MAX_BATCH_SIZE
-    let batches = request.chunks(MAX_BATCH_SIZE);
+    let batches = records.chunks(MAX_BATCH_SIZE);
 
     for batch in batches {
-        let request = batch
-            .into_iter()
-            .map(|data| EncryptedDataRecord::from(data.to_owned()))
-            .collect::<Vec<EncryptedDataRecord>>();
-
-        let res = get_rm_client_with_session(reqwest::Method::POST, "data")?
-            .json(&request)
-            .send()
-            .await?;
-
-        if res.status() == 200 {
-            let result = res.text().await?;
-            let batch_ids: Vec<String> = serde_json::from_str(&result)?;
-            ids.extend(batch_ids);
-        } else if res.status() == 401 {
-            return Err(AvailError::new(
-                AvailErrorType::Unauthorized,
-                "User session has expired.".to_string(),
-                "Your session has expired, please authenticate again.".to_string(),
-            ));
-        } else {
-            return Err(AvailError::new(
-                AvailErrorType::External,
-                "Error posting encrypted data ".to_string(),
-                "".to_string(),
-            ));
-        }
+        let batch_ids = backend()?.post_data(batch.to_vec()).await?;
+        ids.extend(batch_ids);
     }
 
     Ok(ids)
 }
 
 pub async fn send_transaction_in(request: EncryptedData) -> AvailResult<String> {
-    let res = get_rm_client_with_session(reqwest::Method::POST, "tx_sent")?
-        .json(&EncryptedDataRecord::from(request))
-        .send()
-        .await?;
-
-    if res.status() == 200 {
-        let result = res.text().await?;
-
-        Ok(result)
-    } else {
-        Err(AvailError::new(
-            AvailErrorType::External,
-            "Error posting encrypted data ".to_string(),
-            "".to_string(),
-        ))
-    }
+    backend()?
+        .send_transaction_message(EncryptedDataRecord::from(request))
+        .await
 }
 
 pub async fn delete_invalid_transactions_in(ids: Vec<Uuid>) -> AvailResult<String> {
-    let res = get_rm_client_with_session(reqwest::Method::DELETE, "txs_in")?
-        .json(&ids)
-        .send()
-        .await?;
-
-    if res.status() == 200 {
-        let result = res.text().await?;
-
-        Ok(result)
-    } else if res.status() == 401 {
-        Err(AvailError::new(
-            AvailErrorType::Unauthorized,
-            "User session has expired.".to_string(),
-            "Your session has expired, please authenticate again.".to_string(),
-        ))
-    } else {
-        Err(AvailError::new(
-            AvailErrorType::External,
-            "Error deleting invalid transaction messages".to_string(),
-            "Error deleting invalid transaction messages".to_string(),
-        ))
-    }
+    backend()?.delete_transaction_messages(ids).await
 }
 
 pub async fn get_data_count() -> AvailResult<i64> {
-    let res = get_rm_client_with_session(reqwest::Method::GET, "data_count")?
-        .send()
-        .await?;
-
-    if res.status() == 200 {
-        let result = res.text().await?;
-        let count = result.parse::<i64>()?;
-        Ok(count)
-    } else if res.status() == 401 {
-        Err(AvailError::new(
-            AvailErrorType::Unauthorized,
-            "User session has expired.".to_string(),
-            "Your session has expired, please authenticate again.".to_string(),
-        ))
-    } else {
-        Err(AvailError::new(
-            AvailErrorType::External,
-            "Error getting encrypted data count".to_string(),
-            "Error getting encrypted data count".to_string(),
-        ))
-    }
+    backend()?.data_count().await
 }
 
 pub async fn recover_data(_address: &str) -> AvailResult<Data> {
     let data_count = get_data_count().await?;
-    let pages = (data_count as f64 / 300.0).ceil() as i64;
+    let pages = (data_count as f64 / RECOVERY_PAGE_SIZE as f64).ceil() as i64;
 
     let mut encrypted_data: Vec<Data> = vec![];
+    let backend = backend()?;
 
     for page in 0..pages {
         let page_request = PageRequest { page };
 
-        let res = get_rm_client_with_session(reqwest::Method::GET, "recover_data")?
-            .json(&page_request)
-            .send()
-            .await?;
-
-        if res.status() == 200 {
-            let result: Data = res.json().await?;
-            encrypted_data.push(result);
-        } else if res.status() == 401 {
-            return Err(AvailError::new(
-                AvailErrorType::Unauthorized,
-                "User session has expired.".to_string(),
-                "Your session has expired, please authenticate again.".to_string(),
-            ));
-        } else {
-            return Err(AvailError::new(
-                AvailErrorType::External,
-                "Error recovering encrypted data ".to_string(),
-                "Error recovering encrypted data".to_string(),
-            ));
-        }
+        encrypted_data.push(backend.recover_data(page_request).await?);
     }
 
     let mut record_pointers: Vec<EncryptedDataRecord> = vec![];
@@ -317,27 +175,7 @@ pub async fn recover_data(_address: &str) -> AvailResult<Data> {
 }
 
 pub async fn delete_all_server_storage() -> AvailResult<String> {
-    let res = get_rm_client_with_session(reqwest::Method::DELETE, "data")?
-        .send()
-        .await?;
-
-    if res.status() == 200 {
-        let result = res.text().await?;
-
-        Ok(result)
-    } else if res.status() == 401 {
-        Err(AvailError::new(
-            AvailErrorType::Unauthorized,
-            "User session has expired.".to_string(),
-            "Your session has expired, please authenticate again.".to_string(),
-        ))
-    } else {
-        Err(AvailError::new(
-            AvailErrorType::External,
-            "Error deleting encrypted data records ".to_string(),
-            "Error deleting server side data.".to_string(),
-        ))
-    }
+    backend()?.delete_all_data().await
 }
 
 pub async fn import_encrypted_data(request: DataRequest) -> AvailResult<String> {
@@ -399,44 +237,14 @@ pub async fn import_encrypted_data(request: DataRequest) -> AvailResult<String>
 
         data_requests.push(data_request);
 
+        let backend = backend()?;
         for data_request in data_requests {
-            let res = get_rm_client_with_session(reqwest::Method::POST, "import_data")?
-                .json(&data_request)
-                .send()
-                .await?;
-
-            if res.status() != 200 {
-                return Err(AvailError::new(
-                    AvailErrorType::External,
-                    "Error importing encrypted data.".to_string(),
-                    "Error backing up encrypted data.".to_string(),
-                ));
-            }
+            backend.import_data(data_request).await?;
         }
 
         Ok("Imported Succesfully".to_string())
     } else {
-        let res = get_rm_client_with_session(reqwest::Method::POST, "import_data")?
-            .json(&request)
-            .send()
-            .await?;
-
-        if res.status() == 200 {
-            let result = res.text().await?;
-            Ok(result)
-        } else if res.status() == 401 {
-            Err(AvailError::new(
-                AvailErrorType::Unauthorized,
-                "User session has expired.".to_string(),
-                "Your session has expired, please authenticate again.".to_string(),
-            ))
-        } else {
-            Err(AvailError::new(
-                AvailErrorType::External,
-                "Error importing encrypted data.".to_string(),
-                "Error backing up encrypted data.".to_string(),
-            ))
-        }
+        backend()?.import_data(request).await
     }
 }
 
@@ -0,0 +1,409 @@
+use once_cell::sync::Lazy;
+use rand::Rng;
+use std::future::Future;
+use std::sync::{mpsc, Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::models::resilience::{NetworkService, ResilienceSettings};
+use crate::services::local_storage::resilience_settings::get_stored_resilience_settings;
+
+use avail_common::errors::{AvailError, AvailErrorType, AvailResult};
+
+/* --Network Resilience-- */
+
+static SETTINGS: Lazy<RwLock<Option<ResilienceSettings>>> = Lazy::new(|| RwLock::new(None));
+
+static NODE_BREAKER: Lazy<CircuitBreaker> = Lazy::new(CircuitBreaker::default);
+static AVAIL_BREAKER: Lazy<CircuitBreaker> = Lazy::new(CircuitBreaker::default);
+
+/// The stored settings, or the defaults if none were saved
+pub fn resilience_settings() -> ResilienceSettings {
+    if let Some(settings) = SETTINGS.read().unwrap().as_ref() {
+        return settings.clone();
+    }
+
+    let settings = get_stored_resilience_settings()
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    *SETTINGS.write().unwrap() = Some(settings.clone());
+
+    settings
+}
+
+/// Applies new settings to the calls made from now on
+pub fn set_resilience_settings(settings: ResilienceSettings) {
+    *SETTINGS.write().unwrap() = Some(settings);
+}
+
+pub fn breaker(service: NetworkService) -> &'static CircuitBreaker {
+    match service {
+        NetworkService::Node => &NODE_BREAKER,
+        NetworkService::Avail => &AVAIL_BREAKER,
+    }
+}
+
+/// Transport failures, timeouts, rate limits and server errors are retried, rejections are not
+pub fn is_retryable(error: &AvailError) -> bool {
+    match error.error_type {
+        AvailErrorType::Network => true,
+        AvailErrorType::Node | AvailErrorType::External => {
+            let details = error.internal_msg.to_lowercase();
+            if details.contains("429") {
+                return true;
+            }
+
+            ![
+                "status code 4",
+                "status 4",
+                "not found",
+                "invalid",
+                "already exists",
+            ]
+            .iter()
+            .any(|rejection| details.contains(rejection))
+        }
+        _ => false,
+    }
+}
+
+/// Exponential backoff with full jitter, anywhere up to `base * 2^attempt` capped at the max delay
+pub fn backoff_delay(attempt: u32, settings: &ResilienceSettings) -> Duration {
+    let ceiling = settings
+        .base_delay_ms
+        .saturating_mul(2u64.saturating_pow(attempt))
+        .min(settings.max_delay_ms);
+
+    Duration::from_millis(rand::thread_rng().gen_range(0..=ceiling))
+}
+
+fn timeout_error(
+    service: NetworkService,
+    action: &str,
+    settings: &ResilienceSettings,
+) -> AvailError {
+    AvailError::new(
+        AvailErrorType::Network,
+        format!(
+            "{} timed out after {}ms {}",
+            service.name(),
+            settings.timeout_ms,
+            action
+        ),
+        format!("The {} took too long to respond.", service.name()),
+    )
+}
+
+#[derive(Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+/// Fails calls fast while a service keeps failing, letting one call through after the cooldown
+#[derive(Default)]
+pub struct CircuitBreaker {
+    state: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    pub fn is_open(&self) -> bool {
+        match self.state.lock().unwrap().open_until {
+            Some(open_until) => Instant::now() < open_until,
+            None => false,
+        }
+    }
+
+    pub fn check(&self, service: NetworkService) -> AvailResult<()> {
+        match self.is_open() {
+            true => Err(AvailError::new(
+                AvailErrorType::Network,
+                format!("Circuit breaker open for the {}", service.name()),
+                format!(
+                    "The {} is unavailable, please try again shortly.",
+                    service.name()
+                ),
+            )),
+            false => Ok(()),
+        }
+    }
+
+    pub fn record_success(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_failures = 0;
+        state.open_until = None;
+    }
+
+    pub fn record_failure(&self, settings: &ResilienceSettings) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_failures += 1;
+
+        if state.consecutive_failures >= settings.breaker_threshold {
+            state.open_until = Some(Instant::now() + settings.breaker_cooldown());
+        }
+    }
+}
+
+/// Records the outcome on the breaker, returns whether the call failed in a way worth retrying
+fn record_outcome<T>(
+    result: &AvailResult<T>,
+    breaker: &CircuitBreaker,
+    settings: &ResilienceSettings,
+) -> bool {
+    match result {
+        Err(e) if is_retryable(e) => {
+            breaker.record_failure(settings);
+            true
+        }
+        // The service answered, even if it rejected the call
+        _ => {
+            breaker.record_success();
+            false
+        }
+    }
+}
+
+/// Records the outcome on the breaker, returns whether to retry
+fn should_retry<T>(
+    result: &AvailResult<T>,
+    attempt: u32,
+    breaker: &CircuitBreaker,
+    settings: &ResilienceSettings,
+) -> bool {
+    record_outcome(result, breaker, settings)
+        && attempt < settings.max_retries
+        && !breaker.is_open()
+}
+
+/// Threads blocking calls run on. A call that timed out keeps its thread until it returns, the pool
+/// bounds how many of those can pile up instead of leaving a thread behind for every timeout.
+static BLOCKING_CALLS: Lazy<rayon::ThreadPool> = Lazy::new(|| {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(BLOCKING_CALL_THREADS)
+        .thread_name(|index| format!("network-call-{}", index))
+        .build()
+        .expect("Error starting the network call threads")
+});
+
+const BLOCKING_CALL_THREADS: usize = 16;
+
+fn retry_blocking_with<T: Send + 'static>(
+    service: NetworkService,
+    action: &str,
+    breaker: &CircuitBreaker,
+    settings: &ResilienceSettings,
+    call: Arc<dyn Fn() -> AvailResult<T> + Send + Sync>,
+) -> AvailResult<T> {
+    let mut attempt = 0;
+
+    loop {
+        breaker.check(service)?;
+
+        // The call keeps running on its pool thread after a timeout, its result is dropped
+        let (sender, receiver) = mpsc::channel();
+        let attempt_call = call.clone();
+        BLOCKING_CALLS.spawn(move || {
+            let _ = sender.send(attempt_call());
+        });
+
+        let result = match receiver.recv_timeout(settings.timeout()) {
+            Ok(result) => result,
+            Err(_) => Err(timeout_error(service, action, settings)),
+        };
+
+        if !should_retry(&result, attempt, breaker, settings) {
+            return result;
+        }
+
+        std::thread::sleep(backoff_delay(attempt, settings));
+        attempt += 1;
+    }
+}
+
+/// Runs a blocking call with the configured timeout, retries and circuit breaker
+pub fn retry_blocking<T: Send + 'static>(
+    service: NetworkService,
+    action: &str,
+    call: impl Fn() -> AvailResult<T> + Send + Sync + 'static,
+) -> AvailResult<T> {
+    retry_blocking_with(
+        service,
+        action,
+        breaker(service),
+        &resilience_settings(),
+        Arc::new(call),
+    )
+}
+
+/// Runs an async call that must not reach the service twice with the configured timeout and circuit
+/// breaker, it is never retried
+pub async fn call_async_once<T>(
+    service: NetworkService,
+    action: &str,
+    call: impl Future<Output = AvailResult<T>>,
+) -> AvailResult<T> {
+    let settings = resilience_settings();
+    let breaker = breaker(service);
+
+    breaker.check(service)?;
+
+    let result = match tokio::time::timeout(settings.timeout(), call).await {
+        Ok(result) => result,
+        Err(_) => Err(timeout_error(service, action, &settings)),
+    };
+
+    record_outcome(&result, breaker, &settings);
+
+    result
+}
+
+/// Runs an async call with the configured timeout, retries and circuit breaker
+pub async fn retry_async<T, F, Fut>(
+    service: NetworkService,
+    action: &str,
+    mut call: F,
+) -> AvailResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = AvailResult<T>>,
+{
+    let settings = resilience_settings();
+    let breaker = breaker(service);
+    let mut attempt = 0;
+
+    loop {
+        breaker.check(service)?;
+
+        let result = match tokio::time::timeout(settings.timeout(), call()).await {
+            Ok(result) => result,
+            Err(_) => Err(timeout_error(service, action, &settings)),
+        };
+
+        if !should_retry(&result, attempt, breaker, &settings) {
+            return result;
+        }
+
+        tokio::time::sleep(backoff_delay(attempt, &settings)).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod resilience_tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast_settings() -> ResilienceSettings {
+        ResilienceSettings {
+            max_retries: 3,
+            base_delay_ms: 1,
+            max_delay_ms: 5,
+            timeout_ms: 200,
+            breaker_threshold: 3,
+            breaker_cooldown_ms: 50,
+        }
+    }
+
+    fn error(error_type: AvailErrorType, details: &str) -> AvailError {
+        AvailError::new(error_type, details.to_string(), details.to_string())
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(is_retryable(&error(
+            AvailErrorType::Network,
+            "connection reset"
+        )));
+        assert!(is_retryable(&error(
+            AvailErrorType::Node,
+            "Error fetching block 10: status code 503"
+        )));
+        assert!(is_retryable(&error(
+            AvailErrorType::External,
+            "Error getting user: status 429"
+        )));
+        assert!(!is_retryable(&error(
+            AvailErrorType::Node,
+            "Error broadcasting transaction: status code 400"
+        )));
+        assert!(!is_retryable(&error(
+            AvailErrorType::Validation,
+            "bad input"
+        )));
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let settings = fast_settings();
+        for attempt in 0..20 {
+            assert!(backoff_delay(attempt, &settings) <= Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_retries_until_success() {
+        let breaker = CircuitBreaker::default();
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = calls.clone();
+
+        let result = retry_blocking_with(
+            NetworkService::Node,
+            "fetching block",
+            &breaker,
+            &fast_settings(),
+            Arc::new(move || match counter.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(error(AvailErrorType::Network, "connection reset")),
+                _ => Ok(7u32),
+            }),
+        );
+
+        assert_eq!(result.unwrap(), 7);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_rejections_are_not_retried() {
+        let breaker = CircuitBreaker::default();
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = calls.clone();
+
+        let result: AvailResult<()> = retry_blocking_with(
+            NetworkService::Node,
+            "broadcasting",
+            &breaker,
+            &fast_settings(),
+            Arc::new(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Err(error(AvailErrorType::Node, "status code 400"))
+            }),
+        );
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_timeouts_open_the_breaker() {
+        let breaker = CircuitBreaker::default();
+        let mut settings = fast_settings();
+        settings.timeout_ms = 10;
+
+        let result: AvailResult<()> = retry_blocking_with(
+            NetworkService::Node,
+            "fetching block",
+            &breaker,
+            &settings,
+            Arc::new(|| {
+                std::thread::sleep(Duration::from_millis(100));
+                Ok(())
+            }),
+        );
+
+        assert_eq!(result.unwrap_err().error_type, AvailErrorType::Network);
+        assert!(breaker.is_open());
+        assert!(breaker.check(NetworkService::Node).is_err());
+
+        std::thread::sleep(settings.breaker_cooldown());
+        assert!(breaker.check(NetworkService::Node).is_ok());
+    }
+}
@@ -1,11 +1,8 @@
 use snarkvm::prelude::*;
 use std::str::FromStr;
-use tauri_plugin_http::reqwest;
 
-use crate::api::client::get_um_client_with_session;
-use crate::helpers::utils::HOST;
+use crate::api::backend::backend;
 use crate::helpers::validation::validate_address;
-use crate::models::account::AddressRequest;
 use crate::services::local_storage::persistent_storage::{
     get_backup_flag, update_local_backup_flag,
 };
@@ -15,7 +12,7 @@ use crate::services::{
 };
 
 use avail_common::{
-    errors::{AvailError, AvailErrorType, AvailResult},
+    errors::AvailResult,
     models::user::{UpdateBackupRequest, User},
 };
 
@@ -23,75 +20,17 @@ use avail_common::{
 
 // create user online account
 pub async fn create_user(request: User) -> AvailResult<String> {
-    let api = env!("API");
-
-    let client = reqwest::Client::new();
-
-    let res = client
-        .post(format!("{}/user", api))
-        .json(&request)
-        .send()
-        .await?;
-
-    if res.status() == 200 {
-        Ok("User created".to_string())
-    } else {
-        Err(AvailError::new(
-            AvailErrorType::External,
-            "Error creating user".to_string(),
-            "Error creating user".to_string(),
-        ))
-    }
+    backend()?.create_user(request).await
 }
 
 // get user online account
 pub async fn get_user() -> AvailResult<User> {
-    let res = get_um_client_with_session(reqwest::Method::GET, "user")?
-        .send()
-        .await?;
-
-    if res.status() == 200 {
-        let user: User = res.json().await?;
-
-        Ok(user)
-    } else if res.status() == 401 {
-        Err(AvailError::new(
-            AvailErrorType::Unauthorized,
-            "User session has expired.".to_string(),
-            "Your session has expired, please authenticate again.".to_string(),
-        ))
-    } else {
-        Err(AvailError::new(
-            AvailErrorType::External,
-            "Error getting user".to_string(),
-            "Error getting user".to_string(),
-        ))
-    }
+    backend()?.get_user().await
 }
 
 /// delete user on server-side
 pub async fn delete_user() -> AvailResult<String> {
-    let res = get_um_client_with_session(reqwest::Method::DELETE, "user")?
-        .send()
-        .await?;
-
-    if res.status() == 200 {
-        Ok("User deleted".to_string())
-    } else if res.status() == 401 {
-        Err(AvailError::new(
-            AvailErrorType::Unauthorized,
-            "User session has expired.".to_string(),
-            "Your session has expired, please authenticate again.".to_string(),
-        ))
-    } else if res.status() == 404 {
-        Ok("User not found".to_string())
-    } else {
-        Err(AvailError::new(
-            AvailErrorType::External,
-            "Error deleting user account on server side.".to_string(),
-            "Error deleting online account.".to_string(),
-        ))
-    }
+    backend()?.delete_user().await
 }
 
 //get aleo address from username
@@ -101,60 +40,13 @@ pub async fn name_to_address<N: Network>(username: &str) -> AvailResult<Address<
         Err(_) => false,
     };
 
-    let request = AddressRequest {
-        username: username.to_string(),
-    };
-
-    let res = get_um_client_with_session(reqwest::Method::GET, &format!("user_address"))?
-        .json(&request)
-        .send()
-        .await?;
+    let address = backend()?.get_address(username).await?;
 
-    if res.status() == 200 {
-        let address: String = res.json().await?;
-
-        print!("{}", address);
-
-        let address = Address::<N>::from_str(&address).unwrap();
-
-        Ok(address)
-    } else if res.status() == 401 {
-        Err(AvailError::new(
-            AvailErrorType::Unauthorized,
-            "User session has expired.".to_string(),
-            "Your session has expired, please authenticate again.".to_string(),
-        ))
-    } else {
-        Err(AvailError::new(
-            AvailErrorType::External,
-            "Error getting address".to_string(),
-            "Error getting address".to_string(),
-        ))
-    }
+    Ok(Address::<N>::from_str(&address)?)
 }
 
 pub async fn get_username(address: &str) -> AvailResult<Option<String>> {
-    let res = get_um_client_with_session(reqwest::Method::GET, &format!("username/{}", address))?
-        .send()
-        .await?;
-
-    if res.status() == 200 {
-        let username: Option<String> = res.json().await?;
-
-        Ok(username)
-    } else if res.status() == 401 {
-        Err(AvailError::new(
-            AvailErrorType::Unauthorized,
-            "User session has expired.".to_string(),
-            "Your session has expired, please authenticate again.".to_string(),
-        ))
-    } else {
-        Err(AvailError::new(
-            AvailErrorType::External,
-            "Error getting username".to_string(),
-            "Error getting username".to_string(),
-        ))
-    }
+    backend()?.get_username(address).await
 }
 
 #[tauri::command(rename_all = "snake_case")]
@@ -164,32 +56,17 @@ pub async fn update_username(username: &str) -> AvailResult<String> {
 
     let tag = generate_discriminant();
 
-    let res = get_um_client_with_session(reqwest::Method::PUT, "user")?
-        .json(&User::new(
+    let result = backend()?
+        .update_user(User::new(
             Some(username.to_string()),
             address,
             Some(tag as u32),
             backup,
         ))
-        .send()
         .await?;
 
-    if res.status() == 200 {
-        update_username_local(username, tag as i32)?;
-        Ok("Username updated".to_string())
-    } else if res.status() == 401 {
-        Err(AvailError::new(
-            AvailErrorType::Unauthorized,
-            "User session has expired.".to_string(),
-            "Your session has expired, please authenticate again.".to_string(),
-        ))
-    } else {
-        Err(AvailError::new(
-            AvailErrorType::External,
-            "Error updating username".to_string(),
-            "Error updating username".to_string(),
-        ))
-    }
+    update_username_local(username, tag as i32)?;
+    Ok(result)
 }
 
 #[tauri::command(rename_all = "snake_case")]
@@ -201,27 +78,9 @@ pub async fn update_backup_flag(backup_flag: bool) -> AvailResult<()> {
     // TODO - Handle backup flag being false and server side backup being true
     // Should backup get deleted on server side?
 
-    let res = get_um_client_with_session(reqwest::Method::PUT, "backup")?
-        .json(&request)
-        .send()
-        .await?;
+    backend()?.update_backup(request).await?;
 
-    if res.status() == 200 {
-        update_local_backup_flag(backup_flag)?;
-        Ok(())
-    } else if res.status() == 401 {
-        Err(AvailError::new(
-            AvailErrorType::Unauthorized,
-            "User session has expired.".to_string(),
-            "Your session has expired, please authenticate again.".to_string(),
-        ))
-    } else {
-        Err(AvailError::new(
-            AvailErrorType::External,
-            "Error updating username".to_string(),
-            "Error updating username".to_string(),
-        ))
-    }
+    update_local_backup_flag(backup_flag)
 }
 
 #[cfg(test)]
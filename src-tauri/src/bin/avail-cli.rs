@@ -0,0 +1,365 @@
+//! Headless wallet for scripted operations, built on the same services as the app.
+//!
+//! The password is read from `AVAIL_PASSWORD` or prompted for, as is the private key to import from
+//! `AVAIL_PRIVATE_KEY`, so neither ends up in the process arguments. Results are printed to stdout
+//! as a single JSON line, progress events and anything the services print go to stderr. Setting
+//! `AVAIL_LOCAL_BACKEND` to a SQLite file runs the account and backup calls against a local
+//! stand-in for the Avail API.
+
+use snarkvm::circuit::{Aleo, AleoV0, Environment};
+use snarkvm::prelude::{Network, Testnet3};
+use std::collections::HashMap;
+use std::io::Write;
+use std::str::FromStr;
+
+use availx_lib::api::aleo_client::setup_client;
+use availx_lib::helpers::events::{EventEmitter, EventSink};
+use availx_lib::models::storage::languages::Languages;
+use availx_lib::models::transfer::TransferRequest;
+use availx_lib::models::wallet_connect::dapp_session::Caller;
+use availx_lib::models::wallet_connect::get_event::GetEventsRequest;
+use availx_lib::services::account::generation::{create_seed_phrase_wallet, import_wallet};
+use availx_lib::services::account::signer::get_signer;
+use availx_lib::services::local_storage::persistent_storage::{
+    get_address_string, get_last_sync, get_network,
+};
+use availx_lib::services::local_storage::tokens::get_stored_tokens;
+use availx_lib::services::record_handling::{
+    records::get_records, transfer::transfer_raw, utils::get_token_balance,
+};
+use availx_lib::services::wallet_connect_api::{get_events, verify};
+
+use avail_common::{
+    aleo_tools::program_manager::TransferType,
+    errors::{AvailError, AvailErrorType, AvailResult},
+    models::network::SupportedNetworks,
+};
+
+const PASSWORD_ENV: &str = "AVAIL_PASSWORD";
+const PRIVATE_KEY_ENV: &str = "AVAIL_PRIVATE_KEY";
+
+const USAGE: &str = "usage: avail-cli <command> [--option value]
+
+commands:
+  create    [--username <name>] [--length <12|24>]
+  import    [--username <name>]  (private key from AVAIL_PRIVATE_KEY or the prompt)
+  address
+  sync
+  balance   [--asset <token>]
+  transfer  --recipient <address|ans> --amount <u64> [--type private|public|public_to_private|private_to_public]
+            [--fee <u64>] [--fee-private] [--asset <token>] [--message <text>]
+  sign      --message <text>
+  verify    --message <text> --address <address> --signature <signature>
+  history   [--output <path>]";
+
+/// Prints service events as JSON lines on stderr so stdout only carries the result
+struct StderrSink;
+
+impl EventSink for StderrSink {
+    fn emit_json(&self, event: &str, payload: serde_json::Value) -> AvailResult<()> {
+        eprintln!(
+            "{}",
+            serde_json::json!({ "event": event, "payload": payload })
+        );
+        Ok(())
+    }
+}
+
+struct Args {
+    command: String,
+    options: HashMap<String, String>,
+    flags: Vec<String>,
+}
+
+impl Args {
+    fn parse() -> AvailResult<Self> {
+        let mut args = std::env::args().skip(1);
+        let command = args.next().ok_or_else(|| usage_error("Missing command"))?;
+
+        let mut options = HashMap::new();
+        let mut flags = vec![];
+        let mut args = args.peekable();
+
+        while let Some(arg) = args.next() {
+            let name = match arg.strip_prefix("--") {
+                Some(name) => name.to_string(),
+                None => return Err(usage_error(&format!("Unexpected argument {}", arg))),
+            };
+
+            match args.peek() {
+                Some(value) if !value.starts_with("--") => {
+                    options.insert(name, args.next().unwrap_or_default());
+                }
+                _ => flags.push(name),
+            }
+        }
+
+        Ok(Self {
+            command,
+            options,
+            flags,
+        })
+    }
+
+    fn optional(&self, name: &str) -> Option<String> {
+        self.options.get(name).cloned()
+    }
+
+    fn required(&self, name: &str) -> AvailResult<String> {
+        self.optional(name)
+            .ok_or_else(|| usage_error(&format!("Missing --{}", name)))
+    }
+
+    fn number(&self, name: &str, default: Option<u64>) -> AvailResult<u64> {
+        match (self.optional(name), default) {
+            (Some(value), _) => value
+                .parse::<u64>()
+                .map_err(|_| usage_error(&format!("--{} must be a number", name))),
+            (None, Some(default)) => Ok(default),
+            (None, None) => Err(usage_error(&format!("Missing --{}", name))),
+        }
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|flag| flag == name)
+    }
+}
+
+fn usage_error(reason: &str) -> AvailError {
+    AvailError::new(
+        AvailErrorType::Validation,
+        reason.to_string(),
+        format!("{}\n\n{}", reason, USAGE),
+    )
+}
+
+/// Reads a secret from the environment or prompts for it without echoing
+fn secret(env: &str, name: &str) -> AvailResult<String> {
+    match std::env::var(env) {
+        Ok(secret) if !secret.is_empty() => Ok(secret),
+        _ => rpassword::prompt_password(format!("{}: ", name)).map_err(|e| {
+            AvailError::new(
+                AvailErrorType::Internal,
+                format!("Error reading {}: {}", name.to_lowercase(), e),
+                format!("Error reading {}", name.to_lowercase()),
+            )
+        }),
+    }
+}
+
+fn password() -> AvailResult<String> {
+    secret(PASSWORD_ENV, "Password")
+}
+
+/// The services print progress with `println!`, so stdout is pointed at stderr while a command
+/// runs and the result is written to the original stdout
+#[cfg(unix)]
+fn result_output() -> Box<dyn Write> {
+    use std::os::unix::io::FromRawFd;
+
+    unsafe {
+        let stdout = libc::dup(libc::STDOUT_FILENO);
+        if stdout < 0 {
+            return Box::new(std::io::stdout());
+        }
+
+        if libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) < 0 {
+            libc::close(stdout);
+            return Box::new(std::io::stdout());
+        }
+
+        Box::new(std::fs::File::from_raw_fd(stdout))
+    }
+}
+
+#[cfg(not(unix))]
+fn result_output() -> Box<dyn Write> {
+    Box::new(std::io::stdout())
+}
+
+fn transfer_type(name: &str) -> AvailResult<TransferType> {
+    match name {
+        "private" => Ok(TransferType::Private),
+        "public" => Ok(TransferType::Public),
+        "public_to_private" => Ok(TransferType::PublicToPrivate),
+        "private_to_public" => Ok(TransferType::PrivateToPublic),
+        _ => Err(usage_error(&format!("Unknown transfer type {}", name))),
+    }
+}
+
+/// Unlocks the wallet, the signer also sets the view session the record services read from
+fn unlock<N: Network, A: Aleo + Environment<Network = N>>(password: &str) -> AvailResult<()> {
+    get_signer::<N, A>(Some(password.to_string()))?;
+    Ok(())
+}
+
+async fn run<N: Network, A: Aleo + Environment<Network = N>>(
+    args: &Args,
+) -> AvailResult<serde_json::Value> {
+    let result = match args.command.as_str() {
+        "create" => {
+            let length = args.number("length", Some(12))? as usize;
+            let seed_phrase = create_seed_phrase_wallet(
+                args.optional("username"),
+                password()?,
+                false,
+                false,
+                Languages::English,
+                length,
+            )
+            .await?;
+
+            serde_json::json!({ "address": get_address_string()?, "seedPhrase": seed_phrase })
+        }
+        "import" => {
+            if args.optional("private-key").is_some() || args.flag("private-key") {
+                return Err(usage_error(&format!(
+                    "Pass the private key in {} or at the prompt, not as an argument",
+                    PRIVATE_KEY_ENV
+                )));
+            }
+
+            let private_key = secret(PRIVATE_KEY_ENV, "Private key")?;
+            import_wallet(
+                args.optional("username"),
+                password()?,
+                false,
+                &private_key,
+                false,
+                Languages::English,
+            )
+            .await?;
+
+            serde_json::json!({ "address": get_address_string()? })
+        }
+        "address" => serde_json::json!({ "address": get_address_string()? }),
+        "sync" => {
+            unlock::<N, A>(&password()?)?;
+
+            let height = setup_client::<N>()?.latest_height()?;
+            let last_sync = get_last_sync()?;
+            let events = EventEmitter::new(StderrSink);
+
+            let found = tokio::task::spawn_blocking(move || {
+                get_records::<N>(last_sync, height, Some(events))
+            })
+            .await
+            .map_err(|e| {
+                AvailError::new(
+                    AvailErrorType::Internal,
+                    format!("Error scanning Aleo blockchain: {}", e),
+                    "Error scanning Aleo blockchain".to_string(),
+                )
+            })??;
+
+            serde_json::json!({ "from": last_sync, "to": height, "found": found })
+        }
+        "balance" => {
+            unlock::<N, A>(&password()?)?;
+
+            let assets = match args.optional("asset") {
+                Some(asset) => vec![asset],
+                None => get_stored_tokens()?
+                    .iter()
+                    .map(|token| token.token_name().to_string())
+                    .collect(),
+            };
+
+            let balances = assets
+                .iter()
+                .map(|asset| get_token_balance::<N>(asset))
+                .collect::<AvailResult<Vec<_>>>()?;
+
+            serde_json::to_value(balances)?
+        }
+        "transfer" => {
+            let request = TransferRequest::new(
+                args.required("recipient")?,
+                args.number("amount", None)?,
+                args.optional("message"),
+                Some(password()?),
+                transfer_type(&args.optional("type").unwrap_or("private".to_string()))?,
+                args.flag("fee-private"),
+                args.number("fee", Some(300000))?,
+                args.optional("asset").unwrap_or("credits".to_string()),
+            );
+
+            let result = transfer_raw::<N, A>(request, Some(EventEmitter::new(StderrSink))).await?;
+
+            serde_json::json!({ "result": result })
+        }
+        "sign" => {
+            let signer = get_signer::<N, A>(Some(password()?))?;
+            let (signature, message_field) = signer.sign_message(&args.required("message")?)?;
+
+            serde_json::json!({
+                "signature": signature.to_string(),
+                "messageField": message_field.to_string(),
+            })
+        }
+        "verify" => {
+            let valid = verify(
+                &args.required("message")?,
+                &args.required("address")?,
+                &args.required("signature")?,
+                None,
+                None,
+            )?;
+
+            serde_json::json!({ "valid": valid })
+        }
+        "history" => {
+            unlock::<N, A>(&password()?)?;
+
+            let mut request = GetEventsRequest::default();
+            request.page = None;
+            let history = serde_json::to_value(get_events(request, Caller::Wallet).await?)?;
+
+            match args.optional("output") {
+                Some(path) => {
+                    std::fs::write(&path, serde_json::to_string_pretty(&history)?)?;
+                    serde_json::json!({ "path": path })
+                }
+                None => history,
+            }
+        }
+        command => return Err(usage_error(&format!("Unknown command {}", command))),
+    };
+
+    Ok(result)
+}
+
+async fn dispatch(args: &Args) -> AvailResult<serde_json::Value> {
+    // A fresh install has no network stored yet
+    let network = get_network().unwrap_or("testnet3".to_string());
+
+    match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => run::<Testnet3, AleoV0>(args).await,
+        _ => run::<Testnet3, AleoV0>(args).await,
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let mut output = result_output();
+
+    let result = match Args::parse() {
+        Ok(args) => dispatch(&args).await,
+        Err(e) => Err(e),
+    };
+
+    let (line, code) = match result {
+        Ok(result) => (serde_json::json!({ "ok": true, "result": result }), 0),
+        Err(e) => (
+            serde_json::json!({
+                "ok": false,
+                "error": { "message": e.external_msg, "details": e.internal_msg },
+            }),
+            1,
+        ),
+    };
+
+    let _ = writeln!(output, "{}", line);
+    let _ = output.flush();
+    std::process::exit(code);
+}
@@ -1,3 +1,4 @@
+pub mod events;
 pub mod upgrade;
 pub mod utils;
 pub mod validation;
@@ -0,0 +1,98 @@
+use avail_common::errors::{AvailError, AvailErrorType, AvailResult};
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, Window};
+
+/* --Service Events-- */
+
+/// Receives the progress events services emit, the app window or a headless consumer like avail-cli
+pub trait EventSink: Send + Sync {
+    fn emit_json(&self, event: &str, payload: serde_json::Value) -> AvailResult<()>;
+}
+
+impl EventSink for Window {
+    fn emit_json(&self, event: &str, payload: serde_json::Value) -> AvailResult<()> {
+        match Manager::emit(self, event, payload) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(AvailError::new(
+                AvailErrorType::Internal,
+                format!("Error emitting {} event: {}", event, e),
+                format!("Error emitting {} event", event),
+            )),
+        }
+    }
+}
+
+impl EventSink for AppHandle {
+    fn emit_json(&self, event: &str, payload: serde_json::Value) -> AvailResult<()> {
+        match Manager::emit(self, event, payload) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(AvailError::new(
+                AvailErrorType::Internal,
+                format!("Error emitting {} event: {}", event, e),
+                format!("Error emitting {} event", event),
+            )),
+        }
+    }
+}
+
+/// Cloneable handle passed to services in place of a tauri window
+#[derive(Clone)]
+pub struct EventEmitter {
+    sink: Arc<dyn EventSink>,
+}
+
+impl EventEmitter {
+    pub fn new(sink: impl EventSink + 'static) -> Self {
+        Self {
+            sink: Arc::new(sink),
+        }
+    }
+
+    pub fn emit<S: Serialize>(&self, event: &str, payload: S) -> AvailResult<()> {
+        self.sink.emit_json(event, serde_json::to_value(payload)?)
+    }
+}
+
+impl From<Window> for EventEmitter {
+    fn from(window: Window) -> Self {
+        Self::new(window)
+    }
+}
+
+#[cfg(test)]
+mod events_tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingSink(Mutex<Vec<(String, serde_json::Value)>>);
+
+    impl EventSink for Arc<RecordingSink> {
+        fn emit_json(&self, event: &str, payload: serde_json::Value) -> AvailResult<()> {
+            self.0.lock().unwrap().push((event.to_string(), payload));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_emitter_serializes_payloads() {
+        let sink = Arc::new(RecordingSink::default());
+        let emitter = EventEmitter::new(sink.clone());
+
+        emitter.emit("tx_state_change", "pending-id").unwrap();
+        emitter.clone().emit("scan_progress", 42.5).unwrap();
+
+        let events = sink.0.lock().unwrap();
+        assert_eq!(
+            *events,
+            vec![
+                (
+                    "tx_state_change".to_string(),
+                    serde_json::json!("pending-id")
+                ),
+                ("scan_progress".to_string(), serde_json::json!(42.5)),
+            ]
+        );
+    }
+}
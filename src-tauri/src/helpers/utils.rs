@@ -65,3 +65,54 @@ pub fn get_timestamp_from_i64_utc(i: i64) -> AvailResult<DateTime<Utc>> {
 }
 
 pub const HOST: &str = "localhost";
+
+/// Percent encodes a URI component, only unreserved characters are kept as is
+pub fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Decodes a percent encoded URI component, `+` is read as a space
+pub fn percent_decode(value: &str) -> AvailResult<String> {
+    let invalid = |reason: &str| {
+        AvailError::new(
+            AvailErrorType::InvalidData,
+            format!("{} in {}", reason, value),
+            "Invalid link".to_string(),
+        )
+    };
+
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = value
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| invalid("Invalid percent encoding"))?;
+                decoded.push(hex);
+                i += 3;
+            }
+            b'+' => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            byte => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8(decoded).map_err(|_| invalid("Invalid UTF-8"))
+}
@@ -3,20 +3,30 @@ pub mod helpers;
 pub mod models;
 pub mod services;
 
-use crate::services::record_handling::utils::get_all_nft_data;
+use crate::services::record_handling::nfts::get_all_nft_data;
 use services::account::generation::create_seed_phrase_wallet;
 use services::account::generation::import_wallet;
 use services::account::phrase_recovery::recover_wallet_from_seed_phrase;
 use services::account::utils::{open_url, os_type};
+use services::ans::{get_ans_address, get_ans_primary_name};
 use services::authentication::session::get_session;
+use services::deep_link::{flush_deep_links, route_deep_links};
 use services::local_storage::persistent_storage::{
     get_address_string, get_auth_type, get_backup_flag, get_language, get_last_sync, get_network,
     get_username, update_language,
 };
+use services::outbox::{drain_outbox, get_outbox_status, start_outbox_worker};
+use services::payment_request::{
+    create_payment_request, parse_payment_request, payment_request_to_transfer,
+};
+use services::rpc_server::{get_rpc_server_status, start_rpc_server, stop_rpc_server};
 
 use api::user::{update_backup_flag, update_username};
 use services::local_storage::{
+    dapp_policies::{get_dapp_policies, get_policy_decisions, remove_dapp_policy, set_dapp_policy},
+    dapp_sessions::{get_dapp_sessions, grant_dapp_permission, revoke_dapp_session},
     encrypted_data::get_and_store_all_data,
+    resilience_settings::{get_resilience_settings, update_resilience_settings},
     tokens::get_stored_tokens,
     utils::{
         delete_local_for_recovery, delete_util, get_private_key_tauri, get_seed_phrase,
@@ -26,22 +36,29 @@ use services::local_storage::{
 
 // record handliong services
 // use crate::services::record_handling::utils::get_all_nft_data;
+use helpers::events::EventEmitter;
 use services::record_handling::{
+    balances::recompute_balances,
+    disclosure::{create_disclosure, verify_disclosure},
+    offline_signing::{
+        broadcast_offline_execution, cancel_offline_execution, create_offline_execution,
+        export_offline_envelope, import_offline_envelope, offline_envelope_from_qr,
+        offline_envelope_to_qr, sign_offline_execution,
+    },
+    program_abi::get_program_interface,
     sync::{blocks_sync, sync_backup, txs_sync},
-    transfer::{pre_install_inclusion_prover, transfer},
+    transfer::{pre_install_inclusion_prover, transfer, transfer_nft},
+    watcher::start_confirmation_watcher,
 };
 use tauri::Manager;
 use tauri_plugin_deep_link::DeepLinkExt;
 // wallet connect services
 use crate::services::wallet_connect_api::{
-    decrypt_records, get_avail_event, get_avail_events, get_balance, get_event, get_events,
-    get_records, get_succinct_avail_event, get_succinct_avail_events, request_create_event, sign,
+    decrypt_records, estimate_deployment_cost, evaluate_dapp_policy, get_avail_event,
+    get_avail_events, get_balance, get_event, get_events, get_records, get_succinct_avail_event,
+    get_succinct_avail_events, parse_sign_in_message, request_create_event, sign, sign_typed,
     verify,
 };
-#[derive(Clone, serde::Deserialize, serde::Serialize)]
-struct DeepLinkPayload {
-    uri: String,
-}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -56,10 +73,26 @@ pub fn run() {
             app.listen("deep-link://new-url", move |event| {
                 deep_link_print(event, handle.clone())
             });
-            // Remove the on_scheme method call
-            println!("Deep link: {:?}", app.deep_link().get_current());
+            // the listener only sees links opened while running, route the one the app was launched with
+            match app.deep_link().get_current() {
+                Ok(Some(urls)) => {
+                    let urls = urls
+                        .iter()
+                        .map(|url| url.to_string())
+                        .collect::<Vec<String>>();
+                    route_deep_links(&serde_json::to_string(&urls)?, app.handle());
+                }
+                Ok(None) => {}
+                Err(e) => println!("Error reading the launch deep link: {}", e),
+            }
             // NOTE: Updater is only supported on desktop platforms
 
+            // Sends server-side steps queued while the Avail server was unreachable
+            start_outbox_worker();
+
+            // Settles transactions that were still in flight when the app last closed
+            start_confirmation_watcher(EventEmitter::new(app.handle().clone()));
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -83,6 +116,8 @@ pub fn run() {
             get_language,
             update_language,
             get_stored_tokens,
+            get_resilience_settings,
+            update_resilience_settings,
             open_url,
             os_type,
             /* Authentication */
@@ -92,22 +127,58 @@ pub fn run() {
             txs_sync,
             blocks_sync,
             sync_backup,
+            drain_outbox,
+            get_outbox_status,
             /* Avail Services */
             get_avail_event,
             get_avail_events,
             get_all_nft_data,
+            recompute_balances,
             transfer,
+            transfer_nft,
+            get_ans_address,
+            get_ans_primary_name,
+            create_payment_request,
+            parse_payment_request,
+            payment_request_to_transfer,
+            flush_deep_links,
+            create_offline_execution,
+            sign_offline_execution,
+            broadcast_offline_execution,
+            cancel_offline_execution,
+            offline_envelope_to_qr,
+            offline_envelope_from_qr,
+            export_offline_envelope,
+            import_offline_envelope,
             /* --Wallet Connect Api */
             get_event,
             get_events,
             get_records,
             request_create_event,
+            estimate_deployment_cost,
+            get_program_interface,
             sign,
             decrypt_records,
             get_balance,
             get_succinct_avail_event,
             get_succinct_avail_events,
             verify,
+            sign_typed,
+            parse_sign_in_message,
+            create_disclosure,
+            verify_disclosure,
+            grant_dapp_permission,
+            get_dapp_sessions,
+            revoke_dapp_session,
+            evaluate_dapp_policy,
+            set_dapp_policy,
+            get_dapp_policies,
+            remove_dapp_policy,
+            get_policy_decisions,
+            /* Automation */
+            start_rpc_server,
+            stop_rpc_server,
+            get_rpc_server_status,
             /* Aleo Helpers */
             pre_install_inclusion_prover
         ])
@@ -115,8 +186,5 @@ pub fn run() {
         .expect("error while running tauri application");
 }
 fn deep_link_print(event: tauri::Event, handle: tauri::AppHandle) {
-    let uri = event.payload().to_string();
-    handle
-        .emit("deep-link-wc", DeepLinkPayload { uri })
-        .unwrap();
+    route_deep_links(event.payload(), &handle);
 }
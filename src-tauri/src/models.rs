@@ -1,9 +1,18 @@
 pub mod account;
 pub mod auth;
+pub mod deep_link;
+pub mod disclosure;
 pub mod event;
 pub mod event_payloads;
+pub mod nft;
+pub mod offline_signing;
+pub mod outbox;
+pub mod payment_request;
 pub mod pointers;
+pub mod resilience;
+pub mod rpc;
 pub mod storage;
+pub mod tokens;
 pub mod transfer;
 pub mod wallet;
 pub mod wallet_connect;
@@ -106,6 +106,14 @@ impl From<VerifySessionRequest> for VerifySessionResponse {
     }
 }
 
+/// Login request carrying the sign in message that was signed, its nonce is the hash issued by the server
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SignInSessionRequest {
+    pub signature: String,
+    pub session_id: Uuid,
+    pub message: String,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct CreateSessionRequest {
     pub hash: String,
@@ -0,0 +1,144 @@
+use avail_common::errors::{AvailError, AvailErrorType, AvailResult};
+use serde::{Deserialize, Serialize};
+use snarkvm::prelude::Network;
+use std::str::FromStr;
+
+use crate::helpers::utils::percent_decode;
+use crate::models::payment_request::{PaymentRequest, PAYMENT_URI_PREFIX};
+
+/* Deep Links */
+
+pub const DEEP_LINK_PREFIX: &str = "avail://";
+
+/// A validated deep link, serialized as the payload of the event it is dispatched with
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DeepLink {
+    /// `avail://wc?uri=<wallet connect pairing uri>`
+    Pair { uri: String },
+    /// `avail://pay?to=...` or `avail:pay?to=...`
+    Payment {
+        uri: String,
+        request: PaymentRequest,
+    },
+    /// `avail://tx/<transaction id>`
+    Transaction {
+        #[serde(rename = "transactionId")]
+        transaction_id: String,
+    },
+}
+
+impl DeepLink {
+    /// Event the link is dispatched to the frontend with
+    pub fn event(&self) -> &str {
+        match self {
+            DeepLink::Pair { .. } => "deep-link-pair",
+            DeepLink::Payment { .. } => "deep-link-payment",
+            DeepLink::Transaction { .. } => "deep-link-transaction",
+        }
+    }
+
+    pub fn parse<N: Network>(link: &str) -> AvailResult<Self> {
+        if PaymentRequest::is_payment_uri(link) {
+            return Ok(DeepLink::Payment {
+                uri: link.to_string(),
+                request: PaymentRequest::parse::<N>(link)?,
+            });
+        }
+
+        let rest = link
+            .strip_prefix(DEEP_LINK_PREFIX)
+            .ok_or_else(|| invalid(link, "Unsupported scheme"))?;
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+        match path.trim_end_matches('/') {
+            "wc" => {
+                let uri = match query_param(query, "uri") {
+                    Some(uri) => percent_decode(uri)?,
+                    None => return Err(invalid(link, "Missing pairing uri")),
+                };
+
+                // pairing uris look like wc:<topic>@2?relay-protocol=irn&symKey=<key>
+                let valid = uri
+                    .strip_prefix("wc:")
+                    .and_then(|pairing| pairing.split_once('@'))
+                    .map_or(false, |(topic, params)| {
+                        !topic.is_empty() && params.starts_with('2') && params.contains("symKey=")
+                    });
+
+                match valid {
+                    true => Ok(DeepLink::Pair { uri }),
+                    false => Err(invalid(link, "Invalid wallet connect pairing uri")),
+                }
+            }
+            "pay" => Ok(DeepLink::Payment {
+                uri: link.to_string(),
+                request: PaymentRequest::parse::<N>(&format!("{}?{}", PAYMENT_URI_PREFIX, query))?,
+            }),
+            path => match path.strip_prefix("tx/") {
+                Some(transaction_id) => match N::TransactionID::from_str(transaction_id) {
+                    Ok(transaction_id) => Ok(DeepLink::Transaction {
+                        transaction_id: transaction_id.to_string(),
+                    }),
+                    Err(_) => Err(invalid(link, "Invalid transaction id")),
+                },
+                None => Err(invalid(link, "Unknown route")),
+            },
+        }
+    }
+}
+
+fn query_param<'a>(query: &'a str, key: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, value)| value)
+}
+
+fn invalid(link: &str, reason: &str) -> AvailError {
+    AvailError::new(
+        AvailErrorType::InvalidData,
+        format!("{}: {}", reason, link),
+        format!("This link cannot be opened: {}", reason.to_lowercase()),
+    )
+}
+
+#[cfg(test)]
+mod deep_link_tests {
+    use super::*;
+    use avail_common::models::constants::TESTNET_ADDRESS;
+    use snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_parse_pairing_link() {
+        let link = "avail://wc?uri=wc%3A7f6e504bfad60b485450578e05678ed3e8e8c4751d3c6160be17160d63ec90f9%402%3Frelay-protocol%3Dirn%26symKey%3D587d5484ce2a2a6ee3ba1962fdd7e8588e06200c46823bd18fbd67def96ad303";
+
+        match DeepLink::parse::<Testnet3>(link).unwrap() {
+            DeepLink::Pair { uri } => assert!(uri.starts_with("wc:7f6e504b")),
+            other => panic!("Expected a pairing link, got {:?}", other),
+        }
+
+        assert!(DeepLink::parse::<Testnet3>("avail://wc?uri=https%3A%2F%2Fevil.example").is_err());
+    }
+
+    #[test]
+    fn test_parse_payment_and_transaction_links() {
+        let pay = format!("avail://pay?to={}&amount=2", TESTNET_ADDRESS);
+        let link = DeepLink::parse::<Testnet3>(&pay).unwrap();
+        assert_eq!(link.event(), "deep-link-payment");
+
+        let tx = "avail://tx/at1ya42tgdqawkkwf5t8ezuez954j3lgpv92xqhlcc0eajfnzrwlc9qhgve9y";
+        assert_eq!(
+            DeepLink::parse::<Testnet3>(tx).unwrap().event(),
+            "deep-link-transaction"
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_unexpected_links() {
+        assert!(DeepLink::parse::<Testnet3>("https://avail.global").is_err());
+        assert!(DeepLink::parse::<Testnet3>("avail://settings").is_err());
+        assert!(DeepLink::parse::<Testnet3>("avail://tx/not-a-transaction").is_err());
+    }
+}
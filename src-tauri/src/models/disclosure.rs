@@ -0,0 +1,248 @@
+use serde::{Deserialize, Serialize};
+
+/* Selective Disclosure Interfaces */
+
+/// Version of the disclosure package format
+pub const DISCLOSURE_VERSION: u8 = 1;
+
+/// What a disclosure reveals about a transition
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DisclosureScope {
+    /// The transition view key, revealing every private input and output of the transition
+    TransitionViewKey,
+    /// Only the records the wallet owner received in the transition
+    Records,
+}
+
+/// A record output revealed through its record view key
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DisclosedRecord {
+    /// Index of the record in the transition outputs
+    index: usize,
+    #[serde(rename = "recordViewKey")]
+    record_view_key: String,
+    plaintext: String,
+}
+
+impl DisclosedRecord {
+    pub fn new(index: usize, record_view_key: String, plaintext: String) -> Self {
+        Self {
+            index,
+            record_view_key,
+            plaintext,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn record_view_key(&self) -> &String {
+        &self.record_view_key
+    }
+
+    pub fn plaintext(&self) -> &String {
+        &self.plaintext
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DisclosedTransition {
+    #[serde(rename = "transitionId")]
+    transition_id: String,
+    #[serde(rename = "programId")]
+    program_id: String,
+    #[serde(rename = "functionId")]
+    function_id: String,
+    #[serde(rename = "transitionViewKey")]
+    transition_view_key: Option<String>,
+    records: Vec<DisclosedRecord>,
+}
+
+impl DisclosedTransition {
+    pub fn new(
+        transition_id: String,
+        program_id: String,
+        function_id: String,
+        transition_view_key: Option<String>,
+        records: Vec<DisclosedRecord>,
+    ) -> Self {
+        Self {
+            transition_id,
+            program_id,
+            function_id,
+            transition_view_key,
+            records,
+        }
+    }
+
+    pub fn transition_id(&self) -> &String {
+        &self.transition_id
+    }
+
+    pub fn program_id(&self) -> &String {
+        &self.program_id
+    }
+
+    pub fn function_id(&self) -> &String {
+        &self.function_id
+    }
+
+    pub fn transition_view_key(&self) -> Option<&String> {
+        self.transition_view_key.as_ref()
+    }
+
+    pub fn records(&self) -> &Vec<DisclosedRecord> {
+        &self.records
+    }
+}
+
+/// Everything an auditor needs to check a transaction against the chain, without the wallet's view key
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DisclosurePackage {
+    version: u8,
+    /// Network id the transaction was executed on
+    network: u16,
+    #[serde(rename = "transactionId")]
+    transaction_id: String,
+    scope: DisclosureScope,
+    transitions: Vec<DisclosedTransition>,
+}
+
+impl DisclosurePackage {
+    pub fn new(
+        network: u16,
+        transaction_id: String,
+        scope: DisclosureScope,
+        transitions: Vec<DisclosedTransition>,
+    ) -> Self {
+        Self {
+            version: DISCLOSURE_VERSION,
+            network,
+            transaction_id,
+            scope,
+            transitions,
+        }
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn network(&self) -> u16 {
+        self.network
+    }
+
+    pub fn transaction_id(&self) -> &String {
+        &self.transaction_id
+    }
+
+    pub fn scope(&self) -> DisclosureScope {
+        self.scope
+    }
+
+    pub fn transitions(&self) -> &Vec<DisclosedTransition> {
+        &self.transitions
+    }
+}
+
+/// A transition whose disclosed contents were checked against the chain
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VerifiedTransition {
+    #[serde(rename = "transitionId")]
+    transition_id: String,
+    #[serde(rename = "programId")]
+    program_id: String,
+    #[serde(rename = "functionId")]
+    function_id: String,
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+}
+
+impl VerifiedTransition {
+    pub fn new(
+        transition_id: String,
+        program_id: String,
+        function_id: String,
+        inputs: Vec<String>,
+        outputs: Vec<String>,
+    ) -> Self {
+        Self {
+            transition_id,
+            program_id,
+            function_id,
+            inputs,
+            outputs,
+        }
+    }
+
+    pub fn transition_id(&self) -> &String {
+        &self.transition_id
+    }
+
+    pub fn inputs(&self) -> &Vec<String> {
+        &self.inputs
+    }
+
+    pub fn outputs(&self) -> &Vec<String> {
+        &self.outputs
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DisclosureVerification {
+    #[serde(rename = "transactionId")]
+    transaction_id: String,
+    transitions: Vec<VerifiedTransition>,
+}
+
+impl DisclosureVerification {
+    pub fn new(transaction_id: String, transitions: Vec<VerifiedTransition>) -> Self {
+        Self {
+            transaction_id,
+            transitions,
+        }
+    }
+
+    pub fn transaction_id(&self) -> &String {
+        &self.transaction_id
+    }
+
+    pub fn transitions(&self) -> &Vec<VerifiedTransition> {
+        &self.transitions
+    }
+}
+
+#[cfg(test)]
+mod disclosure_tests {
+    use super::*;
+
+    #[test]
+    fn test_package_serialization() {
+        let package = DisclosurePackage::new(
+            3,
+            "at1...".to_string(),
+            DisclosureScope::Records,
+            vec![DisclosedTransition::new(
+                "au1...".to_string(),
+                "credits.aleo".to_string(),
+                "transfer_private".to_string(),
+                None,
+                vec![DisclosedRecord::new(
+                    0,
+                    "1field".to_string(),
+                    "{ owner: aleo1....private }".to_string(),
+                )],
+            )],
+        );
+
+        let json = serde_json::to_string(&package).unwrap();
+        assert!(json.contains(r#""scope":"records""#));
+        assert!(json.contains(r#""recordViewKey":"1field""#));
+
+        let parsed: DisclosurePackage = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, package);
+        assert_eq!(parsed.version(), DISCLOSURE_VERSION);
+    }
+}
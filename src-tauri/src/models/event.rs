@@ -110,6 +110,10 @@ impl Event {
     pub fn get_created(&self) -> DateTime<Local> {
         self.created
     }
+
+    pub fn program_id(&self) -> Option<&String> {
+        self.program_id.as_ref()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -171,6 +175,10 @@ pub struct SuccinctAvailEvent {
     program_id: Option<String>,
     #[serde(rename = "functionId")]
     function_id: Option<String>,
+    #[serde(rename = "toName", default)]
+    to_name: Option<String>,
+    #[serde(rename = "fromName", default)]
+    from_name: Option<String>,
 }
 
 impl SuccinctAvailEvent {
@@ -199,12 +207,32 @@ impl SuccinctAvailEvent {
             created,
             program_id,
             function_id,
+            to_name: None,
+            from_name: None,
         }
     }
 
     pub fn get_created(&self) -> DateTime<Local> {
         self.created
     }
+
+    pub fn program_id(&self) -> Option<&String> {
+        self.program_id.as_ref()
+    }
+
+    pub fn to(&self) -> Option<&String> {
+        self.to.as_ref()
+    }
+
+    pub fn from(&self) -> Option<&String> {
+        self.from.as_ref()
+    }
+
+    /// Sets the ANS names displayed for the sender and recipient
+    pub fn set_names(&mut self, to_name: Option<String>, from_name: Option<String>) {
+        self.to_name = to_name;
+        self.from_name = from_name;
+    }
 }
 
 /// Internal avail event used to display a full event
@@ -239,6 +267,10 @@ pub struct AvailEvent {
     to: Option<String>,
     from: Option<String>,
     amount: Option<f64>,
+    #[serde(rename = "toName", default)]
+    to_name: Option<String>,
+    #[serde(rename = "fromName", default)]
+    from_name: Option<String>,
 }
 
 impl AvailEvent {
@@ -293,12 +325,32 @@ impl AvailEvent {
             to,
             from,
             amount,
+            to_name: None,
+            from_name: None,
         }
     }
 
     pub fn get_created(&self) -> DateTime<Local> {
         self.created
     }
+
+    pub fn program_id(&self) -> Option<&String> {
+        self.program_id.as_ref()
+    }
+
+    pub fn to(&self) -> Option<&String> {
+        self.to.as_ref()
+    }
+
+    pub fn from(&self) -> Option<&String> {
+        self.from.as_ref()
+    }
+
+    /// Sets the ANS names displayed for the sender and recipient
+    pub fn set_names(&mut self, to_name: Option<String>, from_name: Option<String>) {
+        self.to_name = to_name;
+        self.from_name = from_name;
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+/* NFT Gallery Interfaces */
+
+/// An NFT owned by the wallet, resolved from its record and collection program
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Nft {
+    #[serde(rename = "recordId")]
+    record_id: String,
+    collection: String,
+    #[serde(rename = "tokenId")]
+    token_id: Option<String>,
+    uri: Option<String>,
+    metadata: Option<serde_json::Value>,
+    #[serde(rename = "mediaType")]
+    media_type: Option<String>,
+    error: Option<String>,
+}
+
+impl Nft {
+    pub fn new(
+        record_id: String,
+        collection: String,
+        token_id: String,
+        uri: String,
+        metadata: Option<serde_json::Value>,
+        media_type: Option<String>,
+    ) -> Self {
+        Self {
+            record_id,
+            collection,
+            token_id: Some(token_id),
+            uri: Some(uri),
+            metadata,
+            media_type,
+            error: None,
+        }
+    }
+
+    /// An NFT entry whose collection or metadata could not be resolved
+    pub fn from_error(record_id: String, collection: String, error: String) -> Self {
+        Self {
+            record_id,
+            collection,
+            token_id: None,
+            uri: None,
+            metadata: None,
+            media_type: None,
+            error: Some(error),
+        }
+    }
+
+    pub fn record_id(&self) -> &String {
+        &self.record_id
+    }
+
+    pub fn collection(&self) -> &String {
+        &self.collection
+    }
+
+    pub fn token_id(&self) -> Option<&String> {
+        self.token_id.as_ref()
+    }
+
+    pub fn uri(&self) -> Option<&String> {
+        self.uri.as_ref()
+    }
+
+    pub fn metadata(&self) -> Option<&serde_json::Value> {
+        self.metadata.as_ref()
+    }
+
+    pub fn media_type(&self) -> Option<&String> {
+        self.media_type.as_ref()
+    }
+
+    pub fn error(&self) -> Option<&String> {
+        self.error.as_ref()
+    }
+}
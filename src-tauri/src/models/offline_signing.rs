@@ -0,0 +1,370 @@
+use avail_common::errors::{AvailError, AvailErrorType, AvailResult};
+use serde::{Deserialize, Serialize};
+use snarkvm::prelude::{Network, ToBits};
+
+/* Offline Signing Interfaces */
+
+/// Version of the offline signing exchange format
+pub const OFFLINE_SIGNING_VERSION: u8 = 2;
+pub const OFFLINE_QR_PREFIX: &str = "avail-offline";
+/// Characters of envelope data carried by a single QR code
+pub const OFFLINE_QR_CHUNK_SIZE: usize = 800;
+
+/// An execution built by a watch-only wallet, everything the offline wallet needs to sign it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UnsignedExecution {
+    address: String,
+    #[serde(rename = "programId")]
+    program_id: String,
+    #[serde(rename = "functionId")]
+    function_id: String,
+    /// Inputs as plaintext values, records included as decrypted plaintexts
+    inputs: Vec<String>,
+    /// Sources of the program and its imports in dependency order, credits.aleo excluded
+    programs: Vec<String>,
+    /// Fee in units of 10^-6
+    fee: u64,
+    #[serde(rename = "feeRecord")]
+    fee_record: Option<String>,
+    /// Global state root the offline wallet proves against
+    #[serde(rename = "stateRoot")]
+    state_root: String,
+    /// Inclusion paths of the spent records, fee record included, all under `state_root`
+    #[serde(rename = "statePaths")]
+    state_paths: Vec<String>,
+    /// Pending transaction stored by the online wallet that created the request
+    #[serde(rename = "pendingEventId")]
+    pending_event_id: String,
+}
+
+impl UnsignedExecution {
+    pub fn new(
+        address: String,
+        program_id: String,
+        function_id: String,
+        inputs: Vec<String>,
+        programs: Vec<String>,
+        fee: u64,
+        fee_record: Option<String>,
+        state_root: String,
+        state_paths: Vec<String>,
+        pending_event_id: String,
+    ) -> Self {
+        Self {
+            address,
+            program_id,
+            function_id,
+            inputs,
+            programs,
+            fee,
+            fee_record,
+            state_root,
+            state_paths,
+            pending_event_id,
+        }
+    }
+
+    pub fn address(&self) -> &String {
+        &self.address
+    }
+
+    pub fn program_id(&self) -> &String {
+        &self.program_id
+    }
+
+    pub fn function_id(&self) -> &String {
+        &self.function_id
+    }
+
+    pub fn inputs(&self) -> &Vec<String> {
+        &self.inputs
+    }
+
+    pub fn programs(&self) -> &Vec<String> {
+        &self.programs
+    }
+
+    pub fn fee(&self) -> u64 {
+        self.fee
+    }
+
+    pub fn fee_record(&self) -> Option<&String> {
+        self.fee_record.as_ref()
+    }
+
+    pub fn state_root(&self) -> &String {
+        &self.state_root
+    }
+
+    pub fn state_paths(&self) -> &Vec<String> {
+        &self.state_paths
+    }
+
+    pub fn pending_event_id(&self) -> &String {
+        &self.pending_event_id
+    }
+}
+
+/// The execution and fee authorizations signed by the offline wallet and the transaction it proved from them
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SignedExecution {
+    request: UnsignedExecution,
+    execution: String,
+    fee: String,
+    transaction: String,
+}
+
+impl SignedExecution {
+    pub fn new(
+        request: UnsignedExecution,
+        execution: String,
+        fee: String,
+        transaction: String,
+    ) -> Self {
+        Self {
+            request,
+            execution,
+            fee,
+            transaction,
+        }
+    }
+
+    pub fn request(&self) -> &UnsignedExecution {
+        &self.request
+    }
+
+    pub fn execution(&self) -> &String {
+        &self.execution
+    }
+
+    pub fn fee(&self) -> &String {
+        &self.fee
+    }
+
+    pub fn transaction(&self) -> &String {
+        &self.transaction
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OfflinePayload {
+    Unsigned(UnsignedExecution),
+    Signed(SignedExecution),
+}
+
+/// Versioned container moved between the online and offline wallet as a file or QR codes
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OfflineEnvelope {
+    version: u8,
+    network: u16,
+    payload: OfflinePayload,
+    /// BHP1024 hash of the version, network and payload
+    checksum: String,
+}
+
+impl OfflineEnvelope {
+    pub fn new<N: Network>(payload: OfflinePayload) -> AvailResult<Self> {
+        let checksum = checksum::<N>(OFFLINE_SIGNING_VERSION, N::ID, &payload)?;
+
+        Ok(Self {
+            version: OFFLINE_SIGNING_VERSION,
+            network: N::ID,
+            payload,
+            checksum,
+        })
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn network(&self) -> u16 {
+        self.network
+    }
+
+    pub fn checksum(&self) -> &String {
+        &self.checksum
+    }
+
+    /// Returns the payload once the version, network and checksum have been checked
+    pub fn verify<N: Network>(&self) -> AvailResult<&OfflinePayload> {
+        if self.version != OFFLINE_SIGNING_VERSION {
+            return Err(invalid(&format!("Unsupported version {}", self.version)));
+        }
+
+        if self.network != N::ID {
+            return Err(invalid(&format!(
+                "Envelope is for network {} not {}",
+                self.network,
+                N::ID
+            )));
+        }
+
+        if checksum::<N>(self.version, self.network, &self.payload)? != self.checksum {
+            return Err(invalid(
+                "Checksum mismatch, the data was altered or corrupted",
+            ));
+        }
+
+        Ok(&self.payload)
+    }
+
+    /// Splits the envelope into QR code payloads of the form `avail-offline:<index>/<total>:<checksum>:<data>`
+    pub fn to_qr_chunks(&self) -> AvailResult<Vec<String>> {
+        let data = serde_json::to_string(self)?.chars().collect::<Vec<char>>();
+        let chunks = data.chunks(OFFLINE_QR_CHUNK_SIZE).collect::<Vec<&[char]>>();
+
+        Ok(chunks
+            .iter()
+            .enumerate()
+            .map(|(index, chunk)| {
+                format!(
+                    "{}:{}/{}:{}:{}",
+                    OFFLINE_QR_PREFIX,
+                    index + 1,
+                    chunks.len(),
+                    self.checksum,
+                    chunk.iter().collect::<String>()
+                )
+            })
+            .collect())
+    }
+
+    /// Reassembles scanned QR codes in any order and verifies the result
+    pub fn from_qr_chunks<N: Network>(chunks: &[String]) -> AvailResult<Self> {
+        let mut parts: Vec<Option<String>> = vec![];
+        let mut expected_checksum: Option<String> = None;
+
+        for chunk in chunks {
+            let fields = chunk.splitn(4, ':').collect::<Vec<&str>>();
+            let (prefix, position, checksum, data) = match fields.as_slice() {
+                [prefix, position, checksum, data] => (*prefix, *position, *checksum, *data),
+                _ => return Err(invalid("Malformed QR code")),
+            };
+
+            if prefix != OFFLINE_QR_PREFIX {
+                return Err(invalid("Not an offline signing QR code"));
+            }
+
+            let (index, total) = position
+                .split_once('/')
+                .and_then(|(index, total)| {
+                    Some((index.parse::<usize>().ok()?, total.parse::<usize>().ok()?))
+                })
+                .ok_or_else(|| invalid("Malformed QR code position"))?;
+
+            if index == 0 || index > total {
+                return Err(invalid("Malformed QR code position"));
+            }
+
+            match &expected_checksum {
+                Some(expected) if expected != checksum || parts.len() != total => {
+                    return Err(invalid("QR codes belong to different transactions"))
+                }
+                Some(_) => {}
+                None => {
+                    expected_checksum = Some(checksum.to_string());
+                    parts = vec![None; total];
+                }
+            }
+
+            parts[index - 1] = Some(data.to_string());
+        }
+
+        if parts.is_empty() || parts.iter().any(|part| part.is_none()) {
+            return Err(invalid("Some QR codes are missing"));
+        }
+
+        let data = parts.into_iter().flatten().collect::<String>();
+        let envelope: OfflineEnvelope = serde_json::from_str(&data)?;
+
+        if Some(&envelope.checksum) != expected_checksum.as_ref() {
+            return Err(invalid("QR codes belong to different transactions"));
+        }
+
+        envelope.verify::<N>()?;
+
+        Ok(envelope)
+    }
+}
+
+fn checksum<N: Network>(
+    version: u8,
+    network: u16,
+    payload: &OfflinePayload,
+) -> AvailResult<String> {
+    let data = format!(
+        "{}:{}:{}",
+        version,
+        network,
+        serde_json::to_string(payload)?
+    );
+
+    Ok(N::hash_bhp1024(&data.as_bytes().to_bits_le())?.to_string())
+}
+
+fn invalid(reason: &str) -> AvailError {
+    AvailError::new(
+        AvailErrorType::InvalidData,
+        format!("Invalid offline signing data: {}", reason),
+        format!("Invalid offline signing data: {}", reason),
+    )
+}
+
+#[cfg(test)]
+mod offline_signing_tests {
+    use super::*;
+    use avail_common::models::constants::TESTNET_ADDRESS;
+    use snarkvm::prelude::Testnet3;
+
+    fn unsigned() -> OfflinePayload {
+        OfflinePayload::Unsigned(UnsignedExecution::new(
+            TESTNET_ADDRESS.to_string(),
+            "credits.aleo".to_string(),
+            "transfer_public".to_string(),
+            vec![TESTNET_ADDRESS.to_string(), "1000000u64".to_string()],
+            vec![],
+            300000,
+            None,
+            "sr1state".to_string(),
+            vec![],
+            "pending-id".to_string(),
+        ))
+    }
+
+    #[test]
+    fn test_envelope_integrity() {
+        let envelope = OfflineEnvelope::new::<Testnet3>(unsigned()).unwrap();
+        assert!(envelope.verify::<Testnet3>().is_ok());
+
+        let json = serde_json::to_string(&envelope).unwrap();
+        assert!(json.contains(r#""kind":"unsigned""#));
+
+        let tampered: OfflineEnvelope =
+            serde_json::from_str(&json.replace("1000000u64", "9000000u64")).unwrap();
+        assert!(tampered.verify::<Testnet3>().is_err());
+    }
+
+    #[test]
+    fn test_qr_chunks_round_trip() {
+        let envelope = OfflineEnvelope::new::<Testnet3>(unsigned()).unwrap();
+
+        let mut chunks = envelope.to_qr_chunks().unwrap();
+        assert!(chunks
+            .iter()
+            .all(|chunk| chunk.starts_with(OFFLINE_QR_PREFIX)));
+
+        chunks.reverse();
+        assert_eq!(
+            OfflineEnvelope::from_qr_chunks::<Testnet3>(&chunks).unwrap(),
+            envelope
+        );
+
+        chunks.pop();
+        if !chunks.is_empty() {
+            assert!(OfflineEnvelope::from_qr_chunks::<Testnet3>(&chunks).is_err());
+        }
+        assert!(OfflineEnvelope::from_qr_chunks::<Testnet3>(&[]).is_err());
+    }
+}
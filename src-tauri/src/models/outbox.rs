@@ -0,0 +1,120 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use avail_common::models::{encrypted_data::EncryptedDataRecord, user::User};
+
+/* Outbox of server-side steps taken while the Avail server is unreachable */
+
+/// A call to the Avail server that is sent later if it cannot be made now
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", content = "payload", rename_all = "snake_case")]
+pub enum OutboxAction {
+    /// Registers an account created offline
+    CreateUser(User),
+    /// Backs up encrypted data, marking it synced locally once posted
+    PostData(Vec<EncryptedDataRecord>),
+    /// Backs up the current state of local encrypted data by id
+    UpdateData(Vec<String>),
+    /// Leaves a transaction message for its recipient
+    SendTransactionMessage(EncryptedDataRecord),
+    /// Acknowledges transaction messages that were received
+    MarkSynced(Vec<Uuid>),
+    DeleteTransactionMessages(Vec<Uuid>),
+}
+
+impl OutboxAction {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            OutboxAction::CreateUser(_) => "create_user",
+            OutboxAction::PostData(_) => "post_data",
+            OutboxAction::UpdateData(_) => "update_data",
+            OutboxAction::SendTransactionMessage(_) => "send_transaction_message",
+            OutboxAction::MarkSynced(_) => "mark_synced",
+            OutboxAction::DeleteTransactionMessages(_) => "delete_transaction_messages",
+        }
+    }
+
+    /// Nothing to send, e.g. a backup with no new data
+    pub fn is_empty(&self) -> bool {
+        match self {
+            OutboxAction::PostData(records) => records.is_empty(),
+            OutboxAction::UpdateData(ids) => ids.is_empty(),
+            OutboxAction::MarkSynced(ids) | OutboxAction::DeleteTransactionMessages(ids) => {
+                ids.is_empty()
+            }
+            OutboxAction::CreateUser(_) | OutboxAction::SendTransactionMessage(_) => false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboxEntryStatus {
+    Pending,
+    /// Rejected by the server, kept so it can be inspected
+    Failed,
+}
+
+impl OutboxEntryStatus {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            OutboxEntryStatus::Pending => "pending",
+            OutboxEntryStatus::Failed => "failed",
+        }
+    }
+}
+
+impl From<&str> for OutboxEntryStatus {
+    fn from(status: &str) -> Self {
+        match status {
+            "failed" => OutboxEntryStatus::Failed,
+            _ => OutboxEntryStatus::Pending,
+        }
+    }
+}
+
+pub struct OutboxEntry {
+    pub id: String,
+    pub action: OutboxAction,
+    pub attempts: u32,
+    pub next_attempt: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub status: OutboxEntryStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OutboxStatus {
+    pub pending: u32,
+    pub failed: u32,
+    #[serde(rename = "nextAttempt")]
+    pub next_attempt: Option<DateTime<Utc>>,
+    #[serde(rename = "lastError")]
+    pub last_error: Option<String>,
+}
+
+#[cfg(test)]
+mod outbox_tests {
+    use super::*;
+
+    #[test]
+    fn test_outbox_action_wire_format() {
+        let id = Uuid::new_v4();
+        let action = OutboxAction::MarkSynced(vec![id]);
+
+        let value = serde_json::to_value(&action).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({"kind": "mark_synced", "payload": [id]})
+        );
+        assert_eq!(
+            serde_json::from_value::<OutboxAction>(value)
+                .unwrap()
+                .kind(),
+            action.kind()
+        );
+
+        assert!(OutboxAction::PostData(vec![]).is_empty());
+        assert!(!action.is_empty());
+    }
+}
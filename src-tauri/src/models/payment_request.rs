@@ -0,0 +1,344 @@
+use avail_common::{
+    aleo_tools::program_manager::TransferType,
+    errors::{AvailError, AvailErrorType, AvailResult},
+};
+use serde::{Deserialize, Serialize};
+use snarkvm::prelude::{Address, Network};
+use std::str::FromStr;
+
+use crate::helpers::utils::{percent_decode, percent_encode};
+use crate::models::transfer::TransferRequest;
+use crate::services::ans::is_ans_name;
+use crate::services::local_storage::token_registry::token_decimals;
+
+/* Payment Request URIs, i.e `avail:pay?to=aleo1...&amount=1.5&asset=credits&memo=Lunch&privacy=private` */
+
+pub const PAYMENT_URI_PREFIX: &str = "avail:pay";
+const MAX_MEMO_LENGTH: usize = 256;
+
+/// How the recipient wants to receive the payment
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentPrivacy {
+    Private,
+    Public,
+}
+
+impl PaymentPrivacy {
+    pub fn to_str(&self) -> &str {
+        match self {
+            PaymentPrivacy::Private => "private",
+            PaymentPrivacy::Public => "public",
+        }
+    }
+
+    /// The transfer that pays the recipient this way, sending from the sender's private or public balance
+    pub fn transfer_type(&self, from_private: bool) -> TransferType {
+        match (from_private, self) {
+            (true, PaymentPrivacy::Private) => TransferType::Private,
+            (true, PaymentPrivacy::Public) => TransferType::PrivateToPublic,
+            (false, PaymentPrivacy::Private) => TransferType::PublicToPrivate,
+            (false, PaymentPrivacy::Public) => TransferType::Public,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PaymentRequest {
+    /// An address or ANS name
+    to: String,
+    /// Requested amount in the asset's smallest unit, None lets the payer choose
+    amount: Option<u64>,
+    asset: String,
+    memo: Option<String>,
+    privacy: PaymentPrivacy,
+}
+
+impl PaymentRequest {
+    pub fn new(
+        to: String,
+        amount: Option<u64>,
+        asset: String,
+        memo: Option<String>,
+        privacy: PaymentPrivacy,
+    ) -> Self {
+        Self {
+            to,
+            amount,
+            asset,
+            memo,
+            privacy,
+        }
+    }
+
+    pub fn to(&self) -> &String {
+        &self.to
+    }
+
+    pub fn amount(&self) -> Option<u64> {
+        self.amount
+    }
+
+    pub fn asset(&self) -> &String {
+        &self.asset
+    }
+
+    pub fn memo(&self) -> Option<&String> {
+        self.memo.as_ref()
+    }
+
+    pub fn privacy(&self) -> PaymentPrivacy {
+        self.privacy
+    }
+
+    pub fn is_payment_uri(uri: &str) -> bool {
+        uri.starts_with(PAYMENT_URI_PREFIX)
+    }
+
+    /// Parses and validates a payment request URI, the amount is scaled by the asset's decimals
+    pub fn parse<N: Network>(uri: &str) -> AvailResult<Self> {
+        let query = match uri.strip_prefix(PAYMENT_URI_PREFIX) {
+            Some("") => "",
+            Some(rest) => rest
+                .strip_prefix('?')
+                .ok_or_else(|| invalid("Expected a query after avail:pay"))?,
+            None => return Err(invalid("Not a payment request")),
+        };
+
+        let mut to = None;
+        let mut amount = None;
+        let mut asset = None;
+        let mut memo = None;
+        let mut privacy = None;
+
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| invalid(&format!("Missing value for {}", pair)))?;
+            let value = percent_decode(value)?;
+
+            let duplicate = match key {
+                "to" => to.replace(value).is_some(),
+                "amount" => amount.replace(value).is_some(),
+                "asset" => asset.replace(value).is_some(),
+                "memo" => memo.replace(value).is_some(),
+                "privacy" => privacy
+                    .replace(match value.as_str() {
+                        "private" => PaymentPrivacy::Private,
+                        "public" => PaymentPrivacy::Public,
+                        _ => return Err(invalid(&format!("Unknown privacy {}", value))),
+                    })
+                    .is_some(),
+                _ => return Err(invalid(&format!("Unknown parameter {}", key))),
+            };
+
+            if duplicate {
+                return Err(invalid(&format!("Duplicate parameter {}", key)));
+            }
+        }
+
+        let asset = asset.unwrap_or_else(|| "credits".to_string());
+        let amount = match amount {
+            Some(amount) => Some(parse_amount(&amount, token_decimals(&asset)?)?),
+            None => None,
+        };
+
+        let request = Self {
+            to: to.ok_or_else(|| invalid("Missing recipient"))?,
+            amount,
+            asset,
+            memo,
+            privacy: privacy.unwrap_or(PaymentPrivacy::Private),
+        };
+
+        request.validate::<N>()?;
+
+        Ok(request)
+    }
+
+    pub fn validate<N: Network>(&self) -> AvailResult<()> {
+        if Address::<N>::from_str(&self.to).is_err() && !is_ans_name(&self.to) {
+            return Err(invalid(&format!(
+                "Recipient {} is not an address or ANS name",
+                self.to
+            )));
+        }
+
+        if self.amount == Some(0) {
+            return Err(invalid("Amount must be positive"));
+        }
+
+        let valid_asset = self
+            .asset
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == ' ');
+        if self.asset.is_empty() || !valid_asset {
+            return Err(invalid(&format!("Invalid asset {}", self.asset)));
+        }
+
+        if let Some(memo) = &self.memo {
+            if memo.len() > MAX_MEMO_LENGTH {
+                return Err(invalid("Memo is too long"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The URI of the request, which is also the payload encoded in its QR code
+    pub fn to_uri(&self) -> AvailResult<String> {
+        let mut uri = format!("{}?to={}", PAYMENT_URI_PREFIX, percent_encode(&self.to));
+
+        if let Some(amount) = self.amount {
+            let amount = format_amount(amount, token_decimals(&self.asset)?);
+            uri.push_str(&format!("&amount={}", amount));
+        }
+
+        uri.push_str(&format!("&asset={}", percent_encode(&self.asset)));
+
+        if let Some(memo) = &self.memo {
+            uri.push_str(&format!("&memo={}", percent_encode(memo)));
+        }
+
+        uri.push_str(&format!("&privacy={}", self.privacy.to_str()));
+
+        Ok(uri)
+    }
+
+    /// Turns the request into a transfer, `amount` is required if the request leaves it open
+    pub fn to_transfer_request(
+        &self,
+        amount: Option<u64>,
+        from_private: bool,
+        fee_private: bool,
+        fee: u64,
+    ) -> AvailResult<TransferRequest> {
+        let amount = match (self.amount, amount) {
+            (Some(requested), _) => requested,
+            (None, Some(amount)) if amount > 0 => amount,
+            _ => return Err(invalid("An amount is required")),
+        };
+
+        Ok(TransferRequest::new(
+            self.to.clone(),
+            amount,
+            self.memo.clone(),
+            None,
+            self.privacy.transfer_type(from_private),
+            fee_private,
+            fee,
+            self.asset.clone(),
+        ))
+    }
+}
+
+/// Parses a decimal amount such as `1.5` into units of 10^-decimals without going through floats
+fn parse_amount(amount: &str, decimals: u8) -> AvailResult<u64> {
+    let decimals = decimals as usize;
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+
+    let all_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty())
+        || !all_digits(whole)
+        || !all_digits(fraction)
+        || fraction.len() > decimals
+    {
+        return Err(invalid(&format!("Invalid amount {}", amount)));
+    }
+
+    let padded = format!("{}{:0<width$}", whole, fraction, width = decimals);
+    padded
+        .parse::<u64>()
+        .map_err(|_| invalid(&format!("Amount {} is too large", amount)))
+}
+
+fn format_amount(amount: u64, decimals: u8) -> String {
+    let unit = match 10u64.checked_pow(decimals as u32) {
+        Some(unit) => unit,
+        None => return amount.to_string(),
+    };
+    let fraction = format!("{:0>width$}", amount % unit, width = decimals as usize);
+    let fraction = fraction.trim_end_matches('0');
+
+    match fraction.is_empty() {
+        true => (amount / unit).to_string(),
+        false => format!("{}.{}", amount / unit, fraction),
+    }
+}
+
+fn invalid(reason: &str) -> AvailError {
+    AvailError::new(
+        AvailErrorType::InvalidData,
+        format!("Invalid payment request: {}", reason),
+        format!("Invalid payment request: {}", reason),
+    )
+}
+
+#[cfg(test)]
+mod payment_request_tests {
+    use super::*;
+    use avail_common::models::constants::TESTNET_ADDRESS;
+    use snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_payment_uri_round_trip() {
+        let request = PaymentRequest::new(
+            TESTNET_ADDRESS.to_string(),
+            Some(1500000),
+            "credits".to_string(),
+            Some("Lunch & coffee".to_string()),
+            PaymentPrivacy::Private,
+        );
+
+        let uri = request.to_uri().unwrap();
+        assert!(uri.contains("amount=1.5&"));
+        assert!(uri.contains("memo=Lunch%20%26%20coffee"));
+        assert_eq!(PaymentRequest::parse::<Testnet3>(&uri).unwrap(), request);
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed() {
+        let to = format!("{}?to={}", PAYMENT_URI_PREFIX, TESTNET_ADDRESS);
+
+        assert!(PaymentRequest::parse::<Testnet3>(&format!("{}&amount=0", to)).is_err());
+        assert!(PaymentRequest::parse::<Testnet3>(&format!("{}&amount=1.0000001", to)).is_err());
+        assert!(PaymentRequest::parse::<Testnet3>(&format!("{}&privacy=secret", to)).is_err());
+        assert!(PaymentRequest::parse::<Testnet3>(&format!("{}&to=bob.ale", to)).is_err());
+        assert!(PaymentRequest::parse::<Testnet3>("avail:pay?to=not-an-address").is_err());
+        assert!(PaymentRequest::parse::<Testnet3>("avail:pay?to=bob.ale").is_ok());
+    }
+
+    #[test]
+    fn test_amount_scales_by_decimals() {
+        assert_eq!(parse_amount("1.5", 6).unwrap(), 1500000);
+        assert_eq!(parse_amount("1.5", 2).unwrap(), 150);
+        assert_eq!(parse_amount("7", 0).unwrap(), 7);
+        assert!(parse_amount("1.001", 2).is_err());
+        assert!(parse_amount("0.5", 0).is_err());
+
+        assert_eq!(format_amount(150, 2), "1.5");
+        assert_eq!(format_amount(1500000, 6), "1.5");
+        assert_eq!(format_amount(7, 0), "7");
+    }
+
+    #[test]
+    fn test_to_transfer_request() {
+        let uri = format!(
+            "{}?to={}&privacy=public",
+            PAYMENT_URI_PREFIX, TESTNET_ADDRESS
+        );
+        let request = PaymentRequest::parse::<Testnet3>(&uri).unwrap();
+
+        assert!(request.to_transfer_request(None, true, false, 0).is_err());
+
+        let transfer = request
+            .to_transfer_request(Some(2000000), true, false, 300000)
+            .unwrap();
+        assert_eq!(transfer.amount(), &2000000);
+        assert_eq!(transfer.asset_id(), "credits");
+        assert!(matches!(
+            transfer.transfer_type(),
+            TransferType::PrivateToPublic
+        ));
+    }
+}
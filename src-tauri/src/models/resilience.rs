@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use avail_common::errors::{AvailError, AvailErrorType, AvailResult};
+
+/* Network call policy */
+
+/// The remote services the wallet depends on, each with its own circuit breaker
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkService {
+    /// The Aleo node
+    Node,
+    /// The Avail server
+    Avail,
+}
+
+impl NetworkService {
+    pub fn name(&self) -> &'static str {
+        match self {
+            NetworkService::Node => "Aleo node",
+            NetworkService::Avail => "Avail server",
+        }
+    }
+}
+
+/// Retry, timeout and circuit breaker settings applied to every network call
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ResilienceSettings {
+    /// Retries after the first attempt, for retryable errors only
+    #[serde(rename = "maxRetries")]
+    pub max_retries: u32,
+    /// Backoff before the first retry, doubled on every retry
+    #[serde(rename = "baseDelayMs")]
+    pub base_delay_ms: u64,
+    #[serde(rename = "maxDelayMs")]
+    pub max_delay_ms: u64,
+    /// Time allowed for a single attempt
+    #[serde(rename = "timeoutMs")]
+    pub timeout_ms: u64,
+    /// Consecutive failures that open the breaker
+    #[serde(rename = "breakerThreshold")]
+    pub breaker_threshold: u32,
+    /// How long an open breaker fails calls before letting one through
+    #[serde(rename = "breakerCooldownMs")]
+    pub breaker_cooldown_ms: u64,
+}
+
+impl Default for ResilienceSettings {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay_ms: 500,
+            max_delay_ms: 8000,
+            timeout_ms: 60000,
+            breaker_threshold: 5,
+            breaker_cooldown_ms: 30000,
+        }
+    }
+}
+
+impl ResilienceSettings {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
+
+    pub fn breaker_cooldown(&self) -> Duration {
+        Duration::from_millis(self.breaker_cooldown_ms)
+    }
+
+    pub fn validate(&self) -> AvailResult<()> {
+        if self.timeout_ms == 0 || self.breaker_threshold == 0 {
+            return Err(AvailError::new(
+                AvailErrorType::Validation,
+                "Timeout and breaker threshold must be positive".to_string(),
+                "The timeout and failure threshold must be greater than zero.".to_string(),
+            ));
+        }
+
+        if self.base_delay_ms > self.max_delay_ms {
+            return Err(AvailError::new(
+                AvailErrorType::Validation,
+                format!(
+                    "Base delay {}ms exceeds max delay {}ms",
+                    self.base_delay_ms, self.max_delay_ms
+                ),
+                "The retry delay can't be longer than the maximum delay.".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
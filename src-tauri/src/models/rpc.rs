@@ -0,0 +1,190 @@
+use avail_common::errors::{AvailError, AvailErrorType};
+use serde::{Deserialize, Serialize};
+
+use crate::models::wallet_connect::create_event::CreateEventRequest;
+
+/* Local JSON-RPC Interfaces */
+
+pub const JSON_RPC_VERSION: &str = "2.0";
+pub const RPC_DEFAULT_PORT: u16 = 7520;
+
+pub const PARSE_ERROR: i32 = -32700;
+pub const INVALID_REQUEST: i32 = -32600;
+pub const METHOD_NOT_FOUND: i32 = -32601;
+pub const INVALID_PARAMS: i32 = -32602;
+/// Errors returned by the wallet services
+pub const SERVER_ERROR: i32 = -32000;
+pub const UNAUTHORIZED: i32 = -32001;
+
+/// Where the server listens, always local to the machine
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RpcTransport {
+    /// 127.0.0.1 on the given port
+    Tcp { port: u16 },
+    /// Unix domain socket at the given path
+    Unix { path: String },
+}
+
+impl RpcTransport {
+    pub fn endpoint(&self) -> String {
+        match self {
+            RpcTransport::Tcp { port } => format!("tcp://127.0.0.1:{}", port),
+            RpcTransport::Unix { path } => format!("unix://{}", path),
+        }
+    }
+}
+
+impl Default for RpcTransport {
+    fn default() -> Self {
+        RpcTransport::Tcp {
+            port: RPC_DEFAULT_PORT,
+        }
+    }
+}
+
+/// A JSON-RPC 2.0 request, sent as one line, with the server token alongside it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RpcRequest {
+    pub jsonrpc: String,
+    #[serde(default)]
+    pub id: serde_json::Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: serde_json::Value,
+    pub token: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RpcError {
+    pub code: i32,
+    pub message: String,
+}
+
+impl RpcError {
+    pub fn new(code: i32, message: &str) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl From<AvailError> for RpcError {
+    fn from(error: AvailError) -> Self {
+        let code = match error.error_type {
+            AvailErrorType::Unauthorized => UNAUTHORIZED,
+            _ => SERVER_ERROR,
+        };
+
+        Self::new(code, &error.external_msg)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RpcResponse {
+    pub jsonrpc: String,
+    pub id: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    pub fn success(id: serde_json::Value, result: serde_json::Value) -> Self {
+        Self {
+            jsonrpc: JSON_RPC_VERSION.to_string(),
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    pub fn failure(id: serde_json::Value, error: RpcError) -> Self {
+        Self {
+            jsonrpc: JSON_RPC_VERSION.to_string(),
+            id,
+            result: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Params of `request_create_event`, the wallet connect request with the fee privacy next to it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreateEventParams {
+    #[serde(flatten)]
+    pub request: CreateEventRequest,
+    #[serde(default, rename = "feePrivate")]
+    pub fee_private: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RpcServerStatus {
+    pub running: bool,
+    pub endpoint: Option<String>,
+    /// File holding the token clients authenticate with, readable only by the user
+    #[serde(rename = "tokenPath")]
+    pub token_path: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SyncStatus {
+    #[serde(rename = "lastSync")]
+    pub last_sync: u32,
+    #[serde(rename = "latestHeight")]
+    pub latest_height: u32,
+    pub synced: bool,
+}
+
+impl SyncStatus {
+    pub fn new(last_sync: u32, latest_height: u32) -> Self {
+        Self {
+            last_sync,
+            latest_height,
+            synced: last_sync >= latest_height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod rpc_tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_wire_format() {
+        let request: RpcRequest = serde_json::from_str(
+            r#"{"jsonrpc":"2.0","id":7,"method":"get_sync_status","token":"secret"}"#,
+        )
+        .unwrap();
+        assert_eq!(request.id, serde_json::json!(7));
+        assert_eq!(request.params, serde_json::Value::Null);
+
+        let success =
+            serde_json::to_value(RpcResponse::success(request.id.clone(), true.into())).unwrap();
+        assert_eq!(
+            success,
+            serde_json::json!({"jsonrpc": "2.0", "id": 7, "result": true})
+        );
+
+        let failure = serde_json::to_value(RpcResponse::failure(
+            request.id,
+            RpcError::new(METHOD_NOT_FOUND, "Method not found"),
+        ))
+        .unwrap();
+        assert_eq!(failure["error"]["code"], METHOD_NOT_FOUND);
+        assert!(failure.get("result").is_none());
+    }
+
+    #[test]
+    fn test_transport_endpoint() {
+        assert_eq!(
+            RpcTransport::default().endpoint(),
+            format!("tcp://127.0.0.1:{}", RPC_DEFAULT_PORT)
+        );
+        let unix: RpcTransport =
+            serde_json::from_str(r#"{"type":"unix","path":"/tmp/avail.sock"}"#).unwrap();
+        assert_eq!(unix.endpoint(), "unix:///tmp/avail.sock");
+    }
+}
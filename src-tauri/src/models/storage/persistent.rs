@@ -92,6 +92,30 @@ impl PersistentStorage {
 
         Ok(key_vec)
     }
+
+    /// Fetch rows like `get_all`, binding `params` to the ?1, ?2.. placeholders of the query
+    pub fn get_all_with_params<T: FromSql, P: ToSql>(
+        &self,
+        query: &str,
+        params: Vec<P>,
+        item_count: usize,
+    ) -> AvailResult<Vec<Vec<T>>> {
+        let mut statement = self.conn.prepare(query)?;
+
+        let key_iter = statement.query_map(params_from_iter(params.into_iter()), |row| {
+            let data = (0..item_count).flat_map(|i| row.get(i)).collect();
+            Ok(data)
+        })?;
+
+        let key_vec = key_iter
+            .map(|key| match key {
+                Ok(key) => key,
+                Err(_) => Vec::new(),
+            })
+            .collect::<Vec<Vec<T>>>();
+
+        Ok(key_vec)
+    }
 }
 #[test]
 fn test_save() {
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+
+/* Token Interfaces */
+
+/// ARC20 token metadata discovered on chain and stored in the local token registry
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TokenMetadata {
+    #[serde(rename = "tokenName")]
+    token_name: String,
+    #[serde(rename = "programId")]
+    program_id: String,
+    name: String,
+    symbol: String,
+    /// None when the program exposes no token info, amounts cannot be scaled until it is known
+    decimals: Option<u8>,
+}
+
+impl TokenMetadata {
+    pub fn new(
+        token_name: String,
+        program_id: String,
+        name: String,
+        symbol: String,
+        decimals: Option<u8>,
+    ) -> Self {
+        Self {
+            token_name,
+            program_id,
+            name,
+            symbol,
+            decimals,
+        }
+    }
+
+    /// Fallback metadata derived from the program id when the program exposes no token info mapping
+    pub fn from_program_id(token_name: &str, program_id: &str) -> Self {
+        let name = program_id.trim_end_matches(".aleo").to_string();
+        let symbol = name.to_uppercase();
+
+        Self {
+            token_name: token_name.to_string(),
+            program_id: program_id.to_string(),
+            name,
+            symbol,
+            decimals: None,
+        }
+    }
+
+    /// Metadata of the native aleo credits
+    pub fn credits() -> Self {
+        Self {
+            token_name: "credits.record".to_string(),
+            program_id: "credits.aleo".to_string(),
+            name: "Aleo Credits".to_string(),
+            symbol: "ALEO".to_string(),
+            decimals: Some(6),
+        }
+    }
+
+    pub fn token_name(&self) -> &String {
+        &self.token_name
+    }
+
+    pub fn program_id(&self) -> &String {
+        &self.program_id
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn symbol(&self) -> &String {
+        &self.symbol
+    }
+
+    pub fn decimals(&self) -> Option<u8> {
+        self.decimals
+    }
+}
+
+/// Private balance derived from unspent token records compared with the stored balance counter
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReconciledBalance {
+    #[serde(rename = "tokenName")]
+    token_name: String,
+    #[serde(rename = "programId")]
+    program_id: String,
+    balance: u64,
+    #[serde(rename = "storedBalance")]
+    stored_balance: u64,
+    records: usize,
+}
+
+impl ReconciledBalance {
+    pub fn new(
+        token_name: String,
+        program_id: String,
+        balance: u64,
+        stored_balance: u64,
+        records: usize,
+    ) -> Self {
+        Self {
+            token_name,
+            program_id,
+            balance,
+            stored_balance,
+            records,
+        }
+    }
+
+    pub fn token_name(&self) -> &String {
+        &self.token_name
+    }
+
+    pub fn balance(&self) -> u64 {
+        self.balance
+    }
+
+    pub fn stored_balance(&self) -> u64 {
+        self.stored_balance
+    }
+
+    /// True if the stored counter did not match the records and had to be corrected
+    pub fn drifted(&self) -> bool {
+        self.balance != self.stored_balance
+    }
+}
@@ -81,3 +81,75 @@ impl TransferRequest {
         &self.asset_id
     }
 }
+
+/// Privacy mode of an nft transfer, mapping to the collection program's transfer function
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NftTransferPrivacy {
+    Private,
+    PrivateToPublic,
+}
+
+impl NftTransferPrivacy {
+    /// Transfer function names of the collection program, in order of preference
+    pub fn function_names(&self) -> Vec<&'static str> {
+        match self {
+            NftTransferPrivacy::Private => vec!["transfer_private", "transfer"],
+            NftTransferPrivacy::PrivateToPublic => vec!["transfer_private_to_public"],
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NftTransferRequest {
+    record_id: String,
+    recipient: String,
+    privacy: NftTransferPrivacy,
+    password: Option<String>,
+    fee_private: bool,
+    fee: u64,
+}
+
+impl NftTransferRequest {
+    pub fn new(
+        record_id: String,
+        recipient: String,
+        privacy: NftTransferPrivacy,
+        password: Option<String>,
+        fee_private: bool,
+        fee: u64,
+    ) -> Self {
+        Self {
+            record_id,
+            recipient,
+            privacy,
+            password,
+            fee_private,
+            fee,
+        }
+    }
+
+    pub fn record_id(&self) -> &String {
+        &self.record_id
+    }
+
+    pub fn recipient(&self) -> &String {
+        &self.recipient
+    }
+
+    pub fn privacy(&self) -> &NftTransferPrivacy {
+        &self.privacy
+    }
+
+    pub fn password(&self) -> &Option<String> {
+        &self.password
+    }
+
+    pub fn fee_private(&self) -> &bool {
+        &self.fee_private
+    }
+
+    pub fn fee(&self) -> &u64 {
+        &self.fee
+    }
+}
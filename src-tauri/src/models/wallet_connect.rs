@@ -1,6 +1,11 @@
 pub mod balance;
 pub mod create_event;
+pub mod dapp_session;
 pub mod decrypt;
+pub mod deploy;
 pub mod get_event;
+pub mod policy;
+pub mod program_interface;
 pub mod records;
 pub mod sign;
+pub mod sign_in;
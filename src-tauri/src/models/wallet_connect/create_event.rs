@@ -1,5 +1,7 @@
 use avail_common::models::encrypted_data::EventTypeCommon;
 use serde::{Deserialize, Serialize};
+
+use super::deploy::DeployRequest;
 /* Create Event Interfaces */
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -13,6 +15,8 @@ pub struct CreateEventRequest {
     function_id: String,
     fee: f64,
     inputs: Vec<String>,
+    #[serde(default)]
+    deployment: Option<DeployRequest>,
 }
 
 impl CreateEventRequest {
@@ -31,6 +35,7 @@ impl CreateEventRequest {
             function_id,
             fee,
             inputs,
+            deployment: None,
         }
     }
 
@@ -57,6 +62,10 @@ impl CreateEventRequest {
     pub fn inputs(&self) -> &Vec<String> {
         &self.inputs
     }
+
+    pub fn deployment(&self) -> Option<&DeployRequest> {
+        self.deployment.as_ref()
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
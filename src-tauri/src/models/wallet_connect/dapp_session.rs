@@ -0,0 +1,266 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use avail_common::errors::{AvailError, AvailErrorType, AvailResult};
+
+/* Dapp Session Interfaces */
+
+/// Who a wallet connect request comes from, dapps are held to their session and policy
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Caller {
+    /// The wallet's own screens
+    Wallet,
+    /// Local automation authenticated by the rpc server's token
+    Rpc,
+    /// A dapp, identified by its origin
+    Dapp(String),
+}
+
+impl Caller {
+    /// Origin a dapp is checked against, None for the wallet, dapps without an origin are rejected
+    pub fn dapp_origin(&self) -> AvailResult<Option<&String>> {
+        match self {
+            Caller::Wallet | Caller::Rpc => Ok(None),
+            Caller::Dapp(origin) if origin.trim().is_empty() => Err(AvailError::new(
+                AvailErrorType::Unauthorized,
+                "Dapp request without an origin".to_string(),
+                "This dapp could not be identified, the request was rejected.".to_string(),
+            )),
+            Caller::Dapp(origin) => Ok(Some(origin)),
+        }
+    }
+
+    /// Only activity in the wallet's windows keeps the password session alive, automation does not
+    pub fn extends_session(&self) -> bool {
+        !matches!(self, Caller::Rpc)
+    }
+}
+
+/// Access a dapp has been granted through wallet connect
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DappPermission {
+    /// Read records of the listed programs
+    ReadRecords {
+        #[serde(rename = "programIds")]
+        program_ids: Vec<String>,
+    },
+    Decrypt,
+    Sign,
+    Execute,
+}
+
+impl DappPermission {
+    fn same_kind(&self, other: &DappPermission) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+/// The permissions a dapp origin holds until its session expires
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DappSession {
+    origin: String,
+    permissions: Vec<DappPermission>,
+    created: DateTime<Local>,
+    #[serde(rename = "expiresAt")]
+    expires_at: DateTime<Local>,
+}
+
+impl DappSession {
+    pub fn new(
+        origin: String,
+        permissions: Vec<DappPermission>,
+        created: DateTime<Local>,
+        expires_at: DateTime<Local>,
+    ) -> Self {
+        Self {
+            origin,
+            permissions,
+            created,
+            expires_at,
+        }
+    }
+
+    pub fn origin(&self) -> &String {
+        &self.origin
+    }
+
+    pub fn permissions(&self) -> &Vec<DappPermission> {
+        &self.permissions
+    }
+
+    pub fn created(&self) -> DateTime<Local> {
+        self.created
+    }
+
+    pub fn expires_at(&self) -> DateTime<Local> {
+        self.expires_at
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Local::now()
+    }
+
+    pub fn extend(&mut self, expires_at: DateTime<Local>) {
+        if expires_at > self.expires_at {
+            self.expires_at = expires_at;
+        }
+    }
+
+    /// Adds a permission, read record grants are merged into a single entry
+    pub fn grant(&mut self, permission: DappPermission) {
+        let existing = self
+            .permissions
+            .iter_mut()
+            .find(|granted| granted.same_kind(&permission));
+
+        match (existing, permission) {
+            (
+                Some(DappPermission::ReadRecords { program_ids }),
+                DappPermission::ReadRecords {
+                    program_ids: new_ids,
+                },
+            ) => {
+                for program_id in new_ids {
+                    if !program_ids.contains(&program_id) {
+                        program_ids.push(program_id);
+                    }
+                }
+            }
+            (Some(_), _) => {}
+            (None, permission) => self.permissions.push(permission),
+        }
+    }
+
+    /// Checks a permission without program scope, i.e decrypt, sign or execute
+    pub fn allows(&self, permission: &DappPermission) -> bool {
+        !self.is_expired()
+            && self
+                .permissions
+                .iter()
+                .any(|granted| granted.same_kind(permission))
+    }
+
+    /// Programs whose records can be read, empty if none are
+    pub fn readable_programs(&self) -> Vec<String> {
+        self.permissions
+            .iter()
+            .find_map(|granted| match granted {
+                DappPermission::ReadRecords { program_ids } => Some(program_ids.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn can_read_records(&self, program_id: &str) -> bool {
+        if self.is_expired() {
+            return false;
+        }
+
+        self.permissions.iter().any(|granted| match granted {
+            DappPermission::ReadRecords { program_ids } => {
+                program_ids.iter().any(|id| id == program_id)
+            }
+            _ => false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod dapp_session_tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn test_session() -> DappSession {
+        DappSession::new(
+            "https://dapp.example".to_string(),
+            vec![DappPermission::ReadRecords {
+                program_ids: vec!["credits.aleo".to_string()],
+            }],
+            Local::now(),
+            Local::now() + Duration::hours(1),
+        )
+    }
+
+    #[test]
+    fn test_grant_merges_read_records() {
+        let mut session = test_session();
+        session.grant(DappPermission::ReadRecords {
+            program_ids: vec!["token.aleo".to_string()],
+        });
+        session.grant(DappPermission::Sign);
+
+        assert_eq!(session.permissions().len(), 2);
+        assert!(session.can_read_records("token.aleo"));
+        assert!(session.can_read_records("credits.aleo"));
+        assert!(!session.can_read_records("other.aleo"));
+        assert!(session.allows(&DappPermission::Sign));
+        assert!(!session.allows(&DappPermission::Execute));
+    }
+
+    #[test]
+    fn test_expired_session_denies() {
+        let mut session = test_session();
+        session.expires_at = Local::now() - Duration::minutes(1);
+
+        assert!(!session.can_read_records("credits.aleo"));
+        assert!(!session.allows(&DappPermission::ReadRecords {
+            program_ids: vec![]
+        }));
+    }
+
+    #[test]
+    fn test_empty_read_grant_reads_nothing() {
+        let mut session = test_session();
+        session.grant(DappPermission::ReadRecords {
+            program_ids: vec![],
+        });
+
+        assert_eq!(
+            session.readable_programs(),
+            vec!["credits.aleo".to_string()]
+        );
+        assert!(!session.can_read_records("token.aleo"));
+    }
+
+    #[test]
+    fn test_caller_origin() {
+        let wallet: Caller = serde_json::from_str(r#""wallet""#).unwrap();
+        assert_eq!(wallet.dapp_origin().unwrap(), None);
+        assert!(wallet.extends_session());
+
+        assert_eq!(Caller::Rpc.dapp_origin().unwrap(), None);
+        assert!(!Caller::Rpc.extends_session());
+
+        let dapp: Caller = serde_json::from_str(r#"{"dapp":"https://dapp.example"}"#).unwrap();
+        assert_eq!(
+            dapp.dapp_origin().unwrap(),
+            Some(&"https://dapp.example".to_string())
+        );
+
+        let unidentified = Caller::Dapp("".to_string());
+        assert_eq!(
+            unidentified.dapp_origin().unwrap_err().error_type,
+            AvailErrorType::Unauthorized
+        );
+        assert!(serde_json::from_str::<Caller>("{}").is_err());
+    }
+
+    #[test]
+    fn test_permission_serialization() {
+        let permission: DappPermission =
+            serde_json::from_str(r#"{"read_records":{"programIds":["credits.aleo"]}}"#).unwrap();
+
+        assert_eq!(
+            permission,
+            DappPermission::ReadRecords {
+                program_ids: vec!["credits.aleo".to_string()]
+            }
+        );
+        assert_eq!(
+            serde_json::to_string(&DappPermission::Decrypt).unwrap(),
+            r#""decrypt""#
+        );
+    }
+}
@@ -1 +1,127 @@
+use serde::{Deserialize, Serialize};
 
+use super::create_event::CreateEventRequest;
+
+/* Deploy Interfaces */
+
+/// A program deployment requested by a dapp
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DeployRequest {
+    program: String,
+    #[serde(default)]
+    imports: Vec<String>,
+    #[serde(rename = "feeOverride", default)]
+    fee_override: Option<f64>,
+}
+
+impl DeployRequest {
+    pub fn new(program: String, imports: Vec<String>, fee_override: Option<f64>) -> Self {
+        Self {
+            program,
+            imports,
+            fee_override,
+        }
+    }
+
+    /// Builds a deploy request from a create event request, the deployment field takes precedence over the legacy inputs where the program is inputs[0] and its imports follow
+    pub fn from_create_event(request: &CreateEventRequest) -> Option<Self> {
+        if let Some(deployment) = request.deployment() {
+            return Some(deployment.clone());
+        }
+
+        let (program, imports) = request.inputs().split_first()?;
+
+        let fee_override = match request.fee() > 0.0 {
+            true => Some(request.fee()),
+            false => None,
+        };
+
+        Some(Self::new(program.clone(), imports.to_vec(), fee_override))
+    }
+
+    pub fn program(&self) -> &String {
+        &self.program
+    }
+
+    /// Source of the imported programs, imports not listed here are resolved from chain
+    pub fn imports(&self) -> &Vec<String> {
+        &self.imports
+    }
+
+    /// Fee in credits to pay instead of the minimum deployment cost
+    pub fn fee_override(&self) -> Option<f64> {
+        self.fee_override
+    }
+}
+
+/// What a deployment costs in credits, and whether the chosen balance can pay for it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DeploymentCost {
+    #[serde(rename = "programId")]
+    pub program_id: String,
+    /// Deployed imports the program is synthesized against, these are not paid for again
+    pub imports: Vec<String>,
+    #[serde(rename = "storageCost")]
+    pub storage_cost: f64,
+    #[serde(rename = "namespaceCost")]
+    pub namespace_cost: f64,
+    /// Sum of the storage and namespace costs, this network version does not charge for synthesis
+    #[serde(rename = "minimumCost")]
+    pub minimum_cost: f64,
+    /// Fee that will be paid, the override if one was given
+    pub fee: f64,
+    #[serde(rename = "feePrivate")]
+    pub fee_private: bool,
+    /// Public balance, or the largest unspent credits record when paying privately
+    pub available: f64,
+    pub sufficient: bool,
+}
+
+#[cfg(test)]
+mod deploy_tests {
+    use super::*;
+    use avail_common::models::encrypted_data::EventTypeCommon;
+
+    #[test]
+    fn test_from_legacy_inputs() {
+        let request = CreateEventRequest::new(
+            None,
+            EventTypeCommon::Deploy,
+            "hello.aleo".to_string(),
+            "".to_string(),
+            0.0,
+            vec!["program hello.aleo;".to_string()],
+        );
+
+        let deploy_request = DeployRequest::from_create_event(&request).unwrap();
+
+        assert_eq!(deploy_request.program(), "program hello.aleo;");
+        assert!(deploy_request.imports().is_empty());
+        assert_eq!(deploy_request.fee_override(), None);
+    }
+
+    #[test]
+    fn test_from_deployment_field() {
+        let request: CreateEventRequest = serde_json::from_str(
+            r#"{
+                "address": null,
+                "type": "Deploy",
+                "programId": "hello.aleo",
+                "functionId": "",
+                "fee": 0.0,
+                "inputs": [],
+                "deployment": {
+                    "program": "import token.aleo; program hello.aleo;",
+                    "imports": ["program token.aleo;"],
+                    "feeOverride": 5.5
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let deploy_request = DeployRequest::from_create_event(&request).unwrap();
+
+        assert_eq!(deploy_request.imports().len(), 1);
+        assert_eq!(deploy_request.fee_override(), Some(5.5));
+    }
+}
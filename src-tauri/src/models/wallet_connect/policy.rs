@@ -0,0 +1,262 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/* Dapp Policy Interfaces */
+
+/// Auto-approval rules for a dapp origin calling a program.
+/// Budgets only measure credits: `credits.aleo` calls count the amount moved plus the fee,
+/// calls to any other program count just the fee, whatever tokens they move.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DappPolicy {
+    origin: String,
+    #[serde(rename = "programId")]
+    program_id: String,
+    /// Credits, fees included, that can be auto-approved per day
+    #[serde(rename = "dailyBudget")]
+    daily_budget: Option<f64>,
+    /// Credits, fees included, that a single auto-approved transaction can spend
+    #[serde(rename = "perTxBudget")]
+    per_tx_budget: Option<f64>,
+    /// Functions that can be auto-approved
+    #[serde(rename = "allowedFunctions")]
+    allowed_functions: Vec<String>,
+    /// Spend above which the password is required again, even if the user approves
+    #[serde(rename = "reauthThreshold")]
+    reauth_threshold: Option<f64>,
+}
+
+impl DappPolicy {
+    pub fn new(
+        origin: String,
+        program_id: String,
+        daily_budget: Option<f64>,
+        per_tx_budget: Option<f64>,
+        allowed_functions: Vec<String>,
+        reauth_threshold: Option<f64>,
+    ) -> Self {
+        Self {
+            origin,
+            program_id,
+            daily_budget,
+            per_tx_budget,
+            allowed_functions,
+            reauth_threshold,
+        }
+    }
+
+    pub fn origin(&self) -> &String {
+        &self.origin
+    }
+
+    pub fn program_id(&self) -> &String {
+        &self.program_id
+    }
+
+    pub fn daily_budget(&self) -> Option<f64> {
+        self.daily_budget
+    }
+
+    pub fn per_tx_budget(&self) -> Option<f64> {
+        self.per_tx_budget
+    }
+
+    pub fn allowed_functions(&self) -> &Vec<String> {
+        &self.allowed_functions
+    }
+
+    pub fn reauth_threshold(&self) -> Option<f64> {
+        self.reauth_threshold
+    }
+
+    /// Decides how a request spending `spend` credits is handled given what was auto-approved today
+    pub fn evaluate(
+        &self,
+        function_id: &str,
+        spend: f64,
+        spent_today: f64,
+    ) -> (PolicyDecision, String) {
+        if let Some(threshold) = self.reauth_threshold {
+            if spend > threshold {
+                return (
+                    PolicyDecision::Reauthenticate,
+                    format!(
+                        "Spend of {} exceeds the reauthentication threshold of {}",
+                        spend, threshold
+                    ),
+                );
+            }
+        }
+
+        if !self.allowed_functions.iter().any(|f| f == function_id) {
+            return (
+                PolicyDecision::Prompt,
+                format!("Function '{}' is not allowlisted", function_id),
+            );
+        }
+
+        if self.per_tx_budget.is_none() && self.daily_budget.is_none() {
+            return (PolicyDecision::Prompt, "No budget is set".to_string());
+        }
+
+        if let Some(per_tx_budget) = self.per_tx_budget {
+            if spend > per_tx_budget {
+                return (
+                    PolicyDecision::Prompt,
+                    format!(
+                        "Spend of {} exceeds the per transaction budget of {}",
+                        spend, per_tx_budget
+                    ),
+                );
+            }
+        }
+
+        if let Some(daily_budget) = self.daily_budget {
+            if spent_today + spend > daily_budget {
+                return (
+                    PolicyDecision::Prompt,
+                    format!(
+                        "Spend of {} exceeds the remaining daily budget of {}",
+                        spend,
+                        (daily_budget - spent_today).max(0.0)
+                    ),
+                );
+            }
+        }
+
+        (PolicyDecision::AutoApprove, "Within policy".to_string())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyDecision {
+    AutoApprove,
+    Prompt,
+    Reauthenticate,
+}
+
+impl PolicyDecision {
+    pub fn to_str(&self) -> &str {
+        match self {
+            PolicyDecision::AutoApprove => "auto_approve",
+            PolicyDecision::Prompt => "prompt",
+            PolicyDecision::Reauthenticate => "reauthenticate",
+        }
+    }
+
+    pub fn from_str(decision: &str) -> Self {
+        match decision {
+            "auto_approve" => PolicyDecision::AutoApprove,
+            "reauthenticate" => PolicyDecision::Reauthenticate,
+            _ => PolicyDecision::Prompt,
+        }
+    }
+}
+
+/// A logged policy decision
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PolicyDecisionEntry {
+    origin: String,
+    #[serde(rename = "programId")]
+    program_id: String,
+    #[serde(rename = "functionId")]
+    function_id: String,
+    spend: f64,
+    decision: PolicyDecision,
+    reason: String,
+    created: DateTime<Utc>,
+}
+
+impl PolicyDecisionEntry {
+    pub fn new(
+        origin: String,
+        program_id: String,
+        function_id: String,
+        spend: f64,
+        decision: PolicyDecision,
+        reason: String,
+        created: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            origin,
+            program_id,
+            function_id,
+            spend,
+            decision,
+            reason,
+            created,
+        }
+    }
+
+    pub fn origin(&self) -> &String {
+        &self.origin
+    }
+
+    pub fn program_id(&self) -> &String {
+        &self.program_id
+    }
+
+    pub fn function_id(&self) -> &String {
+        &self.function_id
+    }
+
+    pub fn spend(&self) -> f64 {
+        self.spend
+    }
+
+    pub fn decision(&self) -> PolicyDecision {
+        self.decision
+    }
+
+    pub fn reason(&self) -> &String {
+        &self.reason
+    }
+
+    pub fn created(&self) -> DateTime<Utc> {
+        self.created
+    }
+}
+
+#[cfg(test)]
+mod policy_tests {
+    use super::*;
+
+    fn test_policy() -> DappPolicy {
+        DappPolicy::new(
+            "https://trading.example".to_string(),
+            "credits.aleo".to_string(),
+            Some(10.0),
+            Some(2.0),
+            vec!["transfer_public".to_string()],
+            Some(5.0),
+        )
+    }
+
+    #[test]
+    fn test_evaluate_within_budget() {
+        let (decision, _) = test_policy().evaluate("transfer_public", 1.5, 8.0);
+        assert_eq!(decision, PolicyDecision::AutoApprove);
+    }
+
+    #[test]
+    fn test_evaluate_outside_policy() {
+        let policy = test_policy();
+
+        assert_eq!(
+            policy.evaluate("transfer_private", 1.0, 0.0).0,
+            PolicyDecision::Prompt
+        );
+        assert_eq!(
+            policy.evaluate("transfer_public", 3.0, 0.0).0,
+            PolicyDecision::Prompt
+        );
+        assert_eq!(
+            policy.evaluate("transfer_public", 1.5, 9.0).0,
+            PolicyDecision::Prompt
+        );
+        assert_eq!(
+            policy.evaluate("transfer_public", 6.0, 0.0).0,
+            PolicyDecision::Reauthenticate
+        );
+    }
+}
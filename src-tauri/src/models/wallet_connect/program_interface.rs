@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/* Program Interface Interfaces */
+
+/// How a function input or output is passed
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParameterVisibility {
+    Constant,
+    Public,
+    Private,
+    Record,
+    ExternalRecord,
+    Future,
+}
+
+/// A function input or output, the type is a plaintext type, record name or locator
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ParameterInterface {
+    #[serde(rename = "type")]
+    pub value_type: String,
+    pub visibility: ParameterVisibility,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FunctionInterface {
+    pub name: String,
+    pub inputs: Vec<ParameterInterface>,
+    pub outputs: Vec<ParameterInterface>,
+}
+
+/// The functions a deployed program exposes to dapps
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProgramInterface {
+    #[serde(rename = "programId")]
+    pub program_id: String,
+    pub functions: Vec<FunctionInterface>,
+}
+
+impl ProgramInterface {
+    pub fn function(&self, name: &str) -> Option<&FunctionInterface> {
+        self.functions.iter().find(|function| function.name == name)
+    }
+}
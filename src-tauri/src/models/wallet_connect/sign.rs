@@ -1,4 +1,12 @@
+use avail_common::{
+    converters::messages::utf8_string_to_bits,
+    errors::{AvailError, AvailErrorType, AvailResult},
+};
 use serde::{Deserialize, Serialize};
+use snarkvm::prelude::{Network, Plaintext};
+use std::str::FromStr;
+
+use super::sign_in::origin_host;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SignatureRequest {
@@ -53,3 +61,159 @@ impl SignatureResponse {
         self.error.clone()
     }
 }
+
+/// A typed Plaintext value signed under a domain and nonce so the signature cannot be replayed elsewhere
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TypedSignatureRequest {
+    domain: String,
+    nonce: u64,
+    /// A Plaintext literal or struct, i.e `{ owner: aleo1..., amount: 5u64 }`
+    message: String,
+    address: Option<String>,
+}
+
+impl TypedSignatureRequest {
+    pub fn new(domain: String, nonce: u64, message: String, address: Option<String>) -> Self {
+        Self {
+            domain,
+            nonce,
+            message,
+            address,
+        }
+    }
+
+    pub fn get_domain(&self) -> String {
+        self.domain.clone()
+    }
+
+    pub fn get_nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    pub fn get_message(&self) -> String {
+        self.message.clone()
+    }
+
+    pub fn get_address(&self) -> Option<String> {
+        self.address.clone()
+    }
+
+    /// A dapp can only sign under its own domain, given as a host or as a url
+    pub fn validate_domain(&self, origin: &str) -> AvailResult<()> {
+        let domain = origin_host(&self.domain);
+
+        if domain.is_empty() || domain != origin_host(origin) {
+            return Err(AvailError::new(
+                AvailErrorType::Unauthorized,
+                format!(
+                    "Typed signature domain {} does not match origin {}",
+                    self.domain, origin
+                ),
+                "The signature request does not come from the domain it names.".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// The value that is signed, `{ domain: field, nonce: u64, message: <message> }` where the domain is the BHP512 hash of its UTF-8 bits
+    pub fn to_plaintext<N: Network>(&self) -> AvailResult<Plaintext<N>> {
+        let message = Plaintext::<N>::from_str(&self.message)?;
+        let domain = N::hash_bhp512(&utf8_string_to_bits(&self.domain))?;
+
+        Ok(Plaintext::<N>::from_str(&format!(
+            "{{ domain: {}, nonce: {}u64, message: {} }}",
+            domain, self.nonce, message
+        ))?)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TypedSignatureResponse {
+    signature: Option<String>,
+    /// The signed value as a Plaintext, pass it to `signature::verify` in a program
+    plaintext: Option<String>,
+    /// The exact fields the signature covers
+    #[serde(rename = "messageFields")]
+    message_fields: Option<Vec<String>>,
+    error: Option<String>,
+}
+
+impl TypedSignatureResponse {
+    pub fn new(
+        signature: Option<String>,
+        plaintext: Option<String>,
+        message_fields: Option<Vec<String>>,
+        error: Option<String>,
+    ) -> Self {
+        Self {
+            signature,
+            plaintext,
+            message_fields,
+            error,
+        }
+    }
+
+    pub fn get_signature(&self) -> Option<String> {
+        self.signature.clone()
+    }
+
+    pub fn get_plaintext(&self) -> Option<String> {
+        self.plaintext.clone()
+    }
+
+    pub fn get_message_fields(&self) -> Option<Vec<String>> {
+        self.message_fields.clone()
+    }
+
+    pub fn get_error(&self) -> Option<String> {
+        self.error.clone()
+    }
+}
+
+#[cfg(test)]
+mod sign_tests {
+    use super::*;
+    use avail_common::models::constants::TESTNET_ADDRESS;
+    use snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_typed_plaintext_binds_domain_and_nonce() {
+        let message = format!("{{ amount: 5u64, recipient: {} }}", TESTNET_ADDRESS);
+
+        let request =
+            TypedSignatureRequest::new("dapp.example".to_string(), 1, message.to_string(), None);
+        let plaintext = request.to_plaintext::<Testnet3>().unwrap();
+
+        let other_nonce =
+            TypedSignatureRequest::new("dapp.example".to_string(), 2, message.to_string(), None);
+        let other_domain =
+            TypedSignatureRequest::new("other.example".to_string(), 1, message.to_string(), None);
+
+        assert_ne!(plaintext, other_nonce.to_plaintext::<Testnet3>().unwrap());
+        assert_ne!(plaintext, other_domain.to_plaintext::<Testnet3>().unwrap());
+        assert!(plaintext.to_string().contains("nonce: 1u64"));
+    }
+
+    #[test]
+    fn test_validate_domain() {
+        let request =
+            TypedSignatureRequest::new("Dapp.example".to_string(), 1, "5u64".to_string(), None);
+
+        assert!(request
+            .validate_domain("https://dapp.example/trade")
+            .is_ok());
+        assert!(request.validate_domain("https://other.example").is_err());
+        assert!(request
+            .validate_domain("https://dapp.example:8080")
+            .is_err());
+
+        let url_domain = TypedSignatureRequest::new(
+            "https://dapp.example".to_string(),
+            1,
+            "5u64".to_string(),
+            None,
+        );
+        assert!(url_domain.validate_domain("https://dapp.example").is_ok());
+    }
+}
@@ -0,0 +1,365 @@
+use avail_common::errors::{AvailError, AvailErrorType, AvailResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/* Sign-In-With-Aleo, modelled on EIP-4361 */
+
+const HEADER_SUFFIX: &str = " wants you to sign in with your Aleo account:";
+const VERSION: &str = "1";
+
+/// A structured authentication message, signed as its text form
+///
+/// ```text
+/// dapp.example wants you to sign in with your Aleo account:
+/// aleo1...
+///
+/// Sign in to Dapp
+///
+/// URI: https://dapp.example/login
+/// Version: 1
+/// Chain ID: 3
+/// Nonce: 32891756
+/// Issued At: 2024-01-01T00:00:00Z
+/// Expiration Time: 2024-01-01T00:10:00Z
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SignInMessage {
+    domain: String,
+    address: String,
+    statement: Option<String>,
+    uri: String,
+    version: String,
+    /// The network id, i.e `3` for testnet3
+    #[serde(rename = "chainId")]
+    chain_id: String,
+    nonce: String,
+    #[serde(rename = "issuedAt")]
+    issued_at: DateTime<Utc>,
+    #[serde(rename = "expirationTime")]
+    expiration_time: Option<DateTime<Utc>>,
+}
+
+impl SignInMessage {
+    pub fn new(
+        domain: String,
+        address: String,
+        statement: Option<String>,
+        uri: String,
+        chain_id: String,
+        nonce: String,
+        issued_at: DateTime<Utc>,
+        expiration_time: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            domain,
+            address,
+            statement,
+            uri,
+            version: VERSION.to_string(),
+            chain_id,
+            nonce,
+            issued_at,
+            expiration_time,
+        }
+    }
+
+    pub fn domain(&self) -> &String {
+        &self.domain
+    }
+
+    pub fn address(&self) -> &String {
+        &self.address
+    }
+
+    pub fn statement(&self) -> Option<&String> {
+        self.statement.as_ref()
+    }
+
+    pub fn uri(&self) -> &String {
+        &self.uri
+    }
+
+    pub fn chain_id(&self) -> &String {
+        &self.chain_id
+    }
+
+    pub fn nonce(&self) -> &String {
+        &self.nonce
+    }
+
+    pub fn issued_at(&self) -> DateTime<Utc> {
+        self.issued_at
+    }
+
+    pub fn expiration_time(&self) -> Option<DateTime<Utc>> {
+        self.expiration_time
+    }
+
+    /// Checks if a message is meant to be a sign in message, so malformed ones are rejected rather than signed as plain text.
+    /// The header is looked for anywhere, ignoring case and spacing, so it cannot be hidden behind leading text.
+    pub fn is_sign_in_message(message: &str) -> bool {
+        let normalized = message
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .to_lowercase();
+
+        normalized.contains(HEADER_SUFFIX.trim())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expiration_time
+            .map_or(false, |expiration_time| expiration_time <= Utc::now())
+    }
+
+    /// The text that is signed
+    pub fn to_message(&self) -> String {
+        let mut message = format!("{}{}\n{}\n", self.domain, HEADER_SUFFIX, self.address);
+
+        if let Some(statement) = &self.statement {
+            message.push_str(&format!("\n{}\n", statement));
+        }
+
+        message.push_str(&format!(
+            "\nURI: {}\nVersion: {}\nChain ID: {}\nNonce: {}\nIssued At: {}",
+            self.uri,
+            self.version,
+            self.chain_id,
+            self.nonce,
+            self.issued_at.to_rfc3339()
+        ));
+
+        if let Some(expiration_time) = self.expiration_time {
+            message.push_str(&format!(
+                "\nExpiration Time: {}",
+                expiration_time.to_rfc3339()
+            ));
+        }
+
+        message
+    }
+
+    pub fn parse(message: &str) -> AvailResult<Self> {
+        let mut lines = message.lines();
+
+        let domain = lines
+            .next()
+            .and_then(|header| header.strip_suffix(HEADER_SUFFIX))
+            .filter(|domain| !domain.is_empty())
+            .ok_or_else(|| invalid("Missing sign in header"))?
+            .to_string();
+
+        let address = lines
+            .next()
+            .filter(|address| address.starts_with("aleo1"))
+            .ok_or_else(|| invalid("Missing address"))?
+            .to_string();
+
+        if lines.next() != Some("") {
+            return Err(invalid("Expected an empty line after the address"));
+        }
+
+        let mut next = lines.next().ok_or_else(|| invalid("Missing URI"))?;
+        let statement = match next.starts_with("URI: ") {
+            true => None,
+            false => {
+                let statement = next.to_string();
+                if lines.next() != Some("") {
+                    return Err(invalid("Expected an empty line after the statement"));
+                }
+                next = lines.next().ok_or_else(|| invalid("Missing URI"))?;
+                Some(statement)
+            }
+        };
+
+        let uri = field(Some(next), "URI")?;
+        let version = field(lines.next(), "Version")?;
+        if version != VERSION {
+            return Err(invalid(&format!("Unsupported version {}", version)));
+        }
+        let chain_id = field(lines.next(), "Chain ID")?;
+        let nonce = field(lines.next(), "Nonce")?;
+        if nonce.len() < 8 || !nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(invalid("Nonce must be at least 8 alphanumeric characters"));
+        }
+        let issued_at = timestamp(&field(lines.next(), "Issued At")?)?;
+
+        let expiration_time = match lines.next() {
+            Some(line) => Some(timestamp(&field(Some(line), "Expiration Time")?)?),
+            None => None,
+        };
+
+        if lines.next().is_some() {
+            return Err(invalid("Unexpected trailing lines"));
+        }
+
+        Ok(Self {
+            domain,
+            address,
+            statement,
+            uri,
+            version,
+            chain_id,
+            nonce,
+            issued_at,
+            expiration_time,
+        })
+    }
+
+    /// Checks the message was requested by `origin` for this wallet and chain and is still valid
+    pub fn validate(&self, origin: &str, address: &str, chain_id: &str) -> AvailResult<()> {
+        if origin_host(origin).is_empty() {
+            return Err(AvailError::new(
+                AvailErrorType::Validation,
+                "Sign in message without an origin".to_string(),
+                "The sign in request does not say where it comes from.".to_string(),
+            ));
+        }
+
+        if !self.domain.eq_ignore_ascii_case(&origin_host(origin)) {
+            return Err(AvailError::new(
+                AvailErrorType::Unauthorized,
+                format!(
+                    "Sign in domain {} does not match origin {}",
+                    self.domain, origin
+                ),
+                "The sign in request does not come from the domain it names.".to_string(),
+            ));
+        }
+
+        if self.address != address {
+            return Err(AvailError::new(
+                AvailErrorType::Validation,
+                format!("Sign in address {} is not {}", self.address, address),
+                "The sign in request is for a different account.".to_string(),
+            ));
+        }
+
+        if self.chain_id != chain_id {
+            return Err(AvailError::new(
+                AvailErrorType::Validation,
+                format!("Sign in chain {} is not {}", self.chain_id, chain_id),
+                "The sign in request is for a different network.".to_string(),
+            ));
+        }
+
+        if self.is_expired() {
+            return Err(AvailError::new(
+                AvailErrorType::Validation,
+                "Sign in message has expired".to_string(),
+                "The sign in request has expired.".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// The host and port of an origin, i.e `https://dapp.example:8080/path` is `dapp.example:8080`
+pub fn origin_host(origin: &str) -> String {
+    let without_scheme = origin.split_once("://").map_or(origin, |(_, rest)| rest);
+
+    without_scheme
+        .split(|c| c == '/' || c == '?' || c == '#')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+fn field(line: Option<&str>, name: &str) -> AvailResult<String> {
+    line.and_then(|line| line.strip_prefix(&format!("{}: ", name)))
+        .map(|value| value.to_string())
+        .ok_or_else(|| invalid(&format!("Missing {}", name)))
+}
+
+fn timestamp(value: &str) -> AvailResult<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .map_err(|_| invalid(&format!("Invalid timestamp {}", value)))
+}
+
+fn invalid(reason: &str) -> AvailError {
+    AvailError::new(
+        AvailErrorType::InvalidData,
+        format!("Invalid sign in message: {}", reason),
+        "The sign in message is malformed.".to_string(),
+    )
+}
+
+#[cfg(test)]
+mod sign_in_tests {
+    use super::*;
+    use avail_common::models::constants::TESTNET_ADDRESS;
+    use chrono::Duration;
+
+    fn test_message(expiration_time: Option<DateTime<Utc>>) -> SignInMessage {
+        SignInMessage::new(
+            "dapp.example".to_string(),
+            TESTNET_ADDRESS.to_string(),
+            Some("Sign in to Dapp".to_string()),
+            "https://dapp.example/login".to_string(),
+            "3".to_string(),
+            "32891756abc".to_string(),
+            Utc::now(),
+            expiration_time,
+        )
+    }
+
+    #[test]
+    fn test_message_round_trip() {
+        let message = test_message(Some(Utc::now() + Duration::minutes(10)));
+        let text = message.to_message();
+
+        assert!(SignInMessage::is_sign_in_message(&text));
+        assert_eq!(SignInMessage::parse(&text).unwrap().to_message(), text);
+
+        let mut without_statement = message.clone();
+        without_statement.statement = None;
+        let text = without_statement.to_message();
+        assert_eq!(SignInMessage::parse(&text).unwrap(), without_statement);
+    }
+
+    #[test]
+    fn test_disguised_sign_in_messages_are_detected() {
+        let text = test_message(None).to_message();
+
+        // leading whitespace or text before the header still makes it a sign in message, which then fails to parse
+        for disguised in [
+            format!("  \n{}", text),
+            format!("Please sign this harmless note\n{}", text),
+            text.replace(
+                HEADER_SUFFIX,
+                " Wants  you to sign in with your\nAleo account:",
+            ),
+        ] {
+            assert!(SignInMessage::is_sign_in_message(&disguised));
+            assert!(SignInMessage::parse(&disguised).is_err());
+        }
+
+        assert!(!SignInMessage::is_sign_in_message("Sign in to Dapp"));
+    }
+
+    #[test]
+    fn test_validate() {
+        let origin = "https://dapp.example/app".to_string();
+        let other = "https://evil.example".to_string();
+        let message = test_message(Some(Utc::now() + Duration::minutes(10)));
+
+        assert!(message.validate(&origin, TESTNET_ADDRESS, "3").is_ok());
+        assert!(message.validate(&other, TESTNET_ADDRESS, "3").is_err());
+        assert!(message.validate(&origin, TESTNET_ADDRESS, "1").is_err());
+        assert!(message.validate("", TESTNET_ADDRESS, "3").is_err());
+
+        let expired = test_message(Some(Utc::now() - Duration::minutes(1)));
+        assert!(expired.validate(&origin, TESTNET_ADDRESS, "3").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed() {
+        let text = test_message(None)
+            .to_message()
+            .replace("Version: 1", "Version: 2");
+        assert!(SignInMessage::parse(&text).is_err());
+        assert!(SignInMessage::parse("Hello World").is_err());
+    }
+}
@@ -1,6 +1,11 @@
 pub mod account;
+pub mod ans;
 pub mod authentication;
+pub mod deep_link;
 pub mod local_storage;
+pub mod outbox;
+pub mod payment_request;
 pub mod record_handling;
 pub mod records;
+pub mod rpc_server;
 pub mod wallet_connect_api;
@@ -1,4 +1,5 @@
 pub mod generation;
 pub mod key_management;
 pub mod phrase_recovery;
+pub mod signer;
 pub mod utils;
@@ -1,6 +1,11 @@
-use snarkvm::{console::prelude::*, prelude::Testnet3};
+use snarkvm::{
+    console::prelude::*,
+    prelude::{PrivateKey, Testnet3},
+};
 
+use crate::models::outbox::OutboxAction;
 use crate::models::storage::languages::Languages;
+use crate::models::wallet::BetterAvailWallet;
 use crate::services::account::{
     key_management::key_controller::KeyController, utils::generate_discriminant,
 };
@@ -10,9 +15,10 @@ use crate::services::local_storage::{
     encrypted_data::{get_and_store_all_data, initialize_encrypted_data_table},
     persistent_storage::initial_user_preferences,
     session::{password::PASS, view::VIEWSESSION},
+    token_registry::init_token_registry_table,
     tokens::init_tokens_table,
 };
-use crate::{api::user::create_user, models::wallet::BetterAvailWallet};
+use crate::services::outbox::{is_deferrable, send_or_queue};
 
 #[cfg(target_os = "linux")]
 use crate::services::account::key_management::key_controller::linuxKeyController;
@@ -25,6 +31,15 @@ use crate::services::account::key_management::key_controller::macKeyController;
 
 use avail_common::{errors::AvailResult, models::user::User};
 
+/// Logs in on the server, an account created offline logs in once the server is reachable
+async fn login_after_creation(private_key: &PrivateKey<Testnet3>) -> AvailResult<()> {
+    match get_session_after_creation(private_key).await {
+        Ok(_) => Ok(()),
+        Err(e) if is_deferrable(&e) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn create_seed_phrase_wallet(
     username: Option<String>,
@@ -45,7 +60,8 @@ pub async fn create_seed_phrase_wallet(
         backup: false,
     };
 
-    create_user(user_request).await?;
+    // Registered later from the outbox if the server can't be reached
+    send_or_queue(OutboxAction::CreateUser(user_request)).await?;
 
     //TODO: Change to mainnet on launch
     initial_user_preferences(
@@ -59,6 +75,7 @@ pub async fn create_seed_phrase_wallet(
     )?;
 
     init_tokens_table()?;
+    init_token_registry_table()?;
 
     initialize_encrypted_data_table()?;
 
@@ -83,7 +100,7 @@ pub async fn create_seed_phrase_wallet(
 
     PASS.set_pass_session(&password)?;
 
-    get_session_after_creation(&avail_wallet.private_key).await?;
+    login_after_creation(&avail_wallet.private_key).await?;
 
     // NOTE: We can safely unwrap here because we created
     // the wallet using the [`BetterAvailWallet::new`] method
@@ -112,7 +129,8 @@ pub async fn import_wallet(
         backup: false,
     };
 
-    create_user(user_request).await?;
+    // Registered later from the outbox if the server can't be reached
+    send_or_queue(OutboxAction::CreateUser(user_request)).await?;
 
     initial_user_preferences(
         access_type,
@@ -125,6 +143,7 @@ pub async fn import_wallet(
     )?;
 
     init_tokens_table()?;
+    init_token_registry_table()?;
 
     initialize_encrypted_data_table()?;
 
@@ -151,7 +170,7 @@ pub async fn import_wallet(
 
     PASS.set_pass_session(&password)?;
 
-    get_session_after_creation(&avail_wallet.private_key).await?;
+    login_after_creation(&avail_wallet.private_key).await?;
 
     Ok(storage)
 }
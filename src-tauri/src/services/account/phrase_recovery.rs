@@ -18,7 +18,8 @@ use crate::services::account::key_management::key_controller::{
 };
 use crate::services::authentication::session::get_session_after_creation;
 use crate::services::local_storage::{
-    encrypted_data::get_and_store_all_data, tokens::init_tokens_table,
+    encrypted_data::get_and_store_all_data, token_registry::init_token_registry_table,
+    tokens::init_tokens_table,
 };
 use avail_common::models::user::User;
 
@@ -83,6 +84,7 @@ pub async fn recover_wallet_from_seed_phrase(
     )?;
 
     init_tokens_table()?;
+    init_token_registry_table()?;
 
     // some function
 
@@ -0,0 +1,640 @@
+use serde::{Deserialize, Serialize};
+use snarkvm::circuit::{Aleo, Environment};
+use snarkvm::prelude::{
+    Address, Field, Identifier, Network, Plaintext, PrivateKey, ProgramID, Record, Signature,
+    ToFields, Value, ViewKey,
+};
+use snarkvm::synthesizer::{process::Authorization, Process};
+use std::collections::HashSet;
+use std::io::{Read, Write};
+use std::marker::PhantomData;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::services::local_storage::{
+    persistent_storage::get_address,
+    session::view::VIEWSESSION,
+    utils::{get_private_key, get_view_key},
+};
+
+use avail_common::{
+    converters::messages::{field_to_fields, utf8_string_to_bits},
+    errors::{AvailError, AvailErrorType, AvailResult},
+};
+
+/* --Signers-- */
+
+/// Environment variable pointing to an external signer executable, the keychain key is used when unset
+pub const REMOTE_SIGNER_ENV: &str = "AVAIL_REMOTE_SIGNER";
+/// Time an external signer has to answer, it may be waiting on the user to confirm on a device
+pub const REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Everything that needs the account's private key goes through a signer, so keys can live outside the wallet process
+pub trait Signer<N: Network>: Send + Sync {
+    fn address(&self) -> AvailResult<Address<N>>;
+
+    /// Signs the request of a function call, `process` must contain the program and its imports
+    fn authorize(
+        &self,
+        process: &Process<N>,
+        program_id: &ProgramID<N>,
+        function_name: &Identifier<N>,
+        inputs: &[Value<N>],
+    ) -> AvailResult<Authorization<N>>;
+
+    /// Signs the fee of an execution or deployment, paid privately if a fee record is given
+    fn authorize_fee(
+        &self,
+        process: &Process<N>,
+        fee_record: Option<Record<N, Plaintext<N>>>,
+        base_fee: u64,
+        priority_fee: u64,
+        id: Field<N>,
+    ) -> AvailResult<Authorization<N>>;
+
+    fn sign_fields(&self, fields: &[Field<N>]) -> AvailResult<Signature<N>>;
+
+    /// Signs a utf8 message over its BHP512 hash, returns the signature and the hashed message
+    fn sign_message(&self, message: &str) -> AvailResult<(Signature<N>, Field<N>)> {
+        let msg_field = N::hash_bhp512(&utf8_string_to_bits(message))?;
+        let signature = self.sign_fields(&field_to_fields(&msg_field)?)?;
+
+        Ok((signature, msg_field))
+    }
+
+    /// Signs a Plaintext value over its field encoding, as checked by signature::verify in programs
+    fn sign_plaintext(
+        &self,
+        plaintext: &Plaintext<N>,
+    ) -> AvailResult<(Signature<N>, Vec<Field<N>>)> {
+        let fields = plaintext.to_fields()?;
+        let signature = self.sign_fields(&fields)?;
+
+        Ok((signature, fields))
+    }
+}
+
+/// The signer configured for this wallet, a remote signer must hold the wallet's account.
+/// Either way the password or an unexpired session is required, and the view session is unlocked.
+pub fn get_signer<N: Network, A: Aleo + Environment<Network = N>>(
+    password: Option<String>,
+) -> AvailResult<Box<dyn Signer<N>>> {
+    match std::env::var(REMOTE_SIGNER_ENV) {
+        Ok(command) if !command.is_empty() => {
+            let view_key = get_view_key::<N>(password)?;
+            VIEWSESSION.set_view_session(&view_key.to_string())?;
+
+            let signer = RemoteSigner::<N>::new(command, vec![])?;
+            ensure_signer_address::<N>(&signer, &get_address::<N>()?)?;
+
+            Ok(Box::new(signer))
+        }
+        _ => Ok(Box::new(SoftwareSigner::<N, A>::from_keychain(password)?)),
+    }
+}
+
+/// Rejects a signer holding a different account than the wallet it signs for
+pub fn ensure_signer_address<N: Network>(
+    signer: &dyn Signer<N>,
+    address: &Address<N>,
+) -> AvailResult<()> {
+    let signer_address = signer.address()?;
+
+    if &signer_address != address {
+        return Err(AvailError::new(
+            AvailErrorType::Validation,
+            format!(
+                "Signer address {} is not the wallet address {}",
+                signer_address, address
+            ),
+            "The external signer holds a different account than this wallet.".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/* Software */
+
+/// Signs with a private key held in memory
+pub struct SoftwareSigner<N: Network, A: Aleo + Environment<Network = N>> {
+    private_key: PrivateKey<N>,
+    _aleo: PhantomData<fn() -> A>,
+}
+
+impl<N: Network, A: Aleo + Environment<Network = N>> SoftwareSigner<N, A> {
+    pub fn new(private_key: PrivateKey<N>) -> Self {
+        Self {
+            private_key,
+            _aleo: PhantomData,
+        }
+    }
+
+    /// Reads the private key from the keychain, which also unlocks the view session
+    pub fn from_keychain(password: Option<String>) -> AvailResult<Self> {
+        let private_key = get_private_key::<N>(password)?;
+
+        let view_key = ViewKey::<N>::try_from(private_key)?;
+        VIEWSESSION.set_view_session(&view_key.to_string())?;
+
+        Ok(Self::new(private_key))
+    }
+}
+
+impl<N: Network, A: Aleo + Environment<Network = N>> Signer<N> for SoftwareSigner<N, A> {
+    fn address(&self) -> AvailResult<Address<N>> {
+        Ok(Address::<N>::try_from(&self.private_key)?)
+    }
+
+    fn authorize(
+        &self,
+        process: &Process<N>,
+        program_id: &ProgramID<N>,
+        function_name: &Identifier<N>,
+        inputs: &[Value<N>],
+    ) -> AvailResult<Authorization<N>> {
+        Ok(process.authorize::<A, _>(
+            &self.private_key,
+            *program_id,
+            *function_name,
+            inputs.iter(),
+            &mut rand::thread_rng(),
+        )?)
+    }
+
+    fn authorize_fee(
+        &self,
+        process: &Process<N>,
+        fee_record: Option<Record<N, Plaintext<N>>>,
+        base_fee: u64,
+        priority_fee: u64,
+        id: Field<N>,
+    ) -> AvailResult<Authorization<N>> {
+        let rng = &mut rand::thread_rng();
+
+        let authorization = match fee_record {
+            Some(fee_record) => process.authorize_fee_private::<A, _>(
+                &self.private_key,
+                fee_record,
+                base_fee,
+                priority_fee,
+                id,
+                rng,
+            )?,
+            None => process.authorize_fee_public::<A, _>(
+                &self.private_key,
+                base_fee,
+                priority_fee,
+                id,
+                rng,
+            )?,
+        };
+
+        Ok(authorization)
+    }
+
+    fn sign_fields(&self, fields: &[Field<N>]) -> AvailResult<Signature<N>> {
+        Ok(self.private_key.sign(fields, &mut rand::thread_rng())?)
+    }
+}
+
+/* Remote Process */
+
+/// A request written as a single JSON line to the signer's stdin
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum RemoteSignerRequest {
+    Address,
+    Authorize {
+        /// Sources of the program and its imports in dependency order, credits.aleo excluded
+        programs: Vec<String>,
+        #[serde(rename = "programId")]
+        program_id: String,
+        #[serde(rename = "functionId")]
+        function_id: String,
+        inputs: Vec<String>,
+    },
+    AuthorizeFee {
+        #[serde(rename = "feeRecord")]
+        fee_record: Option<String>,
+        #[serde(rename = "baseFee")]
+        base_fee: u64,
+        #[serde(rename = "priorityFee")]
+        priority_fee: u64,
+        id: String,
+    },
+    SignFields {
+        fields: Vec<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RemoteSignerMessage {
+    network: u16,
+    request: RemoteSignerRequest,
+}
+
+/// The signer's reply on stdout, `result` is the string encoding of the address, authorization or signature
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RemoteSignerResponse {
+    result: Option<String>,
+    error: Option<String>,
+}
+
+/// Delegates signing to an external executable, spawned once per request
+pub struct RemoteSigner<N: Network> {
+    command: String,
+    args: Vec<String>,
+    timeout: Duration,
+    address: Address<N>,
+}
+
+impl<N: Network> RemoteSigner<N> {
+    pub fn new(command: String, args: Vec<String>) -> AvailResult<Self> {
+        Self::with_timeout(command, args, REMOTE_SIGNER_TIMEOUT)
+    }
+
+    /// A signer that is killed when it takes longer than `timeout` to answer a request
+    pub fn with_timeout(
+        command: String,
+        args: Vec<String>,
+        timeout: Duration,
+    ) -> AvailResult<Self> {
+        let address = Self::call_command(&command, &args, timeout, RemoteSignerRequest::Address)?;
+
+        Ok(Self {
+            command,
+            args,
+            timeout,
+            address: Address::<N>::from_str(&address)?,
+        })
+    }
+
+    fn call(&self, request: RemoteSignerRequest) -> AvailResult<String> {
+        Self::call_command(&self.command, &self.args, self.timeout, request)
+    }
+
+    fn call_command(
+        command: &str,
+        args: &[String],
+        timeout: Duration,
+        request: RemoteSignerRequest,
+    ) -> AvailResult<String> {
+        let message = serde_json::to_string(&RemoteSignerMessage {
+            network: N::ID,
+            request,
+        })?;
+
+        let mut child = match Command::new(command)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(e) => {
+                return Err(AvailError::new(
+                    AvailErrorType::External,
+                    format!("Error starting signer {}: {}", command, e),
+                    "The external signer could not be started.".to_string(),
+                ))
+            }
+        };
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(format!("{}\n", message).as_bytes())?;
+        }
+
+        // stdout is drained on its own thread so a large response cannot fill the pipe while we wait
+        let mut stdout = child.stdout.take();
+        let reader = std::thread::spawn(move || {
+            let mut output = vec![];
+            if let Some(stdout) = stdout.as_mut() {
+                let _ = stdout.read_to_end(&mut output);
+            }
+            output
+        });
+
+        let deadline = Instant::now() + timeout;
+        while child.try_wait()?.is_none() {
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+
+                return Err(AvailError::new(
+                    AvailErrorType::External,
+                    format!("Signer {} did not respond within {:?}", command, timeout),
+                    "The external signer did not respond in time.".to_string(),
+                ));
+            }
+
+            std::thread::sleep(Duration::from_millis(50));
+        }
+
+        let output = reader.join().unwrap_or_default();
+        let response: RemoteSignerResponse = match serde_json::from_slice(&output) {
+            Ok(response) => response,
+            Err(e) => {
+                return Err(AvailError::new(
+                    AvailErrorType::External,
+                    format!("Invalid signer response: {}", e),
+                    "The external signer returned an invalid response.".to_string(),
+                ))
+            }
+        };
+
+        match (response.result, response.error) {
+            (Some(result), None) => Ok(result),
+            (_, Some(error)) => Err(AvailError::new(
+                AvailErrorType::Unauthorized,
+                format!("Signer refused the request: {}", error),
+                format!("The external signer refused the request: {}", error),
+            )),
+            (None, None) => Err(AvailError::new(
+                AvailErrorType::External,
+                "Signer returned no result".to_string(),
+                "The external signer returned an invalid response.".to_string(),
+            )),
+        }
+    }
+}
+
+/// Sources of a loaded program and its imports in dependency order, credits.aleo is built into every process
+fn program_sources<N: Network>(
+    process: &Process<N>,
+    program_id: &ProgramID<N>,
+) -> AvailResult<Vec<String>> {
+    fn visit<N: Network>(
+        process: &Process<N>,
+        program_id: &ProgramID<N>,
+        visited: &mut HashSet<ProgramID<N>>,
+        sources: &mut Vec<String>,
+    ) -> AvailResult<()> {
+        if program_id.to_string() == "credits.aleo" || !visited.insert(*program_id) {
+            return Ok(());
+        }
+
+        let program = process.get_program(program_id)?;
+        for import_id in program.imports().keys() {
+            visit::<N>(process, import_id, visited, sources)?;
+        }
+        sources.push(program.to_string());
+
+        Ok(())
+    }
+
+    let mut sources = vec![];
+    visit::<N>(process, program_id, &mut HashSet::new(), &mut sources)?;
+
+    Ok(sources)
+}
+
+impl<N: Network> Signer<N> for RemoteSigner<N> {
+    fn address(&self) -> AvailResult<Address<N>> {
+        Ok(self.address)
+    }
+
+    fn authorize(
+        &self,
+        process: &Process<N>,
+        program_id: &ProgramID<N>,
+        function_name: &Identifier<N>,
+        inputs: &[Value<N>],
+    ) -> AvailResult<Authorization<N>> {
+        let authorization = self.call(RemoteSignerRequest::Authorize {
+            programs: program_sources::<N>(process, program_id)?,
+            program_id: program_id.to_string(),
+            function_id: function_name.to_string(),
+            inputs: inputs.iter().map(|input| input.to_string()).collect(),
+        })?;
+
+        Ok(Authorization::<N>::from_str(&authorization)?)
+    }
+
+    fn authorize_fee(
+        &self,
+        _process: &Process<N>,
+        fee_record: Option<Record<N, Plaintext<N>>>,
+        base_fee: u64,
+        priority_fee: u64,
+        id: Field<N>,
+    ) -> AvailResult<Authorization<N>> {
+        let authorization = self.call(RemoteSignerRequest::AuthorizeFee {
+            fee_record: fee_record.map(|record| record.to_string()),
+            base_fee,
+            priority_fee,
+            id: id.to_string(),
+        })?;
+
+        Ok(Authorization::<N>::from_str(&authorization)?)
+    }
+
+    fn sign_fields(&self, fields: &[Field<N>]) -> AvailResult<Signature<N>> {
+        let signature = self.call(RemoteSignerRequest::SignFields {
+            fields: fields.iter().map(|field| field.to_string()).collect(),
+        })?;
+
+        Ok(Signature::<N>::from_str(&signature)?)
+    }
+}
+
+/* Mock */
+
+/// Signs with a throwaway key and records every request, or refuses them all
+pub struct MockSigner<N: Network, A: Aleo + Environment<Network = N>> {
+    inner: SoftwareSigner<N, A>,
+    reject: bool,
+    requests: Mutex<Vec<String>>,
+}
+
+impl<N: Network, A: Aleo + Environment<Network = N>> MockSigner<N, A> {
+    pub fn new() -> AvailResult<Self> {
+        Ok(Self {
+            inner: SoftwareSigner::new(PrivateKey::<N>::new(&mut rand::thread_rng())?),
+            reject: false,
+            requests: Mutex::new(vec![]),
+        })
+    }
+
+    pub fn rejecting() -> AvailResult<Self> {
+        Ok(Self {
+            reject: true,
+            ..Self::new()?
+        })
+    }
+
+    /// Names of the signer methods called so far
+    pub fn requests(&self) -> Vec<String> {
+        match self.requests.lock() {
+            Ok(requests) => requests.clone(),
+            Err(_) => vec![],
+        }
+    }
+
+    fn record(&self, method: &str) -> AvailResult<()> {
+        if let Ok(mut requests) = self.requests.lock() {
+            requests.push(method.to_string());
+        }
+
+        match self.reject {
+            true => Err(AvailError::new(
+                AvailErrorType::Unauthorized,
+                format!("Mock signer rejected {}", method),
+                "The signer refused the request.".to_string(),
+            )),
+            false => Ok(()),
+        }
+    }
+}
+
+impl<N: Network, A: Aleo + Environment<Network = N>> Signer<N> for MockSigner<N, A> {
+    fn address(&self) -> AvailResult<Address<N>> {
+        self.inner.address()
+    }
+
+    fn authorize(
+        &self,
+        process: &Process<N>,
+        program_id: &ProgramID<N>,
+        function_name: &Identifier<N>,
+        inputs: &[Value<N>],
+    ) -> AvailResult<Authorization<N>> {
+        self.record("authorize")?;
+        self.inner
+            .authorize(process, program_id, function_name, inputs)
+    }
+
+    fn authorize_fee(
+        &self,
+        process: &Process<N>,
+        fee_record: Option<Record<N, Plaintext<N>>>,
+        base_fee: u64,
+        priority_fee: u64,
+        id: Field<N>,
+    ) -> AvailResult<Authorization<N>> {
+        self.record("authorize_fee")?;
+        self.inner
+            .authorize_fee(process, fee_record, base_fee, priority_fee, id)
+    }
+
+    fn sign_fields(&self, fields: &[Field<N>]) -> AvailResult<Signature<N>> {
+        self.record("sign_fields")?;
+        self.inner.sign_fields(fields)
+    }
+}
+
+#[cfg(test)]
+mod signer_tests {
+    use super::*;
+    use snarkvm::circuit::AleoV0;
+    use snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_sign_message_verifies() {
+        let signer = MockSigner::<Testnet3, AleoV0>::new().unwrap();
+        let address = signer.address().unwrap();
+
+        let (signature, message_field) = signer.sign_message("Hello Aleo").unwrap();
+        assert!(signature.verify(&address, &field_to_fields(&message_field).unwrap()));
+
+        let plaintext = Plaintext::<Testnet3>::from_str("{ amount: 5u64 }").unwrap();
+        let (signature, fields) = signer.sign_plaintext(&plaintext).unwrap();
+        assert!(signature.verify(&address, &fields));
+
+        assert_eq!(signer.requests(), vec!["sign_fields", "sign_fields"]);
+    }
+
+    #[test]
+    fn test_rejecting_signer() {
+        let signer = MockSigner::<Testnet3, AleoV0>::rejecting().unwrap();
+
+        let error = signer.sign_message("Hello Aleo").unwrap_err();
+        assert_eq!(error.error_type, AvailErrorType::Unauthorized);
+    }
+
+    #[test]
+    fn test_signer_address_must_match_wallet() {
+        let signer = MockSigner::<Testnet3, AleoV0>::new().unwrap();
+        let other = MockSigner::<Testnet3, AleoV0>::new().unwrap();
+
+        assert!(ensure_signer_address::<Testnet3>(&signer, &signer.address().unwrap()).is_ok());
+
+        let error =
+            ensure_signer_address::<Testnet3>(&signer, &other.address().unwrap()).unwrap_err();
+        assert_eq!(error.error_type, AvailErrorType::Validation);
+    }
+
+    #[test]
+    fn test_authorize_transfer_public() {
+        let signer = MockSigner::<Testnet3, AleoV0>::new().unwrap();
+        let process = Process::<Testnet3>::load().unwrap();
+
+        let inputs = vec![
+            Value::from_str(&signer.address().unwrap().to_string()).unwrap(),
+            Value::from_str("1000000u64").unwrap(),
+        ];
+
+        let authorization = signer
+            .authorize(
+                &process,
+                &ProgramID::from_str("credits.aleo").unwrap(),
+                &Identifier::from_str("transfer_public").unwrap(),
+                &inputs,
+            )
+            .unwrap();
+
+        let request = authorization.peek_next().unwrap();
+        assert_eq!(request.signer(), &signer.address().unwrap());
+        assert_eq!(request.function_name().to_string(), "transfer_public");
+    }
+
+    #[test]
+    fn test_remote_request_encoding() {
+        let message = RemoteSignerMessage {
+            network: Testnet3::ID,
+            request: RemoteSignerRequest::SignFields {
+                fields: vec!["1field".to_string()],
+            },
+        };
+
+        let json = serde_json::to_string(&message).unwrap();
+        assert_eq!(
+            json,
+            r#"{"network":3,"request":{"method":"sign_fields","fields":["1field"]}}"#
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_remote_signer_refusal() {
+        let args = vec![
+            "-c".to_string(),
+            r#"cat > /dev/null; echo '{"result":null,"error":"locked"}'"#.to_string(),
+        ];
+
+        let error = RemoteSigner::<Testnet3>::new("sh".to_string(), args)
+            .err()
+            .unwrap();
+        assert_eq!(error.error_type, AvailErrorType::Unauthorized);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_remote_signer_timeout() {
+        let args = vec!["-c".to_string(), "exec sleep 30".to_string()];
+
+        let started = Instant::now();
+        let error = RemoteSigner::<Testnet3>::with_timeout(
+            "sh".to_string(),
+            args,
+            Duration::from_millis(200),
+        )
+        .err()
+        .unwrap();
+
+        assert_eq!(error.error_type, AvailErrorType::External);
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,521 @@
+use chrono::{DateTime, Duration, Utc};
+use snarkvm::prelude::{Address, Field, Literal, Network, Plaintext, Testnet3, ToFields};
+use std::str::FromStr;
+
+use crate::api::aleo_client::setup_client;
+use crate::helpers::validation::validate_address_bool;
+use crate::models::storage::persistent::PersistentStorage;
+use crate::services::local_storage::persistent_storage::get_network;
+
+use avail_common::{
+    errors::{AvailError, AvailErrorType, AvailResult},
+    models::network::SupportedNetworks,
+};
+
+/* --Aleo Name Service-- */
+
+/// Program holding the Aleo Name Service registry
+pub const ANS_PROGRAM_ID: &str = "aleo_name_service_registry_v3.aleo";
+
+/// Top level domain of names registered through the Aleo Name Service
+pub const ANS_TLD: &str = "ale";
+
+/// Minutes an ANS lookup is served from the local cache
+const ANS_CACHE_TTL_MINUTES: i64 = 10;
+
+/// Minutes a failed lookup is remembered, so listings do not retry it for every event
+const ANS_FAILURE_TTL_MINUTES: i64 = 2;
+
+/// A label is packed into four u128s of 16 bytes each
+const MAX_LABEL_LENGTH: usize = 64;
+
+pub fn init_ans_cache_table() -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+    storage.execute_query(
+        "CREATE TABLE IF NOT EXISTS ans_cache (
+            lookup TEXT PRIMARY KEY,
+            result TEXT NOT NULL,
+            expires_at TEXT NOT NULL
+        )",
+    )?;
+    Ok(())
+}
+
+pub fn drop_ans_cache_table() -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+    match storage.execute_query("DROP TABLE IF EXISTS ans_cache") {
+        Ok(r) => r,
+        Err(e) => match e.error_type {
+            AvailErrorType::NotFound => {}
+            _ => {
+                return Err(AvailError::new(
+                    AvailErrorType::Internal,
+                    e.internal_msg,
+                    "Error deleting name cache".to_string(),
+                ))
+            }
+        },
+    };
+
+    Ok(())
+}
+
+/// Gets a cached lookup result, an empty result means the lookup found no name
+fn get_cached_lookup(lookup: &str) -> AvailResult<Option<String>> {
+    init_ans_cache_table()?;
+
+    let storage = PersistentStorage::new()?;
+    let res = storage.get_all_with_params::<String, &str>(
+        "SELECT result, expires_at FROM ans_cache WHERE lookup=?1",
+        vec![lookup],
+        2,
+    )?;
+    match res.get(0) {
+        Some(row) if row.len() == 2 => match DateTime::parse_from_rfc3339(&row[1]) {
+            Ok(expires_at) if expires_at.with_timezone(&Utc) > Utc::now() => {
+                Ok(Some(row[0].clone()))
+            }
+            _ => Ok(None),
+        },
+        _ => Ok(None),
+    }
+}
+
+fn cache_lookup(lookup: &str, result: &str, ttl_minutes: i64) -> AvailResult<()> {
+    init_ans_cache_table()?;
+
+    let expires_at = Utc::now() + Duration::minutes(ttl_minutes);
+
+    let storage = PersistentStorage::new()?;
+    storage.save(
+        vec![
+            lookup.to_string(),
+            result.to_string(),
+            expires_at.to_rfc3339(),
+        ],
+        "INSERT OR REPLACE INTO ans_cache (lookup, result, expires_at) VALUES (?1, ?2, ?3)"
+            .to_string(),
+    )?;
+
+    Ok(())
+}
+
+/// Checks if a recipient is a well formed ANS name, i.e `name.ale` with only valid labels
+pub fn is_ans_name(name: &str) -> bool {
+    match name.rsplit_once('.') {
+        Some((labels, tld)) => {
+            tld == ANS_TLD && labels.split('.').all(|label| validate_label(label).is_ok())
+        }
+        None => false,
+    }
+}
+
+fn validate_label(label: &str) -> AvailResult<()> {
+    let valid_chars = label
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+
+    if label.is_empty() || label.len() > MAX_LABEL_LENGTH || !valid_chars {
+        return Err(AvailError::new(
+            AvailErrorType::InvalidData,
+            format!("Invalid ANS label '{}'", label),
+            "Invalid ANS name".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Packs a label into four little-endian u128s
+pub fn encode_label(label: &str) -> AvailResult<[u128; 4]> {
+    validate_label(label)?;
+
+    let mut bytes = [0u8; MAX_LABEL_LENGTH];
+    bytes[..label.len()].copy_from_slice(label.as_bytes());
+
+    let mut encoded = [0u128; 4];
+    for (i, chunk) in bytes.chunks(16).enumerate() {
+        let mut chunk_bytes = [0u8; 16];
+        chunk_bytes.copy_from_slice(chunk);
+        encoded[i] = u128::from_le_bytes(chunk_bytes);
+    }
+
+    Ok(encoded)
+}
+
+/// Unpacks a label stored as four little-endian u128s
+pub fn decode_label(encoded: &[u128]) -> String {
+    encoded
+        .iter()
+        .flat_map(|chunk| chunk.to_le_bytes())
+        .filter(|byte| *byte != 0)
+        .map(|byte| byte as char)
+        .collect::<String>()
+}
+
+/// Hashes a label under its parent the way the registry derives name hashes
+fn label_hash<N: Network>(label: &str, parent: &Field<N>) -> AvailResult<Field<N>> {
+    let encoded = encode_label(label)?;
+    let name_struct = Plaintext::<N>::from_str(&format!(
+        "{{ name: [{}u128, {}u128, {}u128, {}u128], parent: {} }}",
+        encoded[0], encoded[1], encoded[2], encoded[3], parent
+    ))?;
+
+    Ok(N::hash_psd2(&name_struct.to_fields()?)?)
+}
+
+/// Computes the registry hash of a full name such as `alice.ale`
+pub fn name_hash<N: Network>(name: &str) -> AvailResult<Field<N>> {
+    if !is_ans_name(name) {
+        return Err(AvailError::new(
+            AvailErrorType::InvalidData,
+            format!("'{}' is not an ANS name", name),
+            "Invalid ANS name".to_string(),
+        ));
+    }
+
+    let mut hash = Field::<N>::from_str("0field")?;
+    for label in name.split('.').rev() {
+        hash = label_hash::<N>(label, &hash)?;
+    }
+
+    Ok(hash)
+}
+
+fn get_registry_value<N: Network>(mapping: &str, key: &str) -> AvailResult<Option<String>> {
+    let api_client = setup_client::<N>()?;
+
+    Ok(api_client
+        .get_mapping_value(ANS_PROGRAM_ID, mapping, key)?
+        .map(|value| value.to_string()))
+}
+
+/// Resolves an ANS name to the address that owns it
+pub fn resolve_ans_name<N: Network>(name: &str) -> AvailResult<Address<N>> {
+    // validates every label before the name is used as a cache key
+    let hash = name_hash::<N>(name)?;
+    let lookup = format!("forward:{}", name);
+
+    let address = match get_cached_lookup(&lookup)? {
+        Some(address) => address,
+        None => {
+            let address =
+                get_registry_value::<N>("nft_owners", &hash.to_string())?.unwrap_or_default();
+            cache_lookup(&lookup, &address, ANS_CACHE_TTL_MINUTES)?;
+            address
+        }
+    };
+
+    if address.is_empty() {
+        return Err(AvailError::new(
+            AvailErrorType::NotFound,
+            format!("ANS name '{}' not found", name),
+            format!("The name '{}' is not registered.", name),
+        ));
+    }
+
+    Ok(Address::<N>::from_str(&address)?)
+}
+
+/// Reads the label and parent hash of a registered name
+fn get_name_entry<N: Network>(hash: &str) -> AvailResult<Option<(String, String)>> {
+    let value = match get_registry_value::<N>("names", hash)? {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+
+    let invalid_entry = || {
+        AvailError::new(
+            AvailErrorType::InvalidData,
+            format!("Invalid ANS registry entry for {}", hash),
+            "Invalid ANS name".to_string(),
+        )
+    };
+
+    let members = match Plaintext::<N>::from_str(&value)? {
+        Plaintext::Struct(members, _) => members,
+        _ => return Err(invalid_entry()),
+    };
+
+    let mut label = String::new();
+    let mut parent = String::new();
+    for (member, plaintext) in members.iter() {
+        match (member.to_string().as_str(), plaintext) {
+            ("name", Plaintext::Array(elements, _)) => {
+                let encoded = elements
+                    .iter()
+                    .map(|element| match element {
+                        Plaintext::Literal(Literal::U128(chunk), _) => Ok(**chunk),
+                        _ => Err(invalid_entry()),
+                    })
+                    .collect::<AvailResult<Vec<u128>>>()?;
+                label = decode_label(&encoded);
+            }
+            ("parent", Plaintext::Literal(Literal::Field(field), _)) => {
+                parent = field.to_string();
+            }
+            _ => {}
+        }
+    }
+
+    if label.is_empty() || parent.is_empty() {
+        return Err(invalid_entry());
+    }
+
+    Ok(Some((label, parent)))
+}
+
+fn reverse_lookup(address: &str) -> String {
+    format!("reverse:{}", address)
+}
+
+/// Looks up the primary ANS name of an address
+pub fn resolve_primary_name<N: Network>(address: &str) -> AvailResult<Option<String>> {
+    let lookup = reverse_lookup(address);
+
+    if let Some(name) = get_cached_lookup(&lookup)? {
+        return match name.is_empty() {
+            true => Ok(None),
+            false => Ok(Some(name)),
+        };
+    }
+
+    let name = match lookup_primary_name::<N>(address) {
+        Ok(name) => name,
+        Err(e) => {
+            cache_lookup(&lookup, "", ANS_FAILURE_TTL_MINUTES)?;
+            return Err(e);
+        }
+    };
+    cache_lookup(&lookup, &name, ANS_CACHE_TTL_MINUTES)?;
+
+    match name.is_empty() {
+        true => Ok(None),
+        false => Ok(Some(name)),
+    }
+}
+
+fn lookup_primary_name<N: Network>(address: &str) -> AvailResult<String> {
+    let mut labels: Vec<String> = vec![];
+    if let Some(mut hash) = get_registry_value::<N>("primary_names", address)? {
+        while hash != "0field" {
+            match get_name_entry::<N>(&hash)? {
+                Some((label, parent)) => {
+                    labels.push(label);
+                    hash = parent;
+                }
+                None => {
+                    labels.clear();
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(labels.join("."))
+}
+
+/// Primary name to display next to an address in events, lookup failures are not surfaced
+pub fn display_name<N: Network>(address: Option<&String>) -> Option<String> {
+    let address = address?;
+
+    if !validate_address_bool(address) {
+        return None;
+    }
+
+    resolve_primary_name::<N>(address).unwrap_or_default()
+}
+
+/// Primary name from the cache only, listings use this so they never wait on the network
+pub fn cached_display_name(address: Option<&String>) -> Option<String> {
+    let address = address?;
+
+    if !validate_address_bool(address) {
+        return None;
+    }
+
+    get_cached_lookup(&reverse_lookup(address))
+        .ok()
+        .flatten()
+        .filter(|name| !name.is_empty())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_ans_address(name: &str) -> AvailResult<String> {
+    let network = get_network()?;
+
+    let address = match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => resolve_ans_name::<Testnet3>(name)?,
+        _ => resolve_ans_name::<Testnet3>(name)?,
+    };
+
+    Ok(address.to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_ans_primary_name(address: &str) -> AvailResult<Option<String>> {
+    let network = get_network()?;
+
+    match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => resolve_primary_name::<Testnet3>(address),
+        _ => resolve_primary_name::<Testnet3>(address),
+    }
+}
+
+#[cfg(test)]
+mod ans_tests {
+    use super::*;
+    use crate::api::chain_provider::{with_chain_provider, MockChainProvider};
+    use avail_common::models::constants::TESTNET_ADDRESS;
+    use snarkvm::prelude::{PrivateKey, Value};
+    use std::sync::Arc;
+
+    fn registry_entry(label: &str, parent: &Field<Testnet3>) -> Value<Testnet3> {
+        let encoded = encode_label(label).unwrap();
+        Value::from_str(&format!(
+            "{{ name: [{}u128, {}u128, {}u128, {}u128], parent: {} }}",
+            encoded[0], encoded[1], encoded[2], encoded[3], parent
+        ))
+        .unwrap()
+    }
+
+    /// Registry where `alice.ale` is owned by and the primary name of `address`
+    fn registry_chain(address: &str) -> MockChainProvider<Testnet3> {
+        let root = Field::<Testnet3>::from_str("0field").unwrap();
+        let tld_hash = label_hash::<Testnet3>(ANS_TLD, &root).unwrap();
+        let name_hash = name_hash::<Testnet3>("alice.ale").unwrap();
+
+        let api_client = MockChainProvider::<Testnet3>::new();
+        api_client.set_mapping_value(
+            ANS_PROGRAM_ID,
+            "names",
+            &tld_hash.to_string(),
+            registry_entry(ANS_TLD, &root),
+        );
+        api_client.set_mapping_value(
+            ANS_PROGRAM_ID,
+            "names",
+            &name_hash.to_string(),
+            registry_entry("alice", &tld_hash),
+        );
+        api_client.set_mapping_value(
+            ANS_PROGRAM_ID,
+            "primary_names",
+            address,
+            Value::from_str(&name_hash.to_string()).unwrap(),
+        );
+        api_client.set_mapping_value(
+            ANS_PROGRAM_ID,
+            "nft_owners",
+            &name_hash.to_string(),
+            Value::from_str(address).unwrap(),
+        );
+
+        api_client
+    }
+
+    #[test]
+    fn test_is_ans_name() {
+        assert!(is_ans_name("alice.ale"));
+        assert!(is_ans_name("pay.alice.ale"));
+        assert!(!is_ans_name("alice"));
+        assert!(!is_ans_name(".ale"));
+        assert!(!is_ans_name("pay..ale"));
+        assert!(!is_ans_name("x' OR lookup LIKE 'forward:%' --.ale"));
+        assert!(!is_ans_name(TESTNET_ADDRESS));
+    }
+
+    #[test]
+    fn test_malformed_name_is_rejected_before_the_cache() {
+        cache_lookup("forward:bob.ale", TESTNET_ADDRESS, ANS_CACHE_TTL_MINUTES).unwrap();
+
+        let res = resolve_ans_name::<Testnet3>("x' OR lookup LIKE 'forward:%' --.ale");
+        assert_eq!(res.unwrap_err().error_type, AvailErrorType::InvalidData);
+
+        // quotes are bound as parameters, never interpolated into the query
+        assert_eq!(get_cached_lookup("x' OR '1'='1").unwrap(), None);
+    }
+
+    #[test]
+    fn test_cached_display_name() {
+        let address = "aleo1rhgdu77hgyqd3xjj8ucu3jj9r2krwz6mnzyd80gncr5fxcwlh5rsvzp9px".to_string();
+        cache_lookup(
+            &reverse_lookup(&address),
+            "alice.ale",
+            ANS_CACHE_TTL_MINUTES,
+        )
+        .unwrap();
+        assert_eq!(
+            cached_display_name(Some(&address)),
+            Some("alice.ale".to_string())
+        );
+
+        cache_lookup(&reverse_lookup(&address), "", ANS_FAILURE_TTL_MINUTES).unwrap();
+        assert_eq!(cached_display_name(Some(&address)), None);
+    }
+
+    #[test]
+    fn test_encode_decode_label() {
+        let encoded = encode_label("alice").unwrap();
+        assert_eq!(encoded[1..], [0u128; 3]);
+        assert_eq!(decode_label(&encoded), "alice".to_string());
+
+        assert!(encode_label("Alice").is_err());
+        assert!(encode_label(&"a".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn test_name_hash_depends_on_parent() {
+        let tld_hash = name_hash::<Testnet3>("alice.ale").unwrap();
+        let sub_hash = name_hash::<Testnet3>("pay.alice.ale").unwrap();
+
+        assert_ne!(tld_hash, sub_hash);
+        assert_eq!(tld_hash, name_hash::<Testnet3>("alice.ale").unwrap());
+    }
+
+    #[test]
+    fn test_resolve_primary_name() {
+        // a fresh address so earlier runs left nothing in the cache
+        let private_key = PrivateKey::<Testnet3>::new(&mut rand::thread_rng()).unwrap();
+        let address = Address::<Testnet3>::try_from(&private_key)
+            .unwrap()
+            .to_string();
+        let lookup = reverse_lookup(&address);
+
+        with_chain_provider::<Testnet3, _>(Arc::new(registry_chain(&address)), || {
+            assert_eq!(
+                resolve_primary_name::<Testnet3>(&address).unwrap(),
+                Some("alice.ale".to_string())
+            );
+            assert_eq!(
+                resolve_ans_name::<Testnet3>("alice.ale")
+                    .unwrap()
+                    .to_string(),
+                address
+            );
+        });
+
+        // an empty registry is not consulted while the cached name is fresh
+        let empty_chain = Arc::new(MockChainProvider::<Testnet3>::new());
+        with_chain_provider::<Testnet3, _>(empty_chain.clone(), || {
+            assert_eq!(
+                resolve_primary_name::<Testnet3>(&address).unwrap(),
+                Some("alice.ale".to_string())
+            );
+        });
+
+        // once the entry expires the name is looked up again and is gone
+        cache_lookup(&lookup, "alice.ale", -1).unwrap();
+        assert_eq!(get_cached_lookup(&lookup).unwrap(), None);
+        with_chain_provider::<Testnet3, _>(empty_chain, || {
+            assert_eq!(resolve_primary_name::<Testnet3>(&address).unwrap(), None);
+        });
+        assert_eq!(get_cached_lookup(&lookup).unwrap(), Some("".to_string()));
+
+        // malformed names fail validation before the registry or the cache are read
+        for name in ["Alice.ale", "alice.aleo", "al ice.ale", "alice..ale"] {
+            let err = resolve_ans_name::<Testnet3>(name).unwrap_err();
+            assert_eq!(err.error_type, AvailErrorType::InvalidData);
+        }
+    }
+}
@@ -1,6 +1,8 @@
-use crate::api::client::SESSION;
+use crate::api::{backend::backend, client::SESSION};
 use crate::helpers::utils::HOST;
-use crate::models::auth::{CreateSessionRequest, VerifySessionResponse};
+use crate::models::auth::{CreateSessionRequest, SignInSessionRequest, VerifySessionResponse};
+use crate::models::wallet_connect::sign_in::{origin_host, SignInMessage};
+use crate::services::account::signer::Signer;
 use crate::services::local_storage::{
     persistent_storage::{get_address_string, get_network},
     session::password::PASS,
@@ -21,153 +23,106 @@ use avail_common::{
 #[tauri::command(rename_all = "snake_case")]
 pub async fn get_session(password: Option<String>) -> AvailResult<String> {
     let address = get_address_string()?;
-    let session_request = request_hash(&address).await?;
-
     let network = get_network()?;
 
-    let (sig, _) = match SupportedNetworks::from_str(&network)? {
+    let session_id = match SupportedNetworks::from_str(&network)? {
         SupportedNetworks::Testnet3 => {
-            sign_message::<Testnet3>(&session_request.hash, password.clone())?
+            login::<Testnet3>(Address::<Testnet3>::from_str(&address)?, |message| {
+                Ok(sign_message::<Testnet3>(message, password.clone())?.0)
+            })
+            .await?
+        }
+        _ => {
+            login::<Testnet3>(Address::<Testnet3>::from_str(&address)?, |message| {
+                Ok(sign_message::<Testnet3>(message, password.clone())?.0)
+            })
+            .await?
         }
-        _ => sign_message::<Testnet3>(&session_request.hash, password.clone())?,
     };
 
-    let verify_request = server_auth::VerifySessionRequest {
-        signature: sig.to_string(),
-        session_id: session_request.session_id,
+    let _pass_session = match password {
+        Some(password) => PASS.set_pass_session(&password)?,
+        None => {}
     };
 
-    let api = env!("API");
-
-    let client = reqwest::Client::new();
-
-    let res = client
-        .post(format!("{}/auth/login/", api))
-        .json(&verify_request)
-        .send()
-        .await?;
-
-    if res.status() == 200 {
-        let cookie = res.cookies().next();
-
-        let session_cookie = match cookie {
-            Some(cookie) => cookie,
-            None => {
-                return Err(AvailError::new(
-                    AvailErrorType::Validation,
-                    "Session cookie not found in auth response".to_string(),
-                    "Session cookie not found in auth response".to_string(),
-                ))
-            }
-        };
-
-        SESSION.set_session_token(session_cookie.value().to_string());
-
-        let _pass_session = match password {
-            Some(password) => PASS.set_pass_session(&password)?,
-            None => {}
-        };
-
-        Ok(session_request.session_id.to_string())
-    } else {
-        Err(AvailError::new(
-            AvailErrorType::External,
-            "Invalid Signature".to_string(),
-            "Invalid Signature".to_string(),
-        ))
-    }
+    Ok(session_id)
 }
 
 pub async fn get_session_after_creation<N: Network>(
     private_key: &PrivateKey<N>,
 ) -> AvailResult<String> {
     let address = Address::<N>::try_from(private_key)?;
-    let session_request = request_hash(&address.to_string()).await?;
 
-    let (sig, _) = sign_message_w_key::<N>(&session_request.hash, private_key)?;
+    login::<N>(address, |message| {
+        Ok(sign_message_w_key::<N>(message, private_key)?.0)
+    })
+    .await
+}
 
-    let verify_request = server_auth::VerifySessionRequest {
-        signature: sig.to_string(),
+/// Logs in on the server with the login message signed by the wallet's signer
+pub async fn get_session_with_signer<N: Network>(signer: &dyn Signer<N>) -> AvailResult<String> {
+    login::<N>(signer.address()?, |message| {
+        Ok(signer.sign_message(message)?.0)
+    })
+    .await
+}
+
+async fn login<N: Network>(
+    address: Address<N>,
+    sign: impl Fn(&str) -> AvailResult<Signature<N>>,
+) -> AvailResult<String> {
+    let backend = backend()?;
+    let session_request = backend.request_hash(&address.to_string()).await?;
+
+    let message = login_message::<N>(&address.to_string(), &session_request);
+    let sign_in_request = SignInSessionRequest {
+        signature: sign(&message)?.to_string(),
         session_id: session_request.session_id,
+        message,
     };
 
-    let api = env!("API");
-
-    let res = reqwest::Client::new()
-        .post(format!("{}/auth/login/", api))
-        .json(&verify_request)
-        .send()
-        .await?;
+    // servers without the sign in message login still accept a signature over the bare hash
+    let token = match backend.login(sign_in_request).await {
+        Ok(token) => token,
+        Err(e) if e.error_type == AvailErrorType::NotFound => {
+            let verify_request = VerifySessionRequest {
+                signature: sign(&session_request.hash)?.to_string(),
+                session_id: session_request.session_id,
+            };
 
-    if res.status() == 200 {
-        let cookie = res.cookies().next();
-
-        let session_cookie = match cookie {
-            Some(cookie) => cookie,
-            None => {
-                return Err(AvailError::new(
-                    AvailErrorType::Validation,
-                    "Session cookie not found in auth response".to_string(),
-                    "Session cookie not found in auth response".to_string(),
-                ))
-            }
-        };
+            backend.login_with_hash(verify_request).await?
+        }
+        Err(e) => return Err(e),
+    };
+    SESSION.set_session_token(token);
 
-        SESSION.set_session_token(session_cookie.value().to_string());
+    Ok(session_request.session_id.to_string())
+}
 
-        Ok(session_request.session_id.to_string())
-    } else {
-        if res.status() == 0 {
-            return Err(AvailError::new(
-                AvailErrorType::Network,
-                "No internet connection".to_string(),
-                "No internet connection".to_string(),
-            ));
-        }
+/// The sign in message signed to log in, in the same format dapps request through wallet connect
+pub fn login_message<N: Network>(
+    address: &str,
+    session_request: &server_auth::CreateSessionResponse,
+) -> String {
+    let api = env!("API");
 
-        Err(AvailError::new(
-            AvailErrorType::External,
-            "Invalid Signature".to_string(),
-            "Invalid Signature".to_string(),
-        ))
-    }
+    SignInMessage::new(
+        origin_host(api),
+        address.to_string(),
+        Some("Sign in to Avail".to_string()),
+        api.to_string(),
+        N::ID.to_string(),
+        session_request.hash.clone(),
+        chrono::Utc::now(),
+        Some(session_request.expires_on),
+    )
+    .to_message()
 }
 
 /// requests the initial hash to sign from server
 /// Function 1
 pub async fn request_hash(address: &str) -> AvailResult<server_auth::CreateSessionResponse> {
-    let client = reqwest::Client::new();
-
-    let request = server_auth::CreateSessionRequest {
-        public_key: address.to_owned(),
-    };
-
-    let api = env!("API");
-
-    let res = client
-        .post(format!("{}/auth/request/", api))
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .send()
-        .await?;
-
-    if res.status() == 201 {
-        Ok(res.json::<server_auth::CreateSessionResponse>().await?)
-    } else {
-        if res.status() == 0 {
-            return Err(AvailError::new(
-                AvailErrorType::Network,
-                "No internet connection".to_string(),
-                "No internet connection".to_string(),
-            ));
-        }
-
-        Err(AvailError::new(
-            AvailErrorType::External,
-            "Error requesting auth token.".to_string(),
-            "Error requesting auth token.".to_string(),
-        ))
-    }
+    backend()?.request_hash(address).await
 }
 
 /* -- Not used -- */
@@ -231,7 +186,7 @@ mod tests {
         delete_user_preferences, initial_user_preferences,
     };
     use crate::services::local_storage::session::view::VIEWSESSION;
-    use avail_common::models::constants::{STRONG_PASSWORD, TESTNET_PRIVATE_KEY};
+    use avail_common::models::constants::{STRONG_PASSWORD, TESTNET_ADDRESS, TESTNET_PRIVATE_KEY};
     use avail_common::models::user::User;
     use snarkvm::prelude::Testnet3;
 
@@ -306,6 +261,23 @@ mod tests {
         // assert!(hash.is_ok());
     }
 
+    #[test]
+    fn test_login_message() {
+        let session_request = server_auth::CreateSessionResponse {
+            hash: "KEqj6BDUl0Nh0izyOsXuW916qSoGxtfE".to_string(),
+            session_id: uuid::Uuid::new_v4(),
+            expires_on: chrono::Utc::now() + chrono::Duration::minutes(5),
+        };
+
+        let message = login_message::<Testnet3>(TESTNET_ADDRESS, &session_request);
+        let parsed = SignInMessage::parse(&message).unwrap();
+
+        assert_eq!(parsed.nonce(), &session_request.hash);
+        assert!(parsed
+            .validate(env!("API"), TESTNET_ADDRESS, &Testnet3::ID.to_string())
+            .is_ok());
+    }
+
     #[test]
     fn test_sign_hash() {
         let sig = {
@@ -0,0 +1,106 @@
+use once_cell::sync::Lazy;
+use snarkvm::prelude::Testnet3;
+use std::str::FromStr;
+use std::sync::Mutex;
+use tauri::{Manager, Runtime, Window};
+
+use crate::models::deep_link::DeepLink;
+use crate::services::local_storage::{persistent_storage::get_network, session::password::PASS};
+
+use avail_common::{
+    errors::{AvailError, AvailErrorType, AvailResult},
+    models::network::SupportedNetworks,
+};
+
+/* --Deep Link Router-- */
+
+/// Links that arrived while the wallet was locked or its password session had expired, dispatched once it is unlocked
+static DEEP_LINK_QUEUE: Lazy<Mutex<Vec<DeepLink>>> = Lazy::new(|| Mutex::new(vec![]));
+
+#[derive(Clone, serde::Serialize)]
+struct RejectedDeepLink {
+    uri: String,
+    error: String,
+}
+
+pub fn parse_deep_link(link: &str) -> AvailResult<DeepLink> {
+    // links can open the app before a wallet exists and stores its network
+    let network = match get_network() {
+        Ok(network) => SupportedNetworks::from_str(&network)?,
+        Err(_) => SupportedNetworks::Testnet3,
+    };
+
+    match network {
+        SupportedNetworks::Testnet3 => DeepLink::parse::<Testnet3>(link),
+        _ => DeepLink::parse::<Testnet3>(link),
+    }
+}
+
+fn dispatch<R: Runtime, M: Manager<R>>(link: &DeepLink, emitter: &M) -> AvailResult<()> {
+    match emitter.emit(link.event(), link) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(AvailError::new(
+            AvailErrorType::Internal,
+            format!("Error emitting {}: {}", link.event(), e),
+            "Error opening link".to_string(),
+        )),
+    }
+}
+
+/// Routes the urls of a `deep-link://new-url` event, links are queued while the wallet is locked
+pub fn route_deep_links<R: Runtime, M: Manager<R>>(payload: &str, emitter: &M) {
+    let urls =
+        serde_json::from_str::<Vec<String>>(payload).unwrap_or_else(|_| vec![payload.to_string()]);
+
+    for url in urls {
+        let link = match parse_deep_link(&url) {
+            Ok(link) => link,
+            Err(e) => {
+                let rejected = RejectedDeepLink {
+                    uri: url,
+                    error: e.external_msg,
+                };
+                if let Err(e) = emitter.emit("deep-link-rejected", rejected) {
+                    println!("Error emitting deep-link-rejected: {}", e);
+                }
+                continue;
+            }
+        };
+
+        if !PASS.is_valid() {
+            DEEP_LINK_QUEUE.lock().unwrap().push(link);
+            continue;
+        }
+
+        if let Err(e) = dispatch(&link, emitter) {
+            println!("{}", e.internal_msg);
+        }
+    }
+}
+
+/// Dispatches the links queued while the wallet was locked, returns how many were dispatched.
+/// If one fails, it and the links after it are queued again ahead of links that arrived meanwhile.
+#[tauri::command(rename_all = "snake_case")]
+pub fn flush_deep_links(window: Window) -> AvailResult<usize> {
+    if !PASS.is_valid() {
+        return Err(AvailError::new(
+            AvailErrorType::Unauthorized,
+            "Wallet is locked".to_string(),
+            "Unlock your wallet to open links".to_string(),
+        ));
+    }
+
+    let queued = std::mem::take(&mut *DEEP_LINK_QUEUE.lock().unwrap());
+    for (index, link) in queued.iter().enumerate() {
+        if let Err(e) = dispatch(link, &window) {
+            let mut queue = DEEP_LINK_QUEUE.lock().unwrap();
+            let newer = std::mem::take(&mut *queue);
+            queue.extend(queued[index..].iter().cloned());
+            queue.extend(newer);
+
+            return Err(e);
+        }
+    }
+
+    Ok(queued.len())
+}
@@ -1,6 +1,11 @@
+pub mod dapp_policies;
+pub mod dapp_sessions;
 pub mod encrypted_data;
+pub mod outbox;
 pub mod persistent_storage;
+pub mod resilience_settings;
 pub mod session;
 pub mod storage_api;
+pub mod token_registry;
 pub mod tokens;
 pub mod utils;
@@ -0,0 +1,340 @@
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use rusqlite::{params, Connection, TransactionBehavior};
+
+use crate::models::storage::persistent::PersistentStorage;
+use crate::models::wallet_connect::policy::{DappPolicy, PolicyDecision, PolicyDecisionEntry};
+
+use avail_common::errors::{AvailError, AvailErrorType, AvailResult};
+
+/* --Dapp Policy Engine-- */
+
+pub fn init_dapp_policies_tables() -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+    storage.execute_query(
+        "CREATE TABLE IF NOT EXISTS dapp_policies (
+            origin TEXT NOT NULL,
+            program_id TEXT NOT NULL,
+            policy TEXT NOT NULL,
+            PRIMARY KEY (origin, program_id)
+        )",
+    )?;
+    storage.execute_query(
+        "CREATE TABLE IF NOT EXISTS policy_decisions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            origin TEXT NOT NULL,
+            program_id TEXT NOT NULL,
+            function_id TEXT NOT NULL,
+            spend TEXT NOT NULL,
+            decision TEXT NOT NULL,
+            reason TEXT NOT NULL,
+            spend_state TEXT,
+            created_at TEXT NOT NULL
+        )",
+    )?;
+    Ok(())
+}
+
+pub fn drop_dapp_policies_tables() -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+    for table in ["dapp_policies", "policy_decisions"] {
+        match storage.execute_query(&format!("DROP TABLE IF EXISTS {}", table)) {
+            Ok(r) => r,
+            Err(e) => match e.error_type {
+                AvailErrorType::NotFound => {}
+                _ => {
+                    return Err(AvailError::new(
+                        AvailErrorType::Internal,
+                        e.internal_msg,
+                        "Error deleting dapp policies".to_string(),
+                    ))
+                }
+            },
+        };
+    }
+
+    Ok(())
+}
+
+pub fn store_dapp_policy(policy: &DappPolicy) -> AvailResult<()> {
+    init_dapp_policies_tables()?;
+
+    let storage = PersistentStorage::new()?;
+    storage.save(
+        vec![
+            policy.origin().clone(),
+            policy.program_id().clone(),
+            serde_json::to_string(policy)?,
+        ],
+        "INSERT OR REPLACE INTO dapp_policies (origin, program_id, policy) VALUES (?1, ?2, ?3)"
+            .to_string(),
+    )?;
+
+    Ok(())
+}
+
+pub fn get_dapp_policy(origin: &str, program_id: &str) -> AvailResult<Option<DappPolicy>> {
+    init_dapp_policies_tables()?;
+
+    let storage = PersistentStorage::new()?;
+    let res = storage.get_all_with_params::<String, &str>(
+        "SELECT policy FROM dapp_policies WHERE origin=?1 AND program_id=?2",
+        vec![origin, program_id],
+        1,
+    )?;
+    match res.get(0) {
+        Some(row) => Ok(Some(serde_json::from_str::<DappPolicy>(&row[0])?)),
+        None => Ok(None),
+    }
+}
+
+/// Auto-approved spend holds the daily budget while it is broadcast and is released if that fails
+const SPEND_RESERVED: &str = "reserved";
+const SPEND_BROADCAST: &str = "broadcast";
+const SPEND_RELEASED: &str = "released";
+
+/// Timestamps are stored as fixed width UTC so they compare correctly as strings
+fn to_stored_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+/// Credits auto-approved for an origin and program since the start of the local day
+fn spent_today(conn: &Connection, origin: &str, program_id: &str) -> AvailResult<f64> {
+    let start_of_day = Local::now()
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .and_then(|start| start.and_local_timezone(Local).single())
+        .unwrap_or_else(Local::now)
+        .with_timezone(&Utc);
+
+    let mut statement = conn.prepare(
+        "SELECT spend FROM policy_decisions WHERE origin=?1 AND program_id=?2 AND decision=?3 AND spend_state IN (?4, ?5) AND created_at >= ?6",
+    )?;
+
+    let spent = statement
+        .query_map(
+            params![
+                origin,
+                program_id,
+                PolicyDecision::AutoApprove.to_str(),
+                SPEND_RESERVED,
+                SPEND_BROADCAST,
+                to_stored_timestamp(start_of_day)
+            ],
+            |row| row.get::<_, String>(0),
+        )?
+        .filter_map(|spend| spend.ok().and_then(|spend| spend.parse::<f64>().ok()))
+        .sum();
+
+    Ok(spent)
+}
+
+/// Decides how a dapp request is handled, origins without a policy are always prompted
+pub fn evaluate_dapp_policy_raw(
+    origin: &str,
+    program_id: &str,
+    function_id: &str,
+    spend: f64,
+) -> AvailResult<(PolicyDecision, String)> {
+    match get_dapp_policy(origin, program_id)? {
+        Some(policy) => {
+            let storage = PersistentStorage::new()?;
+            let spent = spent_today(&storage.conn, origin, program_id)?;
+            Ok(policy.evaluate(function_id, spend, spent))
+        }
+        None => Ok((PolicyDecision::Prompt, "No policy set".to_string())),
+    }
+}
+
+/// Evaluates a request that is about to be executed and logs the decision.
+/// An auto-approved spend is reserved in the same transaction as the budget check, so concurrent
+/// requests cannot both fit in the remaining budget, and is settled with `settle_policy_spend`.
+/// Returns the decision and the id of its log entry.
+pub fn enforce_dapp_policy(
+    origin: &str,
+    program_id: &str,
+    function_id: &str,
+    spend: f64,
+) -> AvailResult<(PolicyDecision, i64)> {
+    let policy = get_dapp_policy(origin, program_id)?;
+
+    let mut storage = PersistentStorage::new()?;
+    let transaction = storage
+        .conn
+        .transaction_with_behavior(TransactionBehavior::Immediate)?;
+
+    let (decision, reason) = match policy {
+        Some(policy) => policy.evaluate(
+            function_id,
+            spend,
+            spent_today(&transaction, origin, program_id)?,
+        ),
+        None => (PolicyDecision::Prompt, "No policy set".to_string()),
+    };
+
+    let spend_state = match decision {
+        PolicyDecision::AutoApprove => Some(SPEND_RESERVED),
+        _ => None,
+    };
+
+    transaction.execute(
+        "INSERT INTO policy_decisions (origin, program_id, function_id, spend, decision, reason, spend_state, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            origin,
+            program_id,
+            function_id,
+            spend.to_string(),
+            decision.to_str(),
+            reason,
+            spend_state,
+            to_stored_timestamp(Utc::now())
+        ],
+    )?;
+    let decision_id = transaction.last_insert_rowid();
+    transaction.commit()?;
+
+    Ok((decision, decision_id))
+}
+
+/// Counts a reserved spend against the budget once it was broadcast, or releases it
+pub fn settle_policy_spend(decision_id: i64, broadcast: bool) -> AvailResult<()> {
+    let spend_state = match broadcast {
+        true => SPEND_BROADCAST,
+        false => SPEND_RELEASED,
+    };
+
+    let storage = PersistentStorage::new()?;
+    storage.save_mixed(
+        vec![&spend_state, &decision_id, &SPEND_RESERVED],
+        "UPDATE policy_decisions SET spend_state=?1 WHERE id=?2 AND spend_state=?3".to_string(),
+    )?;
+
+    Ok(())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn set_dapp_policy(policy: DappPolicy) -> AvailResult<()> {
+    store_dapp_policy(&policy)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_dapp_policies() -> AvailResult<Vec<DappPolicy>> {
+    init_dapp_policies_tables()?;
+
+    let storage = PersistentStorage::new()?;
+    storage
+        .get_all::<String>("SELECT policy FROM dapp_policies", 1)?
+        .iter()
+        .map(|row| Ok(serde_json::from_str::<DappPolicy>(&row[0])?))
+        .collect::<AvailResult<Vec<DappPolicy>>>()
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn remove_dapp_policy(origin: &str, program_id: &str) -> AvailResult<()> {
+    init_dapp_policies_tables()?;
+
+    let storage = PersistentStorage::new()?;
+    storage.save(
+        vec![origin.to_string(), program_id.to_string()],
+        "DELETE FROM dapp_policies WHERE origin=?1 AND program_id=?2".to_string(),
+    )?;
+
+    Ok(())
+}
+
+/// Gets the logged policy decisions, most recent first
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_policy_decisions(origin: Option<String>) -> AvailResult<Vec<PolicyDecisionEntry>> {
+    init_dapp_policies_tables()?;
+
+    let storage = PersistentStorage::new()?;
+    let rows = match origin {
+        Some(origin) => storage.get_all_with_params::<String, String>(
+            "SELECT origin, program_id, function_id, spend, decision, reason, created_at FROM policy_decisions WHERE origin=?1 ORDER BY id DESC",
+            vec![origin],
+            7,
+        )?,
+        None => storage.get_all::<String>(
+            "SELECT origin, program_id, function_id, spend, decision, reason, created_at FROM policy_decisions ORDER BY id DESC",
+            7,
+        )?,
+    };
+
+    rows.iter()
+        .map(|row| {
+            let created = DateTime::parse_from_rfc3339(&row[6])
+                .map(|created| created.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now());
+
+            Ok(PolicyDecisionEntry::new(
+                row[0].clone(),
+                row[1].clone(),
+                row[2].clone(),
+                row[3].parse::<f64>().unwrap_or(0.0),
+                PolicyDecision::from_str(&row[4]),
+                row[5].clone(),
+                created,
+            ))
+        })
+        .collect::<AvailResult<Vec<PolicyDecisionEntry>>>()
+}
+
+#[cfg(test)]
+mod dapp_policies_tests {
+    use super::*;
+
+    const TEST_ORIGIN: &str = "https://trading.example";
+
+    #[test]
+    fn test_enforce_dapp_policy_tracks_daily_budget() {
+        let policy = DappPolicy::new(
+            TEST_ORIGIN.to_string(),
+            "credits.aleo".to_string(),
+            Some(1.0),
+            None,
+            vec!["transfer_public".to_string()],
+            None,
+        );
+        store_dapp_policy(&policy).unwrap();
+
+        // the first spend is still reserved while the second is checked
+        let (first, first_id) =
+            enforce_dapp_policy(TEST_ORIGIN, "credits.aleo", "transfer_public", 0.6).unwrap();
+        let (second, _) =
+            enforce_dapp_policy(TEST_ORIGIN, "credits.aleo", "transfer_public", 0.6).unwrap();
+
+        assert_eq!(first, PolicyDecision::AutoApprove);
+        assert_eq!(second, PolicyDecision::Prompt);
+
+        // a failed broadcast gives the budget back
+        settle_policy_spend(first_id, false).unwrap();
+        let (third, third_id) =
+            enforce_dapp_policy(TEST_ORIGIN, "credits.aleo", "transfer_public", 0.6).unwrap();
+        assert_eq!(third, PolicyDecision::AutoApprove);
+        settle_policy_spend(third_id, false).unwrap();
+
+        remove_dapp_policy(TEST_ORIGIN, "credits.aleo").unwrap();
+        let decisions = get_policy_decisions(Some(TEST_ORIGIN.to_string())).unwrap();
+        assert!(decisions.len() >= 2);
+    }
+
+    #[test]
+    fn test_no_policy_prompts() {
+        let (decision, _) = evaluate_dapp_policy_raw(
+            "https://unknown.example",
+            "credits.aleo",
+            "transfer_public",
+            0.1,
+        )
+        .unwrap();
+        assert_eq!(decision, PolicyDecision::Prompt);
+
+        // quotes are bound as parameters, never interpolated into the query
+        assert_eq!(
+            get_dapp_policy("x' OR '1'='1", "credits.aleo").unwrap(),
+            None
+        );
+        assert!(get_policy_decisions(Some("x' OR '1'='1".to_string()))
+            .unwrap()
+            .is_empty());
+    }
+}
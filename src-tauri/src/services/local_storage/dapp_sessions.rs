@@ -0,0 +1,313 @@
+use chrono::{DateTime, Duration, Local};
+
+use crate::models::storage::persistent::PersistentStorage;
+use crate::models::wallet_connect::dapp_session::{Caller, DappPermission, DappSession};
+
+use avail_common::errors::{AvailError, AvailErrorType, AvailResult};
+
+/* --Dapp Session Registry-- */
+
+/// Seconds a granted permission lasts when the caller does not set an expiry
+const DEFAULT_SESSION_SECONDS: i64 = 3600;
+
+pub fn init_dapp_sessions_table() -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+    storage.execute_query(
+        "CREATE TABLE IF NOT EXISTS dapp_sessions (
+            origin TEXT PRIMARY KEY,
+            permissions TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            expires_at TEXT NOT NULL
+        )",
+    )?;
+    Ok(())
+}
+
+pub fn drop_dapp_sessions_table() -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+    match storage.execute_query("DROP TABLE IF EXISTS dapp_sessions") {
+        Ok(r) => r,
+        Err(e) => match e.error_type {
+            AvailErrorType::NotFound => {}
+            _ => {
+                return Err(AvailError::new(
+                    AvailErrorType::Internal,
+                    e.internal_msg,
+                    "Error deleting dapp sessions".to_string(),
+                ))
+            }
+        },
+    };
+
+    Ok(())
+}
+
+fn row_to_session(row: &[String]) -> AvailResult<DappSession> {
+    if row.len() != 4 {
+        return Err(AvailError::new(
+            AvailErrorType::LocalStorage,
+            "Invalid dapp session row".to_string(),
+            "Error reading dapp session".to_string(),
+        ));
+    }
+
+    let permissions = serde_json::from_str::<Vec<DappPermission>>(&row[1])?;
+    let created = DateTime::parse_from_rfc3339(&row[2])
+        .map(|created| created.with_timezone(&Local))
+        .unwrap_or_else(|_| Local::now());
+    let expires_at = DateTime::parse_from_rfc3339(&row[3])
+        .map(|expires_at| expires_at.with_timezone(&Local))
+        .unwrap_or_else(|_| Local::now());
+
+    Ok(DappSession::new(
+        row[0].clone(),
+        permissions,
+        created,
+        expires_at,
+    ))
+}
+
+pub fn store_dapp_session(session: &DappSession) -> AvailResult<()> {
+    init_dapp_sessions_table()?;
+
+    let storage = PersistentStorage::new()?;
+    storage.save(
+        vec![
+            session.origin().clone(),
+            serde_json::to_string(session.permissions())?,
+            session.created().to_rfc3339(),
+            session.expires_at().to_rfc3339(),
+        ],
+        "INSERT OR REPLACE INTO dapp_sessions (origin, permissions, created_at, expires_at) VALUES (?1, ?2, ?3, ?4)"
+            .to_string(),
+    )?;
+
+    Ok(())
+}
+
+pub fn get_dapp_session(origin: &str) -> AvailResult<Option<DappSession>> {
+    init_dapp_sessions_table()?;
+
+    let storage = PersistentStorage::new()?;
+    let query = format!(
+        "SELECT origin, permissions, created_at, expires_at FROM dapp_sessions WHERE origin='{}'",
+        origin.replace('\'', "''")
+    );
+
+    let res = storage.get_all::<String>(&query, 4)?;
+    match res.get(0) {
+        Some(row) => Ok(Some(row_to_session(row)?)),
+        None => Ok(None),
+    }
+}
+
+pub fn delete_dapp_session(origin: &str) -> AvailResult<()> {
+    init_dapp_sessions_table()?;
+
+    let storage = PersistentStorage::new()?;
+    storage.save(
+        vec![origin.to_string()],
+        "DELETE FROM dapp_sessions WHERE origin=?1".to_string(),
+    )?;
+
+    Ok(())
+}
+
+/// Removes sessions past their expiry
+fn delete_expired_dapp_sessions() -> AvailResult<()> {
+    init_dapp_sessions_table()?;
+
+    let storage = PersistentStorage::new()?;
+    let query = "SELECT origin, permissions, created_at, expires_at FROM dapp_sessions";
+
+    for row in storage.get_all::<String>(query, 4)? {
+        let session = row_to_session(&row)?;
+        if session.is_expired() {
+            delete_dapp_session(session.origin())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Adds a permission to an origin's session, creating the session if needed
+pub fn grant_dapp_permission_raw(
+    origin: &str,
+    permission: DappPermission,
+    expires_in: Option<i64>,
+) -> AvailResult<DappSession> {
+    if let DappPermission::ReadRecords { program_ids } = &permission {
+        if program_ids.is_empty() {
+            return Err(AvailError::new(
+                AvailErrorType::Validation,
+                format!("Empty read records grant for {}", origin),
+                "Record access must name the programs it covers.".to_string(),
+            ));
+        }
+    }
+
+    let now = Local::now();
+    let expires_at = now + Duration::seconds(expires_in.unwrap_or(DEFAULT_SESSION_SECONDS));
+
+    let mut session = match get_dapp_session(origin)? {
+        Some(session) if !session.is_expired() => session,
+        _ => DappSession::new(origin.to_string(), vec![], now, expires_at),
+    };
+
+    session.grant(permission);
+    session.extend(expires_at);
+    store_dapp_session(&session)?;
+
+    Ok(session)
+}
+
+fn unauthorized(origin: &str, action: &str) -> AvailError {
+    AvailError::new(
+        AvailErrorType::Unauthorized,
+        format!("{} is not permitted to {}", origin, action),
+        format!(
+            "This dapp is not permitted to {}, please approve it first.",
+            action
+        ),
+    )
+}
+
+/// Gets the active session of an origin
+fn get_active_session(origin: &str, action: &str) -> AvailResult<DappSession> {
+    match get_dapp_session(origin)? {
+        Some(session) if !session.is_expired() => Ok(session),
+        Some(_) => {
+            delete_dapp_session(origin)?;
+            Err(unauthorized(origin, action))
+        }
+        None => Err(unauthorized(origin, action)),
+    }
+}
+
+/// Checks that a dapp holds a decrypt, sign or execute permission, the wallet itself is not checked
+pub fn authorize_dapp(caller: &Caller, permission: &DappPermission) -> AvailResult<()> {
+    let origin = match caller.dapp_origin()? {
+        Some(origin) => origin,
+        None => return Ok(()),
+    };
+
+    let action = match permission {
+        DappPermission::ReadRecords { .. } => "read records",
+        DappPermission::Decrypt => "decrypt records",
+        DappPermission::Sign => "sign messages",
+        DappPermission::Execute => "create transactions",
+    };
+
+    match get_active_session(origin, action)?.allows(permission) {
+        true => Ok(()),
+        false => Err(unauthorized(origin, action)),
+    }
+}
+
+/// Checks that a dapp can read the records of every listed program
+pub fn authorize_record_access(caller: &Caller, program_ids: &[String]) -> AvailResult<()> {
+    let origin = match caller.dapp_origin()? {
+        Some(origin) => origin,
+        None => return Ok(()),
+    };
+
+    let session = get_active_session(origin, "read records")?;
+
+    match program_ids
+        .iter()
+        .all(|program_id| session.can_read_records(program_id))
+    {
+        true => Ok(()),
+        false => Err(unauthorized(origin, "read these records")),
+    }
+}
+
+/// Programs a caller may read records of, None for the wallet which is unrestricted
+pub fn readable_programs(caller: &Caller) -> AvailResult<Option<Vec<String>>> {
+    let origin = match caller.dapp_origin()? {
+        Some(origin) => origin,
+        None => return Ok(None),
+    };
+
+    Ok(Some(
+        get_active_session(origin, "read records")?.readable_programs(),
+    ))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn grant_dapp_permission(
+    origin: &str,
+    permission: DappPermission,
+    expires_in: Option<i64>,
+) -> AvailResult<DappSession> {
+    grant_dapp_permission_raw(origin, permission, expires_in)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_dapp_sessions() -> AvailResult<Vec<DappSession>> {
+    delete_expired_dapp_sessions()?;
+
+    let storage = PersistentStorage::new()?;
+    let query = "SELECT origin, permissions, created_at, expires_at FROM dapp_sessions ORDER BY created_at DESC";
+
+    storage
+        .get_all::<String>(query, 4)?
+        .iter()
+        .map(|row| row_to_session(row))
+        .collect::<AvailResult<Vec<DappSession>>>()
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn revoke_dapp_session(origin: &str) -> AvailResult<()> {
+    delete_dapp_session(origin)
+}
+
+#[cfg(test)]
+mod dapp_sessions_tests {
+    use super::*;
+
+    const TEST_ORIGIN: &str = "https://dapp.example";
+
+    #[test]
+    fn test_grant_and_revoke_dapp_session() {
+        grant_dapp_permission_raw(TEST_ORIGIN, DappPermission::Sign, None).unwrap();
+
+        let caller = Caller::Dapp(TEST_ORIGIN.to_string());
+        assert!(authorize_dapp(&caller, &DappPermission::Sign).is_ok());
+        assert!(authorize_dapp(&caller, &DappPermission::Execute).is_err());
+        assert!(authorize_record_access(&caller, &["credits.aleo".to_string()]).is_err());
+
+        revoke_dapp_session(TEST_ORIGIN).unwrap();
+        assert!(authorize_dapp(&caller, &DappPermission::Sign).is_err());
+    }
+
+    #[test]
+    fn test_wallet_requests_are_not_checked() {
+        assert!(authorize_dapp(&Caller::Wallet, &DappPermission::Execute).is_ok());
+        assert!(readable_programs(&Caller::Wallet).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_dapp_without_origin_is_rejected() {
+        let caller = Caller::Dapp("".to_string());
+
+        assert!(authorize_dapp(&caller, &DappPermission::Sign).is_err());
+        assert!(authorize_record_access(&caller, &[]).is_err());
+        assert!(readable_programs(&caller).is_err());
+    }
+
+    #[test]
+    fn test_empty_read_grant_is_rejected() {
+        let permission = DappPermission::ReadRecords {
+            program_ids: vec![],
+        };
+
+        assert!(grant_dapp_permission_raw(TEST_ORIGIN, permission, None).is_err());
+    }
+
+    #[test]
+    fn test_get_dapp_sessions() {
+        let res = get_dapp_sessions().unwrap();
+        println!("{:?}", res);
+    }
+}
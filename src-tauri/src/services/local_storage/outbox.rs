@@ -0,0 +1,214 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::models::outbox::{OutboxAction, OutboxEntry, OutboxEntryStatus, OutboxStatus};
+use crate::models::storage::persistent::PersistentStorage;
+
+use avail_common::errors::{AvailError, AvailErrorType, AvailResult};
+
+/* --Server Outbox-- */
+
+pub fn init_outbox_table() -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+    storage.execute_query(
+        "CREATE TABLE IF NOT EXISTS outbox (
+            id TEXT PRIMARY KEY,
+            kind TEXT NOT NULL,
+            action TEXT NOT NULL,
+            attempts TEXT NOT NULL,
+            next_attempt TEXT NOT NULL,
+            last_error TEXT NOT NULL,
+            status TEXT NOT NULL
+        )",
+    )?;
+    Ok(())
+}
+
+pub fn drop_outbox_table() -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+    match storage.execute_query("DROP TABLE IF EXISTS outbox") {
+        Ok(r) => r,
+        Err(e) => match e.error_type {
+            AvailErrorType::NotFound => {}
+            _ => {
+                return Err(AvailError::new(
+                    AvailErrorType::Internal,
+                    e.internal_msg,
+                    "Error deleting outbox".to_string(),
+                ))
+            }
+        },
+    };
+
+    Ok(())
+}
+
+fn row_to_entry(row: &[String]) -> AvailResult<OutboxEntry> {
+    if row.len() != 6 {
+        return Err(AvailError::new(
+            AvailErrorType::LocalStorage,
+            "Invalid outbox row".to_string(),
+            "Error reading outbox".to_string(),
+        ));
+    }
+
+    let next_attempt = DateTime::parse_from_rfc3339(&row[3])
+        .map(|next_attempt| next_attempt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now());
+
+    Ok(OutboxEntry {
+        id: row[0].clone(),
+        action: serde_json::from_str::<OutboxAction>(&row[1])?,
+        attempts: row[2].parse::<u32>().unwrap_or(0),
+        next_attempt,
+        last_error: match row[4].is_empty() {
+            true => None,
+            false => Some(row[4].clone()),
+        },
+        status: OutboxEntryStatus::from(row[5].as_str()),
+    })
+}
+
+/// Queues an action behind everything already in the outbox
+pub fn enqueue_outbox_action(action: &OutboxAction) -> AvailResult<String> {
+    init_outbox_table()?;
+
+    let id = Uuid::new_v4().to_string();
+    let storage = PersistentStorage::new()?;
+    storage.save(
+        vec![
+            id.clone(),
+            action.kind().to_string(),
+            serde_json::to_string(action)?,
+            "0".to_string(),
+            Utc::now().to_rfc3339(),
+            String::new(),
+            OutboxEntryStatus::Pending.to_str().to_string(),
+        ],
+        "INSERT INTO outbox (id, kind, action, attempts, next_attempt, last_error, status) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"
+            .to_string(),
+    )?;
+
+    Ok(id)
+}
+
+/// Pending entries in the order they were queued
+pub fn get_pending_outbox_entries() -> AvailResult<Vec<OutboxEntry>> {
+    init_outbox_table()?;
+
+    let storage = PersistentStorage::new()?;
+    let query = format!(
+        "SELECT id, action, attempts, next_attempt, last_error, status FROM outbox WHERE status='{}' ORDER BY rowid",
+        OutboxEntryStatus::Pending.to_str()
+    );
+
+    storage
+        .get_all::<String>(&query, 6)?
+        .iter()
+        .map(|row| row_to_entry(row))
+        .collect()
+}
+
+pub fn delete_outbox_entry(id: &str) -> AvailResult<()> {
+    init_outbox_table()?;
+
+    let storage = PersistentStorage::new()?;
+    storage.save(
+        vec![id.to_string()],
+        "DELETE FROM outbox WHERE id=?1".to_string(),
+    )?;
+
+    Ok(())
+}
+
+/// Records a failed attempt and when to try again
+pub fn update_outbox_entry_attempt(
+    id: &str,
+    attempts: u32,
+    next_attempt: DateTime<Utc>,
+    error: &str,
+    status: OutboxEntryStatus,
+) -> AvailResult<()> {
+    init_outbox_table()?;
+
+    let storage = PersistentStorage::new()?;
+    storage.save(
+        vec![
+            attempts.to_string(),
+            next_attempt.to_rfc3339(),
+            error.to_string(),
+            status.to_str().to_string(),
+            id.to_string(),
+        ],
+        "UPDATE outbox SET attempts=?1, next_attempt=?2, last_error=?3, status=?4 WHERE id=?5"
+            .to_string(),
+    )?;
+
+    Ok(())
+}
+
+pub fn get_outbox_status_raw() -> AvailResult<OutboxStatus> {
+    init_outbox_table()?;
+
+    let storage = PersistentStorage::new()?;
+    let query =
+        "SELECT id, action, attempts, next_attempt, last_error, status FROM outbox ORDER BY rowid";
+
+    let entries = storage
+        .get_all::<String>(query, 6)?
+        .iter()
+        .map(|row| row_to_entry(row))
+        .collect::<AvailResult<Vec<OutboxEntry>>>()?;
+
+    let pending = entries
+        .iter()
+        .filter(|entry| entry.status == OutboxEntryStatus::Pending)
+        .collect::<Vec<&OutboxEntry>>();
+
+    Ok(OutboxStatus {
+        pending: pending.len() as u32,
+        failed: (entries.len() - pending.len()) as u32,
+        next_attempt: pending.iter().map(|entry| entry.next_attempt).min(),
+        last_error: entries
+            .iter()
+            .rev()
+            .find_map(|entry| entry.last_error.clone()),
+    })
+}
+
+#[cfg(test)]
+mod outbox_storage_tests {
+    use super::*;
+
+    #[test]
+    fn test_outbox_queue_order_and_attempts() {
+        drop_outbox_table().unwrap();
+
+        let first = enqueue_outbox_action(&OutboxAction::MarkSynced(vec![Uuid::new_v4()])).unwrap();
+        let second =
+            enqueue_outbox_action(&OutboxAction::UpdateData(vec!["id".to_string()])).unwrap();
+
+        let entries = get_pending_outbox_entries().unwrap();
+        assert_eq!(
+            entries.iter().map(|e| e.id.clone()).collect::<Vec<_>>(),
+            vec![first.clone(), second.clone()]
+        );
+
+        update_outbox_entry_attempt(
+            &first,
+            3,
+            Utc::now(),
+            "Error syncing data",
+            OutboxEntryStatus::Failed,
+        )
+        .unwrap();
+        delete_outbox_entry(&second).unwrap();
+
+        let status = get_outbox_status_raw().unwrap();
+        assert_eq!(status.pending, 0);
+        assert_eq!(status.failed, 1);
+        assert_eq!(status.last_error, Some("Error syncing data".to_string()));
+
+        drop_outbox_table().unwrap();
+    }
+}
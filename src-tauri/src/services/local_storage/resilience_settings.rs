@@ -0,0 +1,105 @@
+use crate::api::resilience::{resilience_settings, set_resilience_settings};
+use crate::models::resilience::ResilienceSettings;
+use crate::models::storage::persistent::PersistentStorage;
+
+use avail_common::errors::{AvailError, AvailErrorType, AvailResult};
+
+/* --Network Resilience Settings-- */
+
+pub fn init_resilience_settings_table() -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+    storage.execute_query(
+        "CREATE TABLE IF NOT EXISTS resilience_settings (
+            id TEXT PRIMARY KEY,
+            settings TEXT NOT NULL
+        )",
+    )?;
+    Ok(())
+}
+
+pub fn drop_resilience_settings_table() -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+    match storage.execute_query("DROP TABLE IF EXISTS resilience_settings") {
+        Ok(r) => r,
+        Err(e) => match e.error_type {
+            AvailErrorType::NotFound => {}
+            _ => {
+                return Err(AvailError::new(
+                    AvailErrorType::Internal,
+                    e.internal_msg,
+                    "Error deleting network settings".to_string(),
+                ))
+            }
+        },
+    };
+
+    set_resilience_settings(ResilienceSettings::default());
+
+    Ok(())
+}
+
+pub fn store_resilience_settings(settings: &ResilienceSettings) -> AvailResult<()> {
+    init_resilience_settings_table()?;
+
+    let storage = PersistentStorage::new()?;
+    storage.save(
+        vec!["network".to_string(), serde_json::to_string(settings)?],
+        "INSERT OR REPLACE INTO resilience_settings (id, settings) VALUES (?1, ?2)".to_string(),
+    )?;
+
+    Ok(())
+}
+
+pub fn get_stored_resilience_settings() -> AvailResult<Option<ResilienceSettings>> {
+    init_resilience_settings_table()?;
+
+    let storage = PersistentStorage::new()?;
+    let query = "SELECT settings FROM resilience_settings WHERE id='network'";
+
+    match storage.get_all::<String>(query, 1)?.first() {
+        Some(row) => Ok(Some(serde_json::from_str::<ResilienceSettings>(&row[0])?)),
+        None => Ok(None),
+    }
+}
+
+/// Retry, timeout and circuit breaker settings used for the Aleo node and the Avail server
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_resilience_settings() -> AvailResult<ResilienceSettings> {
+    Ok(resilience_settings())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn update_resilience_settings(settings: ResilienceSettings) -> AvailResult<()> {
+    settings.validate()?;
+
+    store_resilience_settings(&settings)?;
+    set_resilience_settings(settings);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod resilience_settings_tests {
+    use super::*;
+
+    #[test]
+    fn test_update_resilience_settings() {
+        drop_resilience_settings_table().unwrap();
+        assert_eq!(get_stored_resilience_settings().unwrap(), None);
+
+        let mut settings = ResilienceSettings::default();
+        settings.max_retries = 5;
+        update_resilience_settings(settings.clone()).unwrap();
+
+        assert_eq!(
+            get_stored_resilience_settings().unwrap(),
+            Some(settings.clone())
+        );
+        assert_eq!(get_resilience_settings().unwrap(), settings);
+
+        settings.base_delay_ms = settings.max_delay_ms + 1;
+        assert!(update_resilience_settings(settings).is_err());
+
+        drop_resilience_settings_table().unwrap();
+    }
+}
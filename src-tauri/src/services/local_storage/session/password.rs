@@ -8,6 +8,7 @@ use std::{
 pub struct PassSession {
     password: RwLock<Option<String>>,
     expiration: RwLock<Instant>,
+    authenticated_at: RwLock<Option<Instant>>,
 }
 
 impl PassSession {
@@ -15,13 +16,16 @@ impl PassSession {
         Self {
             password: RwLock::new(None),
             expiration: RwLock::new(Instant::now()),
+            authenticated_at: RwLock::new(None),
         }
     }
 
     pub fn set_pass_session(&self, password: &str) -> AvailResult<()> {
         let mut password_lock = self.password.write().unwrap();
         let mut expiration_lock = self.expiration.write().unwrap();
+        let mut authenticated_at_lock = self.authenticated_at.write().unwrap();
         *password_lock = Some(password.to_string());
+        *authenticated_at_lock = Some(Instant::now());
 
         // Set expiration to 5 minutes from now
         *expiration_lock = Instant::now() + Duration::from_secs(5 * 60);
@@ -35,6 +39,29 @@ impl PassSession {
         Ok(())
     }
 
+    /// Clears the password so the next signing operation requires reauthentication
+    pub fn expire_session(&self) -> AvailResult<()> {
+        let mut password_lock = self.password.write().unwrap();
+        let mut authenticated_at_lock = self.authenticated_at.write().unwrap();
+        *password_lock = None;
+        *authenticated_at_lock = None;
+        Ok(())
+    }
+
+    /// Checks if the password was entered within the given duration, extending the session does not count
+    pub fn authenticated_within(&self, duration: Duration) -> bool {
+        match *self.authenticated_at.read().unwrap() {
+            Some(authenticated_at) => authenticated_at.elapsed() <= duration,
+            None => false,
+        }
+    }
+
+    /// Checks the password is set and its session has not expired, without extending or clearing it
+    pub fn is_valid(&self) -> bool {
+        Instant::now() <= *self.expiration.read().unwrap()
+            && self.password.read().unwrap().is_some()
+    }
+
     pub fn get_instance(&self) -> AvailResult<String> {
         let expiration_lock = self.expiration.read().unwrap();
         if Instant::now() > *expiration_lock {
@@ -72,4 +99,18 @@ mod test_pass_session {
         let password = PASS.get_instance().unwrap();
         assert_eq!(password, "password");
     }
+
+    #[test]
+    fn test_expire_pass_session() {
+        let session = PassSession::new();
+        session.set_pass_session("password").unwrap();
+        assert!(session.authenticated_within(Duration::from_secs(60)));
+
+        assert!(session.is_valid());
+
+        session.expire_session().unwrap();
+        assert!(!session.is_valid());
+        assert!(session.get_instance().is_err());
+        assert!(!session.authenticated_within(Duration::from_secs(60)));
+    }
 }
@@ -24,6 +24,11 @@ impl ViewSession {
         Ok(())
     }
 
+    /// The view key is only in session while the wallet is unlocked
+    pub fn is_set(&self) -> bool {
+        self.view_key.read().unwrap().is_some()
+    }
+
     pub fn get_instance<N: Network>(&self) -> AvailResult<ViewKey<N>> {
         let view_key_lock = self.view_key.read().unwrap();
         let view_key = match &*view_key_lock {
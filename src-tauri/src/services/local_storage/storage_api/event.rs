@@ -11,6 +11,7 @@ use crate::models::{
     event::{AvailEvent, Event, SuccinctAvailEvent},
     storage::persistent::PersistentStorage,
 };
+use crate::services::ans::{cached_display_name, display_name};
 use crate::services::local_storage::{
     encrypted_data::{get_encrypted_data_by_id, handle_encrypted_data_query},
     persistent_storage::{get_address_string, get_network},
@@ -49,7 +50,7 @@ pub fn get_event_raw<N: Network>(id: &str) -> AvailResult<Event> {
 /// Gets an Avail Event by its encrypted data id
 pub fn get_avail_event_raw<N: Network>(id: &str) -> AvailResult<AvailEvent> {
     let encrypted_event = get_encrypted_data_by_id(id)?;
-    let event = match encrypted_event.flavour {
+    let mut event = match encrypted_event.flavour {
         EncryptedDataTypeCommon::Transition => {
             TransitionPointer::<N>::decrypt_to_avail_event(encrypted_event)?
         }
@@ -68,13 +69,18 @@ pub fn get_avail_event_raw<N: Network>(id: &str) -> AvailResult<AvailEvent> {
         }
     };
 
+    event.set_names(
+        display_name::<N>(event.to()),
+        display_name::<N>(event.from()),
+    );
+
     Ok(event)
 }
 
 ///  Gets a Succinct Avail Event by its encrypted data id
 pub fn get_succinct_avail_event_raw<N: Network>(id: &str) -> AvailResult<SuccinctAvailEvent> {
     let encrypted_event = get_encrypted_data_by_id(id)?;
-    let event = match encrypted_event.flavour {
+    let mut event = match encrypted_event.flavour {
         EncryptedDataTypeCommon::Transition => {
             TransitionPointer::<N>::decrypt_to_succinct_avail_event(encrypted_event)?
         }
@@ -93,6 +99,11 @@ pub fn get_succinct_avail_event_raw<N: Network>(id: &str) -> AvailResult<Succinc
         }
     };
 
+    event.set_names(
+        display_name::<N>(event.to()),
+        display_name::<N>(event.from()),
+    );
+
     Ok(event)
 }
 
@@ -293,7 +304,7 @@ pub fn get_avail_events_raw<N: Network>(request: GetEventsRequest) -> AvailResul
     let mut events: Vec<AvailEvent> = vec![];
 
     for encrypted_transaction in encrypted_data {
-        let event = match encrypted_transaction.flavour {
+        let mut event = match encrypted_transaction.flavour {
             EncryptedDataTypeCommon::Transition => {
                 TransitionPointer::<N>::decrypt_to_avail_event(encrypted_transaction)?
             }
@@ -312,6 +323,11 @@ pub fn get_avail_events_raw<N: Network>(request: GetEventsRequest) -> AvailResul
             }
         };
 
+        event.set_names(
+            cached_display_name(event.to()),
+            cached_display_name(event.from()),
+        );
+
         events.push(event);
     }
 
@@ -406,7 +422,7 @@ pub fn get_succinct_avail_events_raw<N: Network>(
     let mut events: Vec<SuccinctAvailEvent> = vec![];
 
     for encrypted_transaction in encrypted_data {
-        let event = match encrypted_transaction.flavour {
+        let mut event = match encrypted_transaction.flavour {
             EncryptedDataTypeCommon::Transition => {
                 TransitionPointer::<N>::decrypt_to_succinct_avail_event(encrypted_transaction)?
             }
@@ -425,6 +441,11 @@ pub fn get_succinct_avail_events_raw<N: Network>(
             }
         };
 
+        event.set_names(
+            cached_display_name(event.to()),
+            cached_display_name(event.from()),
+        );
+
         events.push(event);
     }
 
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use avail_common::errors::AvailError;
+use avail_common::errors::{AvailError, AvailErrorType};
 use snarkvm::circuit::integers::Integer;
 use snarkvm::circuit::{Identifier, Inject};
 use snarkvm::prelude::{
@@ -227,6 +227,21 @@ pub fn get_record_pointers_for_record_type<N: Network>(
     Ok((record_pointers, encrypted_record_pointers_ids))
 }
 
+/// Gets a single record pointer by its encrypted data id
+pub fn get_record_pointer<N: Network>(id: &str) -> AvailResult<AvailRecord<N>> {
+    let encrypted_data = get_encrypted_data_by_id(id)?;
+    let mut record_pointers = decrypt_record_pointers::<N>(vec![encrypted_data])?;
+
+    match record_pointers.pop() {
+        Some(record_pointer) => Ok(record_pointer),
+        None => Err(AvailError::new(
+            AvailErrorType::NotFound,
+            "Record pointer not found".to_string(),
+            "Record not found".to_string(),
+        )),
+    }
+}
+
 /* Utilities */
 
 /// Update record spent status on local storage via nonce
@@ -314,6 +329,20 @@ pub fn update_record_spent_local_via_nonce<N: Network>(
     Ok(())
 }
 
+/// Whether a record is marked spent locally, records the wallet does not hold are not
+pub fn is_record_spent_local<N: Network>(nonce: &str) -> AvailResult<bool> {
+    match get_encrypted_data_by_nonce(nonce)? {
+        Some(encrypted_data) => {
+            let encrypted_struct = encrypted_data.to_enrypted_struct::<N>()?;
+            let record_pointer: AvailRecord<N> =
+                encrypted_struct.decrypt(VIEWSESSION.get_instance::<N>()?)?;
+
+            Ok(record_pointer.metadata.spent)
+        }
+        None => Ok(false),
+    }
+}
+
 pub fn check_if_record_exists<N: Network>(nonce: &str) -> AvailResult<bool> {
     let encrypted_data = get_encrypted_data_by_nonce(nonce)?;
 
@@ -17,6 +17,7 @@ use crate::services::local_storage::{
     persistent_storage::{get_address, get_network},
     session::view::VIEWSESSION,
 };
+use crate::services::record_handling::watcher::{ensure_watched_loaded, is_watched};
 
 use avail_common::{
     errors::{AvailError, AvailErrorType, AvailResult},
@@ -272,12 +273,41 @@ fn get_expired_unconfirmed_transactions<N: Network>() -> AvailResult<Vec<Encrypt
     Ok(encrypted_transactions_to_decrypt)
 }
 
+/// Transactions and deployments that have not reached a final state yet
+pub fn get_in_flight_transactions<N: Network>() -> AvailResult<Vec<EncryptedData>> {
+    let address = get_address::<N>()?;
+    let network = get_network()?;
+
+    let query = format!(
+        "SELECT * FROM encrypted_data WHERE flavour IN ('{}','{}') AND state IN ('{}','{}') AND owner='{}' AND network='{}'",
+        EncryptedDataTypeCommon::Transaction.to_str(),
+        EncryptedDataTypeCommon::Deployment.to_str(),
+        TransactionState::Pending.to_str(),
+        TransactionState::Processing.to_str(),
+        address,
+        network
+    );
+
+    handle_encrypted_data_query(&query)
+}
+
 // This function should get unconfirmed encryped and check if they have been unconfirmed for more than 10 minutes
 // If they have this should update to failed and the records related to the transaction should be updated to unspent
 pub fn check_unconfirmed_transactions<N: Network>() -> AvailResult<()> {
+    // the watch set is loaded first, otherwise a broadcast transaction could be failed here before the
+    // watcher's first pass picks it up
+    ensure_watched_loaded::<N>()?;
+
     let expired_transactions = get_expired_unconfirmed_transactions::<N>()?;
 
     for expired_transaction in expired_transactions {
+        // the confirmation watcher settles these itself
+        if let Some(id) = expired_transaction.id {
+            if is_watched(&id.to_string()) {
+                continue;
+            }
+        }
+
         let encrypted_struct = expired_transaction.to_enrypted_struct::<N>()?;
         match expired_transaction.flavour {
             EncryptedDataTypeCommon::Transaction => {
@@ -0,0 +1,418 @@
+use snarkvm::prelude::{
+    Identifier, IndexMap, Literal, LiteralType, Network, Plaintext, PlaintextType, Program,
+    ProgramID,
+};
+use std::str::FromStr;
+
+use crate::api::{aleo_client::setup_client, chain_provider::ChainProvider};
+use crate::models::storage::persistent::PersistentStorage;
+use crate::models::tokens::TokenMetadata;
+use crate::services::local_storage::tokens::{get_program_id_for_token, init_tokens_table};
+
+use avail_common::errors::{AvailError, AvailErrorType, AvailResult};
+
+/// Mappings token programs commonly use to expose their name, symbol and decimals
+const TOKEN_INFO_MAPPINGS: [&str; 3] = ["token_info", "metadata", "token_metadata"];
+
+pub fn init_token_registry_table() -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+    storage.execute_query(
+        "CREATE TABLE IF NOT EXISTS token_registry (
+            program_id TEXT PRIMARY KEY,
+            token_name TEXT NOT NULL,
+            name TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            decimals TEXT NOT NULL
+        )",
+    )?;
+    Ok(())
+}
+
+pub fn drop_token_registry_table() -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+    match storage.execute_query("DROP TABLE IF EXISTS token_registry") {
+        Ok(r) => r,
+        Err(e) => match e.error_type {
+            AvailErrorType::NotFound => {}
+            _ => {
+                return Err(AvailError::new(
+                    AvailErrorType::Internal,
+                    e.internal_msg,
+                    "Error deleting token registry".to_string(),
+                ))
+            }
+        },
+    };
+
+    Ok(())
+}
+
+/// Stores token metadata, replacing any previous entry for the same program
+pub fn store_token_metadata(metadata: &TokenMetadata) -> AvailResult<()> {
+    init_token_registry_table()?;
+
+    let storage = PersistentStorage::new()?;
+    storage.save(
+        vec![
+            metadata.program_id().to_string(),
+            metadata.token_name().to_string(),
+            metadata.name().to_string(),
+            metadata.symbol().to_string(),
+            metadata
+                .decimals()
+                .map(|decimals| decimals.to_string())
+                .unwrap_or_default(),
+        ],
+        "INSERT OR REPLACE INTO token_registry (program_id, token_name, name, symbol, decimals) VALUES (?1, ?2, ?3, ?4, ?5)"
+            .to_string(),
+    )?;
+
+    Ok(())
+}
+
+fn row_to_metadata(row: &[String]) -> AvailResult<TokenMetadata> {
+    if row.len() != 5 {
+        return Err(AvailError::new(
+            AvailErrorType::LocalStorage,
+            "Malformed token registry row".to_string(),
+            "Error reading token metadata".to_string(),
+        ));
+    }
+
+    Ok(TokenMetadata::new(
+        row[1].clone(),
+        row[0].clone(),
+        row[2].clone(),
+        row[3].clone(),
+        match row[4].is_empty() {
+            true => None,
+            false => Some(row[4].parse::<u8>()?),
+        },
+    ))
+}
+
+/// Gets the stored metadata of a token program if it has been registered
+pub fn get_token_metadata(program_id: &str) -> AvailResult<Option<TokenMetadata>> {
+    init_token_registry_table()?;
+
+    let storage = PersistentStorage::new()?;
+    let query = "SELECT program_id, token_name, name, symbol, decimals FROM token_registry WHERE program_id=?1";
+
+    let res = storage.get_all_with_params::<String, &str>(query, vec![program_id], 5)?;
+    match res.get(0) {
+        Some(row) => Ok(Some(row_to_metadata(row)?)),
+        None => Ok(None),
+    }
+}
+
+/// Gets the metadata of every registered token
+pub fn get_all_token_metadata() -> AvailResult<Vec<TokenMetadata>> {
+    init_token_registry_table()?;
+
+    let storage = PersistentStorage::new()?;
+    let query = "SELECT program_id, token_name, name, symbol, decimals FROM token_registry";
+
+    let res = storage.get_all::<String>(query, 5)?;
+
+    res.iter()
+        .map(|row| row_to_metadata(row))
+        .collect::<AvailResult<Vec<TokenMetadata>>>()
+}
+
+pub fn is_token_registered(program_id: &str) -> AvailResult<bool> {
+    Ok(get_token_metadata(program_id)?.is_some())
+}
+
+/// Decimals of an asset given by token name or program id, fails if they are not known yet
+pub fn token_decimals(asset: &str) -> AvailResult<u8> {
+    let credits = TokenMetadata::credits();
+
+    let program_id = match asset {
+        "credits" | "Aleo Credits" => credits.program_id().clone(),
+        program_id if program_id.ends_with(".aleo") => program_id.to_string(),
+        token_name => get_program_id_for_token(token_name)?,
+    };
+
+    let decimals = match &program_id == credits.program_id() {
+        true => credits.decimals(),
+        false => get_token_metadata(&program_id)?.and_then(|metadata| metadata.decimals()),
+    };
+
+    decimals.ok_or_else(|| {
+        AvailError::new(
+            AvailErrorType::NotFound,
+            format!("Decimals of {} are unknown", asset),
+            format!("The decimals of '{}' are not known yet.", asset),
+        )
+    })
+}
+
+/// Decodes an ascii string packed big-endian into a u128, as token programs store names and symbols
+pub fn u128_to_ascii(value: u128) -> String {
+    value
+        .to_be_bytes()
+        .iter()
+        .filter(|byte| **byte != 0)
+        .map(|byte| *byte as char)
+        .collect::<String>()
+}
+
+/// Default key used to look up a singleton info mapping with the given key type
+fn default_mapping_key<N: Network>(key_type: &PlaintextType<N>) -> Option<String> {
+    match key_type {
+        PlaintextType::Literal(LiteralType::Boolean) => Some("true".to_string()),
+        PlaintextType::Literal(LiteralType::Address)
+        | PlaintextType::Literal(LiteralType::Signature)
+        | PlaintextType::Literal(LiteralType::String) => None,
+        PlaintextType::Literal(literal_type) => Some(format!("0{}", literal_type)),
+        PlaintextType::Struct(_) => None,
+    }
+}
+
+fn get_struct_member<'a, N: Network>(
+    members: &'a IndexMap<Identifier<N>, Plaintext<N>>,
+    name: &str,
+) -> Option<&'a Plaintext<N>> {
+    let identifier = Identifier::<N>::from_str(name).ok()?;
+    members.get(&identifier)
+}
+
+/// Parses name, symbol and decimals out of a token info struct
+fn parse_token_info<N: Network>(
+    token_name: &str,
+    program_id: &str,
+    value: &str,
+) -> Option<TokenMetadata> {
+    let plaintext = Plaintext::<N>::from_str(value).ok()?;
+
+    let members = match &plaintext {
+        Plaintext::Struct(members, _) => members,
+        _ => return None,
+    };
+
+    let name = match get_struct_member(members, "name")? {
+        Plaintext::Literal(Literal::U128(name), _) => u128_to_ascii(**name),
+        _ => return None,
+    };
+
+    let symbol = match get_struct_member(members, "symbol")? {
+        Plaintext::Literal(Literal::U128(symbol), _) => u128_to_ascii(**symbol),
+        _ => return None,
+    };
+
+    let decimals = match get_struct_member(members, "decimals")? {
+        Plaintext::Literal(Literal::U8(decimals), _) => **decimals,
+        _ => return None,
+    };
+
+    Some(TokenMetadata::new(
+        token_name.to_string(),
+        program_id.to_string(),
+        name,
+        symbol,
+        Some(decimals),
+    ))
+}
+
+/// Reads token metadata from the token info mapping of a program, falling back to the program id
+pub fn fetch_token_metadata<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+    program: &Program<N>,
+    token_name: &str,
+) -> AvailResult<TokenMetadata> {
+    let program_id = program.id().to_string();
+
+    if program_id == "credits.aleo" {
+        return Ok(TokenMetadata::credits());
+    }
+
+    for (mapping_name, mapping) in program.mappings().iter() {
+        if !TOKEN_INFO_MAPPINGS.contains(&mapping_name.to_string().as_str()) {
+            continue;
+        }
+
+        let key = match default_mapping_key(mapping.key().plaintext_type()) {
+            Some(key) => key,
+            None => continue,
+        };
+
+        let value = match api_client.get_mapping_value(
+            &program_id,
+            &mapping_name.to_string(),
+            key.as_str(),
+        )? {
+            Some(value) => value,
+            None => continue,
+        };
+
+        if let Some(metadata) = parse_token_info::<N>(token_name, &program_id, &value.to_string()) {
+            return Ok(metadata);
+        }
+    }
+
+    Ok(TokenMetadata::from_program_id(token_name, &program_id))
+}
+
+/// True once a token is registered with known decimals, fallback entries are looked up again
+fn is_token_resolved(program_id: &str) -> AvailResult<bool> {
+    Ok(match get_token_metadata(program_id)? {
+        Some(metadata) => metadata.decimals().is_some(),
+        None => false,
+    })
+}
+
+/// Registers a token program found while scanning if it is not yet known
+pub fn register_token<N: Network>(program: &Program<N>, token_name: &str) -> AvailResult<()> {
+    if is_token_resolved(&program.id().to_string())? {
+        return Ok(());
+    }
+
+    let api_client = setup_client::<N>()?;
+    let metadata = fetch_token_metadata::<N>(&api_client, program, token_name)?;
+    store_token_metadata(&metadata)?;
+
+    Ok(())
+}
+
+/// Program id and token name of held tokens that are unregistered or only have fallback metadata
+fn discovery_candidates() -> AvailResult<Vec<(String, String)>> {
+    init_tokens_table()?;
+    init_token_registry_table()?;
+
+    let storage = PersistentStorage::new()?;
+    let query = "SELECT t.program_id, t.token_name FROM ARC20_tokens t LEFT JOIN token_registry r ON r.program_id = t.program_id WHERE r.program_id IS NULL OR r.decimals = ''";
+
+    let res = storage.get_all::<String>(query, 2)?;
+
+    Ok(res
+        .into_iter()
+        .filter(|row| row.len() == 2)
+        .map(|row| (row[0].clone(), row[1].clone()))
+        .collect())
+}
+
+/// Looks up the metadata of held tokens that are not resolved yet, failed lookups are retried after the next scan
+pub fn discover_tokens<N: Network>() -> AvailResult<Vec<TokenMetadata>> {
+    let api_client = setup_client::<N>()?;
+    discover_tokens_with::<N>(&api_client)
+}
+
+fn discover_tokens_with<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+) -> AvailResult<Vec<TokenMetadata>> {
+    let pending = discovery_candidates()?;
+    let mut discovered: Vec<TokenMetadata> = vec![];
+
+    for (program_id, token_name) in pending {
+        match discover_token::<N>(api_client, &program_id, &token_name) {
+            Ok(Some(metadata)) => discovered.push(metadata),
+            Ok(None) => {}
+            Err(e) => println!("Error discovering token {}: {:?}", program_id, e),
+        }
+    }
+
+    Ok(discovered)
+}
+
+fn discover_token<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+    program_id: &str,
+    token_name: &str,
+) -> AvailResult<Option<TokenMetadata>> {
+    if is_token_resolved(program_id)? {
+        return Ok(None);
+    }
+
+    let program = api_client.require_program(&ProgramID::<N>::from_str(program_id)?)?;
+    let metadata = fetch_token_metadata::<N>(api_client, &program, token_name)?;
+    store_token_metadata(&metadata)?;
+
+    Ok(Some(metadata))
+}
+
+#[cfg(test)]
+mod token_registry_tests {
+    use super::*;
+    use crate::api::chain_provider::MockChainProvider;
+    use crate::services::local_storage::tokens::init_token;
+    use avail_common::models::constants::TESTNET_ADDRESS;
+    use snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_u128_to_ascii() {
+        // "TEST" packed big-endian
+        assert_eq!(u128_to_ascii(1413829460u128), "TEST".to_string());
+        assert_eq!(u128_to_ascii(0u128), "".to_string());
+    }
+
+    #[test]
+    fn test_parse_token_info() {
+        let value =
+            "{ name: 1413829460u128, symbol: 1413829460u128, decimals: 6u8, supply: 1000u128 }";
+        let metadata = parse_token_info::<Testnet3>("test.record", "test.aleo", value).unwrap();
+
+        assert_eq!(metadata.name(), "TEST");
+        assert_eq!(metadata.symbol(), "TEST");
+        assert_eq!(metadata.decimals(), Some(6));
+    }
+
+    #[test]
+    fn test_store_and_get_token_metadata() {
+        let metadata = TokenMetadata::from_program_id("registry_test.record", "registry_test.aleo");
+        store_token_metadata(&metadata).unwrap();
+
+        let stored = get_token_metadata("registry_test.aleo").unwrap().unwrap();
+        assert_eq!(stored, metadata);
+    }
+
+    #[test]
+    fn test_fallback_metadata_has_unknown_decimals() {
+        let metadata = TokenMetadata::from_program_id("fallback.record", "fallback.aleo");
+        store_token_metadata(&metadata).unwrap();
+
+        let stored = get_token_metadata("fallback.aleo").unwrap().unwrap();
+        assert_eq!(stored.decimals(), None);
+        assert!(!is_token_resolved("fallback.aleo").unwrap());
+    }
+
+    #[test]
+    fn test_discover_tokens() {
+        let api_client = MockChainProvider::<Testnet3>::new();
+
+        // held tokens without resolved metadata are candidates, already stored or not
+        let _ = init_token::<Testnet3>(
+            "missing_token.record",
+            "missing_token.aleo",
+            TESTNET_ADDRESS,
+            "100u64",
+        );
+        let _ = init_token::<Testnet3>(
+            "discovery_credits.record",
+            "credits.aleo",
+            TESTNET_ADDRESS,
+            "100u64",
+        );
+        store_token_metadata(&TokenMetadata::from_program_id(
+            "missing_token.record",
+            "missing_token.aleo",
+        ))
+        .unwrap();
+
+        discover_tokens_with::<Testnet3>(&api_client).unwrap();
+
+        // the undeployed program does not stop the others and is retried after the next scan
+        assert_eq!(
+            get_token_metadata("credits.aleo").unwrap(),
+            Some(TokenMetadata::credits())
+        );
+
+        let candidates = discovery_candidates().unwrap();
+        assert!(candidates.contains(&(
+            "missing_token.aleo".to_string(),
+            "missing_token.record".to_string()
+        )));
+        assert!(!candidates
+            .iter()
+            .any(|(program_id, _)| program_id == "credits.aleo"));
+    }
+}
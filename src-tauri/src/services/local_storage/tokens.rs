@@ -1,4 +1,6 @@
 use crate::models::storage::persistent::PersistentStorage;
+use crate::models::tokens::TokenMetadata;
+use crate::services::local_storage::token_registry::get_token_metadata;
 use avail_common::errors::{AvailError, AvailErrorType, AvailResult};
 use snarkvm::prelude::*;
 
@@ -175,6 +177,30 @@ pub fn subtract_balance<N: Network>(
     }
 }
 
+/// Overwrites the stored balance of a token, used when reconciling against the token records
+pub fn set_balance<N: Network>(token_name: &str, balance: &str, vk: ViewKey<N>) -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+
+    let rng = &mut rand::thread_rng();
+    let scalar = Scalar::<N>::rand(rng);
+    let nonce = N::g_scalar_multiply(&scalar);
+    let new_encrypted_balance = Plaintext::<N>::encrypt(
+        &Plaintext::<N>::from_str(balance)?,
+        &vk.to_address(),
+        scalar,
+    )?;
+
+    storage.save(
+        vec![new_encrypted_balance.to_string(), nonce.to_string()],
+        format!(
+            "UPDATE ARC20_tokens SET balance_ciphertext = ?1, nonce = ?2 WHERE token_name='{}'",
+            token_name
+        ),
+    )?;
+
+    Ok(())
+}
+
 pub fn get_balance<N: Network>(token_name: &str, vk: ViewKey<N>) -> AvailResult<String> {
     let storage = PersistentStorage::new()?;
     let query = format!(
@@ -233,14 +259,19 @@ pub fn get_program_id_for_token(token_name: &str) -> AvailResult<String> {
 }
 
 #[tauri::command(rename_all = "snake_case")]
-pub fn get_stored_tokens() -> AvailResult<Vec<String>> {
+pub fn get_stored_tokens() -> AvailResult<Vec<TokenMetadata>> {
     let storage = PersistentStorage::new()?;
-    let query = "SELECT token_name FROM ARC20_tokens";
-    let res = storage.get_all::<String>(query, 1)?;
+    let query = "SELECT token_name, program_id FROM ARC20_tokens";
+    let res = storage.get_all::<String>(query, 2)?;
 
     println!("Token ids ====> {:?}", res);
 
-    Ok(res.iter().map(|x| x[0].clone()).collect())
+    res.iter()
+        .map(|x| match get_token_metadata(&x[1])? {
+            Some(metadata) => Ok(metadata),
+            None => Ok(TokenMetadata::from_program_id(&x[0], &x[1])),
+        })
+        .collect::<AvailResult<Vec<TokenMetadata>>>()
 }
 
 pub fn delete_tokens_table() -> AvailResult<()> {
@@ -267,13 +298,13 @@ pub fn delete_tokens_table() -> AvailResult<()> {
 mod test_tokens {
     use super::*;
 
-    use crate::api::aleo_client::{setup_client, setup_local_client};
+    use crate::api::aleo_client::{setup_local_client, setup_node_client};
     use crate::models::event::Network as EventNetwork;
     use avail_common::{aleo_tools::api::AleoAPIClient, models::constants::*};
 
     #[test]
     fn test_init() {
-        let api_client: AleoAPIClient<Testnet3> = setup_client::<Testnet3>().unwrap();
+        let api_client: AleoAPIClient<Testnet3> = setup_node_client::<Testnet3>().unwrap();
         let pk = PrivateKey::<Testnet3>::from_str(TESTNET_PRIVATE_KEY).unwrap();
         let vk = ViewKey::<Testnet3>::try_from(pk).unwrap();
         let res =
@@ -282,7 +313,7 @@ mod test_tokens {
     }
     #[test]
     fn test_pid() {
-        let api_client: AleoAPIClient<Testnet3> = setup_client::<Testnet3>().unwrap();
+        let api_client: AleoAPIClient<Testnet3> = setup_node_client::<Testnet3>().unwrap();
         let pk = PrivateKey::<Testnet3>::from_str(TESTNET_PRIVATE_KEY).unwrap();
         let vk = ViewKey::<Testnet3>::try_from(pk).unwrap();
         let res = get_program_id_for_token("testnew111.record").unwrap();
@@ -298,7 +329,7 @@ mod test_tokens {
 
     #[test]
     fn test_subtract_balance() {
-        let api_client: AleoAPIClient<Testnet3> = setup_client::<Testnet3>().unwrap();
+        let api_client: AleoAPIClient<Testnet3> = setup_node_client::<Testnet3>().unwrap();
         let pk = PrivateKey::<Testnet3>::from_str(TESTNET_PRIVATE_KEY).unwrap();
         let vk = ViewKey::<Testnet3>::try_from(pk).unwrap();
         let res = subtract_balance("token1", "100u64", vk).unwrap();
@@ -307,7 +338,7 @@ mod test_tokens {
 
     #[test]
     fn test_get_balance() {
-        let api_client: AleoAPIClient<Testnet3> = setup_client::<Testnet3>().unwrap();
+        let api_client: AleoAPIClient<Testnet3> = setup_node_client::<Testnet3>().unwrap();
         let pk = PrivateKey::<Testnet3>::from_str(TESTNET3_PRIVATE_KEY).unwrap();
         //let vk = ViewKey::<Testnet3>::try_from(pk).unwrap();
 
@@ -319,9 +350,28 @@ mod test_tokens {
         println!("{:?}", res);
     }
 
+    #[test]
+    fn test_get_stored_tokens() {
+        // the token may already exist from an earlier run
+        let _ = init_token::<Testnet3>(
+            "stored_test.record",
+            "stored_test.aleo",
+            TESTNET_ADDRESS,
+            "100u64",
+        );
+
+        let res = get_stored_tokens().unwrap();
+        let stored = res
+            .iter()
+            .find(|token| token.token_name() == "stored_test.record")
+            .unwrap();
+
+        assert_eq!(stored.program_id(), "stored_test.aleo");
+    }
+
     #[test]
     fn test_record_exists() {
-        let api_client: AleoAPIClient<Testnet3> = setup_client::<Testnet3>().unwrap();
+        let api_client: AleoAPIClient<Testnet3> = setup_node_client::<Testnet3>().unwrap();
         let res = if_token_exists("token_not_existing").unwrap();
         println!("{:?}", res);
     }
@@ -5,12 +5,22 @@ use crate::api::user::delete_user;
 use crate::models::storage::encryption::{Keys, Keys::PrivateKey as PKey, Keys::ViewKey as VKey};
 use crate::models::storage::languages::Languages;
 use crate::models::wallet::BetterAvailWallet;
+use crate::services::ans::drop_ans_cache_table;
 use crate::services::local_storage::{
+    dapp_policies::drop_dapp_policies_tables,
+    dapp_sessions::drop_dapp_sessions_table,
     encrypted_data::drop_encrypted_data_table,
+    outbox::drop_outbox_table,
     persistent_storage::{delete_user_preferences, get_backup_flag, get_language, get_network},
+    resilience_settings::drop_resilience_settings_table,
     session::view::VIEWSESSION,
+    token_registry::drop_token_registry_table,
     tokens::drop_tokens_table,
 };
+use crate::services::record_handling::{
+    balances::drop_private_balances_table, nfts::drop_nft_cache_tables,
+    offline_signing::drop_offline_executions_table, watcher::reset_watched,
+};
 use avail_common::models::constants::VIEW_KEY;
 use snarkvm::prelude::{
     Ciphertext, Field, Identifier, Network, PrivateKey, Signature, Testnet3, ViewKey,
@@ -226,6 +236,9 @@ pub async fn delete_util(password: &str) -> AvailResult<String> {
         Err(e) => {}
     };
 
+    // stop watching the deleted account's transactions
+    reset_watched();
+
     // delete encrypted data
     drop_encrypted_data_table()?;
 
@@ -234,6 +247,15 @@ pub async fn delete_util(password: &str) -> AvailResult<String> {
 
     // delete tokens
     drop_tokens_table()?;
+    drop_token_registry_table()?;
+    drop_private_balances_table()?;
+    drop_nft_cache_tables()?;
+    drop_offline_executions_table()?;
+    drop_ans_cache_table()?;
+    drop_dapp_sessions_table()?;
+    drop_dapp_policies_tables()?;
+    drop_outbox_table()?;
+    drop_resilience_settings_table()?;
 
     // if backup delete server side storage
     if backup {
@@ -270,6 +292,9 @@ pub fn delete_local_for_recovery(password: &str) -> AvailResult<()> {
         Err(e) => {}
     };
 
+    // stop watching the deleted account's transactions
+    reset_watched();
+
     // delete encrypted data
     drop_encrypted_data_table()?;
 
@@ -278,6 +303,15 @@ pub fn delete_local_for_recovery(password: &str) -> AvailResult<()> {
 
     // delete tokens
     drop_tokens_table()?;
+    drop_token_registry_table()?;
+    drop_private_balances_table()?;
+    drop_nft_cache_tables()?;
+    drop_offline_executions_table()?;
+    drop_ans_cache_table()?;
+    drop_dapp_sessions_table()?;
+    drop_dapp_policies_tables()?;
+    drop_outbox_table()?;
+    drop_resilience_settings_table()?;
 
     Ok(())
 }
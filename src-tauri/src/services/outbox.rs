@@ -0,0 +1,236 @@
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use std::time::Duration;
+
+use crate::api::{
+    backend::backend,
+    client::SESSION,
+    encrypted_data::{delete_invalid_transactions_in, post_encrypted_records, synced, update_data},
+    user::create_user,
+};
+use crate::models::outbox::{OutboxAction, OutboxEntryStatus, OutboxStatus};
+use crate::services::local_storage::{
+    encrypted_data::{get_encrypted_data_by_id, update_encrypted_data_synced_on_by_id},
+    outbox::{
+        delete_outbox_entry, enqueue_outbox_action, get_outbox_status_raw,
+        get_pending_outbox_entries, update_outbox_entry_attempt,
+    },
+};
+
+use avail_common::{
+    errors::{AvailError, AvailErrorType, AvailResult},
+    models::{encrypted_data::EncryptedData, user::User},
+};
+
+/* --Offline First Outbox-- */
+
+const RETRY_BASE_SECONDS: i64 = 15;
+const RETRY_MAX_SECONDS: i64 = 600;
+/// How often the background worker tries to drain the outbox
+const DRAIN_INTERVAL_SECONDS: u64 = 30;
+
+static DRAIN_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+/// The server could not be reached, or there is no session with it to make the call with.
+/// Anything the server answered with is a rejection, retrying it would block the queue behind it.
+pub fn is_deferrable(error: &AvailError) -> bool {
+    match error.error_type {
+        AvailErrorType::Network | AvailErrorType::Unauthorized => true,
+        // calls made without a session fail validation before reaching the server
+        AvailErrorType::Validation => SESSION.get_session_token().is_none(),
+        _ => false,
+    }
+}
+
+fn retry_delay(attempts: u32) -> chrono::Duration {
+    let seconds =
+        RETRY_BASE_SECONDS.saturating_mul(2i64.saturating_pow(attempts.saturating_sub(1)));
+    chrono::Duration::seconds(seconds.min(RETRY_MAX_SECONDS))
+}
+
+async fn perform(action: &OutboxAction) -> AvailResult<()> {
+    match action {
+        OutboxAction::CreateUser(user) => {
+            create_user(User::new(
+                user.username.clone(),
+                user.address.clone(),
+                user.tag,
+                user.backup,
+            ))
+            .await?;
+        }
+        OutboxAction::PostData(records) => {
+            let ids = post_encrypted_records(records.clone()).await?;
+
+            ids.iter()
+                .map(|id| update_encrypted_data_synced_on_by_id(id))
+                .collect::<AvailResult<Vec<()>>>()?;
+        }
+        OutboxAction::UpdateData(ids) => {
+            // Sends the local state at the time of sending, not at the time of queueing
+            let encrypted_data = ids
+                .iter()
+                .map(|id| get_encrypted_data_by_id(id))
+                .collect::<AvailResult<Vec<EncryptedData>>>()?;
+
+            update_data(encrypted_data, ids.clone()).await?;
+        }
+        OutboxAction::SendTransactionMessage(record) => {
+            backend()?.send_transaction_message(record.clone()).await?;
+        }
+        OutboxAction::MarkSynced(ids) => {
+            synced(ids.clone()).await?;
+        }
+        OutboxAction::DeleteTransactionMessages(ids) => {
+            delete_invalid_transactions_in(ids.clone()).await?;
+        }
+    };
+
+    Ok(())
+}
+
+/// Sends an action to the Avail server, queueing it in the outbox when the server is unreachable.
+/// Returns false if the action was queued.
+pub async fn send_or_queue(action: OutboxAction) -> AvailResult<bool> {
+    if action.is_empty() {
+        return Ok(true);
+    }
+
+    // Nothing overtakes what is already queued, an account is registered before its backups
+    if get_outbox_status_raw()?.pending > 0 {
+        enqueue_outbox_action(&action)?;
+        return Ok(false);
+    }
+
+    match perform(&action).await {
+        Ok(_) => Ok(true),
+        Err(e) if is_deferrable(&e) => {
+            println!("Queueing {} for later: {}", action.kind(), e.internal_msg);
+            enqueue_outbox_action(&action)?;
+            Ok(false)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Sends queued actions in order, stopping at the first one that has to wait. Rejected entries are set
+/// aside as failed so the ones behind them still go out. `force` retries entries that are still backing off.
+pub async fn drain_outbox_raw(force: bool) -> AvailResult<OutboxStatus> {
+    let _guard = match DRAIN_LOCK.try_lock() {
+        Ok(guard) => guard,
+        Err(_) => return get_outbox_status_raw(),
+    };
+
+    for entry in get_pending_outbox_entries()? {
+        if !force && entry.next_attempt > Utc::now() {
+            break;
+        }
+
+        match perform(&entry.action).await {
+            Ok(_) => delete_outbox_entry(&entry.id)?,
+            Err(e) => {
+                let attempts = entry.attempts + 1;
+                let deferred = is_deferrable(&e);
+                let status = match deferred {
+                    true => OutboxEntryStatus::Pending,
+                    false => OutboxEntryStatus::Failed,
+                };
+
+                update_outbox_entry_attempt(
+                    &entry.id,
+                    attempts,
+                    Utc::now() + retry_delay(attempts),
+                    &e.internal_msg,
+                    status,
+                )?;
+
+                if deferred {
+                    break;
+                }
+            }
+        }
+    }
+
+    get_outbox_status_raw()
+}
+
+/// Drains the outbox in the background for as long as the app runs
+pub fn start_outbox_worker() {
+    tauri::async_runtime::spawn(async {
+        loop {
+            tokio::time::sleep(Duration::from_secs(DRAIN_INTERVAL_SECONDS)).await;
+
+            if let Err(e) = drain_outbox_raw(false).await {
+                println!("Error draining outbox: {}", e.internal_msg);
+            }
+        }
+    });
+}
+
+/// Retries everything queued now, e.g. after connectivity returns
+#[tauri::command(rename_all = "snake_case")]
+pub async fn drain_outbox() -> AvailResult<OutboxStatus> {
+    drain_outbox_raw(true).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_outbox_status() -> AvailResult<OutboxStatus> {
+    get_outbox_status_raw()
+}
+
+#[cfg(test)]
+mod outbox_tests {
+    use super::*;
+    use crate::api::backend::{clear_backend_service, set_backend_service, LocalBackend};
+    use crate::services::local_storage::outbox::drop_outbox_table;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_retry_delay_is_capped() {
+        assert_eq!(
+            retry_delay(1),
+            chrono::Duration::seconds(RETRY_BASE_SECONDS)
+        );
+        assert_eq!(
+            retry_delay(2),
+            chrono::Duration::seconds(RETRY_BASE_SECONDS * 2)
+        );
+        assert_eq!(
+            retry_delay(40),
+            chrono::Duration::seconds(RETRY_MAX_SECONDS)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_registration_queued_until_server_reachable() {
+        drop_outbox_table().unwrap();
+
+        let user = || {
+            User::new(
+                Some("Karp".to_string()),
+                "aleo1ckcdjd9wned6s9eqprf2km88znlmh2je03jg2ctxat5k4hllzuqq47j3zg".to_string(),
+                Some(1234),
+                false,
+            )
+        };
+
+        // queued behind an entry that is waiting, as if registration had failed offline
+        enqueue_outbox_action(&OutboxAction::CreateUser(user())).unwrap();
+        assert!(!send_or_queue(OutboxAction::CreateUser(user()))
+            .await
+            .unwrap());
+        assert_eq!(get_outbox_status_raw().unwrap().pending, 2);
+
+        // once the server is reachable the first registration goes through and the duplicate is rejected
+        set_backend_service(Arc::new(LocalBackend::in_memory().unwrap()));
+        let status = drain_outbox_raw(true).await.unwrap();
+        clear_backend_service();
+
+        // the rejected entry is set aside instead of holding back what is queued after it
+        assert_eq!(status.pending, 0);
+        assert_eq!(status.failed, 1);
+        assert!(status.last_error.is_some());
+
+        drop_outbox_table().unwrap();
+    }
+}
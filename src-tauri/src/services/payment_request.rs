@@ -0,0 +1,68 @@
+use snarkvm::prelude::{Network, Testnet3};
+use std::str::FromStr;
+
+use crate::models::payment_request::{PaymentPrivacy, PaymentRequest};
+use crate::models::transfer::TransferRequest;
+use crate::services::local_storage::persistent_storage::{get_address_string, get_network};
+
+use avail_common::{errors::AvailResult, models::network::SupportedNetworks};
+
+/* --Payment Requests-- */
+
+fn create_payment_request_raw<N: Network>(
+    amount: Option<u64>,
+    asset: Option<String>,
+    memo: Option<String>,
+    privacy: Option<PaymentPrivacy>,
+) -> AvailResult<String> {
+    let request = PaymentRequest::new(
+        get_address_string()?,
+        amount,
+        asset.unwrap_or_else(|| "credits".to_string()),
+        memo,
+        privacy.unwrap_or(PaymentPrivacy::Private),
+    );
+    request.validate::<N>()?;
+
+    request.to_uri()
+}
+
+/// Creates a payment request to this wallet, the URI is also the QR code payload
+#[tauri::command(rename_all = "snake_case")]
+pub fn create_payment_request(
+    amount: Option<u64>,
+    asset: Option<String>,
+    memo: Option<String>,
+    privacy: Option<PaymentPrivacy>,
+) -> AvailResult<String> {
+    let network = get_network()?;
+
+    match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => {
+            create_payment_request_raw::<Testnet3>(amount, asset, memo, privacy)
+        }
+        _ => create_payment_request_raw::<Testnet3>(amount, asset, memo, privacy),
+    }
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn parse_payment_request(uri: &str) -> AvailResult<PaymentRequest> {
+    let network = get_network()?;
+
+    match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => PaymentRequest::parse::<Testnet3>(uri),
+        _ => PaymentRequest::parse::<Testnet3>(uri),
+    }
+}
+
+/// Builds the transfer paying a request, ready to be passed to `transfer`
+#[tauri::command(rename_all = "snake_case")]
+pub fn payment_request_to_transfer(
+    uri: &str,
+    amount: Option<u64>,
+    from_private: bool,
+    fee_private: bool,
+    fee: u64,
+) -> AvailResult<TransferRequest> {
+    parse_payment_request(uri)?.to_transfer_request(amount, from_private, fee_private, fee)
+}
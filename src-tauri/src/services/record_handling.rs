@@ -1,5 +1,14 @@
+pub mod balances;
+pub mod confirmation;
 pub mod decrypt_transition;
+pub mod deploy;
+pub mod disclosure;
+pub mod execution;
+pub mod nfts;
+pub mod offline_signing;
+pub mod program_abi;
 pub mod records;
 pub mod sync;
 pub mod transfer;
 pub mod utils;
+pub mod watcher;
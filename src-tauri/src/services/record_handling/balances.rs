@@ -0,0 +1,410 @@
+use snarkvm::prelude::{
+    Address, Ciphertext, Group, Network, Plaintext, Scalar, Testnet3, ToBits, Uniform, ViewKey,
+};
+use std::str::FromStr;
+
+use crate::models::pointers::record::AvailRecord;
+use crate::models::storage::persistent::PersistentStorage;
+use crate::models::tokens::ReconciledBalance;
+use crate::models::wallet_connect::records::{GetRecordsRequest, RecordFilterType, RecordsFilter};
+use crate::services::local_storage::{
+    persistent_storage::get_network,
+    session::view::VIEWSESSION,
+    storage_api::records::get_record_pointers,
+    tokens::{get_balance, get_stored_tokens, if_token_exists, init_token, set_balance},
+};
+
+use super::utils::get_record_type_and_amount;
+
+use avail_common::{
+    errors::{AvailError, AvailErrorType, AvailResult},
+    models::network::SupportedNetworks,
+};
+
+/* --Balance Engine-- */
+
+pub fn init_private_balances_table() -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+    storage.execute_query(
+        "CREATE TABLE IF NOT EXISTS private_balances (
+            token_name TEXT PRIMARY KEY,
+            program_id TEXT NOT NULL,
+            balance_ciphertext TEXT NOT NULL,
+            nonce TEXT NOT NULL,
+            records_fingerprint TEXT NOT NULL
+        )",
+    )?;
+    Ok(())
+}
+
+pub fn drop_private_balances_table() -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+    match storage.execute_query("DROP TABLE IF EXISTS private_balances") {
+        Ok(r) => r,
+        Err(e) => match e.error_type {
+            AvailErrorType::NotFound => {}
+            _ => {
+                return Err(AvailError::new(
+                    AvailErrorType::Internal,
+                    e.internal_msg,
+                    "Error deleting private balances".to_string(),
+                ))
+            }
+        },
+    };
+
+    Ok(())
+}
+
+/// Gets the unspent record pointers of a token
+fn get_unspent_token_records<N: Network>(
+    token_name: &str,
+    program_id: &str,
+) -> AvailResult<Vec<AvailRecord<N>>> {
+    let filter = RecordsFilter::new(
+        vec![program_id.to_string()],
+        None,
+        RecordFilterType::Unspent,
+        Some(token_name.to_string()),
+    );
+    let get_records_request = GetRecordsRequest::new(None, Some(filter), None);
+    let (record_pointers, _ids) = get_record_pointers::<N>(get_records_request)?;
+
+    Ok(record_pointers
+        .into_iter()
+        .filter(|record| !record.metadata.spent)
+        .collect())
+}
+
+/// Identifies a set of records by their nonces so a cached balance is only reused for the same set
+pub fn records_fingerprint<N: Network>(records: &[AvailRecord<N>]) -> AvailResult<String> {
+    let mut nonces = records
+        .iter()
+        .map(|record| record.metadata.nonce.clone())
+        .collect::<Vec<String>>();
+    nonces.sort();
+
+    // BHP is stable across releases, unlike the std hasher, so stored fingerprints stay comparable
+    let input = format!("records:{}", nonces.join(","));
+
+    Ok(N::hash_bhp256(&input.as_bytes().to_bits_le())?.to_string())
+}
+
+/// Sums the amount held by each record
+fn sum_record_amounts<N: Network>(
+    records: &[AvailRecord<N>],
+    view_key: ViewKey<N>,
+) -> AvailResult<u64> {
+    let mut balance = 0u64;
+
+    for record_pointer in records.iter() {
+        let record = record_pointer.to_record()?;
+        let amount = get_record_type_and_amount::<N>(
+            record,
+            record_pointer.metadata.name.clone(),
+            view_key,
+        )?;
+
+        let amount = match amount.trim_end_matches("u64") {
+            "" => 0u64,
+            amount => amount.parse::<u64>()?,
+        };
+
+        balance = match balance.checked_add(amount) {
+            Some(balance) => balance,
+            None => {
+                return Err(AvailError::new(
+                    AvailErrorType::InvalidData,
+                    "Private balance overflow".to_string(),
+                    "Error computing private balance".to_string(),
+                ))
+            }
+        };
+    }
+
+    Ok(balance)
+}
+
+fn get_cached_balance<N: Network>(
+    token_name: &str,
+    fingerprint: &str,
+    view_key: ViewKey<N>,
+) -> AvailResult<Option<u64>> {
+    init_private_balances_table()?;
+
+    let storage = PersistentStorage::new()?;
+    let query = format!(
+        "SELECT balance_ciphertext, nonce, records_fingerprint FROM private_balances WHERE token_name='{}'",
+        token_name
+    );
+
+    let res = storage.get_all::<String>(&query, 3)?;
+    match res.get(0) {
+        Some(row) if row.len() == 3 && row[2] == fingerprint => {
+            let balance = Ciphertext::<N>::from_str(&row[0])?
+                .decrypt(view_key, Group::<N>::from_str(&row[1])?)?
+                .to_string();
+
+            Ok(Some(balance.trim_end_matches("u64").parse::<u64>()?))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn cache_balance<N: Network>(
+    token_name: &str,
+    program_id: &str,
+    balance: u64,
+    fingerprint: &str,
+    address: &Address<N>,
+) -> AvailResult<()> {
+    init_private_balances_table()?;
+
+    let rng = &mut rand::thread_rng();
+    let scalar = Scalar::<N>::rand(rng);
+    let nonce = N::g_scalar_multiply(&scalar);
+    let encrypted_balance =
+        Plaintext::<N>::from_str(&format!("{}u64", balance))?.encrypt(address, scalar)?;
+
+    let storage = PersistentStorage::new()?;
+    storage.save(
+        vec![
+            token_name.to_string(),
+            program_id.to_string(),
+            encrypted_balance.to_string(),
+            nonce.to_string(),
+            fingerprint.to_string(),
+        ],
+        "INSERT OR REPLACE INTO private_balances (token_name, program_id, balance_ciphertext, nonce, records_fingerprint) VALUES (?1, ?2, ?3, ?4, ?5)"
+            .to_string(),
+    )?;
+
+    Ok(())
+}
+
+/// Corrects the ARC20_tokens balance counter if it drifted from the balance derived from records
+fn reconcile_balance<N: Network>(
+    token_name: &str,
+    program_id: &str,
+    balance: u64,
+    records: usize,
+    view_key: ViewKey<N>,
+) -> AvailResult<ReconciledBalance> {
+    let stored_balance = get_balance::<N>(token_name, view_key)?
+        .trim_end_matches("u64")
+        .parse::<u64>()?;
+
+    if stored_balance != balance {
+        println!(
+            "Reconciling {} balance counter from {} to {}",
+            token_name, stored_balance, balance
+        );
+
+        let balance_str = format!("{}u64", balance);
+        if if_token_exists(token_name)? {
+            set_balance::<N>(token_name, &balance_str, view_key)?;
+        } else {
+            init_token::<N>(
+                token_name,
+                program_id,
+                &view_key.to_address().to_string(),
+                &balance_str,
+            )?;
+        }
+    }
+
+    Ok(ReconciledBalance::new(
+        token_name.to_string(),
+        program_id.to_string(),
+        balance,
+        stored_balance,
+        records,
+    ))
+}
+
+fn compute_private_balance_from<N: Network>(
+    token_name: &str,
+    program_id: &str,
+    records: &[AvailRecord<N>],
+    fingerprint: &str,
+    view_key: ViewKey<N>,
+) -> AvailResult<ReconciledBalance> {
+    let balance = sum_record_amounts::<N>(records, view_key)?;
+    cache_balance::<N>(
+        token_name,
+        program_id,
+        balance,
+        fingerprint,
+        &view_key.to_address(),
+    )?;
+
+    reconcile_balance::<N>(token_name, program_id, balance, records.len(), view_key)
+}
+
+/// Computes the private balance of a token from its unspent records, caches it and reconciles the stored counter
+pub fn compute_private_balance<N: Network>(
+    token_name: &str,
+    program_id: &str,
+) -> AvailResult<ReconciledBalance> {
+    let view_key = VIEWSESSION.get_instance::<N>()?;
+    let records = get_unspent_token_records::<N>(token_name, program_id)?;
+    let fingerprint = records_fingerprint(&records)?;
+
+    compute_private_balance_from::<N>(token_name, program_id, &records, &fingerprint, view_key)
+}
+
+/// Gets the private balance of a token, reusing the cached value while the unspent records are unchanged
+pub fn get_private_balance<N: Network>(token_name: &str, program_id: &str) -> AvailResult<u64> {
+    let view_key = VIEWSESSION.get_instance::<N>()?;
+    let records = get_unspent_token_records::<N>(token_name, program_id)?;
+    let fingerprint = records_fingerprint(&records)?;
+
+    if let Some(balance) = get_cached_balance::<N>(token_name, &fingerprint, view_key)? {
+        return Ok(balance);
+    }
+
+    Ok(
+        compute_private_balance_from::<N>(
+            token_name,
+            program_id,
+            &records,
+            &fingerprint,
+            view_key,
+        )?
+        .balance(),
+    )
+}
+
+pub fn recompute_balances_raw<N: Network>() -> AvailResult<Vec<ReconciledBalance>> {
+    get_stored_tokens()?
+        .iter()
+        .map(|token| compute_private_balance::<N>(token.token_name(), token.program_id()))
+        .collect::<AvailResult<Vec<ReconciledBalance>>>()
+}
+
+/// Recomputes the private balance of every stored token from its unspent records
+#[tauri::command(rename_all = "snake_case")]
+pub fn recompute_balances() -> AvailResult<Vec<ReconciledBalance>> {
+    let network = get_network()?;
+
+    match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => recompute_balances_raw::<Testnet3>(),
+        _ => recompute_balances_raw::<Testnet3>(),
+    }
+}
+
+#[cfg(test)]
+mod balances_tests {
+    use super::*;
+    use crate::api::chain_provider::{
+        mock_record_transaction, with_chain_provider, ChainProvider, MockChainProvider,
+    };
+    use crate::services::local_storage::storage_api::records::get_test_record_pointer;
+    use avail_common::models::constants::{TESTNET_ADDRESS, TESTNET_PRIVATE_KEY};
+    use snarkvm::prelude::PrivateKey;
+    use std::sync::Arc;
+
+    /// Broadcasts a credits record of `amount` to the mock chain and points to it
+    fn mock_credits_record(
+        api_client: &MockChainProvider<Testnet3>,
+        amount: u64,
+    ) -> AvailRecord<Testnet3> {
+        let owner = Address::<Testnet3>::from_str(TESTNET_ADDRESS).unwrap();
+        let (transaction, record) = mock_record_transaction::<Testnet3>(
+            &owner,
+            "credits.aleo",
+            "transfer_public_to_private",
+            "credits",
+            &format!("microcredits: {}u64.private", amount),
+        )
+        .unwrap();
+
+        let transition = transaction.transitions().next().unwrap().clone();
+        let (commitment, _) = transition.outputs()[0].clone().into_record().unwrap();
+        api_client
+            .transaction_broadcast(transaction.clone())
+            .unwrap();
+
+        let mut record_pointer = get_test_record_pointer();
+        record_pointer.pointer.transaction_id = transaction.id();
+        record_pointer.pointer.transition_id = *transition.id();
+        record_pointer.pointer.commitment = commitment.to_string();
+        record_pointer.metadata.nonce = record.nonce().to_string();
+
+        record_pointer
+    }
+
+    #[test]
+    fn test_records_fingerprint_ignores_order() {
+        let record = get_test_record_pointer();
+        let mut other_record = get_test_record_pointer();
+        other_record.metadata.nonce = "other_nonce".to_string();
+
+        let fingerprint = records_fingerprint(&[record.clone(), other_record.clone()]).unwrap();
+        let reversed_fingerprint = records_fingerprint(&[other_record, record.clone()]).unwrap();
+
+        assert_eq!(fingerprint, reversed_fingerprint);
+        assert_ne!(fingerprint, records_fingerprint(&[record]).unwrap());
+        assert!(records_fingerprint::<Testnet3>(&[]).is_ok());
+    }
+
+    #[test]
+    fn test_private_balance_cache_and_reconcile() {
+        let private_key = PrivateKey::<Testnet3>::from_str(TESTNET_PRIVATE_KEY).unwrap();
+        let view_key = ViewKey::<Testnet3>::try_from(&private_key).unwrap();
+        VIEWSESSION.set_view_session(&view_key.to_string()).unwrap();
+
+        let token_name = "balances_test.record";
+        let program_id = "credits.aleo";
+
+        // drift the stored counter away from the records
+        match if_token_exists(token_name).unwrap() {
+            true => set_balance::<Testnet3>(token_name, "1u64", view_key).unwrap(),
+            false => {
+                init_token::<Testnet3>(token_name, program_id, TESTNET_ADDRESS, "1u64").unwrap()
+            }
+        }
+
+        let api_client = MockChainProvider::<Testnet3>::new();
+        let records = vec![
+            mock_credits_record(&api_client, 100),
+            mock_credits_record(&api_client, 250),
+        ];
+        let fingerprint = records_fingerprint(&records).unwrap();
+
+        with_chain_provider::<Testnet3, _>(Arc::new(api_client), || {
+            // nonces are fresh, so nothing is cached for this set yet
+            assert_eq!(
+                get_cached_balance::<Testnet3>(token_name, &fingerprint, view_key).unwrap(),
+                None
+            );
+
+            let reconciled = compute_private_balance_from::<Testnet3>(
+                token_name,
+                program_id,
+                &records,
+                &fingerprint,
+                view_key,
+            )
+            .unwrap();
+
+            assert_eq!(reconciled.balance(), 350);
+            assert_eq!(reconciled.stored_balance(), 1);
+            assert_eq!(
+                get_balance::<Testnet3>(token_name, view_key).unwrap(),
+                "350u64".to_string()
+            );
+
+            // the same records hit the cache, a changed set misses it
+            assert_eq!(
+                get_cached_balance::<Testnet3>(token_name, &fingerprint, view_key).unwrap(),
+                Some(350)
+            );
+            let spent_fingerprint = records_fingerprint(&records[..1]).unwrap();
+            assert_eq!(
+                get_cached_balance::<Testnet3>(token_name, &spent_fingerprint, view_key).unwrap(),
+                None
+            );
+        });
+    }
+}
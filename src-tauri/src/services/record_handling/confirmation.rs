@@ -0,0 +1,258 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use snarkvm::prelude::*;
+use std::time::{Duration, Instant};
+
+use crate::api::{
+    aleo_client::setup_client,
+    chain_provider::ChainClient,
+    resilience::{backoff_delay, is_retryable, resilience_settings},
+};
+use crate::helpers::utils::get_timestamp_from_i64;
+
+use avail_common::{
+    errors::{AvailError, AvailErrorType, AvailResult},
+    models::encrypted_data::TransactionState,
+};
+
+/* --Transaction Confirmation-- */
+
+/// How long to wait for a broadcast transaction before leaving it unconfirmed
+pub const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(180);
+/// Shortest wait between polls, blocks are not produced faster than this
+const MIN_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How the network settled a transaction
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfirmationOutcome {
+    Accepted,
+    /// Included with only its fee charged
+    Rejected,
+    /// Left out of the block, nothing was charged
+    Aborted,
+}
+
+impl ConfirmationOutcome {
+    pub fn to_transaction_state(&self) -> TransactionState {
+        match self {
+            ConfirmationOutcome::Accepted => TransactionState::Confirmed,
+            ConfirmationOutcome::Rejected => TransactionState::Rejected,
+            ConfirmationOutcome::Aborted => TransactionState::Aborted,
+        }
+    }
+}
+
+/// What finalize did for a transaction
+#[derive(Debug, Clone, PartialEq)]
+pub enum FinalizeOutcome<N: Network> {
+    /// Finalize ran, with the mapping updates it made
+    Applied(Vec<FinalizeOperation<N>>),
+    /// Finalize failed and its updates were reverted, only the fee was charged
+    Failed,
+    /// The transaction never reached finalize
+    NotRun,
+}
+
+/// A transaction as settled in a block
+pub struct Confirmation<N: Network> {
+    pub block_height: u32,
+    pub timestamp: DateTime<Local>,
+    pub outcome: ConfirmationOutcome,
+    pub finalize: FinalizeOutcome<N>,
+    /// Transitions that were applied, only the fee transition for deployments and rejections
+    pub transitions: Vec<Transition<N>>,
+    /// Id the fee was confirmed under when the transaction was rejected
+    pub fee_transaction_id: Option<N::TransactionID>,
+    pub rejected_execution: Option<Execution<N>>,
+    pub fee: Option<f64>,
+}
+
+fn fee_transitions<N: Network>(confirmed: &ConfirmedTransaction<N>) -> Vec<Transition<N>> {
+    match confirmed.transaction().fee_transition() {
+        Some(fee_transition) => vec![fee_transition.transition().clone()],
+        None => vec![],
+    }
+}
+
+fn to_confirmation<N: Network>(
+    block_height: u32,
+    timestamp: DateTime<Local>,
+    confirmed: &ConfirmedTransaction<N>,
+) -> AvailResult<Confirmation<N>> {
+    let fee = Some(*confirmed.transaction().fee_amount()? as f64 / 1000000.0);
+
+    let (outcome, finalize, transitions, fee_transaction_id, rejected_execution) = match confirmed {
+        ConfirmedTransaction::<N>::AcceptedDeploy(_, _, _) => (
+            ConfirmationOutcome::Accepted,
+            FinalizeOutcome::Applied(confirmed.finalize_operations().to_vec()),
+            fee_transitions(confirmed),
+            None,
+            None,
+        ),
+        ConfirmedTransaction::<N>::AcceptedExecute(_, _, _) => (
+            ConfirmationOutcome::Accepted,
+            FinalizeOutcome::Applied(confirmed.finalize_operations().to_vec()),
+            confirmed.transitions().cloned().collect::<Vec<_>>(),
+            None,
+            None,
+        ),
+        ConfirmedTransaction::<N>::RejectedDeploy(_, fee_tx, _, _) => (
+            ConfirmationOutcome::Rejected,
+            FinalizeOutcome::Failed,
+            fee_transitions(confirmed),
+            Some(fee_tx.id()),
+            None,
+        ),
+        ConfirmedTransaction::<N>::RejectedExecute(_, fee_tx, rejected, _) => (
+            ConfirmationOutcome::Rejected,
+            FinalizeOutcome::Failed,
+            confirmed.transitions().cloned().collect::<Vec<_>>(),
+            Some(fee_tx.id()),
+            rejected.execution().cloned(),
+        ),
+    };
+
+    Ok(Confirmation {
+        block_height,
+        timestamp,
+        outcome,
+        finalize,
+        transitions,
+        fee_transaction_id,
+        rejected_execution,
+        fee,
+    })
+}
+
+/// Looks a transaction up by id, None while it is unconfirmed
+pub fn check_confirmation<N: Network>(
+    api_client: &ChainClient<N>,
+    transaction_id: N::TransactionID,
+) -> AvailResult<Option<Confirmation<N>>> {
+    let block_hash = match api_client.find_block_hash(transaction_id)? {
+        Some(block_hash) => block_hash,
+        None => return Ok(None),
+    };
+
+    let block = api_client.get_block_by_hash(block_hash)?;
+    let timestamp = get_timestamp_from_i64(block.timestamp())?;
+
+    if block.aborted_transaction_ids().contains(&transaction_id) {
+        return Ok(Some(Confirmation {
+            block_height: block.height(),
+            timestamp,
+            outcome: ConfirmationOutcome::Aborted,
+            finalize: FinalizeOutcome::NotRun,
+            transitions: vec![],
+            fee_transaction_id: None,
+            rejected_execution: None,
+            fee: None,
+        }));
+    }
+
+    let confirmed = match api_client.get_confirmed_transaction(transaction_id)? {
+        Some(confirmed) => confirmed,
+        // rejected transactions are confirmed under the id of their fee transaction
+        None => match block.transactions().iter().find(|confirmed| {
+            confirmed.to_unconfirmed_transaction_id().ok() == Some(transaction_id)
+        }) {
+            Some(confirmed) => confirmed.clone(),
+            None => {
+                return Err(AvailError::new(
+                    AvailErrorType::Node,
+                    format!(
+                        "Transaction {} not found in block {}",
+                        transaction_id, block_hash
+                    ),
+                    "Error finding the transaction on chain.".to_string(),
+                ))
+            }
+        },
+    };
+
+    Ok(Some(to_confirmation(
+        block.height(),
+        timestamp,
+        &confirmed,
+    )?))
+}
+
+/// Polls for the transaction until it is confirmed or `timeout` passes
+pub fn wait_for_confirmation<N: Network>(
+    transaction_id: N::TransactionID,
+    timeout: Duration,
+) -> AvailResult<Confirmation<N>> {
+    let api_client = setup_client::<N>()?;
+    let settings = resilience_settings();
+
+    let start_time = Instant::now();
+    let mut polls: u32 = 0;
+
+    println!("Waiting for transaction {} to confirm", transaction_id);
+    while start_time.elapsed() < timeout {
+        match check_confirmation(&api_client, transaction_id) {
+            Ok(Some(confirmation)) => return Ok(confirmation),
+            Ok(None) => {}
+            Err(e) if is_retryable(&e) => {
+                println!("Error checking confirmation: {}", e.internal_msg)
+            }
+            Err(e) => return Err(e),
+        }
+
+        std::thread::sleep(backoff_delay(polls, &settings).max(MIN_POLL_INTERVAL));
+        polls += 1;
+    }
+
+    Err(AvailError::new(
+        AvailErrorType::NotFound,
+        "Transaction is unconfirmed, this might be due to issues with the Aleo network."
+            .to_string(),
+        "Transaction is unconfirmed, this might be due to issues with the Aleo network."
+            .to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod confirmation_tests {
+    use super::*;
+    use crate::api::chain_provider::MockChainProvider;
+    use std::sync::Arc;
+
+    fn genesis_chain() -> (ChainClient<Testnet3>, Block<Testnet3>) {
+        let genesis = Block::<Testnet3>::from_bytes_le(Testnet3::genesis_bytes()).unwrap();
+        let provider = MockChainProvider::<Testnet3>::new();
+        provider.push_block(genesis.clone());
+
+        (Arc::new(provider), genesis)
+    }
+
+    #[test]
+    fn test_accepted_transaction_confirmed_by_id() {
+        let (api_client, genesis) = genesis_chain();
+        let transaction_id = *genesis.transaction_ids().next().unwrap();
+
+        let confirmation = check_confirmation(&api_client, transaction_id)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(confirmation.block_height, 0);
+        assert_eq!(confirmation.outcome, ConfirmationOutcome::Accepted);
+        assert_eq!(
+            confirmation.outcome.to_transaction_state(),
+            TransactionState::Confirmed
+        );
+        assert!(matches!(confirmation.finalize, FinalizeOutcome::Applied(_)));
+        assert!(!confirmation.transitions.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_transaction_is_unconfirmed() {
+        let (api_client, _) = genesis_chain();
+        let transaction_id = <Testnet3 as Network>::TransactionID::default();
+
+        assert!(check_confirmation(&api_client, transaction_id)
+            .unwrap()
+            .is_none());
+    }
+}
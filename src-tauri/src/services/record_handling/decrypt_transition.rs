@@ -23,7 +23,10 @@ use crate::{
         },
     },
 };
-use avail_common::{errors::AvailResult, models::encrypted_data::EncryptedData};
+use avail_common::{
+    errors::{AvailError, AvailErrorType, AvailResult},
+    models::encrypted_data::EncryptedData,
+};
 
 pub struct DecryptTransition {}
 
@@ -328,6 +331,75 @@ impl DecryptTransition {
         Ok(transition_output)
     }
 
+    // The transition view key, it decrypts the private inputs and outputs of a single transition only
+    pub fn transition_view_key<N: Network>(view_key: ViewKey<N>, tpk: &Group<N>) -> Field<N> {
+        let scalar = *view_key;
+        (*tpk * scalar).to_x_coordinate()
+    }
+
+    // Decrypts a transition's private inputs and outputs with a disclosed transition view key, records stay encrypted
+    pub fn decrypt_with_transition_view_key<N: Network>(
+        tvk: Field<N>,
+        transition: &Transition<N>,
+    ) -> AvailResult<(Vec<String>, Vec<String>)> {
+        if *transition.tcm() != N::hash_psd2(&[tvk])? {
+            return Err(AvailError::new(
+                AvailErrorType::Validation,
+                format!(
+                    "Transition view key does not match the commitment of {}",
+                    transition.id()
+                ),
+                "The transition view key does not belong to this transition.".to_string(),
+            ));
+        }
+
+        let function_id = N::hash_bhp1024(
+            &(
+                U16::<N>::new(N::ID),
+                transition.program_id().name(),
+                transition.program_id().network(),
+                transition.function_name(),
+            )
+                .to_bits_le(),
+        )?;
+
+        let mut decrypted_inputs: Vec<String> = vec![];
+        let mut decrypted_outputs: Vec<String> = vec![];
+
+        for (index, input) in transition.inputs().iter().enumerate() {
+            match input {
+                Input::Private(_id, Some(ciphertext)) => {
+                    let index_field = Field::from_u16(u16::try_from(index)?);
+                    let input_view_key = N::hash_psd4(&[function_id, tvk, index_field])?;
+                    decrypted_inputs
+                        .push(ciphertext.decrypt_symmetric(input_view_key)?.to_string());
+                }
+                Input::Public(_, Some(plaintext)) | Input::Constant(_, Some(plaintext)) => {
+                    decrypted_inputs.push(plaintext.to_string())
+                }
+                _ => decrypted_inputs.push(input.to_string()),
+            }
+        }
+
+        let num_inputs = transition.inputs().len();
+        for (index, output) in transition.outputs().iter().enumerate() {
+            match output {
+                Output::Private(_id, Some(ciphertext)) => {
+                    let index_field = Field::from_u16(u16::try_from(num_inputs + index)?);
+                    let output_view_key = N::hash_psd4(&[function_id, tvk, index_field])?;
+                    decrypted_outputs
+                        .push(ciphertext.decrypt_symmetric(output_view_key)?.to_string());
+                }
+                Output::Public(_, Some(plaintext)) | Output::Constant(_, Some(plaintext)) => {
+                    decrypted_outputs.push(plaintext.to_string())
+                }
+                _ => decrypted_outputs.push(output.to_string()),
+            }
+        }
+
+        Ok((decrypted_inputs, decrypted_outputs))
+    }
+
     pub fn decrypt_inputs_outputs<N: Network>(
         view_key: ViewKey<N>,
         transition: &Transition<N>,
@@ -574,6 +646,24 @@ mod tests {
         // }
     }
 
+    #[test]
+    fn test_decrypt_with_transition_view_key() {
+        let private_key = PrivateKey::<Testnet3>::from_str(TESTNET_PRIVATE_KEY).unwrap();
+        let view_key = ViewKey::<Testnet3>::try_from(private_key).unwrap();
+        let transition: Transition<Testnet3> = serde_json::from_str(TRANSITION2).unwrap();
+
+        let tvk = DecryptTransition::transition_view_key(view_key, transition.tpk());
+        let (inputs, _outputs) =
+            DecryptTransition::decrypt_with_transition_view_key(tvk, &transition).unwrap();
+        assert!(!inputs[0].starts_with("ciphertext"));
+
+        let other_view_key = ViewKey::<Testnet3>::from_str(INCORRECT_VIEW_KEY).unwrap();
+        let other_tvk = DecryptTransition::transition_view_key(other_view_key, transition.tpk());
+        assert!(
+            DecryptTransition::decrypt_with_transition_view_key(other_tvk, &transition).is_err()
+        );
+    }
+
     fn test_decrypt_ciphertext_input() {
         let view_key = ViewKey::<Testnet3>::from_str(VIEW_KEY).unwrap();
 
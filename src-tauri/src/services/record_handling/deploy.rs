@@ -0,0 +1,315 @@
+use snarkvm::circuit::{Aleo, Environment};
+use snarkvm::ledger::block::Deployment;
+use snarkvm::prelude::{Network, Program, ProgramID};
+use snarkvm::synthesizer::deployment_cost;
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use super::{
+    execution::execution_vm, records::largest_credits_record_amount,
+    utils::get_public_token_balance,
+};
+use crate::api::chain_provider::ChainProvider;
+use crate::models::wallet_connect::deploy::{DeployRequest, DeploymentCost};
+
+use avail_common::errors::{AvailError, AvailErrorType, AvailResult};
+
+/* --Deployment Preparation-- */
+
+/// Minimum deployment cost in microcredits, split the way the network charges it.
+/// This network version charges for storage and the namespace only, synthesis is not priced.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeploymentCostBreakdown {
+    pub storage: u64,
+    pub namespace: u64,
+}
+
+impl DeploymentCostBreakdown {
+    pub fn minimum(&self) -> u64 {
+        self.storage + self.namespace
+    }
+}
+
+/// A deployment that passed validation and is ready to be owned, paid for and broadcast
+pub struct PreparedDeployment<N: Network> {
+    pub program: Program<N>,
+    pub imports: Vec<Program<N>>,
+    pub deployment: Deployment<N>,
+    pub cost: DeploymentCostBreakdown,
+    pub minimum_cost: u64,
+    pub fee: u64,
+}
+
+impl<N: Network> PreparedDeployment<N> {
+    /// Fee paid on top of the minimum deployment cost
+    pub fn priority_fee(&self) -> u64 {
+        self.fee - self.minimum_cost
+    }
+
+    /// Cost breakdown in credits against the balance the fee would be paid from
+    pub fn to_cost(&self, fee_private: bool, available: u64) -> DeploymentCost {
+        DeploymentCost {
+            program_id: self.program.id().to_string(),
+            imports: self
+                .imports
+                .iter()
+                .map(|import| import.id().to_string())
+                .collect(),
+            storage_cost: self.cost.storage as f64 / 1000000.0,
+            namespace_cost: self.cost.namespace as f64 / 1000000.0,
+            minimum_cost: self.minimum_cost as f64 / 1000000.0,
+            fee: self.fee as f64 / 1000000.0,
+            fee_private,
+            available: available as f64 / 1000000.0,
+            sufficient: available >= self.fee,
+        }
+    }
+}
+
+fn parse_program<N: Network>(source: &str) -> AvailResult<Program<N>> {
+    match Program::<N>::from_str(source) {
+        Ok(program) => Ok(program),
+        Err(e) => Err(AvailError::new(
+            AvailErrorType::InvalidData,
+            format!("Program string parsing failure: {}", e),
+            "Program string parsing failure".to_string(),
+        )),
+    }
+}
+
+/// Fetches a program from chain, returns None if it has not been deployed
+pub fn get_deployed_program<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+    program_id: &ProgramID<N>,
+) -> AvailResult<Option<Program<N>>> {
+    api_client.get_program(program_id)
+}
+
+/// Rejects deployments of program ids that already exist on chain
+pub fn ensure_not_deployed<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+    program_id: &ProgramID<N>,
+) -> AvailResult<()> {
+    if get_deployed_program::<N>(api_client, program_id)?.is_some() {
+        return Err(AvailError::new(
+            AvailErrorType::Validation,
+            format!("Program {} already exists on chain", program_id),
+            format!("The program '{}' is already deployed.", program_id),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Resolves every import of a program in dependency order, provided sources must match the deployed programs
+pub fn resolve_imports<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+    program: &Program<N>,
+    provided: &[Program<N>],
+) -> AvailResult<Vec<Program<N>>> {
+    let mut resolved: Vec<Program<N>> = vec![];
+    let mut visited: HashSet<ProgramID<N>> = HashSet::new();
+
+    resolve_imports_inner::<N>(api_client, program, provided, &mut visited, &mut resolved)?;
+
+    Ok(resolved)
+}
+
+fn resolve_imports_inner<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+    program: &Program<N>,
+    provided: &[Program<N>],
+    visited: &mut HashSet<ProgramID<N>>,
+    resolved: &mut Vec<Program<N>>,
+) -> AvailResult<()> {
+    for import_id in program.imports().keys() {
+        if import_id.to_string() == "credits.aleo" || !visited.insert(*import_id) {
+            continue;
+        }
+
+        let deployed = match get_deployed_program::<N>(api_client, import_id)? {
+            Some(deployed) => deployed,
+            None => {
+                return Err(AvailError::new(
+                    AvailErrorType::Validation,
+                    format!("Import {} is not deployed", import_id),
+                    format!("The import '{}' must be deployed first.", import_id),
+                ))
+            }
+        };
+
+        if let Some(local) = provided.iter().find(|local| local.id() == import_id) {
+            if local.to_string() != deployed.to_string() {
+                return Err(AvailError::new(
+                    AvailErrorType::Validation,
+                    format!("Import {} does not match the deployed program", import_id),
+                    format!(
+                        "The import '{}' differs from the deployed program.",
+                        import_id
+                    ),
+                ));
+            }
+        }
+
+        resolve_imports_inner::<N>(api_client, &deployed, provided, visited, resolved)?;
+        resolved.push(deployed);
+    }
+
+    Ok(())
+}
+
+/// Picks the fee to pay, an override below the minimum deployment cost is rejected
+pub fn deployment_fee(minimum_cost: u64, fee_override: Option<f64>) -> AvailResult<u64> {
+    let fee_override = match fee_override {
+        Some(fee_override) => (fee_override * 1000000.0) as u64,
+        None => return Ok(minimum_cost),
+    };
+
+    if fee_override < minimum_cost {
+        return Err(AvailError::new(
+            AvailErrorType::Validation,
+            format!(
+                "Fee override {} is below the minimum deployment cost {}",
+                fee_override, minimum_cost
+            ),
+            format!(
+                "The fee must be at least {} credits.",
+                minimum_cost as f64 / 1000000.0
+            ),
+        ));
+    }
+
+    Ok(fee_override)
+}
+
+/// Balance a fee can be paid from in microcredits, the largest unspent record when paying privately
+pub fn available_fee_balance<N: Network>(fee_private: bool) -> AvailResult<u64> {
+    match fee_private {
+        true => largest_credits_record_amount::<N>(),
+        false => Ok((get_public_token_balance::<N>("credits")? * 1000000.0) as u64),
+    }
+}
+
+/// Rejects a fee the chosen balance cannot cover, so no time is spent proving a deployment that would fail
+pub fn ensure_fee_balance(fee: u64, available: u64, fee_private: bool) -> AvailResult<()> {
+    if available >= fee {
+        return Ok(());
+    }
+
+    let (internal, external) = match fee_private {
+        true => (
+            "No credits record covers the deployment fee",
+            "No single private credits record can pay the",
+        ),
+        false => (
+            "Public balance does not cover the deployment fee",
+            "Your public balance is too low to pay the",
+        ),
+    };
+
+    Err(AvailError::new(
+        AvailErrorType::Validation,
+        format!("{}: {} < {}", internal, available, fee),
+        format!(
+            "{} deployment fee of {} credits.",
+            external,
+            fee as f64 / 1000000.0
+        ),
+    ))
+}
+
+/// Validates a deploy request, resolves its imports, synthesizes the deployment and computes its cost,
+/// nothing here needs the private key
+pub fn prepare_deployment<N: Network, A: Aleo + Environment<Network = N>>(
+    request: &DeployRequest,
+    program_id: &str,
+    api_client: &dyn ChainProvider<N>,
+) -> AvailResult<PreparedDeployment<N>> {
+    let program = parse_program::<N>(request.program())?;
+
+    if !program_id.is_empty() && program.id().to_string() != program_id {
+        return Err(AvailError::new(
+            AvailErrorType::InvalidData,
+            format!(
+                "Requested program id {} does not match program {}",
+                program_id,
+                program.id()
+            ),
+            "Program id does not match the program source".to_string(),
+        ));
+    }
+
+    ensure_not_deployed::<N>(api_client, program.id())?;
+
+    let provided = request
+        .imports()
+        .iter()
+        .map(|import| parse_program::<N>(import))
+        .collect::<AvailResult<Vec<Program<N>>>>()?;
+
+    let imports = resolve_imports::<N>(api_client, &program, &provided)?;
+
+    let vm = execution_vm::<N>(&imports)?;
+    let deployment = vm
+        .process()
+        .read()
+        .deploy::<A, _>(&program, &mut rand::thread_rng())?;
+
+    let (minimum_cost, (storage, namespace)) = deployment_cost(&deployment)?;
+    let cost = DeploymentCostBreakdown { storage, namespace };
+
+    let fee = deployment_fee(minimum_cost, request.fee_override())?;
+
+    Ok(PreparedDeployment {
+        program,
+        imports,
+        deployment,
+        cost,
+        minimum_cost,
+        fee,
+    })
+}
+
+#[cfg(test)]
+mod deploy_tests {
+    use super::*;
+    use crate::api::aleo_client::setup_client;
+    use snarkvm::prelude::Testnet3;
+
+    #[test]
+    fn test_deployment_fee() {
+        assert_eq!(deployment_fee(1000000, None).unwrap(), 1000000);
+        assert_eq!(deployment_fee(1000000, Some(2.5)).unwrap(), 2500000);
+        assert!(deployment_fee(1000000, Some(0.5)).is_err());
+    }
+
+    #[test]
+    fn test_cost_breakdown() {
+        let cost = DeploymentCostBreakdown {
+            storage: 1200000,
+            namespace: 1000000,
+        };
+
+        assert_eq!(cost.minimum(), 2200000);
+    }
+
+    #[test]
+    fn test_ensure_fee_balance() {
+        assert!(ensure_fee_balance(1000000, 1000000, false).is_ok());
+
+        let public = ensure_fee_balance(1000000, 999999, false).unwrap_err();
+        assert_eq!(public.error_type, AvailErrorType::Validation);
+        assert!(public.external_msg.contains("public balance"));
+
+        let private = ensure_fee_balance(1000000, 0, true).unwrap_err();
+        assert!(private.external_msg.contains("private credits record"));
+    }
+
+    #[test]
+    fn test_ensure_not_deployed() {
+        let api_client = setup_client::<Testnet3>().unwrap();
+        let credits = ProgramID::<Testnet3>::from_str("credits.aleo").unwrap();
+
+        assert!(ensure_not_deployed::<Testnet3>(&api_client, &credits).is_err());
+    }
+}
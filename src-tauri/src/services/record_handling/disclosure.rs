@@ -0,0 +1,523 @@
+use snarkvm::prelude::{
+    Field, Network, Output, Plaintext, Record, Testnet3, Transaction, Transition, ValueType,
+    ViewKey,
+};
+use std::str::FromStr;
+
+use super::decrypt_transition::DecryptTransition;
+use crate::api::aleo_client::setup_client;
+use crate::api::chain_provider::ChainProvider;
+use crate::models::disclosure::{
+    DisclosedRecord, DisclosedTransition, DisclosurePackage, DisclosureScope,
+    DisclosureVerification, VerifiedTransition, DISCLOSURE_VERSION,
+};
+use crate::models::pointers::transition::TransitionPointer;
+use crate::services::local_storage::{
+    encrypted_data::get_encrypted_data_by_id, persistent_storage::get_network,
+    session::view::VIEWSESSION, storage_api::transaction::get_transaction_pointer,
+};
+
+use avail_common::{
+    errors::{AvailError, AvailErrorType, AvailResult},
+    models::{encrypted_data::EncryptedDataTypeCommon, network::SupportedNetworks},
+};
+
+/* --Selective Disclosure-- */
+
+fn invalid_package(reason: String) -> AvailError {
+    AvailError::new(
+        AvailErrorType::Validation,
+        reason.clone(),
+        format!("Disclosure could not be verified: {}", reason),
+    )
+}
+
+fn get_transaction<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+    transaction_id: N::TransactionID,
+) -> AvailResult<Transaction<N>> {
+    match api_client.get_transaction(transaction_id) {
+        Ok(transaction) => Ok(transaction),
+        Err(e) => Err(AvailError::new(
+            AvailErrorType::Node,
+            format!("Error fetching transaction {}: {}", transaction_id, e),
+            "Transaction not found".to_string(),
+        )),
+    }
+}
+
+fn find_transition<N: Network>(
+    transaction: &Transaction<N>,
+    transition_id: &N::TransitionID,
+) -> AvailResult<Transition<N>> {
+    match transaction
+        .transitions()
+        .find(|transition| transition.id() == transition_id)
+    {
+        Some(transition) => Ok(transition.clone()),
+        None => Err(invalid_package(format!(
+            "Transition {} is not part of transaction {}",
+            transition_id,
+            transaction.id()
+        ))),
+    }
+}
+
+/// The on-chain transaction and transitions a stored transaction or transition pointer refers to
+fn pointer_transitions<N: Network>(
+    id: &str,
+) -> AvailResult<(N::TransactionID, Vec<N::TransitionID>)> {
+    let encrypted_data = get_encrypted_data_by_id(id)?;
+
+    match encrypted_data.flavour {
+        EncryptedDataTypeCommon::Transition => {
+            let view_key = VIEWSESSION.get_instance::<N>()?;
+            let transition: TransitionPointer<N> = encrypted_data
+                .to_enrypted_struct::<N>()?
+                .decrypt(view_key)?;
+
+            Ok((transition.transaction_id, vec![transition.id]))
+        }
+        EncryptedDataTypeCommon::Transaction => {
+            let transaction = get_transaction_pointer::<N>(id)?;
+
+            let transaction_id = match transaction.transaction_id() {
+                Some(transaction_id) => transaction_id,
+                None => {
+                    return Err(AvailError::new(
+                        AvailErrorType::Validation,
+                        format!("Transaction pointer {} has no transaction id", id),
+                        "Only transactions that reached the chain can be disclosed.".to_string(),
+                    ))
+                }
+            };
+
+            let transitions = transaction
+                .transitions()
+                .iter()
+                .map(|transition| transition.transition_id())
+                .collect::<Vec<N::TransitionID>>();
+
+            Ok((transaction_id, transitions))
+        }
+        _ => Err(AvailError::new(
+            AvailErrorType::InvalidData,
+            format!("Encrypted data {} is not a transaction or transition", id),
+            "Only transactions and transitions can be disclosed.".to_string(),
+        )),
+    }
+}
+
+/// Reveals a transition by its transition view key or by the record view keys of the records the owner received
+pub fn disclose_transition<N: Network>(
+    view_key: ViewKey<N>,
+    transition: &Transition<N>,
+    scope: DisclosureScope,
+) -> AvailResult<DisclosedTransition> {
+    let (transition_view_key, records) = match scope {
+        DisclosureScope::TransitionViewKey => {
+            if !DecryptTransition::owns_transition(view_key, *transition.tpk(), *transition.tcm())?
+            {
+                return Err(AvailError::new(
+                    AvailErrorType::Validation,
+                    format!(
+                        "Transition {} was not executed by this wallet",
+                        transition.id()
+                    ),
+                    "Only transitions you executed can be disclosed with their view key."
+                        .to_string(),
+                ));
+            }
+
+            let tvk = DecryptTransition::transition_view_key(view_key, transition.tpk());
+            (Some(tvk.to_string()), vec![])
+        }
+        DisclosureScope::Records => {
+            let mut records = vec![];
+
+            for (index, output) in transition.outputs().iter().enumerate() {
+                if let Output::Record(_commitment, _checksum, Some(ciphertext)) = output {
+                    if !ciphertext.is_owner(&view_key) {
+                        continue;
+                    }
+
+                    let record_view_key = (*ciphertext.nonce() * *view_key).to_x_coordinate();
+                    let plaintext = ciphertext.decrypt(&view_key)?;
+
+                    records.push(DisclosedRecord::new(
+                        index,
+                        record_view_key.to_string(),
+                        plaintext.to_string(),
+                    ));
+                }
+            }
+
+            (None, records)
+        }
+    };
+
+    Ok(DisclosedTransition::new(
+        transition.id().to_string(),
+        transition.program_id().to_string(),
+        transition.function_name().to_string(),
+        transition_view_key,
+        records,
+    ))
+}
+
+pub fn create_disclosure_raw<N: Network>(
+    id: &str,
+    scope: DisclosureScope,
+) -> AvailResult<DisclosurePackage> {
+    let view_key = VIEWSESSION.get_instance::<N>()?;
+    let api_client = setup_client::<N>()?;
+
+    let (transaction_id, transition_ids) = pointer_transitions::<N>(id)?;
+    let transaction = get_transaction::<N>(&api_client, transaction_id)?;
+
+    let mut transitions = vec![];
+    for transition_id in transition_ids.iter() {
+        let transition = find_transition::<N>(&transaction, transition_id)?;
+        let disclosed = disclose_transition::<N>(view_key, &transition, scope)?;
+
+        if scope == DisclosureScope::Records && disclosed.records().is_empty() {
+            continue;
+        }
+
+        transitions.push(disclosed);
+    }
+
+    if transitions.is_empty() {
+        return Err(AvailError::new(
+            AvailErrorType::Validation,
+            format!("Nothing to disclose for {}", id),
+            "You did not receive any records in this transaction.".to_string(),
+        ));
+    }
+
+    Ok(DisclosurePackage::new(
+        N::ID,
+        transaction_id.to_string(),
+        scope,
+        transitions,
+    ))
+}
+
+/// Decrypts each disclosed record with its record view key and checks it against the on-chain commitment,
+/// any key decrypts to some record so only the commitment shows it is the one that was created
+fn verify_records<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+    transition: &Transition<N>,
+    records: &[DisclosedRecord],
+) -> AvailResult<Vec<String>> {
+    let program = api_client.require_program(transition.program_id())?;
+    let function = program.get_function(transition.function_name())?;
+
+    records
+        .iter()
+        .map(|disclosed| {
+            let not_a_record = || {
+                invalid_package(format!(
+                    "Output {} of transition {} is not a record",
+                    disclosed.index(),
+                    transition.id()
+                ))
+            };
+
+            let (commitment, ciphertext) = match transition.outputs().get(disclosed.index()) {
+                Some(Output::Record(commitment, _checksum, Some(ciphertext))) => {
+                    (commitment, ciphertext)
+                }
+                _ => return Err(not_a_record()),
+            };
+
+            let record_name = match function
+                .outputs()
+                .get(disclosed.index())
+                .map(|output| output.value_type())
+            {
+                Some(ValueType::Record(record_name)) => record_name,
+                _ => return Err(not_a_record()),
+            };
+
+            let record_view_key = Field::<N>::from_str(disclosed.record_view_key())?;
+            let decrypted = ciphertext
+                .decrypt_symmetric_unchecked(&record_view_key)
+                .map_err(|_| {
+                    invalid_package(format!(
+                        "Record view key of record {} of transition {} is invalid",
+                        disclosed.index(),
+                        transition.id()
+                    ))
+                })?;
+            let claimed = Record::<N, Plaintext<N>>::from_str(disclosed.plaintext())?;
+
+            let matches_commitment =
+                decrypted.to_commitment(transition.program_id(), record_name)? == *commitment;
+
+            match matches_commitment && decrypted == claimed {
+                true => Ok(decrypted.to_string()),
+                false => Err(invalid_package(format!(
+                    "Record {} of transition {} does not match the on-chain commitment",
+                    disclosed.index(),
+                    transition.id()
+                ))),
+            }
+        })
+        .collect()
+}
+
+/// Checks every disclosed transition against the chain and returns what it reveals
+pub fn verify_disclosure_raw<N: Network>(
+    package: &DisclosurePackage,
+) -> AvailResult<DisclosureVerification> {
+    if package.version() != DISCLOSURE_VERSION {
+        return Err(invalid_package(format!(
+            "Unsupported package version {}",
+            package.version()
+        )));
+    }
+
+    if package.network() != N::ID {
+        return Err(invalid_package(format!(
+            "Package is for network {} not {}",
+            package.network(),
+            N::ID
+        )));
+    }
+
+    if package.transitions().is_empty() {
+        return Err(invalid_package(
+            "Package discloses no transitions".to_string(),
+        ));
+    }
+
+    let api_client = setup_client::<N>()?;
+    let transaction_id = N::TransactionID::from_str(package.transaction_id())?;
+    let transaction = get_transaction::<N>(&api_client, transaction_id)?;
+
+    let transitions = package
+        .transitions()
+        .iter()
+        .map(|disclosed| {
+            let transition_id = N::TransitionID::from_str(disclosed.transition_id())?;
+            let transition = find_transition::<N>(&transaction, &transition_id)?;
+
+            if &transition.program_id().to_string() != disclosed.program_id()
+                || &transition.function_name().to_string() != disclosed.function_id()
+            {
+                return Err(invalid_package(format!(
+                    "Transition {} does not call {}/{}",
+                    transition_id,
+                    disclosed.program_id(),
+                    disclosed.function_id()
+                )));
+            }
+
+            let (inputs, outputs) = match package.scope() {
+                DisclosureScope::TransitionViewKey => {
+                    let tvk = match disclosed.transition_view_key() {
+                        Some(tvk) => Field::<N>::from_str(tvk)?,
+                        None => {
+                            return Err(invalid_package(format!(
+                                "Transition {} has no transition view key",
+                                transition_id
+                            )))
+                        }
+                    };
+
+                    DecryptTransition::decrypt_with_transition_view_key::<N>(tvk, &transition)?
+                }
+                DisclosureScope::Records => (
+                    vec![],
+                    verify_records::<N>(&api_client, &transition, disclosed.records())?,
+                ),
+            };
+
+            Ok(VerifiedTransition::new(
+                transition_id.to_string(),
+                disclosed.program_id().clone(),
+                disclosed.function_id().clone(),
+                inputs,
+                outputs,
+            ))
+        })
+        .collect::<AvailResult<Vec<VerifiedTransition>>>()?;
+
+    Ok(DisclosureVerification::new(
+        transaction_id.to_string(),
+        transitions,
+    ))
+}
+
+/// Exports a verifiable disclosure of a stored transaction or transition
+#[tauri::command(rename_all = "snake_case")]
+pub fn create_disclosure(id: &str, scope: DisclosureScope) -> AvailResult<DisclosurePackage> {
+    let network = get_network()?;
+
+    match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => create_disclosure_raw::<Testnet3>(id, scope),
+        _ => create_disclosure_raw::<Testnet3>(id, scope),
+    }
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn verify_disclosure(package: DisclosurePackage) -> AvailResult<DisclosureVerification> {
+    let network = get_network()?;
+
+    match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => verify_disclosure_raw::<Testnet3>(&package),
+        _ => verify_disclosure_raw::<Testnet3>(&package),
+    }
+}
+
+#[cfg(test)]
+mod disclosure_tests {
+    use super::*;
+    use crate::api::chain_provider::{
+        mock_record_transaction, with_chain_provider, MockChainProvider,
+    };
+    use avail_common::models::constants::TESTNET_PRIVATE_KEY;
+    use snarkvm::prelude::{Address, PrivateKey, Uniform};
+    use std::sync::Arc;
+
+    /// A mock chain holding a credits record received by the test wallet
+    fn disclosure_chain() -> (
+        MockChainProvider<Testnet3>,
+        ViewKey<Testnet3>,
+        Transaction<Testnet3>,
+        Record<Testnet3, Plaintext<Testnet3>>,
+    ) {
+        let private_key = PrivateKey::<Testnet3>::from_str(TESTNET_PRIVATE_KEY).unwrap();
+        let view_key = ViewKey::<Testnet3>::try_from(&private_key).unwrap();
+        let owner = Address::<Testnet3>::try_from(&private_key).unwrap();
+
+        let (transaction, record) = mock_record_transaction::<Testnet3>(
+            &owner,
+            "credits.aleo",
+            "transfer_public_to_private",
+            "credits",
+            "microcredits: 100u64.private",
+        )
+        .unwrap();
+
+        let api_client = MockChainProvider::<Testnet3>::new();
+        api_client
+            .transaction_broadcast(transaction.clone())
+            .unwrap();
+
+        (api_client, view_key, transaction, record)
+    }
+
+    fn package(
+        transaction: &Transaction<Testnet3>,
+        scope: DisclosureScope,
+        transition: DisclosedTransition,
+    ) -> DisclosurePackage {
+        DisclosurePackage::new(
+            Testnet3::ID,
+            transaction.id().to_string(),
+            scope,
+            vec![transition],
+        )
+    }
+
+    #[test]
+    fn test_verify_records_disclosure() {
+        let (api_client, view_key, transaction, record) = disclosure_chain();
+        let transition = transaction.transitions().next().unwrap().clone();
+
+        let disclosed =
+            disclose_transition::<Testnet3>(view_key, &transition, DisclosureScope::Records)
+                .unwrap();
+        let valid = package(&transaction, DisclosureScope::Records, disclosed.clone());
+
+        // a key that is not the record's decrypts to another record, which the commitment rejects
+        let forged_key = Field::<Testnet3>::rand(&mut rand::thread_rng());
+        let forged_plaintext = match &transition.outputs()[0] {
+            Output::Record(_, _, Some(ciphertext)) => ciphertext
+                .decrypt_symmetric_unchecked(&forged_key)
+                .map(|record| record.to_string())
+                .unwrap_or_else(|_| record.to_string()),
+            _ => unreachable!(),
+        };
+        let forged = package(
+            &transaction,
+            DisclosureScope::Records,
+            DisclosedTransition::new(
+                disclosed.transition_id().clone(),
+                disclosed.program_id().clone(),
+                disclosed.function_id().clone(),
+                None,
+                vec![DisclosedRecord::new(
+                    0,
+                    forged_key.to_string(),
+                    forged_plaintext,
+                )],
+            ),
+        );
+
+        with_chain_provider::<Testnet3, _>(Arc::new(api_client), || {
+            let verification = verify_disclosure_raw::<Testnet3>(&valid).unwrap();
+            assert_eq!(
+                verification.transitions()[0].outputs(),
+                &vec![record.to_string()]
+            );
+
+            let err = verify_disclosure_raw::<Testnet3>(&forged).unwrap_err();
+            assert_eq!(err.error_type, AvailErrorType::Validation);
+        });
+    }
+
+    #[test]
+    fn test_verify_transition_view_key_disclosure() {
+        let (api_client, view_key, transaction, _record) = disclosure_chain();
+        let transition = transaction.transitions().next().unwrap().clone();
+
+        let disclosed = disclose_transition::<Testnet3>(
+            view_key,
+            &transition,
+            DisclosureScope::TransitionViewKey,
+        )
+        .unwrap();
+        let valid = package(
+            &transaction,
+            DisclosureScope::TransitionViewKey,
+            disclosed.clone(),
+        );
+
+        let forged_key = Field::<Testnet3>::rand(&mut rand::thread_rng());
+        let forged = package(
+            &transaction,
+            DisclosureScope::TransitionViewKey,
+            DisclosedTransition::new(
+                disclosed.transition_id().clone(),
+                disclosed.program_id().clone(),
+                disclosed.function_id().clone(),
+                Some(forged_key.to_string()),
+                vec![],
+            ),
+        );
+
+        with_chain_provider::<Testnet3, _>(Arc::new(api_client), || {
+            let verification = verify_disclosure_raw::<Testnet3>(&valid).unwrap();
+            assert_eq!(verification.transaction_id(), &transaction.id().to_string());
+            assert_eq!(verification.transitions().len(), 1);
+
+            let err = verify_disclosure_raw::<Testnet3>(&forged).unwrap_err();
+            assert_eq!(err.error_type, AvailErrorType::Validation);
+        });
+    }
+
+    #[test]
+    fn test_verify_rejects_other_network() {
+        let package = DisclosurePackage::new(
+            Testnet3::ID + 1,
+            "at1...".to_string(),
+            DisclosureScope::TransitionViewKey,
+            vec![],
+        );
+
+        assert!(verify_disclosure_raw::<Testnet3>(&package).is_err());
+    }
+}
@@ -0,0 +1,190 @@
+use snarkvm::ledger::block::ProgramOwner;
+use snarkvm::ledger::store::{helpers::memory::ConsensusMemory, ConsensusStore};
+use snarkvm::prelude::{
+    Identifier, Network, Plaintext, Program, ProgramID, Record, Transaction, Value,
+};
+use snarkvm::synthesizer::{execution_cost, process::Authorization, VM};
+use std::str::FromStr;
+
+use super::deploy::{get_deployed_program, resolve_imports, PreparedDeployment};
+use crate::api::chain_provider::ChainProvider;
+use crate::services::account::signer::Signer;
+
+use avail_common::errors::{AvailError, AvailErrorType, AvailResult};
+
+/* --Signer Executions-- */
+
+/// A program and its imports in dependency order, empty for credits.aleo which every process has
+pub fn get_program_with_imports<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+    program_id: &ProgramID<N>,
+) -> AvailResult<Vec<Program<N>>> {
+    if program_id.to_string() == "credits.aleo" {
+        return Ok(vec![]);
+    }
+
+    let program = match get_deployed_program::<N>(api_client, program_id)? {
+        Some(program) => program,
+        None => {
+            return Err(AvailError::new(
+                AvailErrorType::Validation,
+                format!("Program {} is not deployed", program_id),
+                format!("The program '{}' is not deployed.", program_id),
+            ))
+        }
+    };
+
+    let mut programs = resolve_imports::<N>(api_client, &program, &[])?;
+    programs.push(program);
+
+    Ok(programs)
+}
+
+/// An in-memory VM with the given programs loaded, used to authorize and prove without a local ledger
+pub fn execution_vm<N: Network>(programs: &[Program<N>]) -> AvailResult<VM<N, ConsensusMemory<N>>> {
+    let vm = VM::from(ConsensusStore::<N, ConsensusMemory<N>>::open(None)?)?;
+
+    for program in programs {
+        vm.process().write().add_program(program)?;
+    }
+
+    Ok(vm)
+}
+
+/// Has the signer authorize a function call and the fee paying for it
+pub fn authorize_execution<N: Network>(
+    signer: &dyn Signer<N>,
+    vm: &VM<N, ConsensusMemory<N>>,
+    program_id: &ProgramID<N>,
+    function_name: &Identifier<N>,
+    inputs: &[Value<N>],
+    fee: u64,
+    fee_record: Option<Record<N, Plaintext<N>>>,
+) -> AvailResult<(Authorization<N>, Authorization<N>)> {
+    let process = vm.process();
+    let process = process.read();
+
+    let execution = signer.authorize(&process, program_id, function_name, inputs)?;
+    let fee = signer.authorize_fee(&process, fee_record, fee, 0, execution.to_execution_id()?)?;
+
+    Ok((execution, fee))
+}
+
+/// Proves signed authorizations against the current chain state and broadcasts the transaction
+pub fn prove_and_broadcast<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+    vm: &VM<N, ConsensusMemory<N>>,
+    execution: Authorization<N>,
+    fee: Authorization<N>,
+) -> AvailResult<N::TransactionID> {
+    let query = api_client.query()?;
+    let transaction =
+        vm.execute_authorization(execution, Some(fee), Some(query), &mut rand::thread_rng())?;
+
+    broadcast_execution::<N>(api_client, vm, transaction)
+}
+
+/// Broadcasts a proven execution once its fee is checked.
+/// The whole fee is the base fee, as no priority fee is paid it must cover the execution's minimum cost.
+pub fn broadcast_execution<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+    vm: &VM<N, ConsensusMemory<N>>,
+    transaction: Transaction<N>,
+) -> AvailResult<N::TransactionID> {
+    if let Some(execution) = transaction.execution() {
+        let (minimum_cost, _) = execution_cost(vm, execution)?;
+        let fee = *transaction.fee_amount()?;
+
+        if fee < minimum_cost {
+            return Err(AvailError::new(
+                AvailErrorType::Validation,
+                format!(
+                    "Fee {} is below the minimum execution cost {}",
+                    fee, minimum_cost
+                ),
+                format!(
+                    "The fee must be at least {} credits.",
+                    minimum_cost as f64 / 1000000.0
+                ),
+            ));
+        }
+    }
+
+    broadcast::<N>(api_client, transaction)
+}
+
+/// Has the signer own a prepared deployment and authorize its fee, then proves the fee and broadcasts the deployment
+pub fn deploy_with_signer<N: Network>(
+    signer: &dyn Signer<N>,
+    api_client: &dyn ChainProvider<N>,
+    prepared: PreparedDeployment<N>,
+    fee_record: Option<Record<N, Plaintext<N>>>,
+) -> AvailResult<N::TransactionID> {
+    let vm = execution_vm::<N>(&prepared.imports)?;
+    let deployment_id = prepared.deployment.to_deployment_id()?;
+
+    let owner = ProgramOwner::from(signer.address()?, signer.sign_fields(&[deployment_id])?);
+
+    let fee_authorization = {
+        let process = vm.process();
+        let process = process.read();
+
+        signer.authorize_fee(
+            &process,
+            fee_record,
+            prepared.minimum_cost,
+            prepared.priority_fee(),
+            deployment_id,
+        )?
+    };
+
+    let query = api_client.query()?;
+    let fee =
+        vm.execute_fee_authorization(fee_authorization, Some(query), &mut rand::thread_rng())?;
+
+    let transaction = Transaction::from_deployment(owner, prepared.deployment, fee)?;
+
+    broadcast::<N>(api_client, transaction)
+}
+
+fn broadcast<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+    transaction: Transaction<N>,
+) -> AvailResult<N::TransactionID> {
+    if let Err(e) = api_client.transaction_broadcast(transaction.clone()) {
+        return Err(AvailError::new(
+            AvailErrorType::Node,
+            format!("Error broadcasting transaction {}: {}", transaction.id(), e),
+            "The transaction could not be broadcast.".to_string(),
+        ));
+    }
+
+    Ok(transaction.id())
+}
+
+/// Executes a program function with the signer's key, the fee is in units of 10^-6
+pub fn execute_with_signer<N: Network>(
+    signer: &dyn Signer<N>,
+    api_client: &dyn ChainProvider<N>,
+    program_id: &str,
+    function_id: &str,
+    inputs: &[Value<N>],
+    fee: u64,
+    fee_record: Option<Record<N, Plaintext<N>>>,
+) -> AvailResult<N::TransactionID> {
+    let program_id = ProgramID::<N>::from_str(program_id)?;
+    let function_name = Identifier::<N>::from_str(function_id)?;
+
+    let vm = execution_vm::<N>(&get_program_with_imports::<N>(api_client, &program_id)?)?;
+    let (execution, fee) = authorize_execution::<N>(
+        signer,
+        &vm,
+        &program_id,
+        &function_name,
+        inputs,
+        fee,
+        fee_record,
+    )?;
+
+    prove_and_broadcast::<N>(api_client, &vm, execution, fee)
+}
@@ -0,0 +1,550 @@
+use chrono::{DateTime, Duration, Utc};
+use snarkvm::prelude::{Entry, Identifier, Literal, Network, Plaintext, Record, Testnet3};
+use std::collections::HashMap;
+use std::str::FromStr;
+use tauri_plugin_http::reqwest;
+
+use crate::api::aleo_client::setup_client;
+use crate::models::nft::Nft;
+use crate::models::pointers::record::AvailRecord;
+use crate::models::storage::persistent::PersistentStorage;
+use crate::services::local_storage::{
+    persistent_storage::{get_address_string, get_network},
+    storage_api::records::get_record_pointers_for_record_type,
+};
+
+use avail_common::{
+    errors::{AvailError, AvailErrorType, AvailResult},
+    models::{encrypted_data::RecordTypeCommon, network::SupportedNetworks},
+};
+
+/* --NFT Gallery-- */
+
+/// Keys of the `general_settings` mapping holding the chunks of a collection's base uri
+const BASE_URI_KEYS: [&str; 4] = ["3u8", "4u8", "5u8", "6u8"];
+
+/// Hours a cached collection base uri is reused before being fetched again
+const BASE_URI_TTL_HOURS: i64 = 24;
+
+pub fn init_nft_cache_tables() -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+    storage.execute_query(
+        "CREATE TABLE IF NOT EXISTS nft_collections (
+            program_id TEXT PRIMARY KEY,
+            base_uri TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+    )?;
+    storage.execute_query(
+        "CREATE TABLE IF NOT EXISTS nft_metadata (
+            uri TEXT PRIMARY KEY,
+            metadata TEXT,
+            media_type TEXT
+        )",
+    )?;
+    Ok(())
+}
+
+pub fn drop_nft_cache_tables() -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+    for query in [
+        "DROP TABLE IF EXISTS nft_collections",
+        "DROP TABLE IF EXISTS nft_metadata",
+    ] {
+        match storage.execute_query(query) {
+            Ok(r) => r,
+            Err(e) => match e.error_type {
+                AvailErrorType::NotFound => {}
+                _ => {
+                    return Err(AvailError::new(
+                        AvailErrorType::Internal,
+                        e.internal_msg,
+                        "Error deleting nft cache".to_string(),
+                    ))
+                }
+            },
+        };
+    }
+
+    Ok(())
+}
+
+/// Decodes a string packed little-endian into a u128 as nft programs store uris
+pub fn u128_to_string(u: u128) -> String {
+    let mut temp_u128 = u;
+    let mut bytes = vec![] as Vec<u8>;
+
+    while temp_u128 > 0u128 {
+        let byte = (temp_u128 & 0xff) as u8;
+        bytes.push(byte);
+        temp_u128 >>= 8;
+    }
+
+    String::from_utf8_lossy(&bytes).to_string()
+}
+
+/// Guesses the media type of an nft asset from the extension of its uri
+pub fn guess_media_type(uri: &str) -> Option<String> {
+    let path = uri.split(['?', '#']).next()?;
+    let extension = path.rsplit('.').next()?.to_lowercase();
+
+    let media_type = match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "mp3" => "audio/mpeg",
+        "glb" => "model/gltf-binary",
+        "json" => "application/json",
+        _ => return None,
+    };
+
+    Some(media_type.to_string())
+}
+
+fn get_cached_base_uri(program_id: &str) -> AvailResult<Option<String>> {
+    init_nft_cache_tables()?;
+
+    let storage = PersistentStorage::new()?;
+    let query = format!(
+        "SELECT base_uri, updated_at FROM nft_collections WHERE program_id='{}'",
+        program_id
+    );
+
+    let res = storage.get_all::<String>(&query, 2)?;
+    match res.get(0) {
+        Some(row) if row.len() == 2 => {
+            let updated_at = match DateTime::parse_from_rfc3339(&row[1]) {
+                Ok(updated_at) => updated_at.with_timezone(&Utc),
+                Err(_) => return Ok(None),
+            };
+            if Utc::now() - updated_at < Duration::hours(BASE_URI_TTL_HOURS) {
+                Ok(Some(row[0].clone()))
+            } else {
+                Ok(None)
+            }
+        }
+        _ => Ok(None),
+    }
+}
+
+fn cache_base_uri(program_id: &str, base_uri: &str) -> AvailResult<()> {
+    init_nft_cache_tables()?;
+
+    let storage = PersistentStorage::new()?;
+    storage.save(
+        vec![
+            program_id.to_string(),
+            base_uri.to_string(),
+            Utc::now().to_rfc3339(),
+        ],
+        "INSERT OR REPLACE INTO nft_collections (program_id, base_uri, updated_at) VALUES (?1, ?2, ?3)"
+            .to_string(),
+    )?;
+
+    Ok(())
+}
+
+/// Gets the base uri of an nft collection, reading the `general_settings` chunks only when not cached
+pub fn get_collection_base_uri<N: Network>(program_id: &str) -> AvailResult<String> {
+    if let Some(base_uri) = get_cached_base_uri(program_id)? {
+        return Ok(base_uri);
+    }
+
+    let api_client = setup_client::<N>()?;
+
+    let mut base_uri = String::new();
+    for key in BASE_URI_KEYS {
+        let value = match api_client.get_mapping_value(program_id, "general_settings", key)? {
+            Some(value) => value,
+            None => {
+                return Err(AvailError::new(
+                    AvailErrorType::InvalidData,
+                    format!("Missing base uri chunk {} for {}", key, program_id),
+                    "Invalid nft collection settings".to_string(),
+                ))
+            }
+        };
+        let chunk = value
+            .to_string()
+            .replace("u128", "")
+            .parse::<u128>()
+            .map_err(|_| {
+                AvailError::new(
+                    AvailErrorType::InvalidData,
+                    format!("Invalid base uri chunk {} for {}", key, program_id),
+                    "Invalid nft collection settings".to_string(),
+                )
+            })?;
+
+        base_uri.push_str(&u128_to_string(chunk));
+    }
+
+    cache_base_uri(program_id, &base_uri)?;
+
+    Ok(base_uri)
+}
+
+fn get_entry<'a, N: Network>(
+    record: &'a Record<N, Plaintext<N>>,
+    name: &str,
+) -> Option<&'a Plaintext<N>> {
+    let identifier = Identifier::<N>::from_str(name).ok()?;
+
+    match record.data().get(&identifier)? {
+        Entry::Private(plaintext) | Entry::Public(plaintext) | Entry::Constant(plaintext) => {
+            Some(plaintext)
+        }
+    }
+}
+
+/// Gets the token id of an nft record, either a `data` field or a `data1`/`data2` u128 pair
+pub fn get_nft_token_id<N: Network>(record: &Record<N, Plaintext<N>>) -> AvailResult<String> {
+    if let Some(Plaintext::Literal(Literal::Field(data), _)) = get_entry(record, "data") {
+        return Ok(data.to_string());
+    }
+
+    match (get_entry(record, "data1"), get_entry(record, "data2")) {
+        (
+            Some(Plaintext::Literal(Literal::U128(data1), _)),
+            Some(Plaintext::Literal(Literal::U128(data2), _)),
+        ) => Ok(format!(
+            "{}{}",
+            u128_to_string(**data1),
+            u128_to_string(**data2)
+        )),
+        _ => Err(AvailError::new(
+            AvailErrorType::InvalidData,
+            "Unsupported nft record data".to_string(),
+            "Unsupported nft format".to_string(),
+        )),
+    }
+}
+
+fn get_cached_metadata(
+    uri: &str,
+) -> AvailResult<Option<(Option<serde_json::Value>, Option<String>)>> {
+    init_nft_cache_tables()?;
+
+    let storage = PersistentStorage::new()?;
+    let mut statement = storage
+        .conn
+        .prepare("SELECT metadata, media_type FROM nft_metadata WHERE uri=?1")?;
+
+    let mut rows = statement.query_map([uri], |row| {
+        let metadata: Option<String> = row.get(0)?;
+        let media_type: Option<String> = row.get(1)?;
+        Ok((metadata, media_type))
+    })?;
+
+    match rows.next() {
+        Some(row) => {
+            let (metadata, media_type) = row?;
+            let metadata = match metadata {
+                Some(metadata) => Some(serde_json::from_str::<serde_json::Value>(&metadata)?),
+                None => None,
+            };
+            Ok(Some((metadata, media_type)))
+        }
+        None => Ok(None),
+    }
+}
+
+fn cache_metadata(
+    uri: &str,
+    metadata: &Option<serde_json::Value>,
+    media_type: &Option<String>,
+) -> AvailResult<()> {
+    init_nft_cache_tables()?;
+
+    let metadata = metadata.as_ref().map(|metadata| metadata.to_string());
+
+    let storage = PersistentStorage::new()?;
+    storage.save_mixed(
+        vec![&uri, &metadata, media_type],
+        "INSERT OR REPLACE INTO nft_metadata (uri, metadata, media_type) VALUES (?1, ?2, ?3)"
+            .to_string(),
+    )?;
+
+    Ok(())
+}
+
+/// Resolves the JSON metadata and media type behind an nft uri
+pub async fn resolve_nft_metadata(
+    uri: &str,
+) -> AvailResult<(Option<serde_json::Value>, Option<String>)> {
+    if let Some(cached) = get_cached_metadata(uri)? {
+        return Ok(cached);
+    }
+
+    let res = reqwest::Client::new().get(uri).send().await?;
+
+    if !res.status().is_success() {
+        return Err(AvailError::new(
+            AvailErrorType::External,
+            format!("Error fetching nft metadata from {}: {}", uri, res.status()),
+            "Error fetching nft metadata".to_string(),
+        ));
+    }
+
+    let content_type = res
+        .headers()
+        .get("content-type")
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string());
+
+    let body = res.text().await?;
+
+    let (metadata, media_type) = match serde_json::from_str::<serde_json::Value>(&body) {
+        Ok(metadata) => {
+            let media_uri = metadata
+                .get("animation_url")
+                .or_else(|| metadata.get("image"))
+                .and_then(|media| media.as_str());
+
+            let media_type = metadata
+                .get("mediaType")
+                .or_else(|| metadata.get("media_type"))
+                .and_then(|media_type| media_type.as_str())
+                .map(|media_type| media_type.to_string())
+                .or_else(|| media_uri.and_then(guess_media_type));
+
+            (Some(metadata), media_type)
+        }
+        // the uri points directly to the media
+        Err(_) => (None, content_type.or_else(|| guess_media_type(uri))),
+    };
+
+    cache_metadata(uri, &metadata, &media_type)?;
+
+    Ok((metadata, media_type))
+}
+
+async fn resolve_nft<N: Network>(
+    record_pointer: &AvailRecord<N>,
+    id: &str,
+    base_uri: &str,
+) -> AvailResult<Nft> {
+    let record = record_pointer.to_record()?;
+    let token_id = get_nft_token_id::<N>(&record)?;
+    let uri = format!("{}{}", base_uri, token_id);
+
+    let (metadata, media_type) = resolve_nft_metadata(&uri).await?;
+
+    Ok(Nft::new(
+        id.to_string(),
+        record_pointer.metadata.program_id.clone(),
+        token_id,
+        uri,
+        metadata,
+        media_type,
+    ))
+}
+
+/// Gets all unspent nfts of the wallet, a failing collection or nft only errors its own entries
+pub async fn get_all_nfts_raw<N: Network>() -> AvailResult<Vec<Nft>> {
+    let address = get_address_string()?;
+    let (record_pointers, ids) =
+        get_record_pointers_for_record_type::<N>(RecordTypeCommon::NFT, &address)?;
+
+    Ok(resolve_nfts::<N>(&record_pointers, &ids).await)
+}
+
+/// Resolves the unspent nft records, fetching each collection's base uri once
+async fn resolve_nfts<N: Network>(record_pointers: &[AvailRecord<N>], ids: &[String]) -> Vec<Nft> {
+    let mut base_uris: HashMap<String, Result<String, String>> = HashMap::new();
+    let mut nfts: Vec<Nft> = vec![];
+
+    for (record_pointer, id) in record_pointers.iter().zip(ids.iter()) {
+        if record_pointer.metadata.spent {
+            continue;
+        }
+
+        let collection = record_pointer.metadata.program_id.clone();
+
+        let base_uri = base_uris
+            .entry(collection.clone())
+            .or_insert_with(|| {
+                get_collection_base_uri::<N>(&collection).map_err(|e| e.external_msg)
+            })
+            .clone();
+
+        let nft = match base_uri {
+            Ok(base_uri) => match resolve_nft::<N>(record_pointer, id, &base_uri).await {
+                Ok(nft) => nft,
+                Err(e) => Nft::from_error(id.to_string(), collection, e.external_msg),
+            },
+            Err(e) => Nft::from_error(id.to_string(), collection, e),
+        };
+
+        nfts.push(nft);
+    }
+
+    nfts
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_all_nft_data() -> AvailResult<Vec<Nft>> {
+    let network = get_network()?;
+
+    match SupportedNetworks::from_str(network.as_str())? {
+        SupportedNetworks::Testnet3 => {
+            let nft_data = get_all_nfts_raw::<Testnet3>().await?;
+            println!("===> NFT Data {:?}", nft_data);
+            Ok(nft_data)
+        }
+        _ => Err(AvailError::new(
+            AvailErrorType::Internal,
+            "Network not supported".to_string(),
+            "Network not supported".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod nfts_tests {
+    use super::*;
+    use crate::api::chain_provider::{
+        mock_record_transaction, with_chain_provider, ChainProvider, MockChainProvider,
+    };
+    use crate::services::local_storage::{
+        session::view::VIEWSESSION, storage_api::records::get_test_record_pointer,
+    };
+    use avail_common::models::constants::{TESTNET_ADDRESS, TESTNET_PRIVATE_KEY};
+    use snarkvm::prelude::{Address, PrivateKey, Value, ViewKey};
+    use std::sync::Arc;
+
+    /// Broadcasts an nft record of `collection` to the mock chain and points to it
+    fn mock_nft_record(
+        api_client: &MockChainProvider<Testnet3>,
+        collection: &str,
+        data: &str,
+    ) -> AvailRecord<Testnet3> {
+        let owner = Address::<Testnet3>::from_str(TESTNET_ADDRESS).unwrap();
+        let (transaction, record) =
+            mock_record_transaction::<Testnet3>(&owner, collection, "mint", "NFT", data).unwrap();
+
+        let transition = transaction.transitions().next().unwrap().clone();
+        let (commitment, _) = transition.outputs()[0].clone().into_record().unwrap();
+        api_client
+            .transaction_broadcast(transaction.clone())
+            .unwrap();
+
+        let mut record_pointer = get_test_record_pointer();
+        record_pointer.pointer.transaction_id = transaction.id();
+        record_pointer.pointer.transition_id = *transition.id();
+        record_pointer.pointer.commitment = commitment.to_string();
+        record_pointer.metadata.record_type = RecordTypeCommon::NFT;
+        record_pointer.metadata.program_id = collection.to_string();
+        record_pointer.metadata.name = "NFT".to_string();
+        record_pointer.metadata.nonce = record.nonce().to_string();
+
+        record_pointer
+    }
+
+    #[test]
+    fn test_guess_media_type() {
+        assert_eq!(
+            guess_media_type("https://nft.example/1.png?size=large"),
+            Some("image/png".to_string())
+        );
+        assert_eq!(
+            guess_media_type("ipfs://collection/2.MP4"),
+            Some("video/mp4".to_string())
+        );
+        assert_eq!(guess_media_type("https://nft.example/3"), None);
+    }
+
+    #[test]
+    fn test_u128_to_string() {
+        // "abc" packed little-endian
+        assert_eq!(u128_to_string(6513249u128), "abc".to_string());
+        assert_eq!(u128_to_string(0u128), "".to_string());
+    }
+
+    #[test]
+    fn test_get_all_nft_data() {
+        let private_key = PrivateKey::<Testnet3>::from_str(TESTNET_PRIVATE_KEY).unwrap();
+        let view_key = ViewKey::<Testnet3>::try_from(&private_key).unwrap();
+        VIEWSESSION.set_view_session(&view_key.to_string()).unwrap();
+
+        let collection = "nft_gallery_test.aleo";
+        let broken_collection = "nft_gallery_broken.aleo";
+
+        // "https://" and "nft.test/" packed little-endian, the remaining chunks are empty
+        let api_client = MockChainProvider::<Testnet3>::new();
+        for (key, chunk) in [
+            ("3u8", "3400000511170344040u128"),
+            ("4u8", "875388133544740284014u128"),
+            ("5u8", "0u128"),
+            ("6u8", "0u128"),
+        ] {
+            api_client.set_mapping_value(
+                collection,
+                "general_settings",
+                key,
+                Value::<Testnet3>::from_str(chunk).unwrap(),
+            );
+        }
+
+        let resolved = mock_nft_record(&api_client, collection, "data: 7field.private");
+        let unsupported = mock_nft_record(&api_client, collection, "data: 7u8.private");
+        let broken = mock_nft_record(&api_client, broken_collection, "data: 8field.private");
+        let mut spent = mock_nft_record(&api_client, collection, "data: 9field.private");
+        spent.metadata.spent = true;
+
+        // metadata is served from the cache so the test stays offline
+        let metadata = serde_json::json!({ "name": "Test NFT", "image": "https://nft.test/7.png" });
+        cache_metadata(
+            "https://nft.test/7field",
+            &Some(metadata.clone()),
+            &Some("image/png".to_string()),
+        )
+        .unwrap();
+
+        let record_pointers = vec![resolved, unsupported, broken, spent];
+        let ids = ["resolved", "unsupported", "broken", "spent"]
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<String>>();
+
+        let nfts = with_chain_provider::<Testnet3, _>(Arc::new(api_client), || {
+            tokio::runtime::Runtime::new()
+                .unwrap()
+                .block_on(resolve_nfts::<Testnet3>(&record_pointers, &ids))
+        });
+
+        // the spent record is skipped, each failing entry carries its own error
+        assert_eq!(nfts.len(), 3);
+        assert_eq!(
+            nfts[0],
+            Nft::new(
+                "resolved".to_string(),
+                collection.to_string(),
+                "7field".to_string(),
+                "https://nft.test/7field".to_string(),
+                Some(metadata),
+                Some("image/png".to_string()),
+            )
+        );
+        assert_eq!(
+            nfts[1],
+            Nft::from_error(
+                "unsupported".to_string(),
+                collection.to_string(),
+                "Unsupported nft format".to_string()
+            )
+        );
+        assert_eq!(
+            nfts[2],
+            Nft::from_error(
+                "broken".to_string(),
+                broken_collection.to_string(),
+                "Invalid nft collection settings".to_string()
+            )
+        );
+    }
+}
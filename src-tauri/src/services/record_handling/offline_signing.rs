@@ -0,0 +1,858 @@
+use chrono::Local;
+use once_cell::sync::Lazy;
+use snarkvm::circuit::{Aleo, AleoV0, Environment};
+use snarkvm::ledger::{query::QueryTrait, store::helpers::memory::ConsensusMemory};
+use snarkvm::prelude::{
+    anyhow, Field, Identifier, Network, Plaintext, Program, ProgramID, Record, StatePath, Testnet3,
+    Transaction, Value, ValueType,
+};
+use snarkvm::synthesizer::{process::Authorization, VM};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Mutex;
+use tauri::Window;
+
+use super::execution::{
+    authorize_execution, broadcast_execution, execution_vm, get_program_with_imports,
+};
+use super::program_abi::prepare_inputs;
+use super::records::find_aleo_credits_record_to_spend;
+use super::utils::{
+    handle_encrypted_storage_and_message, handle_transaction_update_and_encrypted_storage,
+    parse_inputs,
+};
+use crate::api::aleo_client::setup_client;
+use crate::api::chain_provider::ChainProvider;
+use crate::helpers::events::EventEmitter;
+use crate::models::offline_signing::{
+    OfflineEnvelope, OfflinePayload, SignedExecution, UnsignedExecution,
+};
+use crate::models::pointers::transaction::TransactionPointer;
+use crate::models::storage::persistent::PersistentStorage;
+use crate::models::wallet_connect::create_event::CreateEventRequest;
+use crate::services::account::signer::get_signer;
+use crate::services::local_storage::{
+    encrypted_data::update_encrypted_transaction_state_by_id,
+    persistent_storage::{get_address, get_network},
+    storage_api::{
+        records::update_record_spent_local_via_nonce, transaction::get_transaction_pointer,
+    },
+};
+
+use avail_common::{
+    errors::{AvailError, AvailErrorType, AvailResult},
+    models::{encrypted_data::TransactionState, network::SupportedNetworks},
+};
+
+/* --Offline Signing-- */
+
+// The online wallet only ever holds the view key, the offline wallet holds the private key and never touches the network.
+// Proving needs a state root and the inclusion paths of the spent records, so the online wallet fetches them when it
+// builds the execution and the offline wallet signs and proves against them. The online wallet checks the signed
+// authorizations against what it exported and that the transaction was proven from them before broadcasting it.
+
+/// Pending event ids of offline executions being broadcast, so a second import cannot broadcast them again
+static IN_FLIGHT: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+pub fn init_offline_executions_table() -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+    storage.execute_query(
+        "CREATE TABLE IF NOT EXISTS offline_executions (
+            pending_event_id TEXT PRIMARY KEY
+        )",
+    )?;
+    Ok(())
+}
+
+pub fn drop_offline_executions_table() -> AvailResult<()> {
+    let storage = PersistentStorage::new()?;
+    match storage.execute_query("DROP TABLE IF EXISTS offline_executions") {
+        Ok(r) => r,
+        Err(e) => match e.error_type {
+            AvailErrorType::NotFound => {}
+            _ => {
+                return Err(AvailError::new(
+                    AvailErrorType::Internal,
+                    e.internal_msg,
+                    "Error deleting offline executions".to_string(),
+                ))
+            }
+        },
+    };
+
+    Ok(())
+}
+
+pub fn store_offline_execution(pending_event_id: &str) -> AvailResult<()> {
+    init_offline_executions_table()?;
+
+    let storage = PersistentStorage::new()?;
+    storage.save(
+        vec![pending_event_id.to_string()],
+        "INSERT OR IGNORE INTO offline_executions (pending_event_id) VALUES (?1)".to_string(),
+    )?;
+
+    Ok(())
+}
+
+/// Whether a pending transaction was exported for offline signing, it waits without a transaction id until imported
+pub fn is_offline_execution(pending_event_id: &str) -> AvailResult<bool> {
+    init_offline_executions_table()?;
+
+    let storage = PersistentStorage::new()?;
+    let rows = storage.get_all_with_params::<String, &str>(
+        "SELECT pending_event_id FROM offline_executions WHERE pending_event_id=?1",
+        vec![pending_event_id],
+        1,
+    )?;
+
+    Ok(!rows.is_empty())
+}
+
+fn emit_state_change(window: &Option<EventEmitter>, pending_event_id: &str) -> AvailResult<()> {
+    if let Some(window) = window {
+        if window.emit("tx_state_change", pending_event_id).is_err() {
+            return Err(AvailError::new(
+                AvailErrorType::Internal,
+                "Error emitting tx_state_change event".to_string(),
+                "Error emitting transaction state".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_programs<N: Network>(request: &UnsignedExecution) -> AvailResult<Vec<Program<N>>> {
+    request
+        .programs()
+        .iter()
+        .map(|source| Ok(Program::<N>::from_str(source)?))
+        .collect()
+}
+
+/// Chain state the offline wallet proves against, fetched by the online wallet when the execution is built
+#[derive(Clone)]
+struct OfflineQuery<N: Network> {
+    state_root: N::StateRoot,
+    state_paths: HashMap<Field<N>, StatePath<N>>,
+}
+
+impl<N: Network> OfflineQuery<N> {
+    fn from_request(request: &UnsignedExecution) -> AvailResult<Self> {
+        let state_paths = request
+            .state_paths()
+            .iter()
+            .map(|state_path| {
+                let state_path = StatePath::<N>::from_str(state_path)?;
+                Ok((*state_path.transition_leaf().id(), state_path))
+            })
+            .collect::<AvailResult<HashMap<Field<N>, StatePath<N>>>>()?;
+
+        Ok(Self {
+            state_root: N::StateRoot::from_str(request.state_root())?,
+            state_paths,
+        })
+    }
+}
+
+impl<N: Network> QueryTrait<N> for OfflineQuery<N> {
+    fn current_state_root(&self) -> snarkvm::prelude::Result<N::StateRoot> {
+        Ok(self.state_root)
+    }
+
+    fn get_state_path_for_commitment(
+        &self,
+        commitment: &Field<N>,
+    ) -> snarkvm::prelude::Result<StatePath<N>> {
+        match self.state_paths.get(commitment) {
+            Some(state_path) => Ok(state_path.clone()),
+            None => Err(anyhow!("No state path exported for record {}", commitment)),
+        }
+    }
+}
+
+fn invalid_request(reason: &str) -> AvailError {
+    AvailError::new(
+        AvailErrorType::Validation,
+        reason.to_string(),
+        "The signed transaction does not match the transaction that was exported.".to_string(),
+    )
+}
+
+/// Commitments of the records an execution spends, the fee record last
+fn record_commitments<N: Network>(
+    vm: &VM<N, ConsensusMemory<N>>,
+    program_id: &ProgramID<N>,
+    function_name: &Identifier<N>,
+    input_values: &[Value<N>],
+    fee_record: Option<&Record<N, Plaintext<N>>>,
+) -> AvailResult<Vec<Field<N>>> {
+    let process = vm.process();
+    let process = process.read();
+    let function = process
+        .get_program(program_id)?
+        .get_function(function_name)?;
+
+    let mut commitments = vec![];
+    for (input, value) in function.inputs().iter().zip(input_values) {
+        if let (ValueType::Record(record_name), Value::Record(record)) = (input.value_type(), value)
+        {
+            commitments.push(record.to_commitment(program_id, record_name)?);
+        }
+    }
+
+    if let Some(fee_record) = fee_record {
+        commitments.push(fee_record.to_commitment(
+            &ProgramID::<N>::from_str("credits.aleo")?,
+            &Identifier::<N>::from_str("credits")?,
+        )?);
+    }
+
+    Ok(commitments)
+}
+
+/// State root and inclusion paths of the spent records, the paths must all lead to the same root to be proven together
+fn inclusion_state<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+    commitments: &[Field<N>],
+) -> AvailResult<(N::StateRoot, Vec<StatePath<N>>)> {
+    let query = api_client.query()?;
+
+    let state_paths = commitments
+        .iter()
+        .map(|commitment| Ok(query.get_state_path_for_commitment(commitment)?))
+        .collect::<AvailResult<Vec<StatePath<N>>>>()?;
+
+    let state_root = match state_paths.first() {
+        Some(state_path) => state_path.global_state_root(),
+        None => query.current_state_root()?,
+    };
+
+    if state_paths
+        .iter()
+        .any(|state_path| state_path.global_state_root() != state_root)
+    {
+        return Err(AvailError::new(
+            AvailErrorType::Node,
+            "Record state paths lead to different state roots".to_string(),
+            "A new block arrived while preparing the transaction, please try again.".to_string(),
+        ));
+    }
+
+    Ok((state_root, state_paths))
+}
+
+/// Proves the signed execution and fee with the exported chain state, no network connection is needed
+fn prove_offline<N: Network, A: Aleo + Environment<Network = N>>(
+    vm: &VM<N, ConsensusMemory<N>>,
+    execution: Authorization<N>,
+    fee: Authorization<N>,
+    query: OfflineQuery<N>,
+) -> AvailResult<Transaction<N>> {
+    let rng = &mut rand::thread_rng();
+    let process = vm.process();
+    let process = process.read();
+
+    let locator = {
+        let request = execution.peek_next()?;
+        format!("{}/{}", request.program_id(), request.function_name())
+    };
+
+    let (_response, mut trace) = process.execute::<A, _>(execution, rng)?;
+    trace.prepare(query.clone())?;
+    let execution = trace.prove_execution::<A, _>(&locator, rng)?;
+
+    let (_response, mut trace) = process.execute::<A, _>(fee, rng)?;
+    trace.prepare(query)?;
+    let fee = trace.prove_fee::<A, _>(rng)?;
+
+    Ok(Transaction::from_execution(execution, Some(fee))?)
+}
+
+/// Builds an unsigned execution with the view key only and stores it as a processing transaction
+pub fn create_offline_execution_raw<N: Network>(
+    request: CreateEventRequest,
+    fee_private: bool,
+    window: Option<EventEmitter>,
+) -> AvailResult<OfflineEnvelope> {
+    let api_client = setup_client::<N>()?;
+    let address = get_address::<N>()?;
+    let fee = (request.fee() * 1000000.0) as u64;
+
+    let inputs = prepare_inputs::<N>(
+        &api_client,
+        request.program_id(),
+        request.function_id(),
+        request.inputs(),
+    )?;
+
+    let (input_values, input_nonces, _recipient_address, amount) =
+        parse_inputs::<N>(inputs.clone(), request.function_id())?;
+
+    let program_id = ProgramID::<N>::from_str(request.program_id())?;
+    let programs = get_program_with_imports::<N>(&api_client, &program_id)?;
+
+    let mut record_nonces = input_nonces.clone();
+    let fee_record = match fee_private {
+        true => {
+            let (fee_record, _fee_commitment, _fee_id) =
+                find_aleo_credits_record_to_spend::<N>(&fee, input_nonces)?;
+            record_nonces.push(fee_record.nonce().to_string());
+
+            Some(fee_record)
+        }
+        false => None,
+    };
+
+    let commitments = record_commitments::<N>(
+        &execution_vm::<N>(&programs)?,
+        &program_id,
+        &Identifier::<N>::from_str(request.function_id())?,
+        &input_values,
+        fee_record.as_ref(),
+    )?;
+    let (state_root, state_paths) = inclusion_state::<N>(&api_client, &commitments)?;
+
+    let pending_transaction = TransactionPointer::<N>::new(
+        None,
+        None,
+        TransactionState::Processing,
+        None,
+        Some(request.program_id().clone()),
+        Some(request.function_id().clone()),
+        vec![],
+        record_nonces.clone(),
+        Local::now(),
+        None,
+        None,
+        request.event_type().to_owned(),
+        amount,
+        Some(request.fee()),
+        None,
+    );
+
+    let pending_event_id = pending_transaction.encrypt_and_store(address)?;
+    store_offline_execution(&pending_event_id)?;
+
+    for nonce in record_nonces {
+        update_record_spent_local_via_nonce::<N>(&nonce, true)?;
+    }
+
+    emit_state_change(&window, &pending_event_id)?;
+
+    OfflineEnvelope::new::<N>(OfflinePayload::Unsigned(UnsignedExecution::new(
+        address.to_string(),
+        request.program_id().clone(),
+        request.function_id().clone(),
+        inputs,
+        programs.iter().map(|program| program.to_string()).collect(),
+        fee,
+        fee_record.map(|fee_record| fee_record.to_string()),
+        state_root.to_string(),
+        state_paths
+            .iter()
+            .map(|state_path| state_path.to_string())
+            .collect(),
+        pending_event_id,
+    )))
+}
+
+/// Signs and proves the execution and its fee on the offline wallet, nothing here requires a network connection
+pub fn sign_offline_execution_raw<N: Network, A: Aleo + Environment<Network = N>>(
+    envelope: &OfflineEnvelope,
+    password: Option<String>,
+) -> AvailResult<OfflineEnvelope> {
+    let request = match envelope.verify::<N>()? {
+        OfflinePayload::Unsigned(request) => request.clone(),
+        OfflinePayload::Signed(_) => {
+            return Err(AvailError::new(
+                AvailErrorType::Validation,
+                "Envelope is already signed".to_string(),
+                "This transaction is already signed, import it on the online wallet.".to_string(),
+            ))
+        }
+    };
+
+    let signer = get_signer::<N, A>(password)?;
+
+    if request.address() != &signer.address()?.to_string() {
+        return Err(AvailError::new(
+            AvailErrorType::Validation,
+            format!("Execution was built for {}", request.address()),
+            "This transaction was created for a different account.".to_string(),
+        ));
+    }
+
+    let (input_values, _input_nonces, _recipient_address, _amount) =
+        parse_inputs::<N>(request.inputs().clone(), request.function_id())?;
+
+    let fee_record = match request.fee_record() {
+        Some(fee_record) => Some(Record::<N, Plaintext<N>>::from_str(fee_record)?),
+        None => None,
+    };
+
+    let vm = execution_vm::<N>(&parse_programs::<N>(&request)?)?;
+    let (execution, fee) = authorize_execution::<N>(
+        signer.as_ref(),
+        &vm,
+        &ProgramID::<N>::from_str(request.program_id())?,
+        &Identifier::<N>::from_str(request.function_id())?,
+        &input_values,
+        request.fee(),
+        fee_record,
+    )?;
+
+    let transaction = prove_offline::<N, A>(
+        &vm,
+        execution.clone(),
+        fee.clone(),
+        OfflineQuery::from_request(&request)?,
+    )?;
+
+    OfflineEnvelope::new::<N>(OfflinePayload::Signed(SignedExecution::new(
+        request,
+        execution.to_string(),
+        fee.to_string(),
+        transaction.to_string(),
+    )))
+}
+
+/// The pending transaction of an offline execution that has not been broadcast, failed or cancelled yet
+fn awaiting_broadcast<N: Network>(
+    request: &UnsignedExecution,
+) -> AvailResult<TransactionPointer<N>> {
+    let pending_transaction = get_transaction_pointer::<N>(request.pending_event_id())?;
+
+    let awaiting = matches!(pending_transaction.state(), TransactionState::Processing)
+        && pending_transaction.transaction_id().is_none()
+        && !IN_FLIGHT
+            .lock()
+            .unwrap()
+            .contains(request.pending_event_id());
+
+    match awaiting {
+        true => Ok(pending_transaction),
+        false => Err(AvailError::new(
+            AvailErrorType::Validation,
+            format!(
+                "Offline execution {} is no longer awaiting its signature",
+                request.pending_event_id()
+            ),
+            "This transaction was already broadcast or cancelled.".to_string(),
+        )),
+    }
+}
+
+/// Unspends the records of an offline execution and stores it as failed, or cancelled by the user
+fn release_offline_execution<N: Network>(
+    request: &UnsignedExecution,
+    mut pending_transaction: TransactionPointer<N>,
+    cancelled: bool,
+    window: &Option<EventEmitter>,
+) -> AvailResult<()> {
+    let address = get_address::<N>()?;
+
+    for nonce in pending_transaction.spent_record_pointers_nonces() {
+        update_record_spent_local_via_nonce::<N>(&nonce, false)?;
+    }
+
+    let state = match cancelled {
+        true => {
+            pending_transaction.update_cancelled_transaction();
+            TransactionState::Cancelled
+        }
+        false => {
+            pending_transaction.update_failed_transaction(
+                "Transaction execution failed, no records were spent.".to_string(),
+                None,
+            );
+            TransactionState::Failed
+        }
+    };
+
+    let encrypted_transaction = pending_transaction.to_encrypted_data(address)?;
+
+    update_encrypted_transaction_state_by_id(
+        request.pending_event_id(),
+        &encrypted_transaction.ciphertext,
+        &encrypted_transaction.nonce,
+        state,
+    )?;
+
+    emit_state_change(window, request.pending_event_id())
+}
+
+/// Checks the signed authorizations call exactly the function, inputs and fee that were exported
+fn check_signed_authorizations<N: Network>(
+    request: &UnsignedExecution,
+    execution: &Authorization<N>,
+    fee: &Authorization<N>,
+) -> AvailResult<()> {
+    let (input_values, _input_nonces, _recipient_address, _amount) =
+        parse_inputs::<N>(request.inputs().clone(), request.function_id())?;
+
+    let signed_request = execution.peek_next()?;
+    if signed_request.program_id().to_string() != *request.program_id()
+        || signed_request.function_name().to_string() != *request.function_id()
+        || signed_request.signer().to_string() != *request.address()
+        || signed_request.inputs() != input_values.as_slice()
+    {
+        return Err(invalid_request(
+            "Signed authorization does not match the execution request",
+        ));
+    }
+
+    let mut fee_inputs = vec![];
+    let fee_function = match request.fee_record() {
+        Some(fee_record) => {
+            fee_inputs.push(Value::Record(Record::<N, Plaintext<N>>::from_str(
+                fee_record,
+            )?));
+            "fee_private"
+        }
+        None => "fee_public",
+    };
+    fee_inputs.push(Value::from_str(&format!("{}u64", request.fee()))?);
+    fee_inputs.push(Value::from_str("0u64")?);
+    fee_inputs.push(Value::from_str(&execution.to_execution_id()?.to_string())?);
+
+    let fee_request = fee.peek_next()?;
+    if fee_request.program_id().to_string() != "credits.aleo"
+        || fee_request.function_name().to_string() != fee_function
+        || fee_request.signer().to_string() != *request.address()
+        || fee_request.inputs() != fee_inputs.as_slice()
+    {
+        return Err(invalid_request(
+            "Signed fee authorization does not match the exported fee",
+        ));
+    }
+
+    Ok(())
+}
+
+/// The transaction must be made of the signed transitions, their ids commit to every input and output
+fn check_proven_transaction<N: Network>(
+    transaction: &Transaction<N>,
+    execution: &Authorization<N>,
+    fee: &Authorization<N>,
+) -> AvailResult<()> {
+    let authorized = execution
+        .transitions()
+        .keys()
+        .chain(fee.transitions().keys())
+        .copied()
+        .collect::<Vec<N::TransitionID>>();
+    let proven = transaction
+        .transition_ids()
+        .copied()
+        .collect::<Vec<N::TransitionID>>();
+
+    match authorized == proven {
+        true => Ok(()),
+        false => Err(invalid_request(
+            "Transaction was not proven from the signed authorizations",
+        )),
+    }
+}
+
+fn broadcast_signed_execution<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+    signed: &SignedExecution,
+) -> AvailResult<N::TransactionID> {
+    let request = signed.request();
+    let execution = Authorization::<N>::from_str(signed.execution())?;
+    let fee = Authorization::<N>::from_str(signed.fee())?;
+    let transaction = Transaction::<N>::from_str(signed.transaction())?;
+
+    check_signed_authorizations::<N>(request, &execution, &fee)?;
+    check_proven_transaction::<N>(&transaction, &execution, &fee)?;
+
+    let vm = execution_vm::<N>(&parse_programs::<N>(request)?)?;
+
+    broadcast_execution::<N>(api_client, &vm, transaction)
+}
+
+/// Checks the transaction proven by the offline wallet against the exported execution and broadcasts it
+pub async fn broadcast_offline_execution_raw<N: Network>(
+    envelope: OfflineEnvelope,
+    window: Option<EventEmitter>,
+) -> AvailResult<String> {
+    let signed = match envelope.verify::<N>()? {
+        OfflinePayload::Signed(signed) => signed.clone(),
+        OfflinePayload::Unsigned(_) => {
+            return Err(AvailError::new(
+                AvailErrorType::Validation,
+                "Envelope is not signed".to_string(),
+                "This transaction must be signed on the offline wallet first.".to_string(),
+            ))
+        }
+    };
+    let request = signed.request().clone();
+
+    // the pending transaction must have been created by this wallet and not be broadcast yet,
+    // so importing an envelope again cannot fail a broadcast transaction and unspend its records
+    let pending_transaction = awaiting_broadcast::<N>(&request)?;
+    if !IN_FLIGHT
+        .lock()
+        .unwrap()
+        .insert(request.pending_event_id().clone())
+    {
+        return Err(AvailError::new(
+            AvailErrorType::Validation,
+            format!(
+                "Offline execution {} is already being broadcast",
+                request.pending_event_id()
+            ),
+            "This transaction is already being broadcast.".to_string(),
+        ));
+    }
+
+    let broadcast = setup_client::<N>()
+        .and_then(|api_client| broadcast_signed_execution::<N>(&api_client, &signed));
+
+    IN_FLIGHT.lock().unwrap().remove(request.pending_event_id());
+
+    let transaction_id = match broadcast {
+        Ok(transaction_id) => transaction_id,
+        Err(e) => {
+            release_offline_execution::<N>(&request, pending_transaction, false, &window)?;
+            return Err(e);
+        }
+    };
+
+    let (_input_values, _input_nonces, recipient_address, _amount) =
+        parse_inputs::<N>(request.inputs().clone(), request.function_id())?;
+
+    match recipient_address {
+        Some(recipient_address) => {
+            handle_encrypted_storage_and_message::<N>(
+                transaction_id,
+                recipient_address,
+                request.pending_event_id(),
+                None,
+                None,
+                true,
+                window,
+            )
+            .await?
+        }
+        None => {
+            handle_transaction_update_and_encrypted_storage::<N>(
+                transaction_id,
+                request.pending_event_id(),
+                None,
+                window,
+            )
+            .await?
+        }
+    }
+
+    Ok(request.pending_event_id().clone())
+}
+
+/// Cancels an offline execution that was not broadcast yet and unspends its records
+pub fn cancel_offline_execution_raw<N: Network>(
+    envelope: &OfflineEnvelope,
+    window: Option<EventEmitter>,
+) -> AvailResult<()> {
+    let request = match envelope.verify::<N>()? {
+        OfflinePayload::Unsigned(request) => request.clone(),
+        OfflinePayload::Signed(signed) => signed.request().clone(),
+    };
+
+    let pending_transaction = awaiting_broadcast::<N>(&request)?;
+
+    release_offline_execution::<N>(&request, pending_transaction, true, &window)
+}
+
+/// Online step one: builds the unsigned execution to carry to the offline wallet
+#[tauri::command(rename_all = "snake_case")]
+pub fn create_offline_execution(
+    request: CreateEventRequest,
+    fee_private: bool,
+    window: Window,
+) -> AvailResult<OfflineEnvelope> {
+    let network = get_network()?;
+
+    match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => {
+            create_offline_execution_raw::<Testnet3>(request, fee_private, Some(window.into()))
+        }
+        _ => create_offline_execution_raw::<Testnet3>(request, fee_private, Some(window.into())),
+    }
+}
+
+/// Offline step: signs and proves an unsigned execution
+#[tauri::command(rename_all = "snake_case")]
+pub fn sign_offline_execution(
+    envelope: OfflineEnvelope,
+    password: Option<String>,
+) -> AvailResult<OfflineEnvelope> {
+    let network = get_network()?;
+
+    match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => {
+            sign_offline_execution_raw::<Testnet3, AleoV0>(&envelope, password)
+        }
+        _ => sign_offline_execution_raw::<Testnet3, AleoV0>(&envelope, password),
+    }
+}
+
+/// Online step two: imports the signed and proven execution, checks and broadcasts it
+#[tauri::command(rename_all = "snake_case")]
+pub async fn broadcast_offline_execution(
+    envelope: OfflineEnvelope,
+    window: Window,
+) -> AvailResult<String> {
+    let network = get_network()?;
+
+    match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => {
+            tokio::task::spawn_blocking(move || {
+                broadcast_offline_execution_raw::<Testnet3>(envelope, Some(window.into()))
+            })
+            .await?
+        }
+        _ => {
+            tokio::task::spawn_blocking(move || {
+                broadcast_offline_execution_raw::<Testnet3>(envelope, Some(window.into()))
+            })
+            .await?
+        }
+    }
+    .await
+}
+
+/// Online: cancels an exported execution that will not be signed or broadcast
+#[tauri::command(rename_all = "snake_case")]
+pub fn cancel_offline_execution(envelope: OfflineEnvelope, window: Window) -> AvailResult<()> {
+    let network = get_network()?;
+
+    match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => {
+            cancel_offline_execution_raw::<Testnet3>(&envelope, Some(window.into()))
+        }
+        _ => cancel_offline_execution_raw::<Testnet3>(&envelope, Some(window.into())),
+    }
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn offline_envelope_to_qr(envelope: OfflineEnvelope) -> AvailResult<Vec<String>> {
+    envelope.to_qr_chunks()
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn offline_envelope_from_qr(chunks: Vec<String>) -> AvailResult<OfflineEnvelope> {
+    let network = get_network()?;
+
+    match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => OfflineEnvelope::from_qr_chunks::<Testnet3>(&chunks),
+        _ => OfflineEnvelope::from_qr_chunks::<Testnet3>(&chunks),
+    }
+}
+
+/// Writes the envelope to the downloads folder and returns the file path
+#[tauri::command(rename_all = "snake_case")]
+pub fn export_offline_envelope(envelope: OfflineEnvelope) -> AvailResult<String> {
+    let directory = match dirs::download_dir() {
+        Some(directory) => directory,
+        None => {
+            return Err(AvailError::new(
+                AvailErrorType::NotFound,
+                "Downloads directory not found".to_string(),
+                "Could not find a folder to export to.".to_string(),
+            ))
+        }
+    };
+
+    let path = directory.join(format!(
+        "avail-offline-{}.json",
+        &envelope.checksum()[..envelope.checksum().len().min(16)]
+    ));
+    std::fs::write(&path, serde_json::to_string_pretty(&envelope)?)?;
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn import_offline_envelope(path: PathBuf) -> AvailResult<OfflineEnvelope> {
+    let envelope: OfflineEnvelope = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+    let network = get_network()?;
+
+    match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => envelope.verify::<Testnet3>()?,
+        _ => envelope.verify::<Testnet3>()?,
+    };
+
+    Ok(envelope)
+}
+
+#[cfg(test)]
+mod offline_signing_tests {
+    use super::*;
+    use crate::services::account::signer::SoftwareSigner;
+    use avail_common::models::constants::TESTNET_PRIVATE_KEY;
+    use snarkvm::prelude::{Address, PrivateKey};
+
+    fn transfer_request(amount: &str, fee: u64) -> UnsignedExecution {
+        let private_key = PrivateKey::<Testnet3>::from_str(TESTNET_PRIVATE_KEY).unwrap();
+        let address = Address::<Testnet3>::try_from(&private_key).unwrap();
+
+        UnsignedExecution::new(
+            address.to_string(),
+            "credits.aleo".to_string(),
+            "transfer_public".to_string(),
+            vec![address.to_string(), amount.to_string()],
+            vec![],
+            fee,
+            None,
+            String::new(),
+            vec![],
+            "pending-id".to_string(),
+        )
+    }
+
+    fn authorize(
+        request: &UnsignedExecution,
+    ) -> (Authorization<Testnet3>, Authorization<Testnet3>) {
+        let signer = SoftwareSigner::<Testnet3, AleoV0>::new(
+            PrivateKey::<Testnet3>::from_str(TESTNET_PRIVATE_KEY).unwrap(),
+        );
+        let (input_values, _input_nonces, _recipient_address, _amount) =
+            parse_inputs::<Testnet3>(request.inputs().clone(), request.function_id()).unwrap();
+
+        authorize_execution::<Testnet3>(
+            &signer,
+            &execution_vm::<Testnet3>(&[]).unwrap(),
+            &ProgramID::<Testnet3>::from_str(request.program_id()).unwrap(),
+            &Identifier::<Testnet3>::from_str(request.function_id()).unwrap(),
+            &input_values,
+            request.fee(),
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_check_signed_authorizations() {
+        let request = transfer_request("1000000u64", 300000);
+        let (execution, fee) = authorize(&request);
+
+        assert!(check_signed_authorizations::<Testnet3>(&request, &execution, &fee).is_ok());
+
+        // inputs or a fee other than the exported ones are rejected
+        let other_amount = transfer_request("9000000u64", 300000);
+        assert!(check_signed_authorizations::<Testnet3>(&other_amount, &execution, &fee).is_err());
+
+        let other_fee = transfer_request("1000000u64", 100);
+        assert!(check_signed_authorizations::<Testnet3>(&other_fee, &execution, &fee).is_err());
+
+        // so is a fee authorized for another execution
+        let (_other_execution, unrelated_fee) = authorize(&other_amount);
+        assert!(
+            check_signed_authorizations::<Testnet3>(&request, &execution, &unrelated_fee).is_err()
+        );
+    }
+}
@@ -0,0 +1,540 @@
+use once_cell::sync::Lazy;
+use snarkvm::prelude::*;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use crate::api::{aleo_client::setup_client, chain_provider::ChainProvider};
+use crate::models::wallet_connect::program_interface::{
+    FunctionInterface, ParameterInterface, ParameterVisibility, ProgramInterface,
+};
+use crate::services::local_storage::{
+    persistent_storage::{get_address, get_network},
+    storage_api::records::is_record_spent_local,
+};
+
+use avail_common::{
+    errors::{AvailError, AvailErrorType, AvailResult},
+    models::network::SupportedNetworks,
+};
+
+/* --Program ABI-- */
+
+/// Sources of deployed programs by network and program id, a deployed program never changes
+static PROGRAMS: Lazy<RwLock<HashMap<String, String>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+fn cache_key<N: Network>(program_id: &ProgramID<N>) -> String {
+    format!("{}/{}", N::ID, program_id)
+}
+
+fn parse_program_id<N: Network>(program_id: &str) -> AvailResult<ProgramID<N>> {
+    match ProgramID::<N>::from_str(program_id) {
+        Ok(program_id) => Ok(program_id),
+        Err(e) => Err(AvailError::new(
+            AvailErrorType::Validation,
+            format!("Invalid program id {}: {}", program_id, e),
+            format!("'{}' is not a valid program id.", program_id),
+        )),
+    }
+}
+
+/// Fetches a deployed program, only the first fetch goes to the network
+pub fn get_program_cached<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+    program_id: &ProgramID<N>,
+) -> AvailResult<Program<N>> {
+    let key = cache_key::<N>(program_id);
+
+    if let Some(source) = PROGRAMS.read().unwrap().get(&key) {
+        if let Ok(program) = Program::<N>::from_str(source) {
+            return Ok(program);
+        }
+    }
+
+    let program = api_client.require_program(program_id)?;
+    PROGRAMS.write().unwrap().insert(key, program.to_string());
+
+    Ok(program)
+}
+
+fn describe_value_type<N: Network>(value_type: &ValueType<N>) -> ParameterInterface {
+    let (value_type, visibility) = match value_type {
+        ValueType::Constant(plaintext_type) => {
+            (plaintext_type.to_string(), ParameterVisibility::Constant)
+        }
+        ValueType::Public(plaintext_type) => {
+            (plaintext_type.to_string(), ParameterVisibility::Public)
+        }
+        ValueType::Private(plaintext_type) => {
+            (plaintext_type.to_string(), ParameterVisibility::Private)
+        }
+        ValueType::Record(record_name) => (record_name.to_string(), ParameterVisibility::Record),
+        ValueType::ExternalRecord(locator) => {
+            (locator.to_string(), ParameterVisibility::ExternalRecord)
+        }
+        ValueType::Future(locator) => (locator.to_string(), ParameterVisibility::Future),
+    };
+
+    ParameterInterface {
+        value_type,
+        visibility,
+    }
+}
+
+/// Describes the inputs and outputs of every function in a program
+pub fn describe_program<N: Network>(program: &Program<N>) -> ProgramInterface {
+    ProgramInterface {
+        program_id: program.id().to_string(),
+        functions: program
+            .functions()
+            .values()
+            .map(|function| FunctionInterface {
+                name: function.name().to_string(),
+                inputs: function
+                    .inputs()
+                    .iter()
+                    .map(|input| describe_value_type(input.value_type()))
+                    .collect(),
+                outputs: function
+                    .outputs()
+                    .iter()
+                    .map(|output| describe_value_type(output.value_type()))
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+fn matches_plaintext_type<N: Network>(
+    program: &Program<N>,
+    plaintext_type: &PlaintextType<N>,
+    plaintext: &Plaintext<N>,
+) -> bool {
+    match (plaintext_type, plaintext) {
+        (PlaintextType::Literal(literal_type), Plaintext::Literal(literal, _)) => {
+            literal.to_type() == *literal_type
+        }
+        (PlaintextType::Struct(struct_name), Plaintext::Struct(members, _)) => {
+            match program.get_struct(struct_name) {
+                Ok(struct_type) => {
+                    struct_type.members().len() == members.len()
+                        && struct_type.members().iter().all(|(name, member_type)| {
+                            match members.get(name) {
+                                Some(member) => {
+                                    matches_plaintext_type(program, member_type, member)
+                                }
+                                None => false,
+                            }
+                        })
+                }
+                Err(_) => false,
+            }
+        }
+        (PlaintextType::Array(array_type), Plaintext::Array(elements, _)) => {
+            elements.len() == **array_type.length() as usize
+                && elements.iter().all(|element| {
+                    matches_plaintext_type(program, array_type.next_element_type(), element)
+                })
+        }
+        _ => false,
+    }
+}
+
+/// Whether a record has the owner visibility and entries of the record type declared in its program
+fn matches_record_type<N: Network>(
+    program: &Program<N>,
+    record_name: &Identifier<N>,
+    record: &Record<N, Plaintext<N>>,
+) -> bool {
+    let record_type = match program.get_record(record_name) {
+        Ok(record_type) => record_type,
+        Err(_) => return false,
+    };
+
+    record_type.owner().is_public() == record.owner().is_public()
+        && record_type.entries().len() == record.data().len()
+        && record_type.entries().iter().all(|(name, entry_type)| {
+            match (entry_type, record.data().get(name)) {
+                (EntryType::Constant(plaintext_type), Some(Entry::Constant(plaintext)))
+                | (EntryType::Public(plaintext_type), Some(Entry::Public(plaintext)))
+                | (EntryType::Private(plaintext_type), Some(Entry::Private(plaintext))) => {
+                    matches_plaintext_type(program, plaintext_type, plaintext)
+                }
+                _ => false,
+            }
+        })
+}
+
+/// Returns the input as the function expects it, None if it does not fit the type.
+/// External records are checked against the program that declares them.
+fn coerce_input<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+    program: &Program<N>,
+    value_type: &ValueType<N>,
+    input: &str,
+) -> Option<String> {
+    let plaintext_type = match value_type {
+        ValueType::Constant(plaintext_type)
+        | ValueType::Public(plaintext_type)
+        | ValueType::Private(plaintext_type) => plaintext_type,
+        ValueType::Record(record_name) => {
+            let record = Record::<N, Plaintext<N>>::from_str(input).ok()?;
+            return matches_record_type(program, record_name, &record).then(|| input.to_string());
+        }
+        ValueType::ExternalRecord(locator) => {
+            let record = Record::<N, Plaintext<N>>::from_str(input).ok()?;
+            let external = get_program_cached::<N>(api_client, locator.program_id()).ok()?;
+            return matches_record_type(&external, locator.resource(), &record)
+                .then(|| input.to_string());
+        }
+        ValueType::Future(_) => return None,
+    };
+
+    if let Ok(plaintext) = Plaintext::<N>::from_str(input) {
+        return matches_plaintext_type(program, plaintext_type, &plaintext)
+            .then(|| input.to_string());
+    }
+
+    // dapps often send bare numbers, the literal type is appended for them
+    if let PlaintextType::Literal(literal_type) = plaintext_type {
+        let suffixed = format!("{}{}", input, literal_type);
+        if let Ok(plaintext) = Plaintext::<N>::from_str(&suffixed) {
+            if matches_plaintext_type(program, plaintext_type, &plaintext) {
+                return Some(suffixed);
+            }
+        }
+    }
+
+    None
+}
+
+fn is_record_input<N: Network>(value_type: &ValueType<N>) -> bool {
+    matches!(
+        value_type,
+        ValueType::Record(_) | ValueType::ExternalRecord(_)
+    )
+}
+
+/// Checks dapp inputs against the function signature and returns them coerced to its types
+pub fn validate_inputs<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+    program: &Program<N>,
+    function_id: &str,
+    inputs: &[String],
+) -> AvailResult<Vec<String>> {
+    let function = match Identifier::<N>::from_str(function_id)
+        .and_then(|function_name| program.get_function(&function_name))
+    {
+        Ok(function) => function,
+        Err(_) => {
+            return Err(AvailError::new(
+                AvailErrorType::Validation,
+                format!("Function {} not found in {}", function_id, program.id()),
+                format!(
+                    "The function '{}' does not exist in '{}'.",
+                    function_id,
+                    program.id()
+                ),
+            ))
+        }
+    };
+
+    if function.inputs().len() != inputs.len() {
+        return Err(AvailError::new(
+            AvailErrorType::Validation,
+            format!(
+                "{}/{} expects {} inputs, got {}",
+                program.id(),
+                function_id,
+                function.inputs().len(),
+                inputs.len()
+            ),
+            format!(
+                "'{}' expects {} inputs but {} were provided.",
+                function_id,
+                function.inputs().len(),
+                inputs.len()
+            ),
+        ));
+    }
+
+    function
+        .inputs()
+        .iter()
+        .zip(inputs.iter())
+        .enumerate()
+        .map(|(index, (expected, input))| {
+            match coerce_input(api_client, program, expected.value_type(), input.trim()) {
+                Some(coerced) => Ok(coerced),
+                None => Err(AvailError::new(
+                    AvailErrorType::Validation,
+                    format!(
+                        "Input {} of {}/{} is not a {}: {}",
+                        index,
+                        program.id(),
+                        function_id,
+                        expected.value_type(),
+                        input
+                    ),
+                    format!(
+                        "Input {} of '{}' must be a {}.",
+                        index + 1,
+                        function_id,
+                        expected.value_type()
+                    ),
+                )),
+            }
+        })
+        .collect()
+}
+
+/// The record inputs among validated inputs, with their position
+fn record_inputs<N: Network>(
+    program: &Program<N>,
+    function_id: &str,
+    inputs: &[String],
+) -> AvailResult<Vec<(usize, Record<N, Plaintext<N>>)>> {
+    let function = program.get_function(&Identifier::<N>::from_str(function_id)?)?;
+
+    function
+        .inputs()
+        .iter()
+        .zip(inputs.iter())
+        .enumerate()
+        .filter(|(_, (expected, _))| is_record_input(expected.value_type()))
+        .map(|(index, (_, input))| Ok((index, Record::<N, Plaintext<N>>::from_str(input)?)))
+        .collect()
+}
+
+/// Rejects record inputs not owned by `owner`, or already spent or in flight in another transaction
+/// as `is_spent` finds them by nonce
+fn check_record_inputs<N: Network>(
+    function_id: &str,
+    records: &[(usize, Record<N, Plaintext<N>>)],
+    owner: &Address<N>,
+    is_spent: impl Fn(&str) -> AvailResult<bool>,
+) -> AvailResult<()> {
+    for (index, record) in records {
+        if **record.owner() != *owner {
+            return Err(AvailError::new(
+                AvailErrorType::Validation,
+                format!(
+                    "Input {} of {} is a record owned by {}",
+                    index,
+                    function_id,
+                    **record.owner()
+                ),
+                format!(
+                    "Input {} of '{}' is a record this wallet does not own.",
+                    index + 1,
+                    function_id
+                ),
+            ));
+        }
+
+        if is_spent(&record.nonce().to_string())? {
+            return Err(AvailError::new(
+                AvailErrorType::Validation,
+                format!("Input {} of {} is a spent record", index, function_id),
+                format!(
+                    "Input {} of '{}' is a record that was already spent.",
+                    index + 1,
+                    function_id
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Fetches the program and validates the inputs a dapp passed for one of its functions,
+/// record inputs must be unspent records of this wallet
+pub fn prepare_inputs<N: Network>(
+    api_client: &dyn ChainProvider<N>,
+    program_id: &str,
+    function_id: &str,
+    inputs: &[String],
+) -> AvailResult<Vec<String>> {
+    let program = get_program_cached::<N>(api_client, &parse_program_id::<N>(program_id)?)?;
+
+    let inputs = validate_inputs::<N>(api_client, &program, function_id, inputs)?;
+
+    let records = record_inputs::<N>(&program, function_id, &inputs)?;
+    if !records.is_empty() {
+        check_record_inputs::<N>(
+            function_id,
+            &records,
+            &get_address::<N>()?,
+            is_record_spent_local::<N>,
+        )?;
+    }
+
+    Ok(inputs)
+}
+
+/// Functions of a deployed program with the types and visibility of their inputs and outputs
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_program_interface(program_id: &str) -> AvailResult<ProgramInterface> {
+    let network = get_network()?;
+
+    match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => get_program_interface_raw::<Testnet3>(program_id),
+        _ => get_program_interface_raw::<Testnet3>(program_id),
+    }
+}
+
+pub fn get_program_interface_raw<N: Network>(program_id: &str) -> AvailResult<ProgramInterface> {
+    let api_client = setup_client::<N>()?;
+    let program = get_program_cached::<N>(&api_client, &parse_program_id::<N>(program_id)?)?;
+
+    Ok(describe_program(&program))
+}
+
+#[cfg(test)]
+mod program_abi_tests {
+    use super::*;
+    use crate::api::chain_provider::MockChainProvider;
+    use avail_common::models::constants::TESTNET_ADDRESS;
+
+    const ABI_TEST_PROGRAM: &str = r"program abi_test.aleo;
+
+struct point:
+    x as u32;
+    y as u32;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function spend:
+    input r0 as token.record;
+    add r0.amount 1u64 into r1;
+    output r1 as u64.private;
+
+function locate:
+    input r0 as point.public;
+    input r1 as u64.private;
+    input r2 as boolean.constant;
+    add r1 r1 into r3;
+    output r3 as u64.private;
+";
+
+    fn provider() -> MockChainProvider<Testnet3> {
+        let provider = MockChainProvider::<Testnet3>::new();
+        provider.add_program(Program::<Testnet3>::from_str(ABI_TEST_PROGRAM).unwrap());
+        provider
+    }
+
+    fn inputs(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn owner() -> Address<Testnet3> {
+        Address::<Testnet3>::from_str(TESTNET_ADDRESS).unwrap()
+    }
+
+    fn record(owner: &str, entry: &str) -> String {
+        format!(
+            "{{ owner: {}.private, {}: 5u64.private, _nonce: {}.public }}",
+            owner,
+            entry,
+            Group::<Testnet3>::generator()
+        )
+    }
+
+    #[test]
+    fn test_describe_program() {
+        let program = Program::<Testnet3>::from_str(ABI_TEST_PROGRAM).unwrap();
+        let interface = describe_program(&program);
+
+        let locate = interface.function("locate").unwrap();
+        assert_eq!(interface.program_id, "abi_test.aleo");
+        assert_eq!(locate.inputs.len(), 3);
+        assert_eq!(
+            interface.function("spend").unwrap().inputs[0].visibility,
+            ParameterVisibility::Record
+        );
+        assert_eq!(locate.inputs[0].value_type, "point");
+        assert_eq!(locate.inputs[0].visibility, ParameterVisibility::Public);
+        assert_eq!(locate.inputs[2].visibility, ParameterVisibility::Constant);
+        assert_eq!(locate.outputs[0].value_type, "u64");
+        assert_eq!(locate.outputs[0].visibility, ParameterVisibility::Private);
+    }
+
+    #[test]
+    fn test_validate_and_coerce_inputs() {
+        let program = Program::<Testnet3>::from_str(ABI_TEST_PROGRAM).unwrap();
+        let provider = provider();
+        let validate = |function_id: &str, values: &[&str]| {
+            validate_inputs::<Testnet3>(&provider, &program, function_id, &inputs(values))
+        };
+
+        let coerced = validate("locate", &["{ x: 1u32, y: 2u32 }", " 10 ", "true"]).unwrap();
+        assert_eq!(coerced[1], "10u64");
+
+        let wrong_type =
+            validate("locate", &["{ x: 1u32, y: 2u32 }", "10u32", "true"]).unwrap_err();
+        assert_eq!(wrong_type.error_type, AvailErrorType::Validation);
+        assert!(wrong_type.external_msg.contains("Input 2"));
+
+        assert!(validate("locate", &["{ x: 1u32 }", "10u64", "true"]).is_err());
+        assert!(validate("locate", &["10u64"]).is_err());
+        assert!(validate("missing", &[]).is_err());
+    }
+
+    #[test]
+    fn test_record_inputs() {
+        let program = Program::<Testnet3>::from_str(ABI_TEST_PROGRAM).unwrap();
+        let provider = provider();
+        let validate = |input: String| {
+            let inputs = validate_inputs::<Testnet3>(&provider, &program, "spend", &[input])?;
+            let records = record_inputs::<Testnet3>(&program, "spend", &inputs)?;
+            check_record_inputs::<Testnet3>("spend", &records, &owner(), |_| Ok(false))?;
+            Ok::<Vec<String>, AvailError>(inputs)
+        };
+
+        let token = record(TESTNET_ADDRESS, "amount");
+        assert_eq!(validate(token.clone()).unwrap(), vec![token.clone()]);
+
+        // a record of another type, e.g. a credits record
+        let wrong_record = validate(record(TESTNET_ADDRESS, "microcredits")).unwrap_err();
+        assert!(wrong_record.external_msg.contains("must be a"));
+
+        let not_owned = validate(record(
+            "aleo1rhgdu77hgyqd3xjj8ucu3jj9r2krwz6mnzyd80gncr5fxcwlh5rsvzp9px",
+            "amount",
+        ))
+        .unwrap_err();
+        assert_eq!(not_owned.error_type, AvailErrorType::Validation);
+        assert!(not_owned.external_msg.contains("does not own"));
+
+        let records = record_inputs::<Testnet3>(&program, "spend", &[token]).unwrap();
+        let spent =
+            check_record_inputs::<Testnet3>("spend", &records, &owner(), |_| Ok(true)).unwrap_err();
+        assert!(spent.external_msg.contains("already spent"));
+    }
+
+    #[test]
+    fn test_programs_are_cached() {
+        let program_id = ProgramID::<Testnet3>::from_str("abi_test.aleo").unwrap();
+        get_program_cached::<Testnet3>(&provider(), &program_id).unwrap();
+
+        // served from the cache even though this provider never saw the program
+        let empty = MockChainProvider::<Testnet3>::new();
+        let program = get_program_cached::<Testnet3>(&empty, &program_id).unwrap();
+        assert_eq!(program.id(), &program_id);
+
+        let coerced = prepare_inputs::<Testnet3>(
+            &empty,
+            "credits.aleo",
+            "transfer_public",
+            &inputs(&[
+                "aleo1rhgdu77hgyqd3xjj8ucu3jj9r2krwz6mnzyd80gncr5fxcwlh5rsvzp9px",
+                "5",
+            ]),
+        )
+        .unwrap();
+        assert_eq!(coerced[1], "5u64");
+    }
+}
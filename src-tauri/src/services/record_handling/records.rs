@@ -6,7 +6,6 @@ use snarkvm::{
     prelude::{ConfirmedTransaction, Network, Plaintext, Record},
 };
 use std::ops::Sub;
-use tauri::{Manager, Window};
 
 use rayon::prelude::*;
 use std::sync::{
@@ -16,8 +15,13 @@ use std::sync::{
 use std::time::Duration;
 
 use crate::{
-    api::aleo_client::{setup_client, setup_local_client},
-    helpers::utils::get_timestamp_from_i64,
+    api::{
+        aleo_client::{setup_client, setup_local_client},
+        chain_provider::ChainClient,
+        resilience::{breaker, is_retryable},
+    },
+    helpers::{events::EventEmitter, utils::get_timestamp_from_i64},
+    models::resilience::NetworkService,
     models::wallet_connect::records::{GetRecordsRequest, RecordFilterType, RecordsFilter},
     services::{
         local_storage::{
@@ -35,6 +39,7 @@ use crate::{
                     get_unconfirmed_and_failed_transaction_ids,
                 },
             },
+            token_registry::discover_tokens,
         },
         record_handling::utils::{
             get_executed_transitions, handle_deployment_confirmed, handle_deployment_rejection,
@@ -50,11 +55,34 @@ use avail_common::{
     models::encrypted_data::{EncryptedData, RecordTypeCommon, TransactionState},
 };
 
+/// Fetches a scan batch, falling back to one block at a time when the range keeps failing,
+/// e.g. a response too large to arrive within the timeout
+fn get_batch_blocks<N: Network>(
+    api_client: &ChainClient<N>,
+    start_height: u32,
+    end_height: u32,
+) -> AvailResult<Vec<Block<N>>> {
+    match api_client.get_blocks(start_height, end_height) {
+        Ok(blocks) => Ok(blocks),
+        Err(e) if is_retryable(&e) && !breaker(NetworkService::Node).is_open() => {
+            println!(
+                "Fetching blocks {} to {} one at a time: {}",
+                start_height, end_height, e.internal_msg
+            );
+
+            (start_height..end_height)
+                .map(|height| api_client.get_block(height))
+                .collect()
+        }
+        Err(e) => Err(e),
+    }
+}
+
 /// Scans the blockchain for new records, distills record pointers, transition pointer and tags, and returns them
 pub fn get_records<N: Network>(
     last_sync: u32,
     height: u32,
-    window: Option<Window>,
+    window: Option<EventEmitter>,
 ) -> AvailResult<bool> {
     let view_key = VIEWSESSION.get_instance::<N>()?;
     let address = view_key.to_address();
@@ -148,7 +176,7 @@ pub fn get_records<N: Network>(
         .map_with(
             processed_blocks.clone(),
             |processed_counter: &mut Arc<AtomicUsize>, (start_height, end_height)| {
-                let blocks = api_client.get_blocks(start_height, end_height)?;
+                let blocks = get_batch_blocks(&api_client, start_height, end_height)?;
 
                 for block in blocks {
                     // Check for deployment transactions
@@ -557,6 +585,12 @@ pub fn get_records<N: Network>(
         )
         .collect::<AvailResult<Vec<()>>>()?;
 
+    // look up metadata of held tokens that are not resolved yet, including ones from earlier scans
+    match discover_tokens::<N>() {
+        Ok(tokens) => println!("Discovered tokens: {:?}", tokens),
+        Err(e) => println!("Error discovering tokens: {:?}", e),
+    }
+
     let found_flag = *found_shared_state.lock().unwrap();
 
     Ok(found_flag)
@@ -617,6 +651,24 @@ pub fn find_aleo_credits_record_to_spend<N: Network>(
     // find first record that satisfies the amount required
 }
 
+/// Amount of the largest unspent aleo credits record, the most a private fee can be
+pub fn largest_credits_record_amount<N: Network>() -> AvailResult<u64> {
+    let address = get_address_string()?;
+    let (record_pointers, _encrypted_record_ids) =
+        get_record_pointers_for_record_type::<N>(RecordTypeCommon::AleoCredits, &address)?;
+
+    let mut largest = 0u64;
+
+    for record in record_pointers
+        .iter()
+        .filter(|record| !record.metadata.spent)
+    {
+        largest = largest.max(record.to_record()?.microcredits()?);
+    }
+
+    Ok(largest)
+}
+
 pub fn find_tokens_to_spend<N: Network>(
     asset_id: &str,
     amount: &u64,
@@ -5,28 +5,27 @@ use uuid::Uuid;
 use crate::{
     api::{
         aleo_client::{setup_client, setup_local_client},
-        encrypted_data::{
-            delete_invalid_transactions_in, get_new_transaction_messages, post_encrypted_data,
-            synced,
-        },
+        encrypted_data::get_new_transaction_messages,
     },
     helpers::utils::get_timestamp_from_i64_utc,
     models::event::TxScanResponse,
+    models::outbox::OutboxAction,
     models::pointers::message::TransactionMessage,
     services::local_storage::{
-        encrypted_data::{
-            get_encrypted_data_to_backup, get_encrypted_data_to_update,
-            update_encrypted_data_synced_on_by_id,
-        },
-        storage_api::records::{encrypt_and_store_records, update_records_spent_backup},
+        encrypted_data::{get_encrypted_data_to_backup, get_encrypted_data_to_update},
+        storage_api::records::encrypt_and_store_records,
     },
+    services::outbox::{is_deferrable, send_or_queue},
 };
 
 use std::str::FromStr;
 
 use avail_common::{
     errors::{AvailError, AvailErrorType, AvailResult},
-    models::{encrypted_data::EncryptedData, network::SupportedNetworks},
+    models::{
+        encrypted_data::{EncryptedData, EncryptedDataRecord},
+        network::SupportedNetworks,
+    },
 };
 
 use crate::services::local_storage::persistent_storage::{
@@ -62,6 +61,13 @@ fn process_transaction<N: Network>(
     }
 }
 
+fn to_records(encrypted_data: Vec<EncryptedData>) -> Vec<EncryptedDataRecord> {
+    encrypted_data
+        .into_iter()
+        .map(EncryptedDataRecord::from)
+        .collect()
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn txs_sync() -> AvailResult<TxScanResponse> {
     let network = get_network()?;
@@ -84,7 +90,12 @@ pub async fn txs_sync_raw<N: Network>() -> AvailResult<TxScanResponse> {
     let address = get_address::<N>()?;
     let latest_height = api_client.latest_height()?;
 
-    let (txs_in, ids) = get_new_transaction_messages::<N>().await?;
+    // Messages wait on the server until it is reachable again
+    let (txs_in, ids) = match get_new_transaction_messages::<N>().await {
+        Ok(messages) => messages,
+        Err(e) if is_deferrable(&e) => (vec![], vec![]),
+        Err(e) => return Err(e),
+    };
 
     println!("Transactions In: {:?}", txs_in);
 
@@ -123,18 +134,9 @@ pub async fn txs_sync_raw<N: Network>() -> AvailResult<TxScanResponse> {
             .filter_map(|(_, _, invalid_id)| *invalid_id)
             .collect::<Vec<Uuid>>();
 
-        let encrypted_record_ids = post_encrypted_data(records_to_post).await?;
-        let encrypted_transition_ids = post_encrypted_data(transitions_to_post).await?;
-
-        encrypted_record_ids
-            .iter()
-            .map(|id| update_encrypted_data_synced_on_by_id(id))
-            .collect::<AvailResult<Vec<()>>>()?;
-
-        encrypted_transition_ids
-            .iter()
-            .map(|id| update_encrypted_data_synced_on_by_id(id))
-            .collect::<AvailResult<Vec<()>>>()?;
+        // Posted later from the outbox if the server can't be reached
+        send_or_queue(OutboxAction::PostData(to_records(records_to_post))).await?;
+        send_or_queue(OutboxAction::PostData(to_records(transitions_to_post))).await?;
 
         let ids_to_sync = ids
             .iter()
@@ -142,11 +144,11 @@ pub async fn txs_sync_raw<N: Network>() -> AvailResult<TxScanResponse> {
             .copied()
             .collect::<Vec<Uuid>>();
 
-        synced(ids_to_sync).await?;
-        delete_invalid_transactions_in(invalid_ids).await?;
+        send_or_queue(OutboxAction::MarkSynced(ids_to_sync)).await?;
+        send_or_queue(OutboxAction::DeleteTransactionMessages(invalid_ids)).await?;
     } else {
         //NOTE - delete encrypted messages (The name may be misleading, but this is just to clear the encrypted transaction messages sent after they are received.)
-        delete_invalid_transactions_in(ids).await?;
+        send_or_queue(OutboxAction::DeleteTransactionMessages(ids)).await?;
     }
 
     let res = TxScanResponse::new(true, latest_height);
@@ -167,7 +169,7 @@ pub async fn blocks_sync(height: u32, window: Window) -> AvailResult<bool> {
     let task = tokio::spawn(async move {
         let found_flag = match SupportedNetworks::from_str(network.as_str())? {
             SupportedNetworks::Testnet3 => {
-                get_records::<Testnet3>(last_sync, height, Some(window))?
+                get_records::<Testnet3>(last_sync, height, Some(window.into()))?
             }
             _ => {
                 return Err(AvailError::new(
@@ -218,24 +220,15 @@ pub async fn sync_backup() -> AvailResult<()> {
             .map(|id| id.to_string())
             .collect::<Vec<String>>();
 
-        // post spent updates
-        match SupportedNetworks::from_str(network.as_str())? {
-            SupportedNetworks::Testnet3 => {
-                update_records_spent_backup::<Testnet3>(ids_to_update).await?
-            }
-            _ => update_records_spent_backup::<Testnet3>(ids_to_update).await?,
-        };
+        // post spent updates, queued in the outbox if the server can't be reached
+        send_or_queue(OutboxAction::UpdateData(ids_to_update)).await?;
 
         /* Handle posting new found encrypted data */
         let encrypted_data = get_encrypted_data_to_backup(last_backup_sync)?;
 
         println!("Encrypted Data: {:?}", encrypted_data);
 
-        let ids = post_encrypted_data(encrypted_data).await?;
-
-        ids.iter()
-            .map(|id| update_encrypted_data_synced_on_by_id(id))
-            .collect::<AvailResult<Vec<()>>>()?;
+        send_or_queue(OutboxAction::PostData(to_records(encrypted_data))).await?;
 
         let last_sync = get_last_sync()?;
 
@@ -301,6 +294,7 @@ pub async fn blocks_sync_test(height: u32) -> AvailResult<bool> {
 mod test {
     use super::*;
 
+    use crate::api::aleo_client::setup_node_client;
     use crate::api::encrypted_data::delete_all_server_storage;
     use crate::api::user::delete_user;
     use crate::models::{storage::languages::Languages, transfer::TransferRequest};
@@ -325,6 +319,7 @@ mod test {
             record_handling::{records::find_aleo_credits_record_to_spend, transfer::transfer_raw},
         },
     };
+    use snarkvm::circuit::AleoV0;
 
     use avail_common::{
         aleo_tools::program_manager::{ProgramManager, TransferType},
@@ -441,7 +436,9 @@ mod test {
             asset_id,
         );
 
-        transfer_raw::<Testnet3>(request, None).await.unwrap();
+        transfer_raw::<Testnet3, AleoV0>(request, None)
+            .await
+            .unwrap();
 
         let recipient_view_key = ViewKey::<Testnet3>::from_str(TESTNET3_VIEW_KEY).unwrap();
 
@@ -544,7 +541,9 @@ mod test {
             asset_id,
         );
 
-        transfer_raw::<Testnet3>(request, None).await.unwrap();
+        transfer_raw::<Testnet3, AleoV0>(request, None)
+            .await
+            .unwrap();
 
         /* --Setup Done-- */
 
@@ -578,7 +577,7 @@ output r1 as u32.public;",
 
         let pk2 = PrivateKey::<Testnet3>::from_str(TESTNET3_PRIVATE_KEY).unwrap();
 
-        let api_client = setup_client::<Testnet3>().unwrap();
+        let api_client = setup_node_client::<Testnet3>().unwrap();
 
         let mut program_manager =
             ProgramManager::<Testnet3>::new(Some(pk2), None, Some(api_client.clone()), None)
@@ -666,7 +665,9 @@ output r1 as u32.public;",
             asset_id,
         );
 
-        transfer_raw::<Testnet3>(request, None).await.unwrap();
+        transfer_raw::<Testnet3, AleoV0>(request, None)
+            .await
+            .unwrap();
 
         /* --Setup Done-- */
 
@@ -793,7 +794,9 @@ output r1 as u32.public;",
             asset_id,
         );
 
-        transfer_raw::<Testnet3>(request, None).await.unwrap();
+        transfer_raw::<Testnet3, AleoV0>(request, None)
+            .await
+            .unwrap();
 
         /* --Setup Done-- */
 
@@ -1,29 +1,36 @@
 use chrono::{DateTime, Local};
 
 use dirs;
-use snarkvm::{ledger::transactions::ConfirmedTransaction, prelude::*};
-use tauri::{Manager, Window};
+use snarkvm::circuit::{Aleo, AleoV0, Environment};
+use snarkvm::prelude::*;
+use tauri::Window;
 use tauri_plugin_http::reqwest;
 
 use std::fs;
-use std::{ops::Add, str::FromStr};
-use tokio::time::{Duration, Instant};
+use std::str::FromStr;
 
 use crate::api::aleo_client::setup_client;
+use crate::api::chain_provider::ChainProvider;
+use crate::helpers::events::EventEmitter;
+use crate::services::account::signer::{get_signer, Signer};
 use crate::services::local_storage::encrypted_data::update_encrypted_transaction_state_by_id;
 use crate::{
-    helpers::utils::get_timestamp_from_i64,
-    services::authentication::session::get_session_after_creation,
-    services::local_storage::storage_api::records::update_record_spent_local,
+    services::authentication::session::get_session_with_signer,
+    services::local_storage::storage_api::records::{
+        get_record_pointer, update_record_spent_local,
+    },
 };
 
-use crate::models::{pointers::transaction::TransactionPointer, transfer::TransferRequest};
+use crate::models::{
+    pointers::transaction::TransactionPointer,
+    transfer::{NftTransferPrivacy, NftTransferRequest, TransferRequest},
+};
 
 use avail_common::{
-    aleo_tools::program_manager::{ProgramManager, TransferType},
+    aleo_tools::program_manager::TransferType,
     errors::{AvailError, AvailErrorType, AvailResult},
     models::{
-        encrypted_data::{EventTypeCommon, TransactionState},
+        encrypted_data::{EventTypeCommon, RecordTypeCommon, TransactionState},
         network::SupportedNetworks,
     },
 };
@@ -31,9 +38,10 @@ use avail_common::{
 use crate::services::local_storage::{
     persistent_storage::{get_address, get_network},
     session::password::PASS,
-    utils::get_private_key,
 };
 
+use super::confirmation::{wait_for_confirmation, CONFIRMATION_TIMEOUT};
+use super::execution::execute_with_signer;
 use super::records::*;
 use super::utils::{get_address_from_recipient, handle_encrypted_storage_and_message};
 
@@ -44,12 +52,16 @@ pub async fn transfer(request: TransferRequest, window: Window) -> AvailResult<S
 
     let transfer_task_res = match SupportedNetworks::from_str(&network)? {
         SupportedNetworks::Testnet3 => {
-            tokio::task::spawn_blocking(move || transfer_raw::<Testnet3>(request, Some(window)))
-                .await?
+            tokio::task::spawn_blocking(move || {
+                transfer_raw::<Testnet3, AleoV0>(request, Some(window.into()))
+            })
+            .await?
         }
         _ => {
-            tokio::task::spawn_blocking(move || transfer_raw::<Testnet3>(request, Some(window)))
-                .await?
+            tokio::task::spawn_blocking(move || {
+                transfer_raw::<Testnet3, AleoV0>(request, Some(window.into()))
+            })
+            .await?
         }
     }
     .await?;
@@ -59,13 +71,13 @@ pub async fn transfer(request: TransferRequest, window: Window) -> AvailResult<S
     Ok(format!("Transaction '{}' Successful", transfer_task_res))
 }
 
-pub async fn transfer_raw<N: Network>(
+pub async fn transfer_raw<N: Network, A: Aleo + Environment<Network = N>>(
     request: TransferRequest,
-    window: Option<Window>,
+    window: Option<EventEmitter>,
 ) -> AvailResult<String> {
     match request.transfer_type() {
         TransferType::Private => {
-            transfer_private_util::<N>(
+            transfer_private_util::<N, A>(
                 request.asset_id().as_str(),
                 request.amount(),
                 request.fee(),
@@ -78,7 +90,7 @@ pub async fn transfer_raw<N: Network>(
             .await
         }
         TransferType::PublicToPrivate => {
-            transfer_public_to_private_util::<N>(
+            transfer_public_to_private_util::<N, A>(
                 request.asset_id().as_str(),
                 request.amount(),
                 request.fee(),
@@ -91,7 +103,7 @@ pub async fn transfer_raw<N: Network>(
             .await
         }
         TransferType::PrivateToPublic => {
-            transfer_private_to_public_util::<N>(
+            transfer_private_to_public_util::<N, A>(
                 request.asset_id().as_str(),
                 request.amount(),
                 request.fee(),
@@ -104,7 +116,7 @@ pub async fn transfer_raw<N: Network>(
             .await
         }
         TransferType::Public => {
-            transfer_public::<N>(
+            transfer_public::<N, A>(
                 request.asset_id().as_str(),
                 request.amount(),
                 request.fee(),
@@ -119,8 +131,57 @@ pub async fn transfer_raw<N: Network>(
     }
 }
 
+/// Calls the token program's transfer function matching the transfer type through the signer,
+/// token programs share the credits.aleo transfer signatures
+fn transfer_with_signer<N: Network>(
+    signer: &dyn Signer<N>,
+    api_client: &dyn ChainProvider<N>,
+    program_id: &str,
+    transfer_type: TransferType,
+    recipient: Address<N>,
+    amount: u64,
+    fee: u64,
+    token_record: Option<Record<N, Plaintext<N>>>,
+    fee_record: Option<Record<N, Plaintext<N>>>,
+) -> AvailResult<N::TransactionID> {
+    let recipient = Value::<N>::from_str(&recipient.to_string())?;
+    let amount = Value::<N>::from_str(&format!("{}u64", amount))?;
+
+    let (function_id, inputs) = match (transfer_type, token_record) {
+        (TransferType::Private, Some(record)) => (
+            "transfer_private",
+            vec![Value::Record(record), recipient, amount],
+        ),
+        (TransferType::PrivateToPublic, Some(record)) => (
+            "transfer_private_to_public",
+            vec![Value::Record(record), recipient, amount],
+        ),
+        (TransferType::PublicToPrivate, _) => {
+            ("transfer_public_to_private", vec![recipient, amount])
+        }
+        (TransferType::Public, _) => ("transfer_public", vec![recipient, amount]),
+        (_, None) => {
+            return Err(AvailError::new(
+                AvailErrorType::Internal,
+                "Private transfer without a token record".to_string(),
+                "No record found to transfer from".to_string(),
+            ))
+        }
+    };
+
+    execute_with_signer::<N>(
+        signer,
+        api_client,
+        program_id,
+        function_id,
+        &inputs,
+        fee,
+        fee_record,
+    )
+}
+
 /// Transfer tokens privately
-async fn transfer_private_util<N: Network>(
+async fn transfer_private_util<N: Network, A: Aleo + Environment<Network = N>>(
     asset_id: &str,
     amount: &u64,
     fee: &u64,
@@ -128,23 +189,20 @@ async fn transfer_private_util<N: Network>(
     message: Option<String>,
     to: &str,
     password: Option<String>,
-    window: Option<Window>,
+    window: Option<EventEmitter>,
 ) -> AvailResult<String> {
     let api_client = setup_client::<N>()?;
 
     let sender_address = get_address::<N>()?;
 
-    let private_key = get_private_key::<N>(password)?;
+    let signer = get_signer::<N, A>(password)?;
 
     //extend session auth
-    let _session_task = get_session_after_creation::<N>(&private_key).await?;
+    let _session_task = get_session_with_signer::<N>(signer.as_ref()).await?;
 
     let recipient = get_address_from_recipient::<N>(to).await?;
     let mut record_nonces: Vec<String> = vec![];
 
-    let program_manager =
-        ProgramManager::<N>::new(Some(private_key), None, Some(api_client.clone()), None).unwrap();
-
     // get required records if private tx
     let (token_record, _token_commitment, token_id) =
         find_tokens_to_spend::<N>(asset_id, amount, vec![])?;
@@ -219,15 +277,16 @@ async fn transfer_private_util<N: Network>(
         };
     };
 
-    let transaction_id = match program_manager.transfer(
+    let transaction_id = match transfer_with_signer::<N>(
+        signer.as_ref(),
+        &api_client,
+        &program_id,
+        TransferType::Private,
+        recipient,
         amount,
         fee,
-        recipient,
-        TransferType::Private,
-        None,
         Some(token_record),
         fee_record.clone(),
-        &program_id,
     ) {
         Ok(tx_id) => tx_id,
         Err(e) => {
@@ -296,7 +355,7 @@ async fn transfer_private_util<N: Network>(
 }
 
 /// Convert public tokens to private tokens
-async fn transfer_public_to_private_util<N: Network>(
+async fn transfer_public_to_private_util<N: Network, A: Aleo + Environment<Network = N>>(
     asset_id: &str,
     amount: &u64,
     fee: &u64,
@@ -304,20 +363,17 @@ async fn transfer_public_to_private_util<N: Network>(
     message: Option<String>,
     to: &str,
     password: Option<String>,
-    window: Option<Window>,
+    window: Option<EventEmitter>,
 ) -> AvailResult<String> {
     let api_client = setup_client::<N>()?;
     let sender_address = get_address::<N>()?;
-    let private_key = get_private_key::<N>(password)?;
+    let signer = get_signer::<N, A>(password)?;
 
     //extend session auth
-    let _session_task = get_session_after_creation::<N>(&private_key).await?;
+    let _session_task = get_session_with_signer::<N>(signer.as_ref()).await?;
     let recipient = get_address_from_recipient::<N>(to).await?;
     let mut record_nonces: Vec<String> = vec![];
 
-    let program_manager =
-        ProgramManager::<N>::new(Some(private_key), None, Some(api_client.clone()), None)?;
-
     let program_id = format!("{}.aleo", asset_id);
 
     //get required records if private fee
@@ -386,15 +442,16 @@ async fn transfer_public_to_private_util<N: Network>(
         };
     };
 
-    let transaction_id = match program_manager.transfer(
-        amount.to_owned(),
-        fee.to_owned(),
-        recipient,
+    let transaction_id = match transfer_with_signer::<N>(
+        signer.as_ref(),
+        &api_client,
+        &program_id,
         TransferType::PublicToPrivate,
-        None,
+        recipient,
+        *amount,
+        *fee,
         None,
         fee_record.clone(),
-        &program_id,
     ) {
         Ok(tx_id) => tx_id,
         Err(e) => {
@@ -453,7 +510,7 @@ async fn transfer_public_to_private_util<N: Network>(
 }
 
 /// Convert private tokens to public tokens
-async fn transfer_private_to_public_util<N: Network>(
+async fn transfer_private_to_public_util<N: Network, A: Aleo + Environment<Network = N>>(
     asset_id: &str,
     amount: &u64,
     fee: &u64,
@@ -461,20 +518,17 @@ async fn transfer_private_to_public_util<N: Network>(
     message: Option<String>,
     to: &str,
     password: Option<String>,
-    window: Option<Window>,
+    window: Option<EventEmitter>,
 ) -> AvailResult<String> {
     let api_client = setup_client::<N>()?;
     let sender_address = get_address::<N>()?;
-    let private_key = get_private_key::<N>(password)?;
+    let signer = get_signer::<N, A>(password)?;
 
     //extend session auth
-    let _session_task = get_session_after_creation::<N>(&private_key).await?;
+    let _session_task = get_session_with_signer::<N>(signer.as_ref()).await?;
     let recipient = get_address_from_recipient::<N>(to).await?;
     let mut record_nonces: Vec<String> = vec![];
 
-    let program_manager =
-        ProgramManager::<N>::new(Some(private_key), None, Some(api_client.clone()), None).unwrap();
-
     // get required records if private tx
     let (token_record, _token_commitment, token_id) =
         find_tokens_to_spend::<N>(asset_id, amount, vec![])?;
@@ -549,15 +603,16 @@ async fn transfer_private_to_public_util<N: Network>(
         };
     };
 
-    let transfer_res = match program_manager.transfer(
-        amount.to_owned(),
-        fee.to_owned(),
-        recipient,
+    let transfer_res = match transfer_with_signer::<N>(
+        signer.as_ref(),
+        &api_client,
+        &program_id,
         TransferType::PrivateToPublic,
-        None,
+        recipient,
+        *amount,
+        *fee,
         Some(token_record.clone()),
         fee_record.clone(),
-        &program_id,
     ) {
         Ok(tx_id) => tx_id,
         Err(e) => {
@@ -618,7 +673,7 @@ async fn transfer_private_to_public_util<N: Network>(
 }
 
 // Transfer tokens publicly
-async fn transfer_public<N: Network>(
+async fn transfer_public<N: Network, A: Aleo + Environment<Network = N>>(
     asset_id: &str,
     amount: &u64,
     fee: &u64,
@@ -626,19 +681,16 @@ async fn transfer_public<N: Network>(
     message: Option<String>,
     to: &str,
     password: Option<String>,
-    window: Option<Window>,
+    window: Option<EventEmitter>,
 ) -> AvailResult<String> {
     let api_client = setup_client::<N>()?;
     let sender_address = get_address::<N>()?;
-    let private_key = get_private_key::<N>(password)?;
+    let signer = get_signer::<N, A>(password)?;
 
     //extend session auth
-    get_session_after_creation::<N>(&private_key).await?;
+    get_session_with_signer::<N>(signer.as_ref()).await?;
     let recipient = get_address_from_recipient::<N>(to).await?;
 
-    let program_manager =
-        ProgramManager::<N>::new(Some(private_key), None, Some(api_client.clone()), None)?;
-
     let mut record_nonces: Vec<String> = vec![];
 
     // get required records if private fee
@@ -709,15 +761,16 @@ async fn transfer_public<N: Network>(
         };
     };
 
-    let transfer_res = match program_manager.transfer(
-        amount.to_owned(),
-        fee.to_owned(),
-        recipient,
+    let transfer_res = match transfer_with_signer::<N>(
+        signer.as_ref(),
+        &api_client,
+        &program_id,
         TransferType::Public,
-        None,
+        recipient,
+        *amount,
+        *fee,
         None,
         fee_record.clone(),
-        &program_id,
     ) {
         Ok(tx_id) => tx_id,
         Err(e) => {
@@ -775,192 +828,294 @@ async fn transfer_public<N: Network>(
     Ok(format!("Transaction Id {}", transfer_res))
 }
 
-// TODO - Add timer threshold for when to stop searching for transaction, and keep in unconfirmed state
-/// Find Transaction on chain and handle state
-pub fn find_confirmed_block_height<N: Network>(
-    tx_id: N::TransactionID,
-) -> AvailResult<(
-    u32,
-    Vec<Transition<N>>,
-    DateTime<Local>,
-    TransactionState,
-    Option<N::TransactionID>,
-    Option<Execution<N>>,
-    Option<f64>,
-)> {
+/// Transfer an nft record through its collection program
+#[tauri::command(rename_all = "snake_case")]
+pub async fn transfer_nft(request: NftTransferRequest, window: Window) -> AvailResult<String> {
+    let network = get_network()?;
+
+    let transfer_task_res = match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => {
+            tokio::task::spawn_blocking(move || {
+                transfer_nft_raw::<Testnet3, AleoV0>(request, Some(window.into()))
+            })
+            .await?
+        }
+        _ => {
+            tokio::task::spawn_blocking(move || {
+                transfer_nft_raw::<Testnet3, AleoV0>(request, Some(window.into()))
+            })
+            .await?
+        }
+    }
+    .await?;
+
+    PASS.extend_session()?;
+
+    Ok(format!("Transaction '{}' Successful", transfer_task_res))
+}
+
+/// Picks the collection's transfer function for the requested privacy, in order of preference
+fn nft_transfer_function<N: Network>(
+    program: &Program<N>,
+    privacy: &NftTransferPrivacy,
+) -> AvailResult<Identifier<N>> {
+    privacy
+        .function_names()
+        .iter()
+        .filter_map(|name| Identifier::<N>::from_str(name).ok())
+        .find(|function_id| program.contains_function(function_id))
+        .ok_or_else(|| {
+            AvailError::new(
+                AvailErrorType::InvalidData,
+                format!("No transfer function found in {}", program.id()),
+                "This nft collection does not support this transfer.".to_string(),
+            )
+        })
+}
+
+/// Builds the transfer function inputs from its signature, passing the nft record and the recipient
+fn nft_transfer_inputs<N: Network>(
+    program: &Program<N>,
+    function_id: &Identifier<N>,
+    nft_record: &Record<N, Plaintext<N>>,
+    recipient: &Address<N>,
+) -> AvailResult<Vec<Value<N>>> {
+    let function = program.get_function(function_id)?;
+
+    function
+        .inputs()
+        .iter()
+        .map(|input| match input.value_type() {
+            ValueType::Record(_) => Ok(Value::Record(nft_record.clone())),
+            ValueType::Private(PlaintextType::Literal(LiteralType::Address))
+            | ValueType::Public(PlaintextType::Literal(LiteralType::Address)) => {
+                Ok(Value::from_str(&recipient.to_string())?)
+            }
+            _ => Err(AvailError::new(
+                AvailErrorType::InvalidData,
+                format!(
+                    "Unsupported input {} for nft transfer function {}",
+                    input.value_type(),
+                    function_id
+                ),
+                "This nft collection does not support transfers from the wallet.".to_string(),
+            )),
+        })
+        .collect::<AvailResult<Vec<Value<N>>>>()
+}
+
+pub async fn transfer_nft_raw<N: Network, A: Aleo + Environment<Network = N>>(
+    request: NftTransferRequest,
+    window: Option<EventEmitter>,
+) -> AvailResult<String> {
     let api_client = setup_client::<N>()?;
+    let sender_address = get_address::<N>()?;
+
+    let record_pointer = get_record_pointer::<N>(request.record_id())?;
 
-    let latest_block_height = api_client.latest_height()?;
+    if record_pointer.metadata.record_type != RecordTypeCommon::NFT {
+        return Err(AvailError::new(
+            AvailErrorType::InvalidData,
+            "Record is not an nft".to_string(),
+            "The selected record is not an nft.".to_string(),
+        ));
+    }
+
+    if record_pointer.metadata.spent {
+        return Err(AvailError::new(
+            AvailErrorType::InvalidData,
+            "Nft record already spent".to_string(),
+            "This nft has already been transferred.".to_string(),
+        ));
+    }
+
+    let signer = get_signer::<N, A>(request.password().clone())?;
+
+    //extend session auth
+    let _session_task = get_session_with_signer::<N>(signer.as_ref()).await?;
+
+    let recipient = get_address_from_recipient::<N>(request.recipient()).await?;
+
+    let program_id = record_pointer.metadata.program_id.clone();
+    let program = api_client.require_program(&ProgramID::<N>::from_str(&program_id)?)?;
 
-    let mut flag: bool = false;
-    let mut iter = latest_block_height;
+    let function_id = nft_transfer_function::<N>(&program, request.privacy())?;
 
-    let start_time = Instant::now();
-    let search_duration = Duration::from_secs(180);
+    let nft_record = record_pointer.to_record()?;
+    let nft_nonce = nft_record.nonce().to_string();
+    let mut record_nonces: Vec<String> = vec![nft_nonce.clone()];
 
-    println!("Waiting for transaction to confirm in block on chain");
-    while !flag && start_time.elapsed() < search_duration {
-        println!("Checking block {}", iter);
-        let latest_height = api_client.latest_height()?;
+    let inputs = nft_transfer_inputs::<N>(&program, &function_id, &nft_record, &recipient)?;
 
-        if iter > latest_height {
-            println!("Iter > Latest Height");
-            std::thread::sleep(std::time::Duration::from_secs(3));
-            continue;
+    let (fee_record, _fee_commitment, fee_id) = match request.fee_private() {
+        true => {
+            let (fee_record, fee_commitment, fee_id) =
+                find_aleo_credits_record_to_spend::<N>(request.fee(), vec![nft_nonce])?;
+            let fee_nonce = fee_record.nonce().to_string();
+            record_nonces.push(fee_nonce);
+            (Some(fee_record), Some(fee_commitment), Some(fee_id))
         }
+        false => (None, None, None),
+    };
 
-        let block = match api_client.get_block(iter) {
-            Ok(block) => {
-                println!("Block {} found", iter);
-                block
+    let mut pending_transaction = TransactionPointer::<N>::new(
+        Some(request.recipient().clone()),
+        None,
+        TransactionState::Processing,
+        None,
+        Some(program_id.clone()),
+        Some(function_id.to_string()),
+        vec![],
+        record_nonces,
+        Local::now(),
+        None,
+        None,
+        EventTypeCommon::Send,
+        None,
+        Some(*request.fee() as f64 / 1000000.0),
+        None,
+    );
+
+    let pending_tx_id = pending_transaction.encrypt_and_store(sender_address)?;
+
+    if let Some(window) = window.clone() {
+        match window.emit("tx_state_change", &pending_tx_id) {
+            Ok(_) => {}
+            Err(e) => {
+                return Err(AvailError::new(
+                    AvailErrorType::Internal,
+                    "Error emitting tx_state_change event".to_string(),
+                    "Error emitting transaction state".to_string(),
+                ));
             }
+        };
+    };
+
+    // update spent states, the nft leaves the gallery while the transfer is in flight
+    update_record_spent_local::<N>(request.record_id(), true)?;
+    if let Some(fee_id) = fee_id.clone() {
+        update_record_spent_local::<N>(&fee_id, true)?;
+    }
+
+    if let Some(window) = window.clone() {
+        match window.emit("nft_gallery_change", request.record_id()) {
+            Ok(_) => {}
             Err(e) => {
-                println!("Error getting block: {:?}\n", e);
-                std::thread::sleep(std::time::Duration::from_secs(3));
-                continue;
+                return Err(AvailError::new(
+                    AvailErrorType::Internal,
+                    "Error emitting nft_gallery_change event".to_string(),
+                    "Error emitting nft gallery update".to_string(),
+                ));
             }
         };
+    };
 
-        let transactions = block.transactions();
+    let transaction_id = match execute_with_signer::<N>(
+        signer.as_ref(),
+        &api_client,
+        &program_id,
+        &function_id.to_string(),
+        &inputs,
+        *request.fee(),
+        fee_record,
+    ) {
+        Ok(tx_id) => tx_id,
+        Err(e) => {
+            println!("{:?}", e);
+            update_record_spent_local::<N>(request.record_id(), false)?;
 
-        // transactions that where in excess of the max limit of the block
-        let aborted_transactions = block.aborted_transaction_ids();
+            if let Some(fee_id) = fee_id {
+                update_record_spent_local::<N>(&fee_id, false)?;
+            }
 
-        if aborted_transactions.contains(&tx_id) {
-            flag = true;
-            let timestamp = get_timestamp_from_i64(block.timestamp())?;
-            return Ok((
-                iter,
-                vec![],
-                timestamp,
-                TransactionState::Aborted,
-                None,
-                None,
+            pending_transaction.update_failed_transaction(
+                "Transaction execution failed, no records were spent.".to_string(),
                 None,
-            ));
-        }
+            );
 
-        let tx = transactions.get(&tx_id);
-
-        // TODO - Make it default to unconfirmed after 1 minute 30 seconds
-        println!("In Progress");
-        if let Some(tx) = tx {
-            println!("Transaction found");
-            // deduce state
-
-            if let ConfirmedTransaction::<N>::AcceptedDeploy(_, _, _) = tx {
-                flag = true;
-                let timestamp = get_timestamp_from_i64(block.timestamp())?;
-                let fee = tx.transaction().fee_amount()?;
-                let mut transitions: Vec<Transition<N>> = vec![];
-
-                if let Some(fee_transition) = tx.transaction().fee_transition() {
-                    let transition = fee_transition.transition();
-                    transitions.push(transition.clone());
-                }
-
-                return Ok((
-                    iter,
-                    transitions,
-                    timestamp,
-                    TransactionState::Aborted,
-                    None,
-                    None,
-                    Some(*fee as f64 / 1000000.0),
-                ));
-            } else if let ConfirmedTransaction::<N>::AcceptedExecute(_, _, _) = tx {
-                flag = true;
-                println!("C1");
-                let timestamp = get_timestamp_from_i64(block.timestamp())?;
-                println!("C2");
-                let fee = tx.transaction().fee_amount()?;
-                println!("C3");
-
-                let transitions = tx.transitions().cloned().collect::<Vec<_>>();
-                println!("C4");
-                return Ok((
-                    iter,
-                    transitions,
-                    timestamp,
-                    TransactionState::Confirmed,
-                    None,
-                    None,
-                    Some(*fee as f64 / 1000000.0),
-                ));
-            }
-        } else {
-            for tx in transactions.iter() {
-                if let ConfirmedTransaction::<N>::RejectedDeploy(_, fee_tx, _, _) = tx {
-                    if tx.to_unconfirmed_transaction_id()? == tx_id {
-                        flag = true;
-                        let timestamp = get_timestamp_from_i64(block.timestamp())?;
-                        let fee = tx.transaction().fee_amount()?;
-
-                        let mut transitions: Vec<Transition<N>> = vec![];
-
-                        if let Some(fee_transition) = tx.transaction().fee_transition() {
-                            let transition = fee_transition.transition();
-                            transitions.push(transition.clone());
-                        }
-
-                        return Ok((
-                            iter,
-                            transitions,
-                            timestamp,
-                            TransactionState::Rejected,
-                            Some(fee_tx.id()),
-                            None,
-                            Some(*fee as f64 / 1000000.0),
+            let encrypted_failed_transaction =
+                pending_transaction.to_encrypted_data(sender_address)?;
+
+            update_encrypted_transaction_state_by_id(
+                &pending_tx_id,
+                &encrypted_failed_transaction.ciphertext,
+                &encrypted_failed_transaction.nonce,
+                TransactionState::Failed,
+            )?;
+
+            if let Some(window) = window.clone() {
+                match window.emit("tx_state_change", &pending_tx_id) {
+                    Ok(_) => {}
+                    Err(e) => {
+                        return Err(AvailError::new(
+                            AvailErrorType::Internal,
+                            "Error emitting tx_state_change event".to_string(),
+                            "Error emitting transaction state".to_string(),
                         ));
                     }
-                } else if let ConfirmedTransaction::<N>::RejectedExecute(
-                    _,
-                    fee_tx,
-                    rejected_execution,
-                    _,
-                ) = tx
-                {
-                    if tx.to_unconfirmed_transaction_id()? == tx_id {
-                        flag = true;
-                        let timestamp = get_timestamp_from_i64(block.timestamp())?;
-                        let fee = tx.transaction().fee_amount()?;
-                        let transitions = tx.transitions().cloned().collect::<Vec<_>>();
-                        if let Some(rejected_execution) = rejected_execution.execution() {
-                            return Ok((
-                                iter,
-                                transitions,
-                                timestamp,
-                                TransactionState::Rejected,
-                                Some(fee_tx.id()),
-                                Some(rejected_execution.to_owned()),
-                                Some(*fee as f64 / 1000000.0),
-                            ));
-                        }
-
-                        return Ok((
-                            iter,
-                            transitions,
-                            timestamp,
-                            TransactionState::Rejected,
-                            Some(fee_tx.id()),
-                            None,
-                            Some(*fee as f64 / 1000000.0),
+                };
+
+                match window.emit("nft_gallery_change", request.record_id()) {
+                    Ok(_) => {}
+                    Err(e) => {
+                        return Err(AvailError::new(
+                            AvailErrorType::Internal,
+                            "Error emitting nft_gallery_change event".to_string(),
+                            "Error emitting nft gallery update".to_string(),
                         ));
                     }
-                }
-            }
+                };
+            };
+
+            return Err(AvailError::new(
+                AvailErrorType::Internal,
+                "Error transferring nft".to_string(),
+                format!("Error transferring nft: {:?}", e),
+            ));
         }
+    };
 
-        iter = iter.add(1);
-        std::thread::sleep(std::time::Duration::from_secs(7));
-    }
+    handle_encrypted_storage_and_message(
+        transaction_id,
+        recipient,
+        &pending_tx_id,
+        Some(request.record_id().clone()),
+        fee_id,
+        false,
+        window,
+    )
+    .await?;
+
+    Ok(format!("Transaction Id {}", transaction_id))
+}
+
+/// Waits for a broadcast transaction to settle, looking it up by id
+pub fn find_confirmed_block_height<N: Network>(
+    tx_id: N::TransactionID,
+) -> AvailResult<(
+    u32,
+    Vec<Transition<N>>,
+    DateTime<Local>,
+    TransactionState,
+    Option<N::TransactionID>,
+    Option<Execution<N>>,
+    Option<f64>,
+)> {
+    let confirmation = wait_for_confirmation::<N>(tx_id, CONFIRMATION_TIMEOUT)?;
 
-    Err(AvailError::new(
-        AvailErrorType::NotFound,
-        "Transaction is unconfirmed, this might be due to issues with the Aleo network."
-            .to_string(),
-        "Transaction is unconfirmed, this might be due to issues with the Aleo network."
-            .to_string(),
+    println!(
+        "Transaction {} settled as {:?} in block {}",
+        tx_id, confirmation.outcome, confirmation.block_height
+    );
+
+    Ok((
+        confirmation.block_height,
+        confirmation.transitions,
+        confirmation.timestamp,
+        confirmation.outcome.to_transaction_state(),
+        confirmation.fee_transaction_id,
+        confirmation.rejected_execution,
+        confirmation.fee,
     ))
 }
 
@@ -1027,6 +1182,8 @@ mod transfer_tests {
         wallet_connect::{get_event::GetEventsRequest, records::GetRecordsRequest},
     };
 
+    use crate::api::aleo_client::setup_node_client;
+    use crate::api::chain_provider::{ChainFixture, MockChainProvider};
     use crate::services::account::generation::import_wallet;
     use crate::services::account::key_management::key_controller::KeyController;
     use crate::services::local_storage::session::view::VIEWSESSION;
@@ -1036,7 +1193,7 @@ mod transfer_tests {
         storage_api::{event::get_avail_events_raw, records::get_record_pointers},
     };
     use crate::services::record_handling::sync::txs_sync;
-    use avail_common::models::constants::*;
+    use avail_common::{aleo_tools::program_manager::ProgramManager, models::constants::*};
 
     use snarkvm::prelude::{Identifier, Testnet3};
 
@@ -1151,7 +1308,9 @@ mod transfer_tests {
             asset_id,
         );
 
-        transfer_raw::<Testnet3>(request, None).await.unwrap();
+        transfer_raw::<Testnet3, AleoV0>(request, None)
+            .await
+            .unwrap();
 
         /* --SETUP COMPLETE */
 
@@ -1185,7 +1344,9 @@ mod transfer_tests {
             asset_id,
         );
 
-        transfer_raw::<Testnet3>(request, None).await.unwrap();
+        transfer_raw::<Testnet3, AleoV0>(request, None)
+            .await
+            .unwrap();
 
         // get events and display
         let (records, _ids) = get_record_pointers::<Testnet3>(get_records_request.clone()).unwrap();
@@ -1276,7 +1437,9 @@ mod transfer_tests {
             asset_id,
         );
 
-        transfer_raw::<Testnet3>(request, None).await.unwrap();
+        transfer_raw::<Testnet3, AleoV0>(request, None)
+            .await
+            .unwrap();
     }
 
     #[tokio::test]
@@ -1339,7 +1502,9 @@ mod transfer_tests {
             asset_id,
         );
 
-        transfer_raw::<Testnet3>(request, None).await.unwrap();
+        transfer_raw::<Testnet3, AleoV0>(request, None)
+            .await
+            .unwrap();
 
         /* --SETUP COMPLETE */
 
@@ -1359,7 +1524,9 @@ mod transfer_tests {
             asset_id,
         );
 
-        transfer_raw::<Testnet3>(request, None).await.unwrap();
+        transfer_raw::<Testnet3, AleoV0>(request, None)
+            .await
+            .unwrap();
     }
 
     #[tokio::test]
@@ -1424,13 +1591,15 @@ mod transfer_tests {
             asset_id,
         );
 
-        transfer_raw::<Testnet3>(request, None).await.unwrap();
+        transfer_raw::<Testnet3, AleoV0>(request, None)
+            .await
+            .unwrap();
     }
 
     // Transfer funds to test wallet on local dev network
     #[tokio::test]
     async fn test_transfer_public_to_private_util() {
-        let api_client = setup_client::<Testnet3>().unwrap();
+        let api_client = setup_node_client::<Testnet3>().unwrap();
         let private_key = PrivateKey::<Testnet3>::from_str(TESTNET_PRIVATE_KEY).unwrap();
 
         let program_manager = ProgramManager::<Testnet3>::new(
@@ -1466,4 +1635,125 @@ mod transfer_tests {
     async fn test_inclusion_prover() {
         let _res = pre_install_inclusion_prover().await;
     }
+
+    const NFT_PROGRAM: &str = r"program nft_transfer_test.aleo;
+
+record NFT:
+    owner as address.private;
+    data as field.private;
+
+function transfer_private:
+    input r0 as NFT.record;
+    input r1 as address.private;
+    cast r1 r0.data into r2 as NFT.record;
+    output r2 as NFT.record;
+
+function transfer_private_to_public:
+    input r0 as NFT.record;
+    input r1 as address.public;
+    cast r1 r0.data into r2 as NFT.record;
+    output r2 as NFT.record;
+
+function transfer_with_memo:
+    input r0 as NFT.record;
+    input r1 as address.private;
+    input r2 as u64.private;
+    cast r1 r0.data into r3 as NFT.record;
+    output r3 as NFT.record;
+";
+
+    const LEGACY_NFT_PROGRAM: &str = r"program nft_legacy_test.aleo;
+
+record NFT:
+    owner as address.private;
+    data as field.private;
+
+function transfer:
+    input r0 as NFT.record;
+    input r1 as address.private;
+    cast r1 r0.data into r2 as NFT.record;
+    output r2 as NFT.record;
+";
+
+    fn nft_chain() -> MockChainProvider<Testnet3> {
+        let mut fixture = ChainFixture::default();
+        fixture.programs = vec![NFT_PROGRAM.to_string(), LEGACY_NFT_PROGRAM.to_string()];
+
+        MockChainProvider::<Testnet3>::from_fixture(fixture).unwrap()
+    }
+
+    fn nft_program(
+        api_client: &MockChainProvider<Testnet3>,
+        program_id: &str,
+    ) -> Program<Testnet3> {
+        api_client
+            .require_program(&ProgramID::<Testnet3>::from_str(program_id).unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_nft_transfer_function() {
+        let api_client = nft_chain();
+        let program = nft_program(&api_client, "nft_transfer_test.aleo");
+        let legacy_program = nft_program(&api_client, "nft_legacy_test.aleo");
+
+        let function_name = |program: &Program<Testnet3>, privacy: NftTransferPrivacy| {
+            nft_transfer_function::<Testnet3>(program, &privacy).map(|id| id.to_string())
+        };
+
+        assert_eq!(
+            function_name(&program, NftTransferPrivacy::Private).unwrap(),
+            "transfer_private"
+        );
+        assert_eq!(
+            function_name(&program, NftTransferPrivacy::PrivateToPublic).unwrap(),
+            "transfer_private_to_public"
+        );
+
+        // older collections only have a private `transfer`
+        assert_eq!(
+            function_name(&legacy_program, NftTransferPrivacy::Private).unwrap(),
+            "transfer"
+        );
+        let err = function_name(&legacy_program, NftTransferPrivacy::PrivateToPublic).unwrap_err();
+        assert_eq!(err.error_type, AvailErrorType::InvalidData);
+        assert_eq!(
+            err.external_msg,
+            "This nft collection does not support this transfer."
+        );
+    }
+
+    #[test]
+    fn test_nft_transfer_inputs() {
+        let api_client = nft_chain();
+        let program = nft_program(&api_client, "nft_transfer_test.aleo");
+
+        let recipient = Address::<Testnet3>::from_str(TESTNET_ADDRESS).unwrap();
+        let nft_record = Record::<Testnet3, Plaintext<Testnet3>>::from_str(&format!(
+            "{{ owner: {}.private, data: 1field.private, _nonce: 0group.public }}",
+            recipient
+        ))
+        .unwrap();
+
+        for function_name in ["transfer_private", "transfer_private_to_public"] {
+            let function_id = Identifier::<Testnet3>::from_str(function_name).unwrap();
+            let inputs =
+                nft_transfer_inputs::<Testnet3>(&program, &function_id, &nft_record, &recipient)
+                    .unwrap();
+
+            assert_eq!(
+                inputs,
+                vec![
+                    Value::Record(nft_record.clone()),
+                    Value::from_str(&recipient.to_string()).unwrap()
+                ]
+            );
+        }
+
+        // inputs the wallet cannot fill in are rejected instead of guessed
+        let function_id = Identifier::<Testnet3>::from_str("transfer_with_memo").unwrap();
+        let err = nft_transfer_inputs::<Testnet3>(&program, &function_id, &nft_record, &recipient)
+            .unwrap_err();
+        assert_eq!(err.error_type, AvailErrorType::InvalidData);
+    }
 }
@@ -13,17 +13,18 @@ use snarkvm::utilities::ToBits;
 use std::collections::HashMap;
 use std::ops::Sub;
 use std::str::FromStr;
-use tauri::{Manager, Window};
 
 use crate::api::{
     aleo_client::{setup_client, setup_local_client},
-    encrypted_data::{post_encrypted_data, send_transaction_in},
+    encrypted_data::post_encrypted_data,
     fee::{create_record, fetch_record},
     user::name_to_address,
 };
 
+use crate::helpers::events::EventEmitter;
 use crate::helpers::validation::validate_address_bool;
 use crate::models::event::EventTransition;
+use crate::models::outbox::OutboxAction;
 use crate::models::pointers::{
     deployment::DeploymentPointer,
     message::TransactionMessage,
@@ -33,9 +34,11 @@ use crate::models::pointers::{
 use crate::models::wallet_connect::balance::Balance;
 
 use crate::models::wallet_connect::records::{GetRecordsRequest, RecordFilterType, RecordsFilter};
+use crate::services::ans::{is_ans_name, resolve_ans_name};
 use crate::services::local_storage::encrypted_data::get_encrypted_data_by_flavour;
+use crate::services::local_storage::token_registry::register_token;
 use crate::services::local_storage::tokens::{
-    add_balance, get_balance, get_program_id_for_token, if_token_exists, init_token,
+    add_balance, get_program_id_for_token, if_token_exists, init_token,
 };
 use crate::services::local_storage::{
     encrypted_data::{
@@ -55,15 +58,19 @@ use crate::services::local_storage::{
         transaction::get_transaction_pointer,
     },
 };
+use crate::services::outbox::send_or_queue;
 use crate::services::record_handling::transfer::find_confirmed_block_height;
 
 use avail_common::{
     aleo_tools::program_manager::{Credits, ProgramManager},
     errors::{AvailError, AvailErrorType, AvailResult},
-    models::encrypted_data::{EncryptedData, EventTypeCommon, RecordTypeCommon, TransactionState},
+    models::encrypted_data::{
+        EncryptedData, EncryptedDataRecord, EventTypeCommon, RecordTypeCommon, TransactionState,
+    },
     models::{fee_request::FeeRequest, network::SupportedNetworks},
 };
 
+use super::balances::get_private_balance;
 use super::decrypt_transition::DecryptTransition;
 
 /// Gets all tags from a given block height to the latest block height
@@ -355,7 +362,7 @@ pub fn transition_to_record_pointer<N: Network>(
 
                         let program_id = transition.program_id();
                         let mut record_type = RecordTypeCommon::None;
-                        let program = api_client.get_program(program_id)?;
+                        let program = api_client.require_program(program_id)?;
                         let record_name =
                             get_record_name(program.clone(), transition.function_name(), index)?;
                         // check if its in the records table
@@ -399,6 +406,11 @@ pub fn transition_to_record_pointer<N: Network>(
                                     view_key.to_address().to_string().as_str(),
                                     balance.to_string().as_str(),
                                 )?;
+
+                                // metadata lookups should not stop the scan, discovery retries later
+                                if let Err(e) = register_token::<N>(&program, &record_name) {
+                                    println!("Error registering token {}: {:?}", program_id, e);
+                                }
                             }
                         }
 
@@ -502,7 +514,7 @@ pub fn output_to_record_pointer<N: Network>(
                     }
 
                     let api_client = setup_client::<N>()?;
-                    let program = api_client.get_program(program_id)?;
+                    let program = api_client.require_program(program_id)?;
                     let record_name = get_record_name(program.clone(), function_id, index)?;
                     let mut balance = "".to_string();
 
@@ -548,6 +560,11 @@ pub fn output_to_record_pointer<N: Network>(
                                 view_key.to_address().to_string().as_str(),
                                 balance.to_string().as_str(),
                             )?;
+
+                            // metadata lookups should not stop the scan, discovery retries later
+                            if let Err(e) = register_token::<N>(&program, &record_name) {
+                                println!("Error registering token {}: {:?}", program_id, e);
+                            }
                         }
                     }
                     let record_pointer = AvailRecord::from_record(
@@ -574,171 +591,6 @@ pub fn output_to_record_pointer<N: Network>(
     }
 }
 
-#[tauri::command(rename_all = "snake_case")]
-pub fn get_all_nft_data() -> AvailResult<Vec<String>> {
-    let network = get_network()?;
-
-    match SupportedNetworks::from_str(network.as_str())? {
-        SupportedNetworks::Testnet3 => {
-            let nft_data = get_all_nft_raw::<Testnet3>()?;
-            println!("===> NFT Data {:?}", nft_data);
-            Ok(nft_data)
-        }
-        _ => Err(AvailError::new(
-            AvailErrorType::Internal,
-            "Network not supported".to_string(),
-            "Network not supported".to_string(),
-        )),
-    }
-}
-
-pub fn get_all_nft_raw<N: Network>() -> AvailResult<Vec<String>> {
-    let nft_encrypted_data =
-        get_encrypted_data_by_flavour(EncryptedDataTypeCommon::Record).unwrap();
-
-    let v_key = VIEWSESSION.get_instance::<N>().unwrap();
-    let api_client = setup_client::<N>()?;
-    let records = nft_encrypted_data
-        .iter()
-        .map(|x| {
-            let encrypted_data = x.to_enrypted_struct::<N>().unwrap();
-            let block: AvailRecord<N> = encrypted_data.decrypt(v_key).unwrap();
-            block
-        })
-        .collect::<Vec<AvailRecord<N>>>();
-    let program_ids = records
-        .iter()
-        .filter(|record| record.metadata.record_type == RecordTypeCommon::NFT)
-        .map(|record| record.metadata.program_id.clone())
-        .collect::<Vec<String>>();
-    let plaintexts: Vec<Record<N, Plaintext<N>>> = records
-        .iter()
-        .filter(|&record| record.metadata.record_type == RecordTypeCommon::NFT) // NFT Records
-        .filter_map(|record: &AvailRecord<N>| match record.to_record() {
-            Ok(record) => Some(record),
-            Err(e) => None,
-        })
-        .collect::<Vec<_>>();
-    fn u128_to_string(u: u128) -> String {
-        let mut temp_u128 = u;
-        let mut bytes = vec![] as Vec<u8>;
-
-        while temp_u128 > 0u128 {
-            let byte = (temp_u128 & 0xff) as u8;
-            bytes.push(byte);
-            temp_u128 >>= 8;
-        }
-
-        String::from_utf8_lossy(&bytes).to_string()
-    }
-    let mut count = 0;
-    let full_urls = plaintexts
-        .iter()
-        .filter_map(|record| {
-            println!("===> PROGRAM ID {:?}", program_ids[count]);
-            println!(
-                "===> BASE {:?}",
-                api_client
-                    .get_mapping_value(program_ids[count].clone(), "general_settings", "3u8")
-                    .unwrap()
-                    .to_string()
-                    .replace("u128", "")
-            );
-            let base_uri_0 = u128_to_string(
-                u128::from_str(
-                    &api_client
-                        .get_mapping_value(program_ids[count].clone(), "general_settings", "3u8")
-                        .unwrap()
-                        .to_string()
-                        .replace("u128", ""),
-                )
-                .unwrap(),
-            );
-            let base_uri_1 = u128_to_string(
-                u128::from_str(
-                    &api_client
-                        .get_mapping_value(program_ids[count].clone(), "general_settings", "4u8")
-                        .unwrap()
-                        .to_string()
-                        .replace("u128", ""),
-                )
-                .unwrap(),
-            );
-            let base_uri_2 = u128_to_string(
-                u128::from_str(
-                    &api_client
-                        .get_mapping_value(program_ids[count].clone(), "general_settings", "5u8")
-                        .unwrap()
-                        .to_string()
-                        .replace("u128", ""),
-                )
-                .unwrap(),
-            );
-            let base_uri_3 = u128_to_string(
-                u128::from_str(
-                    &api_client
-                        .get_mapping_value(program_ids[count].clone(), "general_settings", "6u8")
-                        .unwrap()
-                        .to_string()
-                        .replace("u128", ""),
-                )
-                .unwrap(),
-            );
-            let base_uri = format!("{}{}{}{}", base_uri_0, base_uri_1, base_uri_2, base_uri_3);
-            count = count + 1;
-            let check_if_ans = match record
-                .data()
-                .clone()
-                .get(&Identifier::<N>::from_str("data").unwrap())
-                .cloned()?
-            {
-                Entry::Private(Plaintext::Literal(Literal::<N>::Field(data), _)) => true,
-                _ => false,
-            };
-            if check_if_ans {
-                let data_field = record
-                    .data()
-                    .clone()
-                    .get(&Identifier::<N>::from_str("data").unwrap())
-                    .cloned()?
-                    .to_string();
-                if data_field.contains(".private") {
-                    let data_field = &data_field[..data_field.len() - 8];
-                    Some(format!("{}{}", base_uri, data_field))
-                } else {
-                    println!("===> NFT Data Field {:?}", data_field);
-                    Some(format!("{}{}", base_uri, &data_field[..]))
-                }
-            } else {
-                let data1 = record
-                    .data()
-                    .clone()
-                    .get(&Identifier::<N>::from_str("data1").unwrap())
-                    .cloned()?;
-
-                let data2 = record
-                    .data()
-                    .clone()
-                    .get(&Identifier::<N>::from_str("data2").unwrap())
-                    .cloned()?;
-                match (data1, data2) {
-                    (
-                        Entry::Private(Plaintext::Literal(Literal::<N>::U128(data1), _)),
-                        Entry::Private(Plaintext::Literal(Literal::<N>::U128(data2), _)),
-                    ) => {
-                        let data1 = u128_to_string(*data1);
-                        let data2 = u128_to_string(*data2);
-                        Some(format!("{}{}{}", base_uri, data1, data2))
-                    }
-                    _ => None,
-                }
-            }
-        })
-        .collect::<Vec<_>>();
-
-    Ok(full_urls)
-}
-
 /// Helper to parse the mapping value from the program mapping
 fn parse_with_suffix(input: &str) -> Result<u64, std::num::ParseIntError> {
     //remove last three characters
@@ -758,12 +610,9 @@ pub fn get_public_token_balance<N: Network>(asset_id: &str) -> AvailResult<f64>
     println!("===> PROGRAM ID FOR FETCH {:?}", program_id);
     let api_client = setup_client::<N>()?;
 
-    let credits_mapping = match api_client.get_mapping_value(program_id, "account", &address) {
-        Ok(credits_mapping) => credits_mapping,
-        Err(e) => match e.to_string().as_str() {
-            "Mapping not found" => return Ok(0.0),
-            _ => return Err(e.into()),
-        },
+    let credits_mapping = match api_client.get_mapping_value(&program_id, "account", &address)? {
+        Some(credits_mapping) => credits_mapping,
+        None => return Ok(0.0),
     };
 
     let pub_balance = parse_with_suffix(&credits_mapping.to_string())? as f64;
@@ -773,20 +622,20 @@ pub fn get_public_token_balance<N: Network>(asset_id: &str) -> AvailResult<f64>
     Ok(pub_balance / 1000000.0)
 }
 
-/// Get private balance for any ARC20 token
+/// Get private balance for any ARC20 token, derived from its unspent records
 pub fn get_private_token_balance<N: Network>(asset_id: &str) -> AvailResult<f64> {
-    let address = get_address_string()?;
     let record_name = format!("{}.record", asset_id);
-    let program_id = format!("{}.aleo", asset_id);
+    let mut program_id = get_program_id_for_token(&record_name)?;
+    if program_id.is_empty() {
+        program_id = format!("{}.aleo", asset_id);
+    }
     println!("===> Asset ID in get_private_balance() {:?}", asset_id);
 
-    let vk = VIEWSESSION.get_instance::<N>()?;
-    let balance = get_balance(&record_name, vk)?;
+    let balance = get_private_balance::<N>(&record_name, &program_id)?;
 
     println!("balance: {:?}", balance);
-    let balance_trimmed = balance.trim_end_matches("u64");
 
-    Ok(balance_trimmed.parse::<u64>()? as f64 / 1000000.0)
+    Ok(balance as f64 / 1000000.0)
 }
 
 /// Get Arc20 Token Balance
@@ -811,7 +660,7 @@ pub async fn handle_encrypted_storage_and_message<N: Network>(
     input_id: Option<String>,
     fee_id: Option<String>,
     wallet_connect: bool,
-    window: Option<Window>,
+    window: Option<EventEmitter>,
 ) -> AvailResult<()> {
     let username = get_username()?;
     let backup = get_backup_flag()?;
@@ -1033,7 +882,11 @@ pub async fn handle_encrypted_storage_and_message<N: Network>(
         let encrypted_transaction_message =
             transaction_message.to_encrypted_data(recipient_address)?;
 
-        send_transaction_in(encrypted_transaction_message).await?;
+        // Sent later from the outbox if the server can't be reached
+        send_or_queue(OutboxAction::SendTransactionMessage(
+            EncryptedDataRecord::from(encrypted_transaction_message),
+        ))
+        .await?;
     }
 
     Ok(())
@@ -1044,7 +897,7 @@ pub async fn handle_transaction_update_and_encrypted_storage<N: Network>(
     transaction_id: N::TransactionID,
     transaction_pointer_id: &str,
     fee_id: Option<String>,
-    window: Option<Window>,
+    window: Option<EventEmitter>,
 ) -> AvailResult<()> {
     let backup = get_backup_flag()?;
     let view_key = VIEWSESSION.get_instance::<N>()?;
@@ -1226,7 +1079,7 @@ pub async fn handle_deployment_update_and_encrypted_storage<N: Network>(
     transaction_id: N::TransactionID,
     deployment_pointer_id: &str,
     fee_id: Option<String>,
-    window: Option<Window>,
+    window: Option<EventEmitter>,
 ) -> AvailResult<()> {
     let backup = get_backup_flag()?;
     let sender_address = get_address::<N>()?;
@@ -1749,7 +1602,10 @@ pub async fn get_address_from_recipient<N: Network>(recipient: &str) -> AvailRes
             let address = Address::<N>::from_str(recipient)?;
             Ok(address)
         }
-        false => name_to_address(recipient).await,
+        false => match is_ans_name(recipient) {
+            true => resolve_ans_name::<N>(recipient),
+            false => name_to_address(recipient).await,
+        },
     }
 }
 
@@ -1900,26 +1756,6 @@ mod test {
 
     use super::*;
     use snarkvm::prelude::Testnet3;
-    #[tokio::test]
-    async fn test_get_all_nft_data() {
-        // AViewKey1cbThXosaWwor5t5F87m22K1hSRA4BWL5HrsNxRik15Rq
-        let res = get_all_nft_data().unwrap();
-        // let str0 = u128_to_string(340282366920938463463374607431768211455u128);
-        // let str1 = u128_to_string(340282366920938463463374607431768211455u128);
-        // let str2 = u128_to_string(5459521u128);
-        // let str3 = u128_to_string(60419623520418866384139602471830189160u128);
-        // let str4 = u128_to_string(133468932882108420321626207034102345825u128);
-        // let str5 = u128_to_string(13350705778619439u128);
-        // let str6 = u128_to_string(0u128);
-        // let str7 = u128_to_string(129967232274961287173788227149222870785u128);
-
-        // println!("res\n {:?}\n {:?} \n {:?} \n {:?}, \n{:?}, \n {:?}, \n {:?}", str1, str2, str3, str4, str5, str6, str7);
-        // println!(":{:?}",format!("{:x}", 5459521u128));
-        // println!(":{:?}",format!("{:x}", 60419623520418866384139602471830189160u128));
-        // println!(":{:?}",format!("{:x}", 133468932882108420321626207034102345825u128));
-        // println!(":{:?}",format!("{:x}", 60419623520418866384139602471830189160u128));
-        println!("res\n {:?}", res);
-    }
     fn u128_to_string(u: u128) -> String {
         let mut temp_u128 = u;
         let mut bytes = vec![] as Vec<u8>;
@@ -0,0 +1,607 @@
+use chrono::{DateTime, Local};
+use once_cell::sync::{Lazy, OnceCell};
+use snarkvm::prelude::*;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::api::{aleo_client::setup_client, chain_provider::ChainClient};
+use crate::helpers::events::EventEmitter;
+use crate::models::pointers::{
+    deployment::DeploymentPointer,
+    transaction::{ExecutedTransition, TransactionPointer},
+};
+use crate::services::local_storage::{
+    encrypted_data::update_encrypted_transaction_state_by_id,
+    persistent_storage::{get_address, get_network},
+    session::view::VIEWSESSION,
+    storage_api::{
+        deployment::get_deployment_pointer,
+        records::update_record_spent_local_via_nonce,
+        transaction::{
+            get_in_flight_transactions, get_transaction_pointer, handle_deployment_failed,
+            handle_transaction_failed,
+        },
+    },
+};
+
+use super::confirmation::{check_confirmation, Confirmation, ConfirmationOutcome};
+use super::offline_signing::is_offline_execution;
+use super::utils::{
+    handle_deployment_confirmed, handle_deployment_rejection, handle_transaction_confirmed,
+    handle_transaction_rejection, input_spent_check, transition_to_record_pointer,
+};
+
+use avail_common::{
+    errors::AvailResult,
+    models::{
+        encrypted_data::{EncryptedDataTypeCommon, TransactionState},
+        network::SupportedNetworks,
+    },
+};
+
+/* --Confirmation Watcher-- */
+
+/// How often tracked transactions are looked up on chain
+const WATCH_INTERVAL_SECONDS: u64 = 10;
+/// Minutes a broadcast transaction can go unconfirmed before it is failed, as in `check_unconfirmed_transactions`
+const UNCONFIRMED_EXPIRY_MINUTES: i64 = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum WatchedKind {
+    Transaction,
+    Deployment,
+}
+
+/// Pointer ids being tracked
+static WATCHED: Lazy<Mutex<HashMap<String, WatchedKind>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+/// Network and address the watch set was loaded for, None until it is loaded
+static LOADED_FOR: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+/// When the watcher of this run started, pointers processing since before then were abandoned mid broadcast
+static STARTED_AT: OnceCell<DateTime<Local>> = OnceCell::new();
+
+/// Whether the watcher settles this pointer, so other paths leave it alone
+pub fn is_watched(pointer_id: &str) -> bool {
+    WATCHED.lock().unwrap().contains_key(pointer_id)
+}
+
+fn is_in_flight(state: &TransactionState) -> bool {
+    matches!(
+        state,
+        TransactionState::Pending | TransactionState::Processing
+    )
+}
+
+fn is_expired(created: DateTime<Local>) -> bool {
+    Local::now().signed_duration_since(created).num_minutes() > UNCONFIRMED_EXPIRY_MINUTES
+}
+
+/// A pointer still processing without a transaction id that was created before this run never got broadcast
+fn is_abandoned(state: &TransactionState, has_id: bool, created: DateTime<Local>) -> bool {
+    matches!(state, TransactionState::Processing)
+        && !has_id
+        && STARTED_AT
+            .get()
+            .map_or(false, |started_at| created < *started_at)
+}
+
+/// Fails a transaction the app closed on before broadcasting it and releases its records
+fn fail_abandoned_transaction<N: Network>(
+    mut pointer: TransactionPointer<N>,
+    pointer_id: &str,
+) -> AvailResult<()> {
+    let address = get_address::<N>()?;
+
+    for nonce in pointer.spent_record_pointers_nonces() {
+        update_record_spent_local_via_nonce::<N>(&nonce, false)?;
+    }
+
+    pointer.update_failed_transaction(
+        "Transaction was not broadcast before the wallet closed, no records were spent."
+            .to_string(),
+        None,
+    );
+
+    let encrypted_transaction = pointer.to_encrypted_data(address)?;
+    update_encrypted_transaction_state_by_id(
+        pointer_id,
+        &encrypted_transaction.ciphertext,
+        &encrypted_transaction.nonce,
+        TransactionState::Failed,
+    )
+}
+
+/// Picks up what was left in flight. Pointers without a transaction id were never broadcast: those the
+/// last run abandoned are failed, offline signing envelopes wait for their import and are skipped.
+fn load_in_flight<N: Network>() -> AvailResult<()> {
+    let view_key = VIEWSESSION.get_instance::<N>()?;
+    let mut watched = WATCHED.lock().unwrap();
+
+    for encrypted_transaction in get_in_flight_transactions::<N>()? {
+        let id = match encrypted_transaction.id {
+            Some(id) => id.to_string(),
+            None => continue,
+        };
+
+        let encrypted_struct = encrypted_transaction.to_enrypted_struct::<N>()?;
+        match encrypted_transaction.flavour {
+            EncryptedDataTypeCommon::Transaction => {
+                let pointer: TransactionPointer<N> = encrypted_struct.decrypt(view_key)?;
+                let has_id = pointer.transaction_id().is_some();
+
+                if has_id {
+                    watched.insert(id, WatchedKind::Transaction);
+                } else if is_abandoned(&pointer.state(), has_id, pointer.created())
+                    && !is_offline_execution(&id)?
+                {
+                    fail_abandoned_transaction::<N>(pointer, &id)?;
+                }
+            }
+            EncryptedDataTypeCommon::Deployment => {
+                let pointer: DeploymentPointer<N> = encrypted_struct.decrypt(view_key)?;
+                let has_id = pointer.id.is_some();
+
+                if has_id {
+                    watched.insert(id, WatchedKind::Deployment);
+                } else if is_abandoned(&pointer.state, has_id, pointer.created) {
+                    handle_deployment_failed::<N>(&id)?;
+
+                    if let Some(fee_nonce) = &pointer.spent_fee_nonce {
+                        update_record_spent_local_via_nonce::<N>(fee_nonce, false)?;
+                    }
+                }
+            }
+            _ => {}
+        };
+    }
+
+    println!("Watching {} in flight transactions", watched.len());
+    Ok(())
+}
+
+/// Loads what was left in flight the first time it is needed, by the watcher's first pass or by
+/// anything deciding the fate of in flight transactions after unlock, whichever comes first.
+/// The set is loaded again when the wallet's account or network changed since.
+pub fn ensure_watched_loaded<N: Network>() -> AvailResult<()> {
+    let account = format!("{}/{}", get_network()?, get_address::<N>()?);
+    let mut loaded_for = LOADED_FOR.lock().unwrap();
+
+    if loaded_for.as_ref() != Some(&account) {
+        // another account's pointers cannot be decrypted or settled with this view key
+        WATCHED.lock().unwrap().clear();
+        load_in_flight::<N>()?;
+        *loaded_for = Some(account);
+    }
+
+    Ok(())
+}
+
+/// Forgets the watch set, for when the account is deleted or replaced
+pub fn reset_watched() {
+    let mut loaded_for = LOADED_FOR.lock().unwrap();
+    WATCHED.lock().unwrap().clear();
+    *loaded_for = None;
+}
+
+fn is_loaded() -> bool {
+    LOADED_FOR.lock().unwrap().is_some()
+}
+
+fn apply_transaction_confirmation<N: Network>(
+    mut pointer: TransactionPointer<N>,
+    pointer_id: &str,
+    transaction_id: N::TransactionID,
+    confirmation: Confirmation<N>,
+) -> AvailResult<()> {
+    let view_key = VIEWSESSION.get_instance::<N>()?;
+    let address = get_address::<N>()?;
+    let block_height = confirmation.block_height;
+
+    match confirmation.outcome {
+        ConfirmationOutcome::Accepted => {
+            let mut executed_transitions: Vec<ExecutedTransition<N>> = vec![];
+
+            for transition in confirmation.transitions {
+                if transition.is_fee_public() {
+                    continue;
+                }
+
+                if !transition.is_fee_private() {
+                    input_spent_check::<N>(&transition, true)?;
+                    executed_transitions.push(ExecutedTransition::<N>::new(
+                        transition.program_id().to_string(),
+                        transition.function_name().to_string(),
+                        transition.id().to_owned(),
+                    ));
+                }
+
+                transition_to_record_pointer(transaction_id, transition, block_height, view_key)?;
+            }
+
+            handle_transaction_confirmed(
+                pointer_id,
+                transaction_id,
+                executed_transitions,
+                block_height,
+                confirmation.timestamp,
+                confirmation.fee,
+                address,
+            )?;
+        }
+        ConfirmationOutcome::Rejected => {
+            // remainder of a private fee comes back as a new record
+            for transition in confirmation.transitions {
+                transition_to_record_pointer(transaction_id, transition, block_height, view_key)?;
+            }
+
+            handle_transaction_rejection(
+                pointer,
+                pointer_id,
+                confirmation.rejected_execution,
+                confirmation.fee_transaction_id,
+                block_height,
+                confirmation.fee,
+                address,
+            )?;
+        }
+        ConfirmationOutcome::Aborted => {
+            for nonce in pointer.spent_record_pointers_nonces() {
+                update_record_spent_local_via_nonce::<N>(&nonce, false)?;
+            }
+
+            pointer.update_aborted_transaction(
+                "Transaction aborted by the Aleo blockchain. No tokens were spent.".to_string(),
+                transaction_id,
+                block_height,
+            );
+
+            let updated_encrypted_transaction = pointer.to_encrypted_data(address)?;
+            update_encrypted_transaction_state_by_id(
+                pointer_id,
+                &updated_encrypted_transaction.ciphertext,
+                &updated_encrypted_transaction.nonce,
+                TransactionState::Aborted,
+            )?;
+        }
+    };
+
+    Ok(())
+}
+
+fn apply_deployment_confirmation<N: Network>(
+    mut pointer: DeploymentPointer<N>,
+    pointer_id: &str,
+    transaction_id: N::TransactionID,
+    confirmation: Confirmation<N>,
+) -> AvailResult<()> {
+    let view_key = VIEWSESSION.get_instance::<N>()?;
+    let address = get_address::<N>()?;
+    let block_height = confirmation.block_height;
+
+    if confirmation.outcome == ConfirmationOutcome::Aborted {
+        if let Some(fee_nonce) = &pointer.spent_fee_nonce {
+            update_record_spent_local_via_nonce::<N>(fee_nonce, false)?;
+        }
+
+        pointer.update_aborted_deployment(
+            "Transaction aborted by the Aleo blockchain. No tokens were spent.".to_string(),
+            transaction_id,
+            block_height,
+        );
+
+        let updated_encrypted_deployment = pointer.to_encrypted_data(address)?;
+        update_encrypted_transaction_state_by_id(
+            pointer_id,
+            &updated_encrypted_deployment.ciphertext,
+            &updated_encrypted_deployment.nonce,
+            TransactionState::Aborted,
+        )?;
+
+        return Ok(());
+    }
+
+    // fee transition, spending the fee record and returning its remainder
+    for transition in confirmation.transitions {
+        input_spent_check(&transition, true)?;
+        transition_to_record_pointer(transaction_id, transition, block_height, view_key)?;
+    }
+
+    match confirmation.outcome {
+        ConfirmationOutcome::Rejected => handle_deployment_rejection(
+            pointer,
+            pointer_id,
+            transaction_id,
+            block_height,
+            confirmation.fee,
+            address,
+        ),
+        _ => handle_deployment_confirmed(
+            pointer_id,
+            transaction_id,
+            block_height,
+            confirmation.fee,
+            address,
+        ),
+    }
+}
+
+/// Checks a transaction once, returns true when it no longer needs watching
+fn settle_transaction<N: Network>(
+    api_client: &ChainClient<N>,
+    pointer_id: &str,
+) -> AvailResult<bool> {
+    let pointer = get_transaction_pointer::<N>(pointer_id)?;
+
+    let transaction_id = match pointer.transaction_id() {
+        Some(transaction_id) if is_in_flight(&pointer.state()) => transaction_id,
+        // settled elsewhere, e.g. by a block scan
+        _ => return Ok(true),
+    };
+
+    let confirmation = check_confirmation(api_client, transaction_id)?;
+    apply_transaction_settlement(pointer, pointer_id, transaction_id, confirmation)
+}
+
+/// Applies what a lookup found, expiring the transaction when it stayed unconfirmed for too long
+fn apply_transaction_settlement<N: Network>(
+    pointer: TransactionPointer<N>,
+    pointer_id: &str,
+    transaction_id: N::TransactionID,
+    confirmation: Option<Confirmation<N>>,
+) -> AvailResult<bool> {
+    match confirmation {
+        Some(confirmation) => {
+            apply_transaction_confirmation(pointer, pointer_id, transaction_id, confirmation)?
+        }
+        None if is_expired(pointer.created()) => {
+            handle_transaction_failed::<N>(pointer_id, Some(transaction_id))?;
+
+            for nonce in pointer.spent_record_pointers_nonces() {
+                update_record_spent_local_via_nonce::<N>(&nonce, false)?;
+            }
+        }
+        None => return Ok(false),
+    };
+
+    Ok(true)
+}
+
+fn settle_deployment<N: Network>(
+    api_client: &ChainClient<N>,
+    pointer_id: &str,
+) -> AvailResult<bool> {
+    let pointer = get_deployment_pointer::<N>(pointer_id)?;
+
+    let transaction_id = match pointer.id {
+        Some(transaction_id) if is_in_flight(&pointer.state) => transaction_id,
+        _ => return Ok(true),
+    };
+
+    match check_confirmation(api_client, transaction_id)? {
+        Some(confirmation) => {
+            apply_deployment_confirmation(pointer, pointer_id, transaction_id, confirmation)?
+        }
+        None if is_expired(pointer.created) => {
+            handle_deployment_failed::<N>(pointer_id)?;
+
+            if let Some(fee_nonce) = &pointer.spent_fee_nonce {
+                update_record_spent_local_via_nonce::<N>(fee_nonce, false)?;
+            }
+        }
+        None => return Ok(false),
+    };
+
+    Ok(true)
+}
+
+fn watch_pass<N: Network>(events: &EventEmitter) -> AvailResult<()> {
+    ensure_watched_loaded::<N>()?;
+
+    let api_client = setup_client::<N>()?;
+    let watched = WATCHED
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(id, kind)| (id.clone(), *kind))
+        .collect::<Vec<(String, WatchedKind)>>();
+
+    for (pointer_id, kind) in watched {
+        let settled = match kind {
+            WatchedKind::Transaction => settle_transaction::<N>(&api_client, &pointer_id),
+            WatchedKind::Deployment => settle_deployment::<N>(&api_client, &pointer_id),
+        };
+
+        match settled {
+            Ok(true) => {
+                WATCHED.lock().unwrap().remove(&pointer_id);
+                events.emit("tx_state_change", &pointer_id)?;
+            }
+            Ok(false) => {}
+            Err(e) => println!(
+                "Error checking transaction {}: {}",
+                pointer_id, e.internal_msg
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+fn watch_once(events: &EventEmitter) -> AvailResult<()> {
+    match SupportedNetworks::from_str(&get_network()?)? {
+        SupportedNetworks::Testnet3 => watch_pass::<Testnet3>(events),
+        _ => watch_pass::<Testnet3>(events),
+    }
+}
+
+/// Tracks the transactions left in flight when the app last closed until they settle.
+/// They are picked up once the wallet is unlocked, and the watcher stops when none are left.
+pub fn start_confirmation_watcher(events: EventEmitter) {
+    let _ = STARTED_AT.set(Local::now());
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(Duration::from_secs(WATCH_INTERVAL_SECONDS)).await;
+
+            let pass_events = events.clone();
+            match tauri::async_runtime::spawn_blocking(move || watch_once(&pass_events)).await {
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => println!("Confirmation watcher: {}", e.internal_msg),
+                Err(e) => println!("Confirmation watcher stopped: {}", e),
+            };
+
+            if is_loaded() && WATCHED.lock().unwrap().is_empty() {
+                break;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod watcher_tests {
+    use super::*;
+    use crate::api::chain_provider::MockChainProvider;
+    use crate::services::local_storage::encrypted_data::initialize_encrypted_data_table;
+    use crate::services::record_handling::{
+        confirmation::FinalizeOutcome, offline_signing::store_offline_execution,
+    };
+    use avail_common::models::{constants::TESTNET_PRIVATE_KEY, encrypted_data::EventTypeCommon};
+    use std::sync::Arc;
+
+    fn genesis_chain() -> (ChainClient<Testnet3>, Block<Testnet3>) {
+        let genesis = Block::<Testnet3>::from_bytes_le(Testnet3::genesis_bytes()).unwrap();
+        let provider = MockChainProvider::<Testnet3>::new();
+        provider.push_block(genesis.clone());
+
+        (Arc::new(provider), genesis)
+    }
+
+    /// Stores a transfer of the test account, returns its pointer id
+    fn store_pointer(
+        transaction_id: Option<<Testnet3 as Network>::TransactionID>,
+        state: TransactionState,
+        created: DateTime<Local>,
+    ) -> (String, TransactionPointer<Testnet3>) {
+        let private_key = PrivateKey::<Testnet3>::from_str(TESTNET_PRIVATE_KEY).unwrap();
+        let view_key = ViewKey::<Testnet3>::try_from(&private_key).unwrap();
+        VIEWSESSION.set_view_session(&view_key.to_string()).unwrap();
+        initialize_encrypted_data_table().unwrap();
+
+        let pointer = TransactionPointer::<Testnet3>::new(
+            None,
+            transaction_id,
+            state,
+            None,
+            Some("credits.aleo".to_string()),
+            Some("transfer_public".to_string()),
+            vec![],
+            vec![],
+            created,
+            None,
+            None,
+            EventTypeCommon::Send,
+            Some(1.0),
+            Some(0.3),
+            None,
+        );
+        let pointer_id = pointer
+            .encrypt_and_store(get_address::<Testnet3>().unwrap())
+            .unwrap();
+
+        (pointer_id, pointer)
+    }
+
+    fn state_of(pointer_id: &str) -> TransactionState {
+        get_transaction_pointer::<Testnet3>(pointer_id)
+            .unwrap()
+            .state()
+    }
+
+    #[test]
+    fn test_settle_confirmed_transaction() {
+        let (api_client, genesis) = genesis_chain();
+        let transaction_id = *genesis.transaction_ids().next().unwrap();
+        let (pointer_id, _) = store_pointer(
+            Some(transaction_id),
+            TransactionState::Pending,
+            Local::now(),
+        );
+
+        assert!(settle_transaction::<Testnet3>(&api_client, &pointer_id).unwrap());
+        assert_eq!(state_of(&pointer_id), TransactionState::Confirmed);
+
+        // a settled pointer is let go without another lookup
+        assert!(settle_transaction::<Testnet3>(&api_client, &pointer_id).unwrap());
+    }
+
+    #[test]
+    fn test_settle_rejected_transaction() {
+        let transaction_id = <Testnet3 as Network>::TransactionID::default();
+        let (pointer_id, pointer) = store_pointer(
+            Some(transaction_id),
+            TransactionState::Pending,
+            Local::now(),
+        );
+
+        // a block rejecting a transaction needs a proven fee, so the lookup's result is given directly
+        let confirmation = Confirmation {
+            block_height: 1,
+            timestamp: Local::now(),
+            outcome: ConfirmationOutcome::Rejected,
+            finalize: FinalizeOutcome::Failed,
+            transitions: vec![],
+            fee_transaction_id: Some(transaction_id),
+            rejected_execution: None,
+            fee: Some(0.3),
+        };
+
+        assert!(apply_transaction_settlement::<Testnet3>(
+            pointer,
+            &pointer_id,
+            transaction_id,
+            Some(confirmation)
+        )
+        .unwrap());
+        assert_eq!(state_of(&pointer_id), TransactionState::Rejected);
+    }
+
+    #[test]
+    fn test_settle_expired_transaction() {
+        let (api_client, _) = genesis_chain();
+        let transaction_id = <Testnet3 as Network>::TransactionID::default();
+
+        let (recent_id, _) = store_pointer(
+            Some(transaction_id),
+            TransactionState::Pending,
+            Local::now(),
+        );
+        assert!(!settle_transaction::<Testnet3>(&api_client, &recent_id).unwrap());
+        assert_eq!(state_of(&recent_id), TransactionState::Pending);
+
+        let (expired_id, _) = store_pointer(
+            Some(transaction_id),
+            TransactionState::Pending,
+            Local::now() - chrono::Duration::minutes(UNCONFIRMED_EXPIRY_MINUTES + 1),
+        );
+        assert!(settle_transaction::<Testnet3>(&api_client, &expired_id).unwrap());
+        assert_eq!(state_of(&expired_id), TransactionState::Failed);
+    }
+
+    #[test]
+    fn test_load_fails_abandoned_transactions() {
+        let _ = STARTED_AT.set(Local::now());
+        let before_start = Local::now() - chrono::Duration::minutes(1);
+
+        let (abandoned_id, _) = store_pointer(None, TransactionState::Processing, before_start);
+        let (offline_id, _) = store_pointer(None, TransactionState::Processing, before_start);
+        store_offline_execution(&offline_id).unwrap();
+
+        load_in_flight::<Testnet3>().unwrap();
+
+        assert_eq!(state_of(&abandoned_id), TransactionState::Failed);
+        assert_eq!(state_of(&offline_id), TransactionState::Processing);
+        assert!(!is_watched(&abandoned_id));
+    }
+}
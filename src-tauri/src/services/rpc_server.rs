@@ -0,0 +1,492 @@
+use app_dirs::{app_root, AppDataType, AppInfo};
+use once_cell::sync::Lazy;
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{de::DeserializeOwned, Serialize};
+use snarkvm::prelude::{Network, Testnet3};
+use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use tauri::Window;
+use tokio::io::{
+    AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader, WriteHalf,
+};
+use tokio::net::TcpListener;
+use tokio::sync::watch;
+
+use crate::api::aleo_client::setup_client;
+use crate::helpers::events::EventEmitter;
+use crate::models::rpc::{
+    CreateEventParams, RpcError, RpcRequest, RpcResponse, RpcServerStatus, RpcTransport,
+    SyncStatus, INVALID_PARAMS, INVALID_REQUEST, JSON_RPC_VERSION, METHOD_NOT_FOUND, PARSE_ERROR,
+    UNAUTHORIZED,
+};
+use crate::models::wallet_connect::dapp_session::Caller;
+use crate::services::local_storage::persistent_storage::{get_last_sync, get_network};
+use crate::services::wallet_connect_api::{
+    decrypt_records, get_balance, get_events, get_records, handle_create_event_request,
+    handle_sign_request,
+};
+
+use avail_common::{
+    errors::{AvailError, AvailErrorType, AvailResult},
+    models::network::SupportedNetworks,
+};
+
+/* --Local JSON-RPC Server-- */
+
+// Opt-in server letting local automation drive a running wallet without the UI. Requests are JSON-RPC 2.0,
+// one per line, and carry the token written to the token file on start. Requests have no dapp origin,
+// so they are not held to dapp sessions, but unlike the wallet's own requests they do not extend the
+// password session.
+
+const RPC_TOKEN_FILE: &str = "rpc_token";
+const RPC_TOKEN_LENGTH: usize = 48;
+/// Longest request line accepted, the connection is closed after a longer one
+const MAX_REQUEST_BYTES: usize = 1024 * 1024;
+
+struct RpcServer {
+    transport: RpcTransport,
+    token_path: PathBuf,
+    shutdown: watch::Sender<bool>,
+}
+
+static RPC_SERVER: Lazy<Mutex<Option<RpcServer>>> = Lazy::new(|| Mutex::new(None));
+
+/// Starts the server, emitting the events of the requests it handles to the window
+#[tauri::command(rename_all = "snake_case")]
+pub async fn start_rpc_server(
+    transport: Option<RpcTransport>,
+    window: Window,
+) -> AvailResult<RpcServerStatus> {
+    start_rpc_server_raw(transport.unwrap_or_default(), window.into()).await
+}
+
+pub async fn start_rpc_server_raw(
+    transport: RpcTransport,
+    events: EventEmitter,
+) -> AvailResult<RpcServerStatus> {
+    if RPC_SERVER.lock().unwrap().is_some() {
+        return Err(AvailError::new(
+            AvailErrorType::Validation,
+            "RPC server already running".to_string(),
+            "The automation server is already running.".to_string(),
+        ));
+    }
+
+    let token = Arc::new(generate_token());
+    let token_path = write_token(&token)?;
+    let (shutdown, shutdown_rx) = watch::channel(false);
+
+    match &transport {
+        RpcTransport::Tcp { port } => {
+            let listener = TcpListener::bind(("127.0.0.1", *port)).await?;
+            tokio::spawn(accept_tcp(listener, token, events, shutdown_rx));
+        }
+        #[cfg(unix)]
+        RpcTransport::Unix { path } => {
+            remove_stale_socket(path)?;
+            let listener = tokio::net::UnixListener::bind(path)?;
+            set_owner_only(std::path::Path::new(path))?;
+            tokio::spawn(accept_unix(listener, token, events, shutdown_rx));
+        }
+        #[cfg(not(unix))]
+        RpcTransport::Unix { .. } => {
+            return Err(AvailError::new(
+                AvailErrorType::Validation,
+                "Unix sockets are not supported on this platform".to_string(),
+                "Unix sockets are not supported on this platform.".to_string(),
+            ));
+        }
+    }
+
+    let mut server = RPC_SERVER.lock().unwrap();
+    *server = Some(RpcServer {
+        transport,
+        token_path,
+        shutdown,
+    });
+
+    Ok(status(server.as_ref()))
+}
+
+/// Stops accepting requests, closes open connections and deletes the token
+#[tauri::command(rename_all = "snake_case")]
+pub fn stop_rpc_server() -> AvailResult<RpcServerStatus> {
+    if let Some(server) = RPC_SERVER.lock().unwrap().take() {
+        let _ = server.shutdown.send(true);
+        let _ = std::fs::remove_file(&server.token_path);
+
+        #[cfg(unix)]
+        {
+            if let RpcTransport::Unix { path } = &server.transport {
+                remove_stale_socket(path)?;
+            }
+        }
+    }
+
+    Ok(status(None))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn get_rpc_server_status() -> AvailResult<RpcServerStatus> {
+    Ok(status(RPC_SERVER.lock().unwrap().as_ref()))
+}
+
+fn status(server: Option<&RpcServer>) -> RpcServerStatus {
+    match server {
+        Some(server) => RpcServerStatus {
+            running: true,
+            endpoint: Some(server.transport.endpoint()),
+            token_path: Some(server.token_path.to_string_lossy().to_string()),
+        },
+        None => RpcServerStatus {
+            running: false,
+            endpoint: None,
+            token_path: None,
+        },
+    }
+}
+
+fn generate_token() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(RPC_TOKEN_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// Writes the token next to the wallet's local storage, readable only by the user
+fn write_token(token: &str) -> AvailResult<PathBuf> {
+    let path = app_root(
+        AppDataType::UserData,
+        &AppInfo {
+            name: "avail_wallet",
+            author: "Avail",
+        },
+    )?
+    .join(RPC_TOKEN_FILE);
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    options.open(&path)?.write_all(token.as_bytes())?;
+
+    // the mode above only applies when the file is created, a token file left by an older version keeps its own
+    #[cfg(unix)]
+    set_owner_only(&path)?;
+
+    Ok(path)
+}
+
+/// Restricts a file or socket to the current user
+#[cfg(unix)]
+fn set_owner_only(path: &std::path::Path) -> AvailResult<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+
+    Ok(())
+}
+
+/// Removes a socket left behind by a previous run, other files at the path are left alone
+#[cfg(unix)]
+fn remove_stale_socket(path: &str) -> AvailResult<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    if let Ok(metadata) = std::fs::symlink_metadata(path) {
+        if metadata.file_type().is_socket() {
+            std::fs::remove_file(path)?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn accept_tcp(
+    listener: TcpListener,
+    token: Arc<String>,
+    events: EventEmitter,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    tokio::spawn(serve_connection(stream, token.clone(), events.clone(), shutdown.clone()));
+                }
+                Err(e) => println!("Error accepting RPC connection: {}", e),
+            },
+            _ = shutdown.changed() => break,
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn accept_unix(
+    listener: tokio::net::UnixListener,
+    token: Arc<String>,
+    events: EventEmitter,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    tokio::spawn(serve_connection(stream, token.clone(), events.clone(), shutdown.clone()));
+                }
+                Err(e) => println!("Error accepting RPC connection: {}", e),
+            },
+            _ = shutdown.changed() => break,
+        }
+    }
+}
+
+async fn serve_connection<S: AsyncRead + AsyncWrite + Send + 'static>(
+    stream: S,
+    token: Arc<String>,
+    events: EventEmitter,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut reader = BufReader::new(reader);
+
+    loop {
+        let mut line = vec![];
+        tokio::select! {
+            read = (&mut reader).take(MAX_REQUEST_BYTES as u64 + 1).read_until(b'\n', &mut line) => match read {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            },
+            _ = shutdown.changed() => break,
+        };
+
+        // the rest of an oversized line cannot be told apart from the next request, so the connection ends here
+        if line.len() > MAX_REQUEST_BYTES {
+            let response = RpcResponse::failure(
+                serde_json::Value::Null,
+                RpcError::new(INVALID_REQUEST, "Request too large"),
+            );
+            write_response(&mut writer, &response).await;
+            break;
+        }
+
+        let line = String::from_utf8_lossy(&line);
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = handle_rpc_line(&line, &token, &events).await;
+        if !write_response(&mut writer, &response).await {
+            break;
+        }
+    }
+}
+
+/// Writes a response line, returns whether the connection can still be used
+async fn write_response<S: AsyncWrite>(writer: &mut WriteHalf<S>, response: &RpcResponse) -> bool {
+    let mut response = match serde_json::to_string(response) {
+        Ok(response) => response,
+        Err(e) => {
+            println!("Error serializing RPC response: {}", e);
+            return false;
+        }
+    };
+    response.push('\n');
+
+    writer.write_all(response.as_bytes()).await.is_ok()
+}
+
+/// Parses, authenticates and handles a single request line
+pub async fn handle_rpc_line(line: &str, token: &str, events: &EventEmitter) -> RpcResponse {
+    let request = match serde_json::from_str::<RpcRequest>(line) {
+        Ok(request) => request,
+        Err(e) => {
+            return RpcResponse::failure(
+                serde_json::Value::Null,
+                RpcError::new(PARSE_ERROR, &format!("Parse error: {}", e)),
+            )
+        }
+    };
+
+    if request.jsonrpc != JSON_RPC_VERSION {
+        return RpcResponse::failure(
+            request.id,
+            RpcError::new(INVALID_REQUEST, "Only JSON-RPC 2.0 is supported"),
+        );
+    }
+
+    let authorized = match &request.token {
+        Some(request_token) => tokens_match(request_token, token),
+        None => false,
+    };
+
+    if !authorized {
+        return RpcResponse::failure(request.id, RpcError::new(UNAUTHORIZED, "Invalid token"));
+    }
+
+    match dispatch(&request.method, request.params, events).await {
+        Ok(result) => RpcResponse::success(request.id, result),
+        Err(error) => RpcResponse::failure(request.id, error),
+    }
+}
+
+/// Compares in constant time so the token cannot be guessed byte by byte
+fn tokens_match(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+async fn dispatch(
+    method: &str,
+    params: serde_json::Value,
+    events: &EventEmitter,
+) -> Result<serde_json::Value, RpcError> {
+    match method {
+        "get_balance" => to_result(get_balance(parse_params(params)?, Caller::Rpc)),
+        "get_records" => to_result(get_records(parse_params(params)?, Caller::Rpc).await),
+        "get_events" => to_result(get_events(parse_params(params)?, Caller::Rpc).await),
+        "request_create_event" => {
+            let params: CreateEventParams = parse_params(params)?;
+            to_result(
+                handle_create_event_request(
+                    params.request,
+                    params.fee_private,
+                    Caller::Rpc,
+                    Some(events.clone()),
+                )
+                .await,
+            )
+        }
+        "sign" => to_result(handle_sign_request(
+            parse_params(params)?,
+            Caller::Rpc,
+            Some(events.clone()),
+        )),
+        "decrypt_records" => to_result(decrypt_records(parse_params(params)?, Caller::Rpc)),
+        "get_sync_status" => to_result(get_sync_status()),
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            &format!("Method {} not found", method),
+        )),
+    }
+}
+
+/// Params are the wallet connect request of the method, omitted params are read as an empty object
+fn parse_params<T: DeserializeOwned>(params: serde_json::Value) -> Result<T, RpcError> {
+    let params = match params {
+        serde_json::Value::Null => serde_json::json!({}),
+        params => params,
+    };
+
+    serde_json::from_value(params)
+        .map_err(|e| RpcError::new(INVALID_PARAMS, &format!("Invalid params: {}", e)))
+}
+
+fn to_result<T: Serialize>(result: AvailResult<T>) -> Result<serde_json::Value, RpcError> {
+    let result = result?;
+    serde_json::to_value(result).map_err(|e| RpcError::from(AvailError::from(e)))
+}
+
+fn get_sync_status() -> AvailResult<SyncStatus> {
+    match SupportedNetworks::from_str(&get_network()?)? {
+        SupportedNetworks::Testnet3 => get_sync_status_raw::<Testnet3>(),
+        _ => get_sync_status_raw::<Testnet3>(),
+    }
+}
+
+fn get_sync_status_raw<N: Network>() -> AvailResult<SyncStatus> {
+    let latest_height = setup_client::<N>()?.latest_height()?;
+
+    Ok(SyncStatus::new(get_last_sync()?, latest_height))
+}
+
+#[cfg(test)]
+mod rpc_server_tests {
+    use super::*;
+    use crate::helpers::events::EventSink;
+
+    struct NoopSink;
+
+    impl EventSink for NoopSink {
+        fn emit_json(&self, _event: &str, _payload: serde_json::Value) -> AvailResult<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        let token = generate_token();
+        assert_eq!(token.len(), RPC_TOKEN_LENGTH);
+        assert!(tokens_match(&token, &token.clone()));
+        assert!(!tokens_match(&token, &generate_token()));
+        assert!(!tokens_match(&token, &token[1..]));
+    }
+
+    #[tokio::test]
+    async fn test_rpc_request_rejections() {
+        let events = EventEmitter::new(NoopSink);
+
+        let response = handle_rpc_line("{not json", "secret", &events).await;
+        assert_eq!(response.error.unwrap().code, PARSE_ERROR);
+
+        let response = handle_rpc_line(
+            r#"{"jsonrpc":"2.0","id":1,"method":"get_sync_status","token":"wrong"}"#,
+            "secret",
+            &events,
+        )
+        .await;
+        assert_eq!(response.id, serde_json::json!(1));
+        assert_eq!(response.error.unwrap().code, UNAUTHORIZED);
+
+        let response = handle_rpc_line(
+            r#"{"jsonrpc":"2.0","id":2,"method":"transfer","token":"secret"}"#,
+            "secret",
+            &events,
+        )
+        .await;
+        assert_eq!(response.error.unwrap().code, METHOD_NOT_FOUND);
+
+        let response = handle_rpc_line(
+            r#"{"jsonrpc":"2.0","id":3,"method":"decrypt_records","params":{"ciphertexts":1},"token":"secret"}"#,
+            "secret",
+            &events,
+        )
+        .await;
+        assert_eq!(response.error.unwrap().code, INVALID_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_request_closes_connection() {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        let (_shutdown, shutdown_rx) = watch::channel(false);
+        tokio::spawn(serve_connection(
+            server,
+            Arc::new("secret".to_string()),
+            EventEmitter::new(NoopSink),
+            shutdown_rx,
+        ));
+
+        let (client_reader, mut client_writer) = tokio::io::split(client);
+        tokio::spawn(async move {
+            let request = vec![b'a'; MAX_REQUEST_BYTES + 1];
+            let _ = client_writer.write_all(&request).await;
+        });
+
+        let mut lines = BufReader::new(client_reader).lines();
+        let response: RpcResponse =
+            serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+        assert_eq!(response.error.unwrap().code, INVALID_REQUEST);
+
+        assert!(lines.next_line().await.unwrap().is_none());
+    }
+}
@@ -1,5 +1,8 @@
 use super::{
+    account::signer::get_signer,
     local_storage::{
+        dapp_policies::{enforce_dapp_policy, evaluate_dapp_policy_raw, settle_policy_spend},
+        dapp_sessions::{authorize_dapp, authorize_record_access, readable_programs},
         encrypted_data::update_encrypted_transaction_state_by_id,
         persistent_storage::{get_address, get_address_string, get_network},
         session::{password::PASS, view::VIEWSESSION},
@@ -13,9 +16,12 @@ use super::{
                 update_record_spent_local_via_nonce,
             },
         },
-        utils::{get_private_key, sign_message},
+        tokens::get_program_id_for_token,
     },
     record_handling::{
+        deploy::{available_fee_balance, ensure_fee_balance, prepare_deployment},
+        execution::{deploy_with_signer, execute_with_signer},
+        program_abi::prepare_inputs,
         records::find_aleo_credits_record_to_spend,
         utils::{
             get_token_balance, handle_deployment_update_and_encrypted_storage,
@@ -26,23 +32,33 @@ use super::{
 };
 use chrono::Local;
 use std::str::FromStr;
+use std::time::Duration;
 
 use crate::api::aleo_client::setup_client;
-use crate::models::event::{AvailEvent, SuccinctAvailEvent};
+use crate::helpers::events::EventEmitter;
+use crate::models::event::{AvailEvent, Event, SuccinctAvailEvent};
 use crate::models::pointers::{deployment::DeploymentPointer, transaction::TransactionPointer};
 use crate::models::wallet_connect::{
     balance::{BalanceRequest, BalanceResponse},
     create_event::{CreateEventRequest, CreateEventResponse},
+    dapp_session::{Caller, DappPermission},
     decrypt::{DecryptRequest, DecryptResponse},
-    get_event::{GetEventRequest, GetEventResponse, GetEventsRequest, GetEventsResponse},
-    records::{GetRecordsRequest, GetRecordsResponse, RecordWithPlaintext},
-    sign::{SignatureRequest, SignatureResponse},
+    deploy::{DeployRequest, DeploymentCost},
+    get_event::{
+        EventsFilter, GetEventRequest, GetEventResponse, GetEventsRequest, GetEventsResponse,
+    },
+    policy::PolicyDecision,
+    records::{
+        GetRecordsRequest, GetRecordsResponse, RecordFilterType, RecordWithPlaintext, RecordsFilter,
+    },
+    sign::{SignatureRequest, SignatureResponse, TypedSignatureRequest, TypedSignatureResponse},
+    sign_in::SignInMessage,
 };
 
 use snarkvm::circuit::Aleo;
 use snarkvm::{
     circuit::{AleoV0, Environment},
-    prelude::{Address, Ciphertext, Field, Network, Program, Record, Signature, Testnet3},
+    prelude::{Address, Ciphertext, Field, Network, Record, Signature, Testnet3, ToFields},
 };
 
 use tauri::{Manager, Window};
@@ -57,8 +73,11 @@ use avail_common::{
     },
 };
 
+/// How recently the password must have been entered for events above a policy's reauthentication threshold
+const REAUTH_WINDOW: Duration = Duration::from_secs(60);
+
 #[tauri::command(rename_all = "snake_case")]
-pub fn get_balance(request: BalanceRequest) -> AvailResult<BalanceResponse> {
+pub fn get_balance(request: BalanceRequest, caller: Caller) -> AvailResult<BalanceResponse> {
     let network = get_network()?;
     println!(
         "===> Asset ID in Request Backend {:?}",
@@ -69,6 +88,12 @@ pub fn get_balance(request: BalanceRequest) -> AvailResult<BalanceResponse> {
         Some(asset_id) => asset_id,
         None => "credits".to_string(),
     };
+
+    let program_id = match asset_id.as_str() {
+        "credits" | "Aleo Credits" => "credits.aleo".to_string(),
+        token_name => get_program_id_for_token(token_name)?,
+    };
+    authorize_record_access(&caller, &[program_id])?;
     println!("===> Asset ID in Backend {:?}", asset_id);
     //TODO - V2 HD wallet support
     let _address = match request.address() {
@@ -88,27 +113,142 @@ pub fn get_balance(request: BalanceRequest) -> AvailResult<BalanceResponse> {
 pub async fn request_create_event(
     request: CreateEventRequest,
     fee_private: bool,
+    caller: Caller,
     window: Window,
 ) -> AvailResult<CreateEventResponse> {
+    handle_create_event_request(request, fee_private, caller, Some(window.into())).await
+}
+
+/// Applies a dapp's policy before creating the event, events the wallet creates itself are not checked
+pub async fn handle_create_event_request(
+    request: CreateEventRequest,
+    fee_private: bool,
+    caller: Caller,
+    window: Option<EventEmitter>,
+) -> AvailResult<CreateEventResponse> {
+    let network = get_network()?;
+    let mut reserved_spend: Option<i64> = None;
+
+    if let Some(origin) = caller.dapp_origin()? {
+        let spend = match SupportedNetworks::from_str(&network)? {
+            SupportedNetworks::Testnet3 => request_spend::<Testnet3>(&request)?,
+            _ => request_spend::<Testnet3>(&request)?,
+        };
+
+        let (decision, decision_id) =
+            enforce_dapp_policy(origin, request.program_id(), request.function_id(), spend)?;
+
+        // auto-approved requests are covered by the policy, anything else needs the user's approval
+        if decision == PolicyDecision::AutoApprove {
+            reserved_spend = Some(decision_id);
+        } else {
+            authorize_dapp(&caller, &DappPermission::Execute)?;
+        }
+
+        if decision == PolicyDecision::Reauthenticate && !PASS.authenticated_within(REAUTH_WINDOW) {
+            PASS.expire_session()?;
+        }
+    }
+
+    let response = match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => {
+            request_create_event_raw::<Testnet3, AleoV0>(request, fee_private, &caller, window)
+                .await
+        }
+        _ => {
+            request_create_event_raw::<Testnet3, AleoV0>(request, fee_private, &caller, window)
+                .await
+        } //SupportedNetworks::Mainnet => request_create_event_raw::<Mainnet>(request),
+    };
+
+    // the auto-approved spend only counts against the budget if the event was broadcast
+    if let Some(decision_id) = reserved_spend {
+        let broadcast = matches!(&response, Ok(response) if response.error().is_none());
+        settle_policy_spend(decision_id, broadcast)?;
+    }
+
+    response
+}
+
+/// Decides if a dapp's event can be auto-approved without prompting the user
+#[tauri::command(rename_all = "snake_case")]
+pub fn evaluate_dapp_policy(
+    caller: Caller,
+    request: CreateEventRequest,
+) -> AvailResult<PolicyDecision> {
+    let origin = match caller.dapp_origin()? {
+        Some(origin) => origin,
+        None => return Ok(PolicyDecision::Prompt),
+    };
+
+    let network = get_network()?;
+
+    let spend = match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => request_spend::<Testnet3>(&request)?,
+        _ => request_spend::<Testnet3>(&request)?,
+    };
+
+    let (decision, _reason) =
+        evaluate_dapp_policy_raw(origin, request.program_id(), request.function_id(), spend)?;
+
+    Ok(decision)
+}
+
+/// Credits an event spends, the fee plus the amount of credits transferred.
+/// Amounts moved by other programs are not credits, so only their fee counts against a policy budget.
+fn request_spend<N: Network>(request: &CreateEventRequest) -> AvailResult<f64> {
+    let mut spend = request.fee();
+
+    if request.event_type() == &EventTypeCommon::Execute && request.program_id() == "credits.aleo" {
+        let (_values, _nonces, _recipient, amount) =
+            parse_inputs::<N>(request.inputs().clone(), request.function_id())?;
+        spend += amount.unwrap_or(0.0);
+    }
+
+    Ok(spend)
+}
+
+/// Prices a deployment without proving it, with the balance its fee would be paid from
+#[tauri::command(rename_all = "snake_case")]
+pub fn estimate_deployment_cost(
+    request: DeployRequest,
+    fee_private: bool,
+) -> AvailResult<DeploymentCost> {
     let network = get_network()?;
+
     match SupportedNetworks::from_str(&network)? {
         SupportedNetworks::Testnet3 => {
-            request_create_event_raw::<Testnet3, AleoV0>(request, fee_private, Some(window)).await
+            estimate_deployment_cost_raw::<Testnet3, AleoV0>(request, fee_private)
         }
-        _ => request_create_event_raw::<Testnet3, AleoV0>(request, fee_private, Some(window)).await, //SupportedNetworks::Mainnet => request_create_event_raw::<Mainnet>(request),
+        _ => estimate_deployment_cost_raw::<Testnet3, AleoV0>(request, fee_private),
     }
 }
 
+pub fn estimate_deployment_cost_raw<N: Network, A: Aleo + Environment<Network = N>>(
+    request: DeployRequest,
+    fee_private: bool,
+) -> AvailResult<DeploymentCost> {
+    let api_client = setup_client::<N>()?;
+    let deployment = prepare_deployment::<N, A>(&request, "", &api_client)?;
+
+    let available = available_fee_balance::<N>(fee_private)?;
+
+    Ok(deployment.to_cost(fee_private, available))
+}
+
 pub async fn request_create_event_raw<N: Network, A: Aleo + Environment<Network = N>>(
     request: CreateEventRequest,
     fee_private: bool,
-    window: Option<Window>,
+    caller: &Caller,
+    window: Option<EventEmitter>,
 ) -> AvailResult<CreateEventResponse> {
     let api_client = setup_client::<N>()?;
-    let private_key = match get_private_key::<N>(None) {
-        Ok(private_key) => {
-            PASS.extend_session()?;
-            private_key
+    let signer = match get_signer::<N, A>(None) {
+        Ok(signer) => {
+            if caller.extends_session() {
+                PASS.extend_session()?;
+            }
+            signer
         }
         Err(e) => match e.error_type {
             AvailErrorType::Unauthorized => {
@@ -142,14 +282,11 @@ pub async fn request_create_event_raw<N: Network, A: Aleo + Environment<Network
     let address = get_address::<N>()?;
     let fee = (request.fee() * 1000000.0) as u64;
 
-    let mut program_manager =
-        ProgramManager::<N>::new(Some(private_key), None, Some(api_client), None)?;
-
     let mut fee_record_nonce: Option<String> = None;
 
     if request.event_type() == &EventTypeCommon::Deploy {
-        let program = match request.inputs().get(0) {
-            Some(program) => program,
+        let deploy_request = match DeployRequest::from_create_event(&request) {
+            Some(deploy_request) => deploy_request,
             None => {
                 return Err(AvailError::new(
                     AvailErrorType::Internal,
@@ -159,25 +296,19 @@ pub async fn request_create_event_raw<N: Network, A: Aleo + Environment<Network
             }
         };
 
-        let program = match Program::<N>::from_str(program) {
-            Ok(program) => program,
-            Err(_) => {
-                return Err(AvailError::new(
-                    AvailErrorType::Internal,
-                    "Program string parsing failure".to_string(),
-                    "Program string parsing failure".to_string(),
-                ))
-            }
-        };
-
-        program_manager.add_program(&program)?;
+        let deployment =
+            match prepare_deployment::<N, A>(&deploy_request, request.program_id(), &api_client) {
+                Ok(deployment) => deployment,
+                Err(e) => return Ok(CreateEventResponse::new(None, Some(e.external_msg))),
+            };
 
-        // TODO - Check if dapps take all costs into account for deployment
-        //let (minimum_deployment_cost, (_storage_cost, _namespace_cost)) =
-        // program_manager.estimate_deployment_fee::<A>(&program, &private_key)?;
+        let program = deployment.program.clone();
+        let fee = deployment.fee;
 
-        //let mut fee = minimum_deployment_cost;
-        //fee += request.fee();
+        let available = available_fee_balance::<N>(fee_private)?;
+        if let Err(e) = ensure_fee_balance(fee, available, fee_private) {
+            return Ok(CreateEventResponse::new(None, Some(e.external_msg)));
+        }
 
         let (fee_record, _fee_commitment, fee_id) = match fee_private {
             true => {
@@ -194,8 +325,8 @@ pub async fn request_create_event_raw<N: Network, A: Aleo + Environment<Network
 
         let mut pending_deployment_tx = DeploymentPointer::<N>::new(
             None,
-            request.program_id().clone(),
-            request.fee(),
+            program.id().to_string(),
+            fee as f64 / 1000000.0,
             TransactionState::Processing,
             None,
             fee_record_nonce,
@@ -223,47 +354,47 @@ pub async fn request_create_event_raw<N: Network, A: Aleo + Environment<Network
             update_record_spent_local::<N>(&fee_id, true)?;
         }
 
-        let transaction_id = match program_manager.deploy_program(program.id(), 0, fee_record, None)
-        {
-            Ok(tx_id) => tx_id,
-            Err(_) => {
-                if let Some(fee_id) = fee_id {
-                    update_record_spent_local::<N>(&fee_id, false)?;
-                }
+        let transaction_id =
+            match deploy_with_signer::<N>(signer.as_ref(), &api_client, deployment, fee_record) {
+                Ok(tx_id) => tx_id,
+                Err(_) => {
+                    if let Some(fee_id) = fee_id {
+                        update_record_spent_local::<N>(&fee_id, false)?;
+                    }
 
-                pending_deployment_tx.update_failed_deployment(
-                    "Deployment failed, no records were spent.".to_string(),
-                );
+                    pending_deployment_tx.update_failed_deployment(
+                        "Deployment failed, no records were spent.".to_string(),
+                    );
 
-                let encrypted_failed_deployment =
-                    pending_deployment_tx.to_encrypted_data(address)?;
+                    let encrypted_failed_deployment =
+                        pending_deployment_tx.to_encrypted_data(address)?;
 
-                update_encrypted_transaction_state_by_id(
-                    &pending_event_id,
-                    &encrypted_failed_deployment.ciphertext,
-                    &encrypted_failed_deployment.nonce,
-                    TransactionState::Failed,
-                )?;
+                    update_encrypted_transaction_state_by_id(
+                        &pending_event_id,
+                        &encrypted_failed_deployment.ciphertext,
+                        &encrypted_failed_deployment.nonce,
+                        TransactionState::Failed,
+                    )?;
 
-                if let Some(window) = window.clone() {
-                    match window.emit("tx_state_change", &pending_event_id) {
-                        Ok(_) => {}
-                        Err(e) => {
-                            return Err(AvailError::new(
-                                AvailErrorType::Internal,
-                                "Error emitting tx_state_change event".to_string(),
-                                "Error emitting transaction state".to_string(),
-                            ));
-                        }
-                    };
-                }
+                    if let Some(window) = window.clone() {
+                        match window.emit("tx_state_change", &pending_event_id) {
+                            Ok(_) => {}
+                            Err(e) => {
+                                return Err(AvailError::new(
+                                    AvailErrorType::Internal,
+                                    "Error emitting tx_state_change event".to_string(),
+                                    "Error emitting transaction state".to_string(),
+                                ));
+                            }
+                        };
+                    }
 
-                return Ok(CreateEventResponse::new(
-                    Some(pending_event_id),
-                    Some(format!("Error deploying program: '{}'", program.id())),
-                ));
-            }
-        };
+                    return Ok(CreateEventResponse::new(
+                        Some(pending_event_id),
+                        Some(format!("Error deploying program: '{}'", program.id())),
+                    ));
+                }
+            };
 
         handle_deployment_update_and_encrypted_storage::<N>(
             transaction_id,
@@ -277,8 +408,18 @@ pub async fn request_create_event_raw<N: Network, A: Aleo + Environment<Network
     } else {
         let mut record_nonces: Vec<String> = vec![];
 
+        let inputs = match prepare_inputs::<N>(
+            &api_client,
+            request.program_id(),
+            request.function_id(),
+            request.inputs(),
+        ) {
+            Ok(inputs) => inputs,
+            Err(e) => return Ok(CreateEventResponse::new(None, Some(e.external_msg))),
+        };
+
         let (input_values, input_nonces, recipient_address, amount) =
-            parse_inputs::<N>(request.inputs().clone(), &request.function_id().clone())?;
+            parse_inputs::<N>(inputs, &request.function_id().clone())?;
 
         let (fee_record, _fee_commitment, fee_id) = match fee_private {
             true => {
@@ -337,13 +478,14 @@ pub async fn request_create_event_raw<N: Network, A: Aleo + Environment<Network
             update_record_spent_local::<N>(&fee_id, false)?;
         }
         println!("=====> INPUTS {:?}", input_values);
-        let transaction_id = match program_manager.execute_program(
-            request.program_id().clone(),
-            request.function_id().clone(),
-            input_values.iter(),
-            0,
+        let transaction_id = match execute_with_signer::<N>(
+            signer.as_ref(),
+            &api_client,
+            request.program_id(),
+            request.function_id(),
+            &input_values,
+            fee,
             fee_record,
-            None,
         ) {
             Ok(tx_id) => tx_id,
             Err(_) => {
@@ -423,7 +565,21 @@ pub async fn request_create_event_raw<N: Network, A: Aleo + Environment<Network
 }
 
 #[tauri::command(rename_all = "snake_case")]
-pub async fn get_records(request: GetRecordsRequest) -> AvailResult<GetRecordsResponse> {
+pub async fn get_records(
+    request: GetRecordsRequest,
+    caller: Caller,
+) -> AvailResult<GetRecordsResponse> {
+    let request = match scope_records_request(request, &caller) {
+        Ok(request) => request,
+        Err(error) => {
+            return Ok(GetRecordsResponse::new(
+                vec![],
+                None,
+                Some(error.external_msg),
+            ))
+        }
+    };
+
     let network = get_network()?;
     match SupportedNetworks::from_str(&network)? {
         SupportedNetworks::Testnet3 => match get_records_raw::<Testnet3>(request) {
@@ -449,6 +605,51 @@ pub async fn get_records(request: GetRecordsRequest) -> AvailResult<GetRecordsRe
     }
 }
 
+/// Limits a dapp's records request to the programs its session can read
+fn scope_records_request(
+    request: GetRecordsRequest,
+    caller: &Caller,
+) -> AvailResult<GetRecordsRequest> {
+    let requested_programs = match request.filter() {
+        Some(filter) => filter.program_ids().clone(),
+        None => vec![],
+    };
+
+    if !requested_programs.is_empty() {
+        authorize_record_access(caller, &requested_programs)?;
+        return Ok(request);
+    }
+
+    let program_ids = match readable_programs(caller)? {
+        Some(program_ids) => program_ids,
+        None => return Ok(request),
+    };
+
+    if program_ids.is_empty() {
+        return Err(AvailError::new(
+            AvailErrorType::Unauthorized,
+            "Dapp has no record permissions".to_string(),
+            "This dapp is not permitted to read records, please approve it first.".to_string(),
+        ));
+    }
+
+    let filter = match request.filter() {
+        Some(filter) => RecordsFilter::new(
+            program_ids,
+            filter.function_id().clone(),
+            RecordFilterType::from_string(filter.record_type()).clone(),
+            filter.record_name().clone(),
+        ),
+        None => RecordsFilter::new(program_ids, None, RecordFilterType::All, None),
+    };
+
+    Ok(GetRecordsRequest::new(
+        request.address().clone(),
+        Some(filter),
+        *request.page(),
+    ))
+}
+
 pub fn get_records_raw<N: Network>(
     request: GetRecordsRequest,
 ) -> AvailResult<(Vec<RecordWithPlaintext>, i32)> {
@@ -469,46 +670,57 @@ pub fn get_records_raw<N: Network>(
 }
 
 #[tauri::command(rename_all = "snake_case")]
-pub fn sign(request: SignatureRequest, window: Window) -> AvailResult<SignatureResponse> {
+pub fn sign(
+    request: SignatureRequest,
+    caller: Caller,
+    window: Window,
+) -> AvailResult<SignatureResponse> {
+    handle_sign_request(request, caller, Some(window.into()))
+}
+
+pub fn handle_sign_request(
+    request: SignatureRequest,
+    caller: Caller,
+    window: Option<EventEmitter>,
+) -> AvailResult<SignatureResponse> {
+    if let Err(e) = authorize_dapp(&caller, &DappPermission::Sign) {
+        return Ok(SignatureResponse::new(None, None, Some(e.external_msg)));
+    }
+
     let network = get_network()?;
 
-    match SupportedNetworks::from_str(&network)? {
-        SupportedNetworks::Testnet3 => {
-            match sign_message::<Testnet3>(&request.get_message(), None) {
-                Ok((signature, message_field)) => Ok(SignatureResponse::new(
-                    Some(signature.to_string()),
-                    Some(message_field.to_string()),
-                    None,
-                )),
-                Err(e) => {
-                    if e.error_type == AvailErrorType::Unauthorized {
-                        match window.emit("reauthenticate", "sign") {
-                            Ok(_) => {}
-                            Err(e) => {
-                                return Err(AvailError::new(
-                                    AvailErrorType::Internal,
-                                    "Error emitting reauthentication event".to_string(),
-                                    "Error emitting reauthentication state".to_string(),
-                                ));
-                            }
-                        };
-                    }
-                    Ok(SignatureResponse::new(
-                        None,
-                        None,
-                        Some("Signing Failed".to_string()),
-                    ))
-                }
+    if SignInMessage::is_sign_in_message(&request.get_message()) {
+        let validation = match SupportedNetworks::from_str(&network)? {
+            SupportedNetworks::Testnet3 => {
+                validate_sign_in_message::<Testnet3>(&request.get_message(), &caller)
             }
+            _ => validate_sign_in_message::<Testnet3>(&request.get_message(), &caller),
+        };
+
+        if let Err(e) = validation {
+            return Ok(SignatureResponse::new(None, None, Some(e.external_msg)));
         }
-        _ => match sign_message::<Testnet3>(&request.get_message(), None) {
-            Ok((signature, message_field)) => Ok(SignatureResponse::new(
-                Some(signature.to_string()),
-                Some(message_field.to_string()),
-                None,
-            )),
-            Err(e) => {
-                if e.error_type == AvailErrorType::Unauthorized {
+    }
+
+    let signature = match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => get_signer::<Testnet3, AleoV0>(None)
+            .and_then(|signer| signer.sign_message(&request.get_message()))
+            .map(|(signature, message_field)| (signature.to_string(), message_field.to_string())),
+        _ => get_signer::<Testnet3, AleoV0>(None)
+            .and_then(|signer| signer.sign_message(&request.get_message()))
+            .map(|(signature, message_field)| (signature.to_string(), message_field.to_string())),
+        //SupportedNetworks::Mainnet => decrypt_record_raw::<Mainnet>(ciphertext),
+    };
+
+    match signature {
+        Ok((signature, message_field)) => Ok(SignatureResponse::new(
+            Some(signature),
+            Some(message_field),
+            None,
+        )),
+        Err(e) => {
+            if e.error_type == AvailErrorType::Unauthorized {
+                if let Some(window) = window {
                     match window.emit("reauthenticate", "sign") {
                         Ok(_) => {}
                         Err(e) => {
@@ -520,28 +732,160 @@ pub fn sign(request: SignatureRequest, window: Window) -> AvailResult<SignatureR
                         }
                     };
                 }
-                Ok(SignatureResponse::new(
-                    None,
-                    None,
-                    Some("Signing Failed".to_string()),
-                ))
             }
-        },
-        //SupportedNetworks::Mainnet => decrypt_record_raw::<Mainnet>(ciphertext),
+            Ok(SignatureResponse::new(
+                None,
+                None,
+                Some("Signing Failed".to_string()),
+            ))
+        }
+    }
+}
+
+/// Checks a sign in message was requested by the dapp it names, for this wallet, and has not expired
+fn validate_sign_in_message<N: Network>(
+    message: &str,
+    caller: &Caller,
+) -> AvailResult<SignInMessage> {
+    let origin = match caller.dapp_origin()? {
+        Some(origin) => origin,
+        None => {
+            return Err(AvailError::new(
+                AvailErrorType::Validation,
+                "Sign in message requested without an origin".to_string(),
+                "Sign in requests can only come from a dapp.".to_string(),
+            ))
+        }
+    };
+
+    let sign_in = SignInMessage::parse(message)?;
+    sign_in.validate(origin, &get_address_string()?, &N::ID.to_string())?;
+
+    Ok(sign_in)
+}
+
+/// Parses a sign in message so it can be shown to the user, None if the message is plain text
+#[tauri::command(rename_all = "snake_case")]
+pub fn parse_sign_in_message(message: &str) -> AvailResult<Option<SignInMessage>> {
+    match SignInMessage::is_sign_in_message(message) {
+        true => Ok(Some(SignInMessage::parse(message)?)),
+        false => Ok(None),
     }
 }
 
 #[tauri::command(rename_all = "snake_case")]
-pub fn verify(message: &str, address: &str, signature: &str) -> AvailResult<bool> {
+pub fn sign_typed(
+    request: TypedSignatureRequest,
+    caller: Caller,
+    window: Window,
+) -> AvailResult<TypedSignatureResponse> {
+    let authorized = authorize_dapp(&caller, &DappPermission::Sign).and_then(|_| {
+        match caller.dapp_origin()? {
+            Some(origin) => request.validate_domain(origin),
+            None => Ok(()),
+        }
+    });
+
+    if let Err(e) = authorized {
+        return Ok(TypedSignatureResponse::new(
+            None,
+            None,
+            None,
+            Some(e.external_msg),
+        ));
+    }
+
     let network = get_network()?;
 
-    match SupportedNetworks::from_str(&network)? {
-        SupportedNetworks::Testnet3 => verify_signature::<Testnet3>(message, address, signature),
-        _ => verify_signature::<Testnet3>(message, address, signature),
+    let result = match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => sign_typed_raw::<Testnet3, AleoV0>(&request),
+        _ => sign_typed_raw::<Testnet3, AleoV0>(&request),
+    };
+
+    match result {
+        Ok(response) => Ok(response),
+        Err(e) => {
+            if e.error_type == AvailErrorType::Unauthorized {
+                match window.emit("reauthenticate", "sign") {
+                    Ok(_) => {}
+                    Err(e) => {
+                        return Err(AvailError::new(
+                            AvailErrorType::Internal,
+                            "Error emitting reauthentication event".to_string(),
+                            "Error emitting reauthentication state".to_string(),
+                        ));
+                    }
+                };
+            }
+
+            Ok(TypedSignatureResponse::new(
+                None,
+                None,
+                None,
+                Some(e.external_msg),
+            ))
+        }
     }
 }
 
-fn verify_signature<N: Network>(
+fn sign_typed_raw<N: Network, A: Aleo + Environment<Network = N>>(
+    request: &TypedSignatureRequest,
+) -> AvailResult<TypedSignatureResponse> {
+    let plaintext = request.to_plaintext::<N>()?;
+    let (signature, fields) = get_signer::<N, A>(None)?.sign_plaintext(&plaintext)?;
+
+    Ok(TypedSignatureResponse::new(
+        Some(signature.to_string()),
+        Some(plaintext.to_string()),
+        Some(fields.iter().map(|field| field.to_string()).collect()),
+        None,
+    ))
+}
+
+/// Verifies a signature over a message, if a domain and nonce are given the message is treated as a typed Plaintext
+#[tauri::command(rename_all = "snake_case")]
+pub fn verify(
+    message: &str,
+    address: &str,
+    signature: &str,
+    domain: Option<String>,
+    nonce: Option<u64>,
+) -> AvailResult<bool> {
+    let network = get_network()?;
+
+    match (domain, nonce) {
+        (Some(domain), Some(nonce)) => {
+            let request = TypedSignatureRequest::new(domain, nonce, message.to_string(), None);
+            match SupportedNetworks::from_str(&network)? {
+                SupportedNetworks::Testnet3 => {
+                    verify_typed_signature::<Testnet3>(&request, address, signature)
+                }
+                _ => verify_typed_signature::<Testnet3>(&request, address, signature),
+            }
+        }
+        _ => match SupportedNetworks::from_str(&network)? {
+            SupportedNetworks::Testnet3 => {
+                verify_signature::<Testnet3>(message, address, signature)
+            }
+            _ => verify_signature::<Testnet3>(message, address, signature),
+        },
+    }
+}
+
+fn verify_typed_signature<N: Network>(
+    request: &TypedSignatureRequest,
+    address: &str,
+    signature: &str,
+) -> AvailResult<bool> {
+    let signature = Signature::<N>::from_str(signature)?;
+    let address = Address::<N>::from_str(address)?;
+
+    let msg = request.to_plaintext::<N>()?.to_fields()?;
+
+    Ok(signature.verify(&address, &msg))
+}
+
+pub fn verify_signature<N: Network>(
     message: &str,
     address: &str,
     signature: &str,
@@ -559,7 +903,11 @@ fn verify_signature<N: Network>(
 }
 
 #[tauri::command(rename_all = "snake_case")]
-pub fn decrypt_records(request: DecryptRequest) -> AvailResult<DecryptResponse> {
+pub fn decrypt_records(request: DecryptRequest, caller: Caller) -> AvailResult<DecryptResponse> {
+    if let Err(e) = authorize_dapp(&caller, &DappPermission::Decrypt) {
+        return Ok(DecryptResponse::new(vec![], Some(e.external_msg)));
+    }
+
     let network = get_network()?;
     match SupportedNetworks::from_str(&network)? {
         SupportedNetworks::Testnet3 => match decrypt_records_raw::<Testnet3>(request.ciphertexts) {
@@ -596,11 +944,71 @@ pub fn decrypt_records_raw<N: Network>(ciphertext: Vec<String>) -> AvailResult<V
     Ok(records)
 }
 
+/// Programs whose events a caller may see, None for the wallet which is unrestricted
+fn event_scope(caller: &Caller, filter: Option<&EventsFilter>) -> AvailResult<Option<Vec<String>>> {
+    if let Some(program_id) = filter.and_then(|filter| filter.program_id.as_ref()) {
+        authorize_record_access(caller, &[program_id.clone()])?;
+    }
+
+    let program_ids = match readable_programs(caller)? {
+        Some(program_ids) => program_ids,
+        None => return Ok(None),
+    };
+
+    if program_ids.is_empty() {
+        return Err(AvailError::new(
+            AvailErrorType::Unauthorized,
+            "Dapp has no record permissions".to_string(),
+            "This dapp is not permitted to read your activity, please approve it first."
+                .to_string(),
+        ));
+    }
+
+    Ok(Some(program_ids))
+}
+
+fn in_event_scope(scope: &Option<Vec<String>>, program_id: Option<&String>) -> bool {
+    match scope {
+        Some(program_ids) => {
+            program_id.map_or(false, |program_id| program_ids.contains(program_id))
+        }
+        None => true,
+    }
+}
+
+fn get_scoped_events<N: Network>(
+    request: GetEventsRequest,
+    caller: &Caller,
+) -> AvailResult<Vec<Event>> {
+    let scope = event_scope(caller, request.filter.as_ref())?;
+    let mut events = get_events_raw::<N>(request)?;
+    events.retain(|event| in_event_scope(&scope, event.program_id()));
+
+    Ok(events)
+}
+
+fn get_scoped_event<N: Network>(id: &str, caller: &Caller) -> AvailResult<Event> {
+    let scope = event_scope(caller, None)?;
+    let event = get_event_raw::<N>(id)?;
+
+    match in_event_scope(&scope, event.program_id()) {
+        true => Ok(event),
+        false => Err(AvailError::new(
+            AvailErrorType::Unauthorized,
+            format!("Event {} is outside the dapp's readable programs", id),
+            "This dapp is not permitted to read this event.".to_string(),
+        )),
+    }
+}
+
 #[tauri::command(rename_all = "snake_case")]
-pub async fn get_events(request: GetEventsRequest) -> AvailResult<GetEventsResponse> {
+pub async fn get_events(
+    request: GetEventsRequest,
+    caller: Caller,
+) -> AvailResult<GetEventsResponse> {
     let network = get_network()?;
     match SupportedNetworks::from_str(&network)? {
-        SupportedNetworks::Testnet3 => match get_events_raw::<Testnet3>(request) {
+        SupportedNetworks::Testnet3 => match get_scoped_events::<Testnet3>(request, &caller) {
             Ok(events) => Ok(GetEventsResponse::new(events, None, None)),
             Err(error) => Ok(GetEventsResponse::new(
                 vec![],
@@ -608,7 +1016,7 @@ pub async fn get_events(request: GetEventsRequest) -> AvailResult<GetEventsRespo
                 Some(error.external_msg),
             )),
         },
-        _ => match get_events_raw::<Testnet3>(request) {
+        _ => match get_scoped_events::<Testnet3>(request, &caller) {
             Ok(events) => Ok(GetEventsResponse::new(events, None, None)),
             Err(error) => Ok(GetEventsResponse::new(
                 vec![],
@@ -621,14 +1029,14 @@ pub async fn get_events(request: GetEventsRequest) -> AvailResult<GetEventsRespo
 }
 
 #[tauri::command(rename_all = "snake_case")]
-pub fn get_event(request: GetEventRequest) -> AvailResult<GetEventResponse> {
+pub fn get_event(request: GetEventRequest, caller: Caller) -> AvailResult<GetEventResponse> {
     let network = get_network()?;
     match SupportedNetworks::from_str(&network)? {
-        SupportedNetworks::Testnet3 => match get_event_raw::<Testnet3>(&request.id) {
+        SupportedNetworks::Testnet3 => match get_scoped_event::<Testnet3>(&request.id, &caller) {
             Ok(event) => Ok(GetEventResponse::new(Some(event), None)),
             Err(error) => Ok(GetEventResponse::new(None, Some(error.external_msg))),
         },
-        _ => match get_event_raw::<Testnet3>(&request.id) {
+        _ => match get_scoped_event::<Testnet3>(&request.id, &caller) {
             Ok(event) => Ok(GetEventResponse::new(Some(event), None)),
             Err(error) => Ok(GetEventResponse::new(None, Some(error.external_msg))),
         },
@@ -638,24 +1046,35 @@ pub fn get_event(request: GetEventRequest) -> AvailResult<GetEventResponse> {
 
 /* --Avail Events-- */
 #[tauri::command(rename_all = "snake_case")]
-pub fn get_avail_events(request: GetEventsRequest) -> AvailResult<Vec<AvailEvent>> {
+pub fn get_avail_events(request: GetEventsRequest, caller: Caller) -> AvailResult<Vec<AvailEvent>> {
+    let scope = event_scope(&caller, request.filter.as_ref())?;
+
     let network = get_network()?;
-    match SupportedNetworks::from_str(&network)? {
-        SupportedNetworks::Testnet3 => get_avail_events_raw::<Testnet3>(request),
-        _ => get_avail_events_raw::<Testnet3>(request), //SupportedNetworks::Mainnet => get_events_raw::<Mainnet>(request),
-    }
+    let mut events = match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => get_avail_events_raw::<Testnet3>(request)?,
+        _ => get_avail_events_raw::<Testnet3>(request)?, //SupportedNetworks::Mainnet => get_events_raw::<Mainnet>(request),
+    };
+    events.retain(|event| in_event_scope(&scope, event.program_id()));
+
+    Ok(events)
 }
 
 #[tauri::command(rename_all = "snake_case")]
 pub fn get_succinct_avail_events(
     request: GetEventsRequest,
+    caller: Caller,
 ) -> AvailResult<Vec<SuccinctAvailEvent>> {
+    let scope = event_scope(&caller, request.filter.as_ref())?;
+
     let network = get_network()?;
-    match SupportedNetworks::from_str(&network)? {
-        SupportedNetworks::Testnet3 => get_succinct_avail_events_raw::<Testnet3>(request),
-        _ => get_succinct_avail_events_raw::<Testnet3>(request),
-    }
+    let mut events = match SupportedNetworks::from_str(&network)? {
+        SupportedNetworks::Testnet3 => get_succinct_avail_events_raw::<Testnet3>(request)?,
+        _ => get_succinct_avail_events_raw::<Testnet3>(request)?,
+    };
     //SupportedNetworks::Mainnet => get_events_raw::<Mainnet>(request),
+    events.retain(|event| in_event_scope(&scope, event.program_id()));
+
+    Ok(events)
 }
 
 #[tauri::command(rename_all = "snake_case")]
@@ -746,7 +1165,7 @@ mod test {
             "credits".to_string(),
         );
 
-        transfer_raw::<Testnet3>(request, None).await.unwrap();
+        transfer_raw::<Testnet3, AleoV0>(request, None).await.unwrap();
     }
     */
 
@@ -789,7 +1208,7 @@ mod test {
             " <<<<<<<<<<<<<< Testing get_balance() fn in Wallet Connect Rust API >>>>>>>>>>>>>>>"
         );
         let request = BalanceRequest::new(Some("credits"), None);
-        let res = get_balance(request).unwrap();
+        let res = get_balance(request, None).unwrap();
         println!("res: {:?}", res);
     }
 
@@ -876,7 +1295,9 @@ mod test {
             "credits".to_string(),
         );
 
-        transfer_raw::<Testnet3>(request, None).await.unwrap();
+        transfer_raw::<Testnet3, AleoV0>(request, None)
+            .await
+            .unwrap();
         /* --SETUP COMPLETE */
 
         let recipient = Address::<Testnet3>::from_str(TESTNET3_ADDRESS).unwrap();
@@ -904,7 +1325,7 @@ mod test {
         PASS.set_pass_session(STRONG_PASSWORD).unwrap();
 
         let result_create_event =
-            request_create_event_raw::<Testnet3, AleoV0>(request, false, None)
+            request_create_event_raw::<Testnet3, AleoV0>(request, false, &Caller::Wallet, None)
                 .await
                 .unwrap();
         println!("res: {:?}", result_create_event);
@@ -935,7 +1356,7 @@ mod test {
         }
 
         let request = DecryptRequest::new(ciphertexts);
-        let res = decrypt_records(request).unwrap();
+        let res = decrypt_records(request, None).unwrap();
 
         println!("Result: {:?}", res);
     }
@@ -957,7 +1378,7 @@ mod test {
             page: None,
         };
 
-        let res = get_events(request).await.unwrap();
+        let res = get_events(request, Caller::Wallet).await.unwrap();
 
         println!("Result: {:?}", res);
     }
@@ -993,11 +1414,92 @@ mod test {
 
         let address = Address::<Testnet3>::try_from(&pk).unwrap();
 
-        let res = verify(message, &address.to_string(), &signature.to_string()).unwrap();
+        let res = verify(
+            message,
+            &address.to_string(),
+            &signature.to_string(),
+            None,
+            None,
+        )
+        .unwrap();
 
         assert_eq!(res, true);
     }
 
+    #[test]
+    fn test_verify_sign_in_signature() {
+        let pk = PrivateKey::<Testnet3>::from_str(TESTNET_PRIVATE_KEY).unwrap();
+        let address = Address::<Testnet3>::try_from(&pk).unwrap();
+
+        let message = SignInMessage::new(
+            "dapp.example".to_string(),
+            address.to_string(),
+            None,
+            "https://dapp.example".to_string(),
+            Testnet3::ID.to_string(),
+            "KEqj6BDUl0Nh0izy".to_string(),
+            chrono::Utc::now(),
+            None,
+        )
+        .to_message();
+
+        let (signature, _) = sign_message_w_key::<Testnet3>(&message, &pk).unwrap();
+
+        let parsed = parse_sign_in_message(&message).unwrap().unwrap();
+        assert_eq!(parsed.address(), &address.to_string());
+
+        let res = verify(
+            &message,
+            &address.to_string(),
+            &signature.to_string(),
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(res);
+    }
+
+    #[test]
+    fn test_verify_typed_signature() {
+        let pk = PrivateKey::<Testnet3>::from_str(TESTNET_PRIVATE_KEY).unwrap();
+        let address = Address::<Testnet3>::try_from(&pk).unwrap();
+
+        let request = TypedSignatureRequest::new(
+            "dapp.example".to_string(),
+            7,
+            "{ amount: 5u64 }".to_string(),
+            None,
+        );
+        let fields = request
+            .to_plaintext::<Testnet3>()
+            .unwrap()
+            .to_fields()
+            .unwrap();
+        let signature = pk.sign(&fields, &mut rand::thread_rng()).unwrap();
+
+        let res = verify_typed_signature::<Testnet3>(
+            &request,
+            &address.to_string(),
+            &signature.to_string(),
+        )
+        .unwrap();
+        assert!(res);
+
+        let replayed = TypedSignatureRequest::new(
+            "dapp.example".to_string(),
+            8,
+            "{ amount: 5u64 }".to_string(),
+            None,
+        );
+        let res = verify_typed_signature::<Testnet3>(
+            &replayed,
+            &address.to_string(),
+            &signature.to_string(),
+        )
+        .unwrap();
+        assert!(!res);
+    }
+
     #[test]
     fn test_fee_f64() {
         let fee = 0.3;
@@ -1009,4 +1511,44 @@ mod test {
         let fee_x = (fee_x as f64) / 1000000.0;
         println!("fee_x: {:?}", fee_x);
     }
+
+    #[test]
+    fn test_event_scope() {
+        use crate::services::local_storage::dapp_sessions::{
+            grant_dapp_permission_raw, revoke_dapp_session,
+        };
+
+        let origin = "https://events.example";
+        let caller = Caller::Dapp(origin.to_string());
+        let credits = "credits.aleo".to_string();
+        let other = "other_program.aleo".to_string();
+
+        // the wallet sees every event
+        let scope = event_scope(&Caller::Wallet, None).unwrap();
+        assert!(scope.is_none());
+        assert!(in_event_scope(&scope, Some(&other)));
+
+        revoke_dapp_session(origin).unwrap();
+        assert!(event_scope(&caller, None).is_err());
+
+        grant_dapp_permission_raw(
+            origin,
+            DappPermission::ReadRecords {
+                program_ids: vec![credits.clone()],
+            },
+            None,
+        )
+        .unwrap();
+
+        let scope = event_scope(&caller, None).unwrap();
+        assert!(in_event_scope(&scope, Some(&credits)));
+        assert!(!in_event_scope(&scope, Some(&other)));
+        assert!(!in_event_scope(&scope, None));
+
+        let filter = EventsFilter::new(None, Some(other), None);
+        let err = event_scope(&caller, Some(&filter)).unwrap_err();
+        assert_eq!(err.error_type, AvailErrorType::Unauthorized);
+
+        revoke_dapp_session(origin).unwrap();
+    }
 }
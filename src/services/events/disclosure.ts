@@ -0,0 +1,16 @@
+import {invoke} from '@tauri-apps/api/core';
+
+// Types
+import {
+	type DisclosurePackage, type DisclosureScope, type DisclosureVerification,
+} from 'src/types/avail-events/disclosure';
+
+export async function createDisclosure(id: string, scope: DisclosureScope) {
+	const res: DisclosurePackage = await invoke('create_disclosure', {id, scope});
+	return res;
+}
+
+export async function verifyDisclosure(disclosure: DisclosurePackage) {
+	const res: DisclosureVerification = await invoke('verify_disclosure', {package: disclosure});
+	return res;
+}
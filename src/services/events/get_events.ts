@@ -13,12 +13,12 @@ export async function getRecentAvailEvents() {
 		page: 0,
 	};
 
-	const res: AvailEvent[] = await invoke('get_avail_events', {request});
+	const res: AvailEvent[] = await invoke('get_avail_events', {request, caller: 'wallet'});
 	return res;
 }
 
 export async function getAvailEvents(request: GetEventsRequest) {
-	const res: AvailEvent[] = await invoke('get_avail_events', {request});
+	const res: AvailEvent[] = await invoke('get_avail_events', {request, caller: 'wallet'});
 	return res;
 }
 
@@ -33,7 +33,7 @@ export async function getAvailEventSuccinct(id: string) {
 }
 
 export async function getAvailEventsSuccinct(request: GetEventsRequest) {
-	const res: SuccinctAvailEvent[] = await invoke('get_succinct_avail_events', {request});
+	const res: SuccinctAvailEvent[] = await invoke('get_succinct_avail_events', {request, caller: 'wallet'});
 	return res;
 }
 
@@ -43,6 +43,6 @@ export async function getRecentAvailEventsSuccinct() {
 		page: 0,
 	};
 
-	const res: SuccinctAvailEvent[] = await invoke('get_succinct_avail_events', {request});
+	const res: SuccinctAvailEvent[] = await invoke('get_succinct_avail_events', {request, caller: 'wallet'});
 	return res;
 }
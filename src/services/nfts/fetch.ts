@@ -1,33 +1,37 @@
 import {invoke} from '@tauri-apps/api/core';
-import {type INft} from '../../types/nfts/nft';
+import {type INft, type NftEntry} from '../../types/nfts/nft';
 
 export async function get_nfts() {
-	const nft_uris = await invoke<[string]>('get_all_nft_data', {});
+	const entries = await invoke<NftEntry[]>('get_all_nft_data', {});
 
-	// Get metadata for each nft, just fetch from nft_uris, they are https links
-	const nfts = await Promise.all(nft_uris.map(async uri => {
-		console.log('fetching nft metadata from', uri);
-		const metadata = await fetch(uri).then(async res => {
-			console.log('Res==>', res);
-			return res.json();
-		});
-		console.log('fetched nft metadata', metadata);
-		// The metadata is either an image or an INft object
-		if (metadata.image === undefined) {
-			return {
-				name: '',
-				image: metadata,
-				attributes: [],
-				mintNumber: 0,
-				collectionLink: '',
-				collectionName: '',
-				collectionDescription: '',
-				sourceLink: '',
-			};
-		}
+	const nfts = entries
+		.filter(entry => {
+			if (entry.error) {
+				console.log('skipping nft', entry.recordId, 'of', entry.collection, entry.error);
+				return false;
+			}
+
+			return true;
+		})
+		.map(entry => {
+			const metadata = entry.metadata as INft | undefined;
 
-		return metadata as INft;
-	}));
+			// The uri points directly to the media when there is no metadata
+			if (metadata?.image === undefined) {
+				return {
+					name: '',
+					image: entry.uri ?? '',
+					attributes: [],
+					mintNumber: 0,
+					collectionLink: '',
+					collectionName: entry.collection,
+					collectionDescription: '',
+					sourceLink: '',
+				};
+			}
+
+			return metadata;
+		});
 
 	return nfts;
 }
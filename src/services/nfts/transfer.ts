@@ -0,0 +1,14 @@
+import {invoke} from '@tauri-apps/api/core';
+
+export type NftTransferRequest = {
+	record_id: string;
+	recipient: string;
+	privacy: 'private' | 'private_to_public';
+	password?: string;
+	fee_private: boolean;
+	fee: number;
+};
+
+export async function transfer_nft(request: NftTransferRequest) {
+	return invoke<string>('transfer_nft', {request});
+}
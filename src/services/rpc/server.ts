@@ -0,0 +1,15 @@
+import {invoke} from '@tauri-apps/api/core';
+import {type RpcServerStatus, type RpcTransport} from 'src/types/rpc/server';
+
+// Starts the local JSON-RPC server, defaults to 127.0.0.1:7520
+export async function startRpcServer(transport?: RpcTransport) {
+	return invoke<RpcServerStatus>('start_rpc_server', {transport});
+}
+
+export async function stopRpcServer() {
+	return invoke<RpcServerStatus>('stop_rpc_server');
+}
+
+export async function getRpcServerStatus() {
+	return invoke<RpcServerStatus>('get_rpc_server_status');
+}
@@ -0,0 +1,11 @@
+import {invoke} from '@tauri-apps/api/core';
+import {type OutboxStatus} from 'src/types/outbox';
+
+// Retries everything queued now, e.g. after connectivity returns
+export async function drainOutbox() {
+	return invoke<OutboxStatus>('drain_outbox');
+}
+
+export async function getOutboxStatus() {
+	return invoke<OutboxStatus>('get_outbox_status');
+}
@@ -0,0 +1,10 @@
+import {invoke} from '@tauri-apps/api/core';
+import {type ResilienceSettings} from 'src/types/resilience';
+
+export async function getResilienceSettings() {
+	return invoke<ResilienceSettings>('get_resilience_settings');
+}
+
+export async function updateResilienceSettings(settings: ResilienceSettings) {
+	return invoke('update_resilience_settings', {settings});
+}
@@ -8,15 +8,24 @@ function parseTokenName(name: string) {
 	return name.split('.')[0];
 }
 
-/// returns all asset ids stored
+export type TokenMetadata = {
+	tokenName: string;
+	programId: string;
+	name: string;
+	symbol: string;
+	decimals: number | null;
+};
+
+/// returns metadata of all tokens stored
 export async function get_stored_tokens() {
-	const res: string[] = await invoke('get_stored_tokens');
+	const res: TokenMetadata[] = await invoke('get_stored_tokens');
 	return res;
 }
 
 /// returns all tokens as AssetType
 export async function handleGetTokens() {
-	const tokens = await get_stored_tokens();
+	const metadata = await get_stored_tokens();
+	const tokens = metadata.map(token => token.tokenName);
 
 	console.log('Tokens: ' + tokens);
 
@@ -41,13 +50,14 @@ export async function handleGetTokens() {
 			let token_total = token_balance.private + token_balance.public;
 			token_total = Math.round((token_total + Number.EPSILON) * 1000) / 1000;
 
-			let symbol = parseTokenName(tokens[i]);
+			const token_metadata = metadata.find(token => token.tokenName === tokens[i]);
+			let symbol = token_metadata?.symbol ?? parseTokenName(tokens[i]);
 			let image_reference;
 
-			if (symbol === 'credits') {
+			if (symbol === 'credits' || symbol === 'ALEO') {
 				symbol = 'ALEO';
 				image_reference = aleo;
-			} else if (symbol === 'usdc') {
+			} else if (symbol.toLowerCase() === 'usdc') {
 				image_reference = usdc;
 			}
 
@@ -0,0 +1,39 @@
+import {invoke} from '@tauri-apps/api/core';
+import {type CreateEventRequest} from 'src/services/wallet-connect/WCTypes';
+import {type OfflineEnvelope} from 'src/types/offline_signing/envelope';
+
+// Online wallet: builds the unsigned execution with the view key only
+export async function createOfflineExecution(request: CreateEventRequest, feePrivate: boolean) {
+	return invoke<OfflineEnvelope>('create_offline_execution', {request, fee_private: feePrivate});
+}
+
+// Offline wallet: signs and proves the execution and its fee
+export async function signOfflineExecution(envelope: OfflineEnvelope, password?: string) {
+	return invoke<OfflineEnvelope>('sign_offline_execution', {envelope, password});
+}
+
+// Online wallet: checks the proven execution against the exported one and broadcasts it, returns the pending event id
+export async function broadcastOfflineExecution(envelope: OfflineEnvelope) {
+	return invoke<string>('broadcast_offline_execution', {envelope});
+}
+
+// Online wallet: cancels an execution that was exported but will not be broadcast, its records become spendable again
+export async function cancelOfflineExecution(envelope: OfflineEnvelope) {
+	return invoke('cancel_offline_execution', {envelope});
+}
+
+export async function offlineEnvelopeToQr(envelope: OfflineEnvelope) {
+	return invoke<string[]>('offline_envelope_to_qr', {envelope});
+}
+
+export async function offlineEnvelopeFromQr(chunks: string[]) {
+	return invoke<OfflineEnvelope>('offline_envelope_from_qr', {chunks});
+}
+
+export async function exportOfflineEnvelope(envelope: OfflineEnvelope) {
+	return invoke<string>('export_offline_envelope', {envelope});
+}
+
+export async function importOfflineEnvelope(path: string) {
+	return invoke<OfflineEnvelope>('import_offline_envelope', {path});
+}
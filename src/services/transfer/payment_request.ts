@@ -0,0 +1,19 @@
+import {invoke} from '@tauri-apps/api/core';
+import {type PaymentPrivacy, type PaymentRequest, type TransferRequest} from 'src/types/transfer_props/tokens';
+
+// Returns the avail:pay URI of a request to this wallet, which is also its QR code payload
+export async function createPaymentRequest(amount?: number, asset?: string, memo?: string, privacy?: PaymentPrivacy) {
+	return invoke<string>('create_payment_request', {
+		amount, asset, memo, privacy,
+	});
+}
+
+export async function parsePaymentRequest(uri: string) {
+	return invoke<PaymentRequest>('parse_payment_request', {uri});
+}
+
+export async function paymentRequestToTransfer(uri: string, fromPrivate: boolean, feePrivate: boolean, fee: number, amount?: number) {
+	return invoke<TransferRequest>('payment_request_to_transfer', {
+		uri, amount, from_private: fromPrivate, fee_private: feePrivate, fee,
+	});
+}
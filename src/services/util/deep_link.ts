@@ -1,5 +1,11 @@
+import {invoke} from '@tauri-apps/api/core';
 import {onOpenUrl} from '@tauri-apps/plugin-deep-link';
 
 await onOpenUrl(urls => {
 	console.log('deep link:', urls);
 });
+
+// Dispatches the links that arrived while the wallet was locked, call once it is unlocked
+export async function flushDeepLinks() {
+	return invoke<number>('flush_deep_links');
+}
@@ -6,7 +6,7 @@ export async function sign(message: string) {
 		message,
 	};
 
-	return invoke<SignatureResponse>('sign', {request});
+	return invoke<SignatureResponse>('sign', {request, caller: 'wallet'});
 }
 
 export async function verify(message: string, signature: string, address: string) {
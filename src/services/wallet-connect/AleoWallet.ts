@@ -28,6 +28,9 @@ import {
 	type GetSelectedAccountResponse,
 	type SignatureRequest,
 	type SignatureResponse,
+	type SignInMessage,
+	type TypedSignatureRequest,
+	type TypedSignatureResponse,
 	type WalletConnectRequest,
 	type GetEventRequest,
 	type GetEventsRequest,
@@ -38,6 +41,40 @@ import {
 	type GetBackendRecordsResponse,
 	convertGetRecordsResponse,
 } from './WCTypes';
+import {type DappPermission, dappCaller, grantDappPermission} from './DappSessions';
+import {type PolicyDecision} from './DappPolicies';
+import {get_stored_tokens} from '../tokens/get_tokens';
+
+/**
+ * Get the program id backing a balance asset
+ * @param assetId - The asset id of a balance request
+ * @returns The program id of the asset
+ */
+async function programIdForAsset(assetId: string): Promise<string> {
+	if (assetId === 'Aleo Credits' || assetId === 'credits') {
+		return 'credits.aleo';
+	}
+
+	const tokens = await get_stored_tokens();
+	return tokens.find(token => token.tokenName === assetId)?.programId ?? assetId;
+}
+
+/**
+ * Format a sign in message for the approval dialog
+ * @param signIn - The parsed sign in message
+ * @returns The fields of the message, one per line
+ */
+function formatSignInMessage(signIn: SignInMessage): string {
+	return [
+		signIn.statement,
+		`Account: ${signIn.address}`,
+		`URI: ${signIn.uri}`,
+		`Chain ID: ${signIn.chainId}`,
+		`Nonce: ${signIn.nonce}`,
+		`Issued At: ${signIn.issuedAt}`,
+		signIn.expirationTime ? `Expires At: ${signIn.expirationTime}` : undefined,
+	].filter(line => line !== undefined).join('\n');
+}
 
 function checkWindow(reference: string) {
 	return getAll().some(win => win.label === reference);
@@ -158,6 +195,8 @@ export async function createWalletConnectDialog(
 		requestType: AleoMethod;
 		requestIdentifier: string;
 		requestEvent?: Web3WalletTypes.SessionRequest;
+		origin?: string;
+		permission?: DappPermission;
 	},
 	wcRequest: WalletConnectRequest,
 ): Promise<JsonRpcResult | JsonRpcError> {
@@ -176,6 +215,10 @@ export async function createWalletConnectDialog(
 		once(dialogConfig.approveEventString, async response => {
 			storeSession(dialogConfig.requestIdentifier);
 			console.log('Approve listener triggered');
+			if (dialogConfig.origin && dialogConfig.permission) {
+				await grantDappPermission(dialogConfig.origin, dialogConfig.permission);
+			}
+
 			dialogConfig
 				.onApprove(response, webview)
 				.then(async response => {
@@ -269,6 +312,10 @@ export class AleoWallet {
 				return this.handleSign(requestEvent);
 			}
 
+			case AleoMethod.ALEO_SIGN_TYPED: {
+				return this.handleSignTyped(requestEvent);
+			}
+
 			case AleoMethod.ALEO_CREATE_EVENT: {
 				return this.handleCreateRequestEvent(requestEvent);
 			}
@@ -311,7 +358,7 @@ export class AleoWallet {
 
 		if (checkNotExpired(requestIdentifier)) {
 			return new Promise((resolve, reject) => {
-				invoke<GetBalancesResponse>('get_balance', {request})
+				invoke<GetBalancesResponse>('get_balance', {request, caller: dappCaller(metadata)})
 					.then(response => {
 						resolve(formatJsonRpcResult(requestEvent.id, response));
 					})
@@ -346,6 +393,7 @@ export class AleoWallet {
 				async onApprove() {
 					const response = await invoke<GetBalancesResponse>('get_balance', {
 						request,
+						caller: dappCaller(metadata),
 					});
 					return formatJsonRpcResult(requestEvent.id, response);
 				},
@@ -356,6 +404,8 @@ export class AleoWallet {
 				requestType: AleoMethod.ALEO_GETBALANCE,
 				requestIdentifier,
 				requestEvent,
+				caller: dappCaller(metadata),
+				permission: {read_records: {programIds: [await programIdForAsset(assetId)]}},
 			},
 			wcRequest,
 		);
@@ -391,7 +441,7 @@ export class AleoWallet {
 
 		async function action(): Promise<JsonRpcResult | JsonRpcError> {
 			return new Promise((resolve, reject) => {
-				invoke<DecryptResponse>('decrypt_records', {request})
+				invoke<DecryptResponse>('decrypt_records', {request, caller: dappCaller(metadata)})
 					.then(response => {
 						resolve(formatJsonRpcResult(requestEvent.id, response));
 					})
@@ -428,6 +478,8 @@ export class AleoWallet {
 				requestType: AleoMethod.ALEO_DECRYPT,
 				requestIdentifier,
 				requestEvent,
+				caller: dappCaller(metadata),
+				permission: 'decrypt',
 			},
 			wcRequest,
 		);
@@ -439,21 +491,26 @@ export class AleoWallet {
 		const metadata = getDappMetadata(requestEvent.topic);
 		const request = requestEvent.params.request.params as SignatureRequest;
 
+		// Sign in messages are shown field by field, malformed ones are rejected by the backend on approval
+		const signIn = await invoke<SignInMessage | undefined>('parse_sign_in_message', {message: request.message})
+			.catch(() => undefined);
+
 		const wcRequest: WalletConnectRequest = {
 			method: 'sign',
-			question:
-        (metadata?.name ?? 'Someone?') + ' wants you to sign this message',
+			question: signIn
+				? `${signIn.domain} wants you to sign in with your Aleo account`
+				: (metadata?.name ?? 'Someone?') + ' wants you to sign this message',
 			imageRef: '../wc-images/sign.svg',
 			approveResponse: 'User approved signature.',
 			rejectResponse: 'User rejected signature.',
-			message: request.message,
+			message: signIn ? formatSignInMessage(signIn) : request.message,
 			dappImage: metadata?.img,
 			dappUrl: metadata?.url,
 		};
 
 		async function action(): Promise<JsonRpcResult | JsonRpcError> {
 			return new Promise((resolve, reject) => {
-				invoke<SignatureResponse>('sign', {request})
+				invoke<SignatureResponse>('sign', {request, caller: dappCaller(metadata)})
 					.then(response => {
 						resolve(formatJsonRpcResult(requestEvent.id, response));
 					})
@@ -473,6 +530,55 @@ export class AleoWallet {
 				requestType: AleoMethod.ALEO_SIGN,
 				requestIdentifier: 'sign' + (metadata?.name ?? ''),
 				requestEvent,
+				caller: dappCaller(metadata),
+				permission: 'sign',
+			},
+			wcRequest,
+		);
+	}
+
+	private async handleSignTyped(
+		requestEvent: Web3WalletTypes.SessionRequest,
+	): Promise<JsonRpcResult | JsonRpcError> {
+		const metadata = getDappMetadata(requestEvent.topic);
+		const request = requestEvent.params.request.params as TypedSignatureRequest;
+
+		const wcRequest: WalletConnectRequest = {
+			method: 'sign',
+			question:
+        (metadata?.name ?? 'Someone?') + ' wants you to sign this data',
+			imageRef: '../wc-images/sign.svg',
+			approveResponse: 'User approved signature.',
+			rejectResponse: 'User rejected signature.',
+			message: `${request.domain} #${request.nonce}\n${request.message}`,
+			dappImage: metadata?.img,
+			dappUrl: metadata?.url,
+		};
+
+		async function action(): Promise<JsonRpcResult | JsonRpcError> {
+			return new Promise((resolve, reject) => {
+				invoke<TypedSignatureResponse>('sign_typed', {request, caller: dappCaller(metadata)})
+					.then(response => {
+						resolve(formatJsonRpcResult(requestEvent.id, response));
+					})
+					.catch((error: AvailError) => {
+						reject(formatJsonRpcError(requestEvent.id, error.external_msg));
+					});
+			});
+		}
+
+		return createWalletConnectDialog(
+			{
+				onApprove: action,
+				onReject: async () =>
+					formatJsonRpcError(requestEvent.id, 'User rejected signature'),
+				approveEventString: 'sign-approved',
+				rejectEventString: 'sign-rejected',
+				requestType: AleoMethod.ALEO_SIGN_TYPED,
+				requestIdentifier: 'signTyped' + (metadata?.name ?? ''),
+				requestEvent,
+				caller: dappCaller(metadata),
+				permission: 'sign',
 			},
 			wcRequest,
 		);
@@ -485,6 +591,31 @@ export class AleoWallet {
 		const metadata = getDappMetadata(requestEvent.topic);
 		const request = requestEvent.params.request.params as CreateEventRequest;
 		console.log('===========> Request full', request);
+
+		// Requests within the dapp's policy are executed without prompting
+		if (metadata?.url) {
+			const decision = await invoke<PolicyDecision>('evaluate_dapp_policy', {
+				caller: dappCaller(metadata),
+				request,
+			});
+
+			if (decision === 'auto_approve') {
+				return new Promise((resolve, reject) => {
+					invoke<CreateEventResponse>('request_create_event', {
+						request,
+						fee_private: false,
+						caller: dappCaller(metadata),
+					})
+						.then(response => {
+							resolve(formatJsonRpcResult(requestEvent.id, response));
+						})
+						.catch((error: AvailError) => {
+							reject(formatJsonRpcError(requestEvent.id, error.external_msg));
+						});
+				});
+			}
+		}
+
 		// TODO - User fee privacy choice
 		const wcRequest: WalletConnectRequest = {
 			method: 'create-request-event',
@@ -525,6 +656,7 @@ export class AleoWallet {
 						invoke<CreateEventResponse>('request_create_event', {
 							request,
 							fee_private: feeOption,
+							caller: dappCaller(metadata),
 						})
 							.then(response => {
 								resolve(formatJsonRpcResult(requestEvent.id, response));
@@ -547,6 +679,8 @@ export class AleoWallet {
 				requestIdentifier:
           'createRequestEvent' + (metadata?.name ?? '') + request.programId,
 				requestEvent,
+				caller: dappCaller(metadata),
+				permission: 'execute',
 			},
 			wcRequest,
 		);
@@ -563,7 +697,7 @@ export class AleoWallet {
 
 		if (!checkExpired(request_identifier)) {
 			return new Promise((resolve, reject) => {
-				invoke<GetEventResponse>('get_event', {request})
+				invoke<GetEventResponse>('get_event', {request, caller: dappCaller(metadata)})
 					.then(response => {
 						resolve(formatJsonRpcResult(requestEvent.id, response));
 					})
@@ -628,7 +762,7 @@ export class AleoWallet {
 					storeSession(request_identifier);
 
 					try {
-						invoke<GetEventResponse>('get_event', {request})
+						invoke<GetEventResponse>('get_event', {request, caller: dappCaller(metadata)})
 							.then(response => {
 								resolve(formatJsonRpcResult(requestEvent.id, response));
 							})
@@ -671,7 +805,7 @@ export class AleoWallet {
 
 		if (!checkExpired(request_identifier)) {
 			return new Promise((resolve, reject) => {
-				invoke<GetEventsResponse>('get_events', {request})
+				invoke<GetEventsResponse>('get_events', {request, caller: dappCaller(metadata)})
 					.then(response => {
 						resolve(formatJsonRpcResult(requestEvent.id, response));
 					})
@@ -740,7 +874,7 @@ export class AleoWallet {
 					storeSession(request_identifier);
 
 					try {
-						invoke<GetEventsResponse>('get_events', {request})
+						invoke<GetEventsResponse>('get_events', {request, caller: dappCaller(metadata)})
 							.then(response => {
 								resolve(formatJsonRpcResult(requestEvent.id, response));
 							})
@@ -797,7 +931,7 @@ export class AleoWallet {
 
 		if (!checkExpired(request_identifier)) {
 			return new Promise((resolve, reject) => {
-				invoke<GetBackendRecordsResponse>('get_records', {request})
+				invoke<GetBackendRecordsResponse>('get_records', {request, caller: dappCaller(metadata)})
 					.then(response => {
 						const res = convertGetRecordsResponse(response);
 						resolve(formatJsonRpcResult(requestEvent.id, res));
@@ -868,10 +1002,19 @@ export class AleoWallet {
 
 					try {
 						console.log('===================> INSIDE GETRECORDS');
+						// the user approved this request, later requests may only read the programs it returned
 						invoke<GetBackendRecordsResponse>('get_records', {
 							request,
+							caller: 'wallet',
 						})
-							.then(response => {
+							.then(async response => {
+								const programIds = [
+									...new Set((response.records ?? []).map(record => record.record.programId)),
+								];
+								if (metadata?.url && programIds.length > 0) {
+									await grantDappPermission(metadata.url, {read_records: {programIds}});
+								}
+
 								const res = convertGetRecordsResponse(response);
 								resolve(formatJsonRpcResult(requestEvent.id, res));
 							})
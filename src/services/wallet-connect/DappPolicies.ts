@@ -0,0 +1,38 @@
+import {invoke} from '@tauri-apps/api/core';
+
+export type PolicyDecision = 'auto_approve' | 'prompt' | 'reauthenticate';
+
+export type DappPolicy = {
+	origin: string;
+	programId: string;
+	dailyBudget?: number;
+	perTxBudget?: number;
+	allowedFunctions: string[];
+	reauthThreshold?: number;
+};
+
+export type PolicyDecisionEntry = {
+	origin: string;
+	programId: string;
+	functionId: string;
+	spend: number;
+	decision: PolicyDecision;
+	reason: string;
+	created: string;
+};
+
+export async function setDappPolicy(policy: DappPolicy) {
+	return invoke('set_dapp_policy', {policy});
+}
+
+export async function getDappPolicies() {
+	return invoke<DappPolicy[]>('get_dapp_policies');
+}
+
+export async function removeDappPolicy(origin: string, programId: string) {
+	return invoke('remove_dapp_policy', {origin, program_id: programId});
+}
+
+export async function getPolicyDecisions(origin?: string) {
+	return invoke<PolicyDecisionEntry[]>('get_policy_decisions', {origin});
+}
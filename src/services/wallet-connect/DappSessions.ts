@@ -0,0 +1,33 @@
+import {invoke} from '@tauri-apps/api/core';
+
+export type DappPermission =
+	| {read_records: {programIds: string[]}}
+	| 'decrypt'
+	| 'sign'
+	| 'execute';
+
+/// Who a request comes from, a dapp without a url gets an empty origin which the wallet rejects
+export type Caller = 'wallet' | {dapp: string};
+
+export function dappCaller(metadata?: {url?: string}): Caller {
+	return {dapp: metadata?.url ?? ''};
+}
+
+export type DappSession = {
+	origin: string;
+	permissions: DappPermission[];
+	created: string;
+	expiresAt: string;
+};
+
+export async function grantDappPermission(origin: string, permission: DappPermission, expiresIn?: number) {
+	return invoke<DappSession>('grant_dapp_permission', {origin, permission, expires_in: expiresIn});
+}
+
+export async function getDappSessions() {
+	return invoke<DappSession[]>('get_dapp_sessions');
+}
+
+export async function revokeDappSession(origin: string) {
+	return invoke('revoke_dapp_session', {origin});
+}
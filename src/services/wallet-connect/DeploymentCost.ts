@@ -0,0 +1,6 @@
+import {invoke} from '@tauri-apps/api/core';
+import {type DeployRequest, type DeploymentCost} from './WCTypes';
+
+export async function estimateDeploymentCost(request: DeployRequest, feePrivate: boolean) {
+	return invoke<DeploymentCost>('estimate_deployment_cost', {request, fee_private: feePrivate});
+}
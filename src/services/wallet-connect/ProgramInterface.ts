@@ -0,0 +1,29 @@
+import {invoke} from '@tauri-apps/api/core';
+
+export type ParameterVisibility =
+	| 'constant'
+	| 'public'
+	| 'private'
+	| 'record'
+	| 'external_record'
+	| 'future';
+
+export type ParameterInterface = {
+	type: string;
+	visibility: ParameterVisibility;
+};
+
+export type FunctionInterface = {
+	name: string;
+	inputs: ParameterInterface[];
+	outputs: ParameterInterface[];
+};
+
+export type ProgramInterface = {
+	programId: string;
+	functions: FunctionInterface[];
+};
+
+export async function getProgramInterface(programId: string) {
+	return invoke<ProgramInterface>('get_program_interface', {program_id: programId});
+}
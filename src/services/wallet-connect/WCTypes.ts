@@ -6,6 +6,7 @@ export enum AleoMethod {
 	ALEO_GETACCOUNT = 'getSelectedAccount',
 	ALEO_DECRYPT = 'decrypt',
 	ALEO_SIGN = 'requestSignature',
+	ALEO_SIGN_TYPED = 'requestTypedSignature',
 	ALEO_GET_RECORDS = 'getRecords',
 	ALEO_CREATE_EVENT = 'requestCreateEvent',
 	ALEO_GET_EVENT = 'getEvent',
@@ -132,6 +133,32 @@ export type SignatureResponse = {
 	error?: string;
 };
 
+/* Sign-In-With-Aleo message, signed through ALEO_SIGN as its text form */
+export type SignInMessage = {
+	domain: string;
+	address: string;
+	statement?: string;
+	uri: string;
+	version: string;
+	chainId: string;
+	nonce: string;
+	issuedAt: string;
+	expirationTime?: string;
+};
+
+export type TypedSignatureRequest = {
+	domain: string;
+	nonce: number;
+	message: string;
+	address?: string;
+};
+export type TypedSignatureResponse = {
+	signature?: string;
+	plaintext?: string;
+	messageFields?: string[];
+	error?: string;
+};
+
 /* --GetRecords-- */
 export type GetRecordsRequest = {
 	address?: string;
@@ -305,6 +332,25 @@ export type CreateEventRequest = {
 	functionId: string;
 	fee: number;
 	inputs: string[];
+	deployment?: DeployRequest;
+};
+
+export type DeployRequest = {
+	program: string;
+	imports?: string[];
+	feeOverride?: number;
+};
+
+export type DeploymentCost = {
+	programId: string;
+	imports: string[];
+	storageCost: number;
+	namespaceCost: number;
+	minimumCost: number;
+	fee: number;
+	feePrivate: boolean;
+	available: number;
+	sufficient: boolean;
 };
 
 export enum EventType {
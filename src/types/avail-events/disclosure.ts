@@ -0,0 +1,36 @@
+export type DisclosureScope = 'transition_view_key' | 'records';
+
+export type DisclosedRecord = {
+	index: number;
+	recordViewKey: string;
+	plaintext: string;
+};
+
+export type DisclosedTransition = {
+	transitionId: string;
+	programId: string;
+	functionId: string;
+	transitionViewKey?: string;
+	records: DisclosedRecord[];
+};
+
+export type DisclosurePackage = {
+	version: number;
+	network: number;
+	transactionId: string;
+	scope: DisclosureScope;
+	transitions: DisclosedTransition[];
+};
+
+export type VerifiedTransition = {
+	transitionId: string;
+	programId: string;
+	functionId: string;
+	inputs: string[];
+	outputs: string[];
+};
+
+export type DisclosureVerification = {
+	transactionId: string;
+	transitions: VerifiedTransition[];
+};
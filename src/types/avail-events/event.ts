@@ -12,4 +12,6 @@ export type SuccinctAvailEvent = {
 	created: Date;
 	programId?: string;
 	functionId?: string;
+	toName?: string;
+	fromName?: string;
 };
@@ -11,3 +11,13 @@ export type INft = {
 	collectionDescription: string;
 	sourceLink: string;
 };
+
+export type NftEntry = {
+	recordId: string;
+	collection: string;
+	tokenId?: string;
+	uri?: string;
+	metadata?: Record<string, unknown>;
+	mediaType?: string;
+	error?: string;
+};
@@ -0,0 +1,30 @@
+export type UnsignedExecution = {
+	address: string;
+	programId: string;
+	functionId: string;
+	inputs: string[];
+	programs: string[];
+	fee: number;
+	feeRecord?: string;
+	stateRoot: string;
+	statePaths: string[];
+	pendingEventId: string;
+};
+
+export type SignedExecution = {
+	request: UnsignedExecution;
+	execution: string;
+	fee: string;
+	transaction: string;
+};
+
+export type OfflinePayload =
+	| ({kind: 'unsigned'} & UnsignedExecution)
+	| ({kind: 'signed'} & SignedExecution);
+
+export type OfflineEnvelope = {
+	version: number;
+	network: number;
+	payload: OfflinePayload;
+	checksum: string;
+};
@@ -0,0 +1,8 @@
+// Server-side steps queued while the Avail server was unreachable
+export type OutboxStatus = {
+	pending: number;
+	// Rejected by the server too many times
+	failed: number;
+	nextAttempt?: string;
+	lastError?: string;
+};
@@ -0,0 +1,12 @@
+// Retry, timeout and circuit breaker settings for calls to the Aleo node and the Avail server
+export type ResilienceSettings = {
+	// Retries after the first attempt
+	maxRetries: number;
+	baseDelayMs: number;
+	maxDelayMs: number;
+	// Time allowed for a single attempt
+	timeoutMs: number;
+	// Consecutive failures before calls fail fast
+	breakerThreshold: number;
+	breakerCooldownMs: number;
+};
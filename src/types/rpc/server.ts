@@ -0,0 +1,10 @@
+export type RpcTransport =
+	| {type: 'tcp'; port: number}
+	| {type: 'unix'; path: string};
+
+export type RpcServerStatus = {
+	running: boolean;
+	endpoint?: string;
+	// File holding the token automation clients authenticate with
+	tokenPath?: string;
+};
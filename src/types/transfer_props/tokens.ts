@@ -28,3 +28,14 @@ export type TransferRequest = {
 	fee: number;
 	asset_id: string;
 };
+
+export type PaymentPrivacy = 'private' | 'public';
+
+/* Parsed avail:pay URI, amount is in the smallest unit of the asset */
+export type PaymentRequest = {
+	to: string;
+	amount?: number;
+	asset: string;
+	memo?: string;
+	privacy: PaymentPrivacy;
+};
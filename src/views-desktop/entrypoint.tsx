@@ -21,6 +21,7 @@ import { ErrorAlert } from '../components/snackbars/alerts';
 import update from '../services/util/updater';
 import { useWalletConnectManager } from '../context/WalletConnect';
 import { listen } from '@tauri-apps/api/event';
+import { type PaymentRequest } from '../types/transfer_props/tokens';
 
 
 function Entrypoint() {
@@ -57,16 +58,27 @@ function Entrypoint() {
 			});
 		}, 3000);
 	}
+	// Deep links are parsed and validated by the backend, which queues them while the wallet is locked
 	const initDeepLink = async () => {
-		listen('deep-link-wc', async (event) => {
-			const { uri } = event.payload as { uri: string }; // Add type assertion
-			console.log('Deep link uri:', uri.split('\"')[1].split('avail://wc?uri=')[1]);
-			// decode the uri
-			let wcUri = uri.split('\"')[1].split('avail://wc?uri=')[1];
-			let decodedUri = decodeURIComponent(wcUri);
-			console.log('Decoded uri:', decodedUri);
-			// if (decodedUri)
-			await walletConnectManager.pair(decodedUri);
+		listen('deep-link-pair', async event => {
+			const {uri} = event.payload as {uri: string};
+			await walletConnectManager.pair(uri);
+		});
+
+		listen('deep-link-payment', event => {
+			const {request} = event.payload as {uri: string; request: PaymentRequest};
+			navigate('/send', {state: {paymentRequest: request}});
+		});
+
+		listen('deep-link-transaction', event => {
+			const {transactionId} = event.payload as {transactionId: string};
+			navigate('/activity', {state: {transactionId}});
+		});
+
+		listen('deep-link-rejected', event => {
+			const {error} = event.payload as {uri: string; error: string};
+			setAlertMessage(error);
+			setAlert(true);
 		});
 	};
 
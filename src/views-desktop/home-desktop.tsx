@@ -52,6 +52,7 @@ import {
 	set_first_visit, get_first_visit, set_visit_session_flag, get_visit_session_flag
 } from '../services/storage/localStorage';
 import { os } from '../services/util/open';
+import { flushDeepLinks } from '../services/util/deep_link';
 import { pre_install_inclusion_prover } from '../services/transfer/inclusion';
 import { sync_backup } from '../services/scans/backup';
 import { scan_blocks } from '../services/scans/blocks';
@@ -274,6 +275,12 @@ function Home() {
 		});
 	};
 
+	React.useEffect(() => {
+		flushDeepLinks().catch(error => {
+			console.log(error);
+		});
+	}, []);
+
 	React.useEffect(() => {
 		if (shouldRunEffect.current) {
 			handleTransferCheck();
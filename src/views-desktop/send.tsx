@@ -1,6 +1,6 @@
 import * as React from 'react';
 import * as mui from '@mui/material';
-import {useNavigate} from 'react-router-dom';
+import {useLocation, useNavigate} from 'react-router-dom';
 
 // Services
 import {useTranslation} from 'react-i18next';
@@ -29,7 +29,7 @@ import {
 } from '../components/snackbars/alerts';
 
 // Types
-import {type TransferRequest, TransferType} from '../types/transfer_props/tokens';
+import {type PaymentRequest, type TransferRequest, TransferType} from '../types/transfer_props/tokens';
 import {type AvailError, AvailErrorType} from '../types/errors';
 import {getAuthType} from '../services/storage/persistent';
 
@@ -89,9 +89,29 @@ function Send() {
 	// Scan states
 	const {scanInProgress, startScan, endScan} = useScan();
 	const navigate = useNavigate();
+	const location = useLocation();
 
 	const {t} = useTranslation();
 
+	/* --Payment request opened from an avail:pay link-- */
+	React.useEffect(() => {
+		const paymentRequest = (location.state as {paymentRequest?: PaymentRequest} | undefined)?.paymentRequest;
+		if (!paymentRequest) {
+			return;
+		}
+
+		setRecipient(paymentRequest.to);
+		setToken(paymentRequest.asset === 'credits' ? 'ALEO' : paymentRequest.asset);
+		setIsPrivateTransferTo(paymentRequest.privacy === 'private');
+		if (paymentRequest.amount !== undefined) {
+			setAmount(paymentRequest.amount / 1_000_000);
+		}
+
+		if (paymentRequest.memo !== undefined) {
+			setTransferMessage(paymentRequest.memo);
+		}
+	}, [location.state]);
+
 	const getAuth = async () => {
 		const auth = await getAuthType();
 		if (auth === 'true') {